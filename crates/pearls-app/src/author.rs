@@ -0,0 +1,78 @@
+// Rust guideline compliant 2026-02-10
+
+//! Default author identity for new Pearls and comments.
+
+use crate::repo::RepoContext;
+use pearls_core::Config;
+use std::path::Path;
+use std::process::Command;
+
+/// Returns the author recorded when none is given.
+///
+/// The first of these that is set wins: `user.name` from the Pearls config,
+/// Git's `user.name`, `user.email` from the Pearls config, Git's
+/// `user.email`, and the `USER` or `USERNAME` environment variable.
+///
+/// # Arguments
+///
+/// * `repo` - The repository, whose config and Git settings are used; `None`
+///   uses the global config and the current directory
+///
+/// # Returns
+///
+/// The author, or `None` if no identity is known.
+pub fn default_author(repo: Option<&RepoContext>) -> Option<String> {
+    let config = match repo {
+        Some(repo) => repo.load_config().ok(),
+        None => Config::load_layered(None)
+            .ok()
+            .map(|layered| layered.config),
+    }
+    .unwrap_or_default();
+    let dir = repo.map(RepoContext::root);
+    author_from(&config, dir)
+}
+
+/// Resolves the author from a config and Git run in `dir`.
+fn author_from(config: &Config, dir: Option<&Path>) -> Option<String> {
+    let non_empty = |value: &Option<String>| value.clone().filter(|value| !value.is_empty());
+    non_empty(&config.user.name)
+        .or_else(|| git_config("user.name", dir))
+        .or_else(|| non_empty(&config.user.email))
+        .or_else(|| git_config("user.email", dir))
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+}
+
+/// Reads a Git config value; `None` if Git is missing or the value is unset.
+fn git_config(key: &str, dir: Option<&Path>) -> Option<String> {
+    let mut command = Command::new("git");
+    command.args(["config", key]);
+    if let Some(dir) = dir {
+        command.current_dir(dir);
+    }
+    let output = command.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pearls_core::UserConfig;
+
+    #[test]
+    fn test_configured_name_wins() {
+        let config = Config {
+            user: UserConfig {
+                name: Some("Ada".to_string()),
+                email: Some("ada@example.com".to_string()),
+            },
+            ..Config::default()
+        };
+        assert_eq!(author_from(&config, None).as_deref(), Some("Ada"));
+    }
+}
@@ -0,0 +1,657 @@
+// Rust guideline compliant 2026-02-09
+
+//! SQLite query cache for Pearls.
+//!
+//! The cache is a disposable projection of the active Pearls and
+//! `archive.jsonl` into `.pearls/cache.db`. The storage remains the source of
+//! truth: the cache stores the content hash of both and is rebuilt whenever
+//! either of them changes, so it can be dropped at any time without losing data.
+
+use crate::error::Result;
+use crate::list::{parse_status, ListOptions};
+use crate::repo::RepoContext;
+use crate::search::{normalize_query, search_text};
+use crate::stats::Stats;
+use pearls_core::index::ContentHash;
+use pearls_core::{DepType, Pearl, Status, Storage};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Version of the cache schema. Bumping it discards existing caches.
+const SCHEMA_VERSION: &str = "1";
+
+const SCHEMA: &str = "
+CREATE TABLE pearls (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    status TEXT NOT NULL,
+    priority INTEGER NOT NULL,
+    author TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL,
+    archived INTEGER NOT NULL,
+    search_text TEXT NOT NULL,
+    body TEXT NOT NULL
+);
+CREATE TABLE labels (
+    pearl_id TEXT NOT NULL,
+    label TEXT NOT NULL,
+    PRIMARY KEY (pearl_id, label)
+);
+CREATE TABLE deps (
+    source_id TEXT NOT NULL,
+    target_id TEXT NOT NULL,
+    dep_type TEXT NOT NULL
+);
+CREATE INDEX pearls_status ON pearls (archived, status);
+CREATE INDEX pearls_priority ON pearls (archived, priority);
+CREATE INDEX labels_label ON labels (label);
+CREATE INDEX deps_source ON deps (source_id, dep_type);
+CREATE INDEX deps_target ON deps (target_id, dep_type);
+";
+
+/// Condition matching Pearls with a non-closed, active `blocks` target.
+const BLOCKED_CONDITION: &str = "EXISTS (
+    SELECT 1 FROM deps d JOIN pearls t ON t.id = d.target_id
+    WHERE d.source_id = p.id AND d.dep_type = 'blocks'
+      AND t.archived = 0 AND t.status != 'closed'
+)";
+
+/// SQLite projection of the repository used to answer queries.
+pub struct QueryCache {
+    conn: Connection,
+    issues: Storage,
+    archive: Storage,
+}
+
+impl QueryCache {
+    /// Opens the cache for a repository, rebuilding it if it is stale.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - Repository context
+    ///
+    /// # Returns
+    ///
+    /// A cache that reflects the current storage.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the storage or the database cannot be opened or
+    /// rebuilt.
+    pub fn open(repo: &RepoContext) -> Result<Self> {
+        let mut cache = Self::open_unchecked(repo)?;
+        cache.refresh()?;
+        Ok(cache)
+    }
+
+    /// Opens the cache for a repository without checking whether it is stale.
+    ///
+    /// Use this when the caller rebuilds the cache right away.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - Repository context
+    ///
+    /// # Returns
+    ///
+    /// The cache as it is on disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the storage or the database cannot be opened.
+    pub fn open_unchecked(repo: &RepoContext) -> Result<Self> {
+        repo.ignore_local_files()?;
+        Self::connect(
+            repo.cache_path(),
+            repo.open_storage()?,
+            Storage::new(repo.archive_path().to_path_buf())?,
+        )
+    }
+
+    /// Opens a cache at an explicit path, rebuilding it if it is stale.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the SQLite database
+    /// * `issues` - Storage for the active Pearls
+    /// * `archive` - Storage for `archive.jsonl`
+    ///
+    /// # Returns
+    ///
+    /// A cache that reflects the current storage.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be opened or rebuilt.
+    pub fn open_at(path: &Path, issues: Storage, archive: Storage) -> Result<Self> {
+        let mut cache = Self::connect(path, issues, archive)?;
+        cache.refresh()?;
+        Ok(cache)
+    }
+
+    /// Checks whether the cache no longer matches the stored Pearls.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the active or archived content hash differs from the one
+    /// recorded at the last rebuild.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the files or the database cannot be read.
+    pub fn is_stale(&self) -> Result<bool> {
+        let issues_hash = hex(&self.issues.content_hash()?);
+        let archive_hash = hex(&self.archive.content_hash()?);
+        Ok(
+            self.meta("issues_hash")?.as_deref() != Some(issues_hash.as_str())
+                || self.meta("archive_hash")?.as_deref() != Some(archive_hash.as_str()),
+        )
+    }
+
+    /// Rebuilds the cache if it is stale.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the cache was rebuilt.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be checked or rebuilt.
+    pub fn refresh(&mut self) -> Result<bool> {
+        if !self.is_stale()? {
+            return Ok(false);
+        }
+        self.rebuild()?;
+        Ok(true)
+    }
+
+    /// Rebuilds the cache from the active and archived Pearls.
+    ///
+    /// Archived Pearls are loaded first so that an active copy of the same ID
+    /// takes precedence.
+    ///
+    /// # Returns
+    ///
+    /// The number of Pearls in the cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the files cannot be read or the database cannot be
+    /// written.
+    pub fn rebuild(&mut self) -> Result<usize> {
+        let issues_hash = hex(&self.issues.content_hash()?);
+        let archive_hash = hex(&self.archive.content_hash()?);
+
+        let mut pearls = BTreeMap::new();
+        for pearl in self.archive.load_all()? {
+            pearls.insert(pearl.id.clone(), (pearl, true));
+        }
+        for pearl in self.issues.load_all()? {
+            pearls.insert(pearl.id.clone(), (pearl, false));
+        }
+
+        let tx = self.conn.transaction()?;
+        tx.execute_batch("DELETE FROM pearls; DELETE FROM labels; DELETE FROM deps;")?;
+        {
+            let mut insert_pearl = tx.prepare(
+                "INSERT INTO pearls (id, title, status, priority, author, created_at, \
+                 updated_at, archived, search_text, body) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )?;
+            let mut insert_label =
+                tx.prepare("INSERT OR IGNORE INTO labels (pearl_id, label) VALUES (?1, ?2)")?;
+            let mut insert_dep = tx
+                .prepare("INSERT INTO deps (source_id, target_id, dep_type) VALUES (?1, ?2, ?3)")?;
+
+            for (pearl, archived) in pearls.values() {
+                insert_pearl.execute(params![
+                    pearl.id,
+                    pearl.title,
+                    status_key(pearl.status),
+                    pearl.priority,
+                    pearl.author,
+                    pearl.created_at,
+                    pearl.updated_at,
+                    archived,
+                    search_text(pearl),
+                    serde_json::to_string(pearl).map_err(pearls_core::Error::from)?,
+                ])?;
+                for label in &pearl.labels {
+                    insert_label.execute(params![pearl.id, label.to_ascii_lowercase()])?;
+                }
+                for dep in &pearl.deps {
+                    insert_dep.execute(params![
+                        pearl.id,
+                        dep.target_id,
+                        dep_type_key(dep.dep_type)
+                    ])?;
+                }
+            }
+        }
+        set_meta(&tx, "issues_hash", &issues_hash)?;
+        set_meta(&tx, "archive_hash", &archive_hash)?;
+        tx.commit()?;
+
+        Ok(pearls.len())
+    }
+
+    /// Returns the number of active Pearls in the cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be queried.
+    pub fn count(&self) -> Result<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM pearls WHERE archived = 0",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    /// Loads the Pearls matching the filters in `options`.
+    ///
+    /// Archived Pearls are marked with an `archived` metadata flag. The result
+    /// still needs to go through [`crate::list_pearls`] for sorting.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - List options
+    /// * `include_archived` - Whether to include archived Pearls
+    ///
+    /// # Returns
+    ///
+    /// The matching Pearls.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be queried.
+    pub fn list(&self, options: &ListOptions, include_archived: bool) -> Result<Vec<Pearl>> {
+        let mut sql = String::from("SELECT body, archived FROM pearls p WHERE 1 = 1");
+        let mut values: Vec<Value> = Vec::new();
+        let mut push = |condition: &str, value: Value| {
+            sql.push_str(" AND ");
+            sql.push_str(condition);
+            values.push(value);
+        };
+
+        if !include_archived {
+            push("archived = ?", Value::Integer(0));
+        }
+        if let Some(status) = options.status {
+            push("status = ?", Value::Text(status_key(status).to_string()));
+        }
+        if let Some(priority) = options.priority {
+            push("priority = ?", Value::Integer(i64::from(priority)));
+        }
+        for label in &options.labels {
+            push(
+                "EXISTS (SELECT 1 FROM labels l WHERE l.pearl_id = p.id AND l.label = ?)",
+                Value::Text(label.to_ascii_lowercase()),
+            );
+        }
+        if let Some(author) = &options.author {
+            push("author = ?", Value::Text(author.clone()));
+        }
+        if let Some(dep_type) = options.dep_type {
+            push(
+                "EXISTS (SELECT 1 FROM deps d WHERE d.source_id = p.id AND d.dep_type = ?)",
+                Value::Text(dep_type_key(dep_type).to_string()),
+            );
+        }
+        if let Some(after) = options.created_after {
+            push("created_at >= ?", Value::Integer(after));
+        }
+        if let Some(before) = options.created_before {
+            push("created_at <= ?", Value::Integer(before));
+        }
+        if let Some(after) = options.updated_after {
+            push("updated_at >= ?", Value::Integer(after));
+        }
+        if let Some(before) = options.updated_before {
+            push("updated_at <= ?", Value::Integer(before));
+        }
+        sql.push_str(" ORDER BY updated_at DESC, id");
+
+        self.query_pearls(&sql, values)
+    }
+
+    /// Returns the Pearls whose text contains the query, most recently updated first.
+    ///
+    /// Matches the same text as [`crate::search_pearls`].
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Search query
+    /// * `include_archived` - Whether to include archived Pearls
+    ///
+    /// # Returns
+    ///
+    /// The matching Pearls.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query is empty or the database cannot be queried.
+    pub fn search(&self, query: &str, include_archived: bool) -> Result<Vec<Pearl>> {
+        let archived = if include_archived {
+            ""
+        } else {
+            " AND archived = 0"
+        };
+        let sql = format!(
+            "SELECT body, archived FROM pearls WHERE instr(search_text, ?) > 0{} \
+             ORDER BY updated_at DESC, id",
+            archived
+        );
+        self.query_pearls(&sql, vec![Value::Text(normalize_query(query)?)])
+    }
+
+    /// Computes the ready queue.
+    ///
+    /// Ready Pearls are active Pearls that are open, in progress, or blocked and
+    /// have no `blocks` dependency on a non-closed active Pearl.
+    ///
+    /// # Returns
+    ///
+    /// Vector of ready Pearls, sorted by priority and recency.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be queried.
+    pub fn ready_queue(&self) -> Result<Vec<Pearl>> {
+        let sql = format!(
+            "SELECT body, archived FROM pearls p \
+             WHERE archived = 0 AND status IN ('open', 'in_progress', 'blocked') \
+             AND NOT {} ORDER BY priority ASC, updated_at DESC, id",
+            BLOCKED_CONDITION
+        );
+        self.query_pearls(&sql, Vec::new())
+    }
+
+    /// Computes aggregate statistics.
+    ///
+    /// # Returns
+    ///
+    /// The same statistics as [`crate::compute_stats`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be queried.
+    pub fn stats(&self) -> Result<Stats> {
+        let count = |sql: &str| -> Result<usize> {
+            let count: i64 = self.conn.query_row(sql, [], |row| row.get(0))?;
+            Ok(count as usize)
+        };
+
+        let mut stats = Stats {
+            total: self.count()?,
+            archived: count("SELECT COUNT(*) FROM pearls WHERE archived = 1")?,
+            blocked: count(&format!(
+                "SELECT COUNT(*) FROM pearls p WHERE archived = 0 AND status != 'closed' AND {}",
+                BLOCKED_CONDITION
+            ))?,
+            ..Stats::default()
+        };
+
+        for (status, count) in self.group_counts::<String>(
+            "SELECT status, COUNT(*) FROM pearls WHERE archived = 0 GROUP BY status",
+        )? {
+            stats.by_status.insert(parse_status(&status)?, count);
+        }
+        for (priority, count) in self.group_counts(
+            "SELECT priority, COUNT(*) FROM pearls WHERE archived = 0 GROUP BY priority",
+        )? {
+            stats.by_priority.insert(priority, count);
+        }
+        stats.by_label = self
+            .group_counts(
+                "SELECT l.label, COUNT(*) FROM labels l JOIN pearls p ON p.id = l.pearl_id \
+                 WHERE p.archived = 0 GROUP BY l.label",
+            )?
+            .into_iter()
+            .collect();
+
+        Ok(stats)
+    }
+
+    fn connect(path: &Path, issues: Storage, archive: Storage) -> Result<Self> {
+        let cache = Self {
+            conn: Connection::open(path)?,
+            issues,
+            archive,
+        };
+        cache.ensure_schema()?;
+        Ok(cache)
+    }
+
+    fn ensure_schema(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);",
+        )?;
+        if self.meta("schema_version")?.as_deref() == Some(SCHEMA_VERSION) {
+            return Ok(());
+        }
+
+        self.conn.execute_batch(
+            "DROP TABLE IF EXISTS pearls; DROP TABLE IF EXISTS labels; \
+             DROP TABLE IF EXISTS deps; DELETE FROM meta;",
+        )?;
+        self.conn.execute_batch(SCHEMA)?;
+        set_meta(&self.conn, "schema_version", SCHEMA_VERSION)
+    }
+
+    fn meta(&self, key: &str) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row("SELECT value FROM meta WHERE key = ?1", [key], |row| {
+                row.get(0)
+            })
+            .optional()?)
+    }
+
+    fn group_counts<K: rusqlite::types::FromSql>(&self, sql: &str) -> Result<Vec<(K, usize)>> {
+        let mut statement = self.conn.prepare(sql)?;
+        let rows = statement.query_map([], |row| {
+            Ok((row.get::<_, K>(0)?, row.get::<_, i64>(1)? as usize))
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    fn query_pearls(&self, sql: &str, values: Vec<Value>) -> Result<Vec<Pearl>> {
+        let mut statement = self.conn.prepare(sql)?;
+        let rows = statement
+            .query_map(params_from_iter(values), |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        rows.into_iter()
+            .map(|(body, archived)| {
+                let mut pearl: Pearl =
+                    serde_json::from_str(&body).map_err(pearls_core::Error::from)?;
+                if archived {
+                    pearl
+                        .metadata
+                        .insert("archived".to_string(), serde_json::Value::Bool(true));
+                }
+                Ok(pearl)
+            })
+            .collect()
+    }
+}
+
+/// Deletes the cache database for a repository.
+///
+/// # Arguments
+///
+/// * `repo` - Repository context
+///
+/// # Returns
+///
+/// `true` if a cache file was removed.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be removed.
+pub fn drop_cache(repo: &RepoContext) -> Result<bool> {
+    match std::fs::remove_file(repo.cache_path()) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn set_meta(conn: &Connection, key: &str, value: &str) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO meta (key, value) VALUES (?1, ?2)",
+        [key, value],
+    )?;
+    Ok(())
+}
+
+fn hex(hash: &ContentHash) -> String {
+    hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn status_key(status: Status) -> &'static str {
+    match status {
+        Status::Open => "open",
+        Status::InProgress => "in_progress",
+        Status::Blocked => "blocked",
+        Status::Deferred => "deferred",
+        Status::Closed => "closed",
+    }
+}
+
+fn dep_type_key(dep_type: DepType) -> &'static str {
+    match dep_type {
+        DepType::Blocks => "blocks",
+        DepType::ParentChild => "parent_child",
+        DepType::Related => "related",
+        DepType::DiscoveredFrom => "discovered_from",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_stats, list_pearls, ready_queue, search_pearls};
+    use pearls_core::Dependency;
+
+    fn sample_pearl(id: &str, status: Status, label: &str, blocked_by: Option<&str>) -> Pearl {
+        Pearl {
+            id: id.to_string(),
+            title: format!("Pearl {}", id),
+            description: "Parser work".to_string(),
+            status,
+            priority: 2,
+            created_at: 1000,
+            updated_at: 1000 + i64::from(id.as_bytes()[4]),
+            author: "author".to_string(),
+            labels: vec![label.to_string()],
+            deps: blocked_by
+                .map(|target| Dependency {
+                    target_id: target.to_string(),
+                    dep_type: DepType::Blocks,
+                })
+                .into_iter()
+                .collect(),
+            metadata: Default::default(),
+            comments: Vec::new(),
+            extra: Default::default(),
+        }
+    }
+
+    fn ids(pearls: &[Pearl]) -> Vec<&str> {
+        pearls.iter().map(|pearl| pearl.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_cache_matches_jsonl_queries() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let issues_path = temp_dir.path().join("issues.jsonl");
+        let archive_path = temp_dir.path().join("archive.jsonl");
+        let pearls = vec![
+            sample_pearl("prl-aaa111", Status::Open, "Core", None),
+            sample_pearl("prl-bbb222", Status::Open, "core", Some("prl-aaa111")),
+            sample_pearl("prl-ccc333", Status::Closed, "ui", None),
+            sample_pearl("prl-ddd444", Status::InProgress, "ui", Some("prl-ccc333")),
+            sample_pearl("prl-eee555", Status::Open, "ui", Some("prl-fff666")),
+        ];
+        Storage::new(issues_path.clone())
+            .expect("storage")
+            .save_all(&pearls)
+            .expect("save");
+        Storage::new(archive_path.clone())
+            .expect("archive")
+            .save_all(&[sample_pearl("prl-fff666", Status::Open, "core", None)])
+            .expect("save archive");
+
+        let cache = QueryCache::open_at(
+            &temp_dir.path().join("cache.db"),
+            Storage::new(issues_path).expect("storage"),
+            Storage::new(archive_path).expect("archive"),
+        )
+        .expect("cache");
+
+        let options = ListOptions {
+            labels: vec!["CORE".to_string()],
+            ..ListOptions::default()
+        };
+        assert_eq!(
+            ids(&list_pearls(
+                cache.list(&options, false).expect("list"),
+                &options
+            )),
+            ids(&list_pearls(pearls.clone(), &options))
+        );
+        let archived = cache.list(&options, true).expect("list archived");
+        assert_eq!(archived.len(), 3);
+
+        assert_eq!(
+            ids(&cache.ready_queue().expect("ready")),
+            ids(&ready_queue(pearls.clone()).expect("graph ready"))
+        );
+        assert_eq!(
+            ids(&cache.search("parser", false).expect("search")),
+            ids(&search_pearls(pearls.clone(), "parser").expect("search"))
+        );
+        assert_eq!(cache.stats().expect("stats"), compute_stats(&pearls, 1));
+    }
+
+    fn open_cache(cache_path: &Path, issues_path: &Path, archive_path: &Path) -> QueryCache {
+        QueryCache::open_at(
+            cache_path,
+            Storage::new(issues_path.to_path_buf()).expect("storage"),
+            Storage::new(archive_path.to_path_buf()).expect("archive"),
+        )
+        .expect("cache")
+    }
+
+    #[test]
+    fn test_cache_rebuilds_when_jsonl_changes() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let issues_path = temp_dir.path().join("issues.jsonl");
+        let archive_path = temp_dir.path().join("archive.jsonl");
+        let cache_path = temp_dir.path().join("cache.db");
+        let mut storage = Storage::new(issues_path.clone()).expect("storage");
+        storage
+            .save(&sample_pearl("prl-aaa111", Status::Open, "core", None))
+            .expect("save");
+
+        let cache = open_cache(&cache_path, &issues_path, &archive_path);
+        assert_eq!(cache.count().expect("count"), 1);
+        assert!(!cache.is_stale().expect("stale"));
+
+        storage
+            .save(&sample_pearl("prl-bbb222", Status::Open, "core", None))
+            .expect("save");
+        assert!(cache.is_stale().expect("stale"));
+
+        let cache = open_cache(&cache_path, &issues_path, &archive_path);
+        assert_eq!(cache.count().expect("count"), 2);
+    }
+}
@@ -0,0 +1,333 @@
+// Rust guideline compliant 2026-02-10
+
+//! Changelog generation from closed Pearls.
+//!
+//! Closed Pearls are grouped into sections by label or issue type according to
+//! [`ChangelogConfig`], then rendered through a small template language:
+//! `{{name}}` inserts a value and `{{#list}}...{{/list}}` repeats a block for
+//! each item. Inside a block, names resolve against the item first, so
+//! `{{title}}` is the section title in `sections` and the Pearl title in
+//! `entries`. Lines holding only a block tag produce no output.
+
+use pearls_core::{ChangelogConfig, Pearl};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Template for plain Markdown output.
+pub const MARKDOWN_TEMPLATE: &str = "\
+# Changelog
+
+Changes from {{since}} to {{until}}.
+
+{{#sections}}
+## {{title}}
+
+{{#entries}}
+- {{title}} ({{id}})
+{{/entries}}
+
+{{/sections}}
+";
+
+/// Template for a Keep a Changelog release entry.
+pub const KEEP_A_CHANGELOG_TEMPLATE: &str = "\
+## [{{version}}] - {{date}}
+
+{{#sections}}
+### {{title}}
+
+{{#entries}}
+- {{title}} ({{id}})
+{{/entries}}
+
+{{/sections}}
+";
+
+/// A closed Pearl and the time it was closed.
+#[derive(Debug, Clone)]
+pub struct ClosedPearl {
+    /// The Pearl.
+    pub pearl: Pearl,
+    /// Unix timestamp of the close.
+    pub closed_at: i64,
+}
+
+/// One line of the changelog.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangelogEntry {
+    /// Pearl ID.
+    pub id: String,
+    /// Pearl title.
+    pub title: String,
+    /// Labels joined by `, `.
+    pub labels: String,
+    /// Issue type from metadata, or empty.
+    #[serde(rename = "type")]
+    pub issue_type: String,
+    /// Pearl author.
+    pub author: String,
+    /// Close date as `YYYY-MM-DD`.
+    pub date: String,
+}
+
+/// A changelog section with its entries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangelogGroup {
+    /// Section heading.
+    pub title: String,
+    /// Entries in close order.
+    pub entries: Vec<ChangelogEntry>,
+}
+
+/// A changelog ready to render.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Changelog {
+    /// Release name, such as `Unreleased` or `1.2.0`.
+    pub version: String,
+    /// Release date as `YYYY-MM-DD`.
+    pub date: String,
+    /// Start of the range, as given.
+    pub since: String,
+    /// End of the range, as given.
+    pub until: String,
+    /// Non-empty sections in configured order.
+    pub sections: Vec<ChangelogGroup>,
+}
+
+/// Groups closed Pearls into changelog sections.
+///
+/// Pearls with the configured exclude label are left out. Each remaining
+/// Pearl goes into the first section whose labels or types match, or into the
+/// default section; sections without entries are dropped.
+///
+/// # Arguments
+///
+/// * `closed` - Closed Pearls in the range
+/// * `config` - Section mapping
+///
+/// # Returns
+///
+/// The sections in configured order, with the default section appended when
+/// it is not configured explicitly.
+#[must_use]
+pub fn group_changelog(closed: &[ClosedPearl], config: &ChangelogConfig) -> Vec<ChangelogGroup> {
+    let mut closed: Vec<&ClosedPearl> = closed
+        .iter()
+        .filter(|item| !item.pearl.labels.contains(&config.exclude_label))
+        .collect();
+    closed.sort_by(|a, b| {
+        a.closed_at
+            .cmp(&b.closed_at)
+            .then_with(|| a.pearl.id.cmp(&b.pearl.id))
+    });
+
+    let mut groups: Vec<ChangelogGroup> = config
+        .sections
+        .iter()
+        .map(|section| ChangelogGroup {
+            title: section.title.clone(),
+            entries: Vec::new(),
+        })
+        .collect();
+    let default_idx = match groups
+        .iter()
+        .position(|group| group.title == config.default_section)
+    {
+        Some(idx) => idx,
+        None => {
+            groups.push(ChangelogGroup {
+                title: config.default_section.clone(),
+                entries: Vec::new(),
+            });
+            groups.len() - 1
+        }
+    };
+
+    for item in closed {
+        let issue_type = item
+            .pearl
+            .metadata
+            .get(&config.type_key)
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let idx = config
+            .sections
+            .iter()
+            .position(|section| {
+                section
+                    .labels
+                    .iter()
+                    .any(|label| item.pearl.labels.contains(label))
+                    || (!issue_type.is_empty()
+                        && section.types.iter().any(|kind| kind == issue_type))
+            })
+            .unwrap_or(default_idx);
+        groups[idx].entries.push(ChangelogEntry {
+            id: item.pearl.id.clone(),
+            title: item.pearl.title.clone(),
+            labels: item.pearl.labels.join(", "),
+            issue_type: issue_type.to_string(),
+            author: item.pearl.author.clone(),
+            date: date(item.closed_at),
+        });
+    }
+
+    groups.retain(|group| !group.entries.is_empty());
+    groups
+}
+
+/// Renders a changelog through a template.
+///
+/// # Arguments
+///
+/// * `template` - Template text
+/// * `changelog` - Values available to the template
+///
+/// # Returns
+///
+/// The rendered text, ending with a single newline.
+#[must_use]
+pub fn render_changelog(template: &str, changelog: &Changelog) -> String {
+    let context = serde_json::to_value(changelog).unwrap_or(Value::Null);
+    let template = strip_standalone_tags(template);
+    let mut out = String::new();
+    render_block(&template, &[&context], &mut out);
+    format!("{}\n", out.trim_end())
+}
+
+/// Formats a Unix timestamp as `YYYY-MM-DD` in UTC.
+///
+/// # Arguments
+///
+/// * `seconds` - Unix timestamp
+///
+/// # Returns
+///
+/// The date, or an empty string if the timestamp is out of range.
+#[must_use]
+pub fn date(seconds: i64) -> String {
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Removes the line breaks around lines that hold only a block tag.
+fn strip_standalone_tags(template: &str) -> String {
+    let mut out = String::with_capacity(template.len());
+    for line in template.split_inclusive('\n') {
+        let trimmed = line.trim();
+        let standalone = trimmed.starts_with("{{")
+            && trimmed.ends_with("}}")
+            && matches!(trimmed.as_bytes().get(2), Some(b'#') | Some(b'/'))
+            && trimmed[2..].find("{{").is_none();
+        if standalone {
+            out.push_str(trimmed);
+        } else {
+            out.push_str(line);
+        }
+    }
+    out
+}
+
+fn render_block(template: &str, scopes: &[&Value], out: &mut String) {
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            return;
+        };
+        let tag = after[..end].trim();
+        rest = &after[end + 2..];
+
+        if let Some(name) = tag.strip_prefix('#') {
+            let name = name.trim();
+            let close = format!("{{{{/{}}}}}", name);
+            let (inner, remainder) = match rest.find(&close) {
+                Some(pos) => (&rest[..pos], &rest[pos + close.len()..]),
+                None => (rest, ""),
+            };
+            rest = remainder;
+            let items: Vec<&Value> = match lookup(scopes, name) {
+                Some(Value::Array(items)) => items.iter().collect(),
+                Some(Value::Bool(false)) | Some(Value::Null) | None => Vec::new(),
+                Some(Value::String(text)) if text.is_empty() => Vec::new(),
+                Some(value) => vec![value],
+            };
+            for item in items {
+                let mut inner_scopes = scopes.to_vec();
+                inner_scopes.push(item);
+                render_block(inner, &inner_scopes, out);
+            }
+        } else if tag.starts_with('/') {
+            // Unmatched closing tag; ignore it.
+        } else {
+            match lookup(scopes, tag) {
+                Some(Value::String(text)) => out.push_str(text),
+                Some(Value::Null) | None => {}
+                Some(value) => out.push_str(&value.to_string()),
+            }
+        }
+    }
+    out.push_str(rest);
+}
+
+fn lookup<'a>(scopes: &[&'a Value], name: &str) -> Option<&'a Value> {
+    scopes.iter().rev().find_map(|scope| scope.get(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn closed(id: &str, title: &str, labels: &[&str], closed_at: i64) -> ClosedPearl {
+        let mut pearl = Pearl::new(title.to_string(), "alice".to_string());
+        pearl.id = id.to_string();
+        pearl.labels = labels.iter().map(|label| label.to_string()).collect();
+        ClosedPearl { pearl, closed_at }
+    }
+
+    #[test]
+    fn test_group_and_render_changelog() {
+        let mut typed = closed("prl-ddd444", "Dark mode", &[], 40);
+        typed
+            .pearl
+            .metadata
+            .insert("issue_type".to_string(), "feature".into());
+        let items = vec![
+            closed("prl-aaa111", "Crash on start", &["bug"], 30),
+            closed("prl-bbb222", "Refactor", &[], 20),
+            closed(
+                "prl-ccc333",
+                "Internal cleanup",
+                &["bug", "no-changelog"],
+                10,
+            ),
+            typed,
+        ];
+
+        let sections = group_changelog(&items, &ChangelogConfig::default());
+        let titles: Vec<&str> = sections.iter().map(|group| group.title.as_str()).collect();
+        assert_eq!(titles, vec!["Added", "Changed", "Fixed"]);
+
+        let changelog = Changelog {
+            version: "1.2.0".to_string(),
+            date: "2026-02-10".to_string(),
+            since: "v1.1.0".to_string(),
+            until: "HEAD".to_string(),
+            sections,
+        };
+        assert_eq!(
+            render_changelog(KEEP_A_CHANGELOG_TEMPLATE, &changelog),
+            "## [1.2.0] - 2026-02-10\n\n\
+             ### Added\n\n- Dark mode (prl-ddd444)\n\n\
+             ### Changed\n\n- Refactor (prl-bbb222)\n\n\
+             ### Fixed\n\n- Crash on start (prl-aaa111)\n"
+        );
+        assert_eq!(
+            render_changelog("{{#sections}}{{title}}:{{#entries}} {{id}}@{{date}}{{/entries}};{{/sections}}", &changelog),
+            "Added: prl-ddd444@1970-01-01;Changed: prl-bbb222@1970-01-01;Fixed: prl-aaa111@1970-01-01;\n"
+        );
+    }
+}
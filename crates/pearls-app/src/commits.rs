@@ -0,0 +1,75 @@
+// Rust guideline compliant 2026-02-10
+
+//! Commits linked to Pearls.
+//!
+//! The post-commit hook records each commit that references a Pearl in the
+//! Pearl's `commits` metadata entry, where `prl show` and the MCP handoff
+//! prompt read it back.
+
+use pearls_core::Pearl;
+use serde::{Deserialize, Serialize};
+
+/// Metadata key holding the commits linked to a Pearl.
+pub const COMMITS_METADATA_KEY: &str = "commits";
+
+/// A commit linked to a Pearl.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkedCommit {
+    /// Full commit SHA.
+    pub sha: String,
+    /// Commit author as `Name <email>`.
+    pub author: String,
+    /// First line of the commit message.
+    pub subject: String,
+    /// Whether the commit used a closing verb.
+    #[serde(default)]
+    pub closes: bool,
+}
+
+/// Returns the commits linked to a Pearl.
+///
+/// # Arguments
+///
+/// * `pearl` - The Pearl to inspect
+///
+/// # Returns
+///
+/// The linked commits in the order they were recorded; entries that cannot be
+/// read are skipped.
+#[must_use]
+pub fn linked_commits(pearl: &Pearl) -> Vec<LinkedCommit> {
+    pearl
+        .metadata
+        .get(COMMITS_METADATA_KEY)
+        .and_then(|value| value.as_array())
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| serde_json::from_value(entry.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Records a commit on a Pearl.
+///
+/// # Arguments
+///
+/// * `pearl` - The Pearl to update
+/// * `commit` - The commit to link
+///
+/// # Returns
+///
+/// `true` if the commit was added, `false` if it was already linked.
+pub fn link_commit(pearl: &mut Pearl, commit: LinkedCommit) -> bool {
+    let mut commits = linked_commits(pearl);
+    if commits.iter().any(|existing| existing.sha == commit.sha) {
+        return false;
+    }
+    commits.push(commit);
+    pearl.metadata.insert(
+        COMMITS_METADATA_KEY.to_string(),
+        serde_json::to_value(commits).unwrap_or_default(),
+    );
+    true
+}
@@ -3,9 +3,99 @@
 //! Archive compaction helpers for Pearls.
 
 use crate::error::Result;
-use pearls_core::{Pearl, Status};
+use crate::repo::RepoContext;
+use chrono::{Duration, Utc};
+use pearls_core::{Pearl, Status, Storage};
+use serde::Serialize;
 use std::collections::HashMap;
 
+/// Outcome of a compaction run.
+#[derive(Debug, Clone, Serialize)]
+pub struct CompactReport {
+    /// Whether the run only previewed the archive.
+    pub dry_run: bool,
+    /// Age in days after which closed Pearls are archived.
+    pub threshold_days: u32,
+    /// Unix timestamp; closed Pearls last updated at or before it are archived.
+    pub cutoff_timestamp: i64,
+    /// Pearls eligible for the archive.
+    pub eligible: Vec<Pearl>,
+    /// Number of Pearls in the archive after the run.
+    pub archived_total: usize,
+    /// Number of Pearls left in active storage.
+    pub active_remaining: usize,
+}
+
+/// Moves closed Pearls older than a threshold to `.pearls/archive.jsonl`.
+///
+/// Pearls already in the archive keep their archived record.
+///
+/// # Arguments
+///
+/// * `repo` - The repository to compact
+/// * `threshold_days` - Override for `compact_threshold_days` from the config
+/// * `dry_run` - Whether to report the eligible Pearls without moving them
+///
+/// # Returns
+///
+/// The eligible Pearls and the resulting archive and active counts. On a dry
+/// run or when nothing is eligible, `archived_total` is zero.
+///
+/// # Errors
+///
+/// Returns an error if the configuration, issues, or archive cannot be read,
+/// or if either file cannot be written.
+pub fn compact(
+    repo: &RepoContext,
+    threshold_days: Option<u32>,
+    dry_run: bool,
+) -> Result<CompactReport> {
+    let config = repo.load_config()?;
+    let threshold_days = threshold_days.unwrap_or(config.compact_threshold_days);
+    let cutoff_timestamp = (Utc::now() - Duration::days(i64::from(threshold_days))).timestamp();
+
+    let mut storage = Storage::open(repo.pearls_dir(), &config)?;
+    let (eligible, remaining): (Vec<Pearl>, Vec<Pearl>) = storage
+        .load_all()?
+        .into_iter()
+        .partition(|pearl| pearl.status == Status::Closed && pearl.updated_at <= cutoff_timestamp);
+    let mut report = CompactReport {
+        dry_run,
+        threshold_days,
+        cutoff_timestamp,
+        eligible,
+        archived_total: 0,
+        active_remaining: remaining.len(),
+    };
+    if dry_run || report.eligible.is_empty() {
+        return Ok(report);
+    }
+
+    let mut archive_storage = Storage::new(repo.archive_path().to_path_buf())?;
+    let archive_pearls = if repo.archive_path().exists() {
+        archive_storage.load_all()?
+    } else {
+        Vec::new()
+    };
+    let mut archive_map: HashMap<String, Pearl> = archive_pearls
+        .into_iter()
+        .map(|pearl| (pearl.id.clone(), pearl))
+        .collect();
+    for pearl in &report.eligible {
+        archive_map
+            .entry(pearl.id.clone())
+            .or_insert_with(|| pearl.clone());
+    }
+
+    let mut merged_archive: Vec<Pearl> = archive_map.into_values().collect();
+    merged_archive.sort_by(|a, b| a.id.cmp(&b.id));
+    archive_storage.save_all(&merged_archive)?;
+    storage.save_all(&remaining)?;
+
+    report.archived_total = merged_archive.len();
+    Ok(report)
+}
+
 /// Computes archive candidates and remaining Pearls.
 ///
 /// # Arguments
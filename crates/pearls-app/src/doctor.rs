@@ -0,0 +1,507 @@
+// Rust guideline compliant 2026-02-10
+
+//! Integrity checks and repairs for a Pearls repository.
+//!
+//! Validates record syntax, schema compliance, graph integrity, and index
+//! freshness for either storage layout. Each problem is reported as a
+//! [`Finding`] with a severity and, where one exists, a suggested repair.
+
+use crate::error::Result;
+use crate::repo::RepoContext;
+use pearls_core::{
+    Config, Index, IssueGraph, Pearl, Status, Storage, StorageLayout, CURRENT_SCHEMA_VERSION,
+};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::path::Path;
+
+/// Severity of a doctor finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The repository is inconsistent.
+    Error,
+    /// The repository works but something should be looked at.
+    Warning,
+    /// A repair that was applied.
+    Info,
+}
+
+/// One problem found by the doctor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// What is wrong.
+    pub message: String,
+    /// Suggested repair, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+    /// Whether running the doctor with `fix` applies the suggestion.
+    pub fixable: bool,
+}
+
+impl Finding {
+    /// Creates a finding without a suggestion.
+    #[must_use]
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            suggestion: None,
+            fixable: false,
+        }
+    }
+
+    /// Adds a suggestion that must be carried out by hand.
+    #[must_use]
+    pub fn suggest(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    /// Adds a suggestion that the doctor applies in fix mode.
+    #[must_use]
+    pub fn fixable(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self.fixable = true;
+        self
+    }
+}
+
+/// Result of a doctor run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoctorReport {
+    /// Findings in the order they were detected.
+    pub findings: Vec<Finding>,
+    /// Whether repairs were applied.
+    pub fix_applied: bool,
+    /// Number of records read.
+    pub records_scanned: usize,
+    /// Whether errors were found that the fix mode cannot repair, such as
+    /// dependency cycles.
+    pub unrepairable: bool,
+}
+
+impl DoctorReport {
+    /// Whether any finding is an error.
+    #[must_use]
+    pub fn has_errors(&self) -> bool {
+        self.findings
+            .iter()
+            .any(|finding| finding.severity == Severity::Error)
+    }
+}
+
+/// Checks a repository and optionally repairs it.
+///
+/// Repairs remove duplicate records (keeping the last one), invalid records,
+/// and orphaned dependencies, and rebuild the index.
+///
+/// # Arguments
+///
+/// * `repo` - The repository to check
+/// * `fix` - Whether to apply repairs
+/// * `on_record` - Called with the running count of records read
+///
+/// # Returns
+///
+/// The findings, including one `Info` finding per applied repair.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The configuration is invalid or from a newer schema
+/// - The issues cannot be read
+/// - Repairs cannot be written
+pub fn diagnose(repo: &RepoContext, fix: bool, on_record: &dyn Fn(usize)) -> Result<DoctorReport> {
+    let pearls_dir = repo.pearls_dir();
+    let config = repo.load_config()?;
+    let issues_path = repo.issues_path();
+    let mut findings = Vec::new();
+    let mut pearls = Vec::new();
+    let mut unrepairable = false;
+
+    let (records_scanned, invalid_records) = match config.layout {
+        StorageLayout::Jsonl => scan_jsonl(issues_path, &mut pearls, &mut findings, on_record)?,
+        StorageLayout::Dir => scan_dir(
+            &pearls_dir.join("issues"),
+            &mut pearls,
+            &mut findings,
+            on_record,
+        )?,
+    };
+
+    if config.schema_version < CURRENT_SCHEMA_VERSION {
+        findings.push(
+            Finding::new(
+                Severity::Warning,
+                format!(
+                    "Schema version {} is older than {}",
+                    config.schema_version, CURRENT_SCHEMA_VERSION
+                ),
+            )
+            .suggest("Run 'prl migrate' to upgrade"),
+        );
+    }
+
+    let (deduped, duplicate_ids) = dedupe_pearls(&pearls);
+    if !duplicate_ids.is_empty() {
+        findings.push(
+            Finding::new(
+                Severity::Error,
+                format!("Duplicate Pearl IDs detected: {}", duplicate_ids.join(", ")),
+            )
+            .fixable("Keep the last record of each ID"),
+        );
+    }
+
+    for (pearl_id, target_id) in &find_orphaned_deps(&deduped) {
+        findings.push(
+            Finding::new(
+                Severity::Warning,
+                format!(
+                    "Orphaned dependency: {} references missing {}",
+                    pearl_id, target_id
+                ),
+            )
+            .fixable("Remove the dependency"),
+        );
+    }
+
+    match IssueGraph::from_pearls(deduped.clone()) {
+        Ok(graph) => {
+            for pearl in &deduped {
+                if pearl.status == Status::Blocked && !graph.is_blocked(&pearl.id) {
+                    findings.push(
+                        Finding::new(
+                            Severity::Warning,
+                            format!(
+                                "Pearl {} is marked blocked but has no open blockers",
+                                pearl.id
+                            ),
+                        )
+                        .suggest("Move it back to open or in_progress"),
+                    );
+                }
+                if pearl.status == Status::Closed && graph.is_blocked(&pearl.id) {
+                    unrepairable = true;
+                    findings.push(
+                        Finding::new(
+                            Severity::Error,
+                            format!(
+                                "Pearl {} is closed but still blocked by open dependencies",
+                                pearl.id
+                            ),
+                        )
+                        .suggest("Close its blockers or reopen it"),
+                    );
+                }
+            }
+        }
+        Err(err) => {
+            unrepairable = true;
+            findings.push(
+                Finding::new(Severity::Error, format!("Cycle detected: {}", err))
+                    .suggest("Remove one of the dependencies in the cycle"),
+            );
+        }
+    }
+
+    let index_path = repo.index_path();
+    let check_index =
+        config.layout == StorageLayout::Jsonl && (config.use_index || index_path.exists());
+    if check_index {
+        if let Some(message) = check_index_file(index_path, issues_path, deduped.len())? {
+            findings.push(Finding::new(Severity::Warning, message).fixable("Rebuild the index"));
+        }
+    }
+
+    if fix {
+        let mut fixed = deduped.clone();
+        let removed = remove_orphaned_deps(&mut fixed);
+
+        let mut storage = Storage::open(
+            pearls_dir,
+            &Config {
+                use_index: false,
+                ..config.clone()
+            },
+        )?;
+        storage.save_all(&fixed)?;
+
+        if check_index {
+            let mut index = Index::new(index_path.to_path_buf());
+            index.rebuild(issues_path)?;
+            index.save()?;
+            findings.push(Finding::new(
+                Severity::Info,
+                format!("Index rebuilt with {} entries", index.len()),
+            ));
+        }
+
+        findings.push(Finding::new(
+            Severity::Info,
+            format!(
+                "Fix applied: removed {} orphaned deps, {} duplicate IDs, {} invalid records",
+                removed,
+                duplicate_ids.len(),
+                invalid_records
+            ),
+        ));
+    }
+
+    Ok(DoctorReport {
+        findings,
+        fix_applied: fix,
+        records_scanned,
+        unrepairable,
+    })
+}
+
+/// Scans `issues.jsonl` line by line.
+///
+/// Returns the number of records read and the number of lines that are not
+/// valid JSON.
+fn scan_jsonl(
+    issues_path: &Path,
+    pearls: &mut Vec<Pearl>,
+    findings: &mut Vec<Finding>,
+    on_record: &dyn Fn(usize),
+) -> Result<(usize, usize)> {
+    if !issues_path.exists() {
+        return Ok((0, 0));
+    }
+
+    let mut invalid_lines = 0usize;
+    let file = std::fs::File::open(issues_path)?;
+    let reader = std::io::BufReader::with_capacity(64 * 1024, file);
+    let mut processed = 0usize;
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        processed += 1;
+        on_record(processed);
+        match serde_json::from_str::<Pearl>(&line) {
+            Ok(pearl) => {
+                if let Err(err) = pearl.validate() {
+                    findings.push(Finding::new(
+                        Severity::Error,
+                        format!("Line {}: {}", idx + 1, err),
+                    ));
+                }
+                pearls.push(pearl);
+            }
+            Err(err) => {
+                invalid_lines += 1;
+                findings.push(
+                    Finding::new(
+                        Severity::Error,
+                        format!("Line {}: Invalid JSON ({})", idx + 1, err),
+                    )
+                    .fixable("Drop the line"),
+                );
+            }
+        }
+    }
+    Ok((processed, invalid_lines))
+}
+
+/// Scans the `issues/` directory file by file.
+///
+/// Returns the number of files read and the number that are not valid JSON.
+/// Files whose name does not match the Pearl ID they contain are reported as
+/// errors.
+fn scan_dir(
+    issues_dir: &Path,
+    pearls: &mut Vec<Pearl>,
+    findings: &mut Vec<Finding>,
+    on_record: &dyn Fn(usize),
+) -> Result<(usize, usize)> {
+    if !issues_dir.exists() {
+        return Ok((0, 0));
+    }
+
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(issues_dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") && path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    let mut invalid_files = 0usize;
+    for (idx, path) in files.iter().enumerate() {
+        on_record(idx + 1);
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let content = std::fs::read_to_string(path)?;
+        match serde_json::from_str::<Pearl>(&content) {
+            Ok(pearl) => {
+                if let Err(err) = pearl.validate() {
+                    findings.push(Finding::new(
+                        Severity::Error,
+                        format!("File issues/{}: {}", name, err),
+                    ));
+                }
+                if name != format!("{}.json", pearl.id) {
+                    findings.push(
+                        Finding::new(
+                            Severity::Error,
+                            format!(
+                                "File issues/{}: name does not match Pearl ID {}",
+                                name, pearl.id
+                            ),
+                        )
+                        .fixable(format!("Rename it to {}.json", pearl.id)),
+                    );
+                }
+                pearls.push(pearl);
+            }
+            Err(err) => {
+                invalid_files += 1;
+                findings.push(
+                    Finding::new(
+                        Severity::Error,
+                        format!("File issues/{}: Invalid JSON ({})", name, err),
+                    )
+                    .fixable("Remove the file"),
+                );
+            }
+        }
+    }
+    Ok((files.len(), invalid_files))
+}
+
+/// Verifies the binary index against the issues file.
+///
+/// Returns a message describing the problem if the index is missing, unreadable,
+/// stale, or disagrees with the number of Pearls.
+fn check_index_file(
+    index_path: &Path,
+    issues_path: &Path,
+    expected: usize,
+) -> Result<Option<String>> {
+    if !index_path.exists() {
+        return Ok(Some(
+            "Index is enabled but .pearls/index.bin is missing".to_string(),
+        ));
+    }
+
+    let index = match Index::load(index_path.to_path_buf()) {
+        Ok(index) => index,
+        Err(err) => return Ok(Some(format!("Index is unreadable: {}", err))),
+    };
+
+    if index.is_stale(issues_path)? {
+        return Ok(Some(
+            "Index is stale: content hash does not match issues.jsonl".to_string(),
+        ));
+    }
+
+    if index.len() != expected {
+        return Ok(Some(format!(
+            "Index has {} entries but issues.jsonl holds {} Pearls",
+            index.len(),
+            expected
+        )));
+    }
+
+    Ok(None)
+}
+
+/// Removes duplicate IDs, keeping the last occurrence of each.
+///
+/// Incremental writes append updated records, so the last line for an ID is the
+/// most recent one.
+fn dedupe_pearls(pearls: &[Pearl]) -> (Vec<Pearl>, Vec<String>) {
+    let mut seen = HashSet::new();
+    let mut dupes = Vec::new();
+    let mut unique = Vec::new();
+
+    for pearl in pearls.iter().rev() {
+        if seen.contains(&pearl.id) {
+            dupes.push(pearl.id.clone());
+        } else {
+            seen.insert(pearl.id.clone());
+            unique.push(pearl.clone());
+        }
+    }
+
+    unique.reverse();
+    dupes.reverse();
+    (unique, dupes)
+}
+
+fn find_orphaned_deps(pearls: &[Pearl]) -> Vec<(String, String)> {
+    let ids: HashSet<String> = pearls.iter().map(|pearl| pearl.id.clone()).collect();
+    let mut orphaned = Vec::new();
+
+    for pearl in pearls {
+        for dep in &pearl.deps {
+            if !ids.contains(&dep.target_id) {
+                orphaned.push((pearl.id.clone(), dep.target_id.clone()));
+            }
+        }
+    }
+
+    orphaned
+}
+
+fn remove_orphaned_deps(pearls: &mut [Pearl]) -> usize {
+    let ids: HashSet<String> = pearls.iter().map(|pearl| pearl.id.clone()).collect();
+    let mut removed = 0usize;
+
+    for pearl in pearls {
+        let before = pearl.deps.len();
+        pearl.deps.retain(|dep| ids.contains(&dep.target_id));
+        removed += before - pearl.deps.len();
+    }
+
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pearls_core::{DepType, Dependency};
+
+    fn sample_pearl(id: &str, title: &str, blocked_by: Option<&str>) -> Pearl {
+        let mut pearl = Pearl::new(title.to_string(), "author".to_string());
+        pearl.id = id.to_string();
+        pearl.deps = blocked_by
+            .map(|target| Dependency {
+                target_id: target.to_string(),
+                dep_type: DepType::Blocks,
+            })
+            .into_iter()
+            .collect();
+        pearl
+    }
+
+    #[test]
+    fn test_dedupe_keeps_last_record_and_orphans_are_removed() {
+        let pearls = vec![
+            sample_pearl("prl-aaa111", "Old", None),
+            sample_pearl("prl-bbb222", "Other", Some("prl-zzz999")),
+            sample_pearl("prl-aaa111", "New", None),
+        ];
+
+        let (mut unique, dupes) = dedupe_pearls(&pearls);
+        assert_eq!(dupes, vec!["prl-aaa111"]);
+        assert_eq!(unique.len(), 2);
+        assert_eq!(unique[1].title, "New");
+        assert_eq!(
+            find_orphaned_deps(&unique),
+            vec![("prl-bbb222".to_string(), "prl-zzz999".to_string())]
+        );
+        assert_eq!(remove_orphaned_deps(&mut unique), 1);
+        assert!(find_orphaned_deps(&unique).is_empty());
+    }
+}
@@ -32,6 +32,10 @@ pub enum ErrorCode {
     InvalidInput,
     /// JSON serialization or parsing failed.
     JsonError,
+    /// The repository was written by a newer version of Pearls.
+    UnsupportedSchema,
+    /// The call was refused by the MCP access policy.
+    PolicyDenied,
     /// A fallback for unexpected errors.
     Unknown,
 }
@@ -57,6 +61,14 @@ pub enum AppError {
     /// IO error not represented by core errors.
     #[error(transparent)]
     Io(#[from] std::io::Error),
+
+    /// Error from the SQLite query cache.
+    #[error("Query cache error: {0}")]
+    Cache(#[from] rusqlite::Error),
+
+    /// The call was refused by an access policy.
+    #[error("Denied by policy: {0}")]
+    PolicyDenied(String),
 }
 
 impl AppError {
@@ -67,6 +79,8 @@ impl AppError {
             AppError::RepoNotInitialized { .. } => ErrorCode::RepoNotInitialized,
             AppError::InvalidInput(_) => ErrorCode::InvalidInput,
             AppError::Io(_) => ErrorCode::IoError,
+            AppError::Cache(_) => ErrorCode::IoError,
+            AppError::PolicyDenied(_) => ErrorCode::PolicyDenied,
             AppError::Core(core) => match core {
                 CoreError::NotFound(_) => ErrorCode::NotFound,
                 CoreError::AmbiguousId(_, _) => ErrorCode::AmbiguousId,
@@ -76,6 +90,7 @@ impl AppError {
                 CoreError::Io(_) => ErrorCode::IoError,
                 CoreError::Json(_) => ErrorCode::JsonError,
                 CoreError::Git(_) => ErrorCode::GitError,
+                CoreError::UnsupportedSchema(_, _) => ErrorCode::UnsupportedSchema,
             },
         }
     }
@@ -89,6 +104,8 @@ impl AppError {
             })),
             AppError::InvalidInput(_) => None,
             AppError::Io(_) => None,
+            AppError::Cache(_) => None,
+            AppError::PolicyDenied(_) => None,
             AppError::Core(core) => match core {
                 CoreError::AmbiguousId(partial, matches) => Some(serde_json::json!({
                     "partial": partial,
@@ -97,6 +114,10 @@ impl AppError {
                 CoreError::CycleDetected(cycle) => Some(serde_json::json!({
                     "cycle": cycle,
                 })),
+                CoreError::UnsupportedSchema(found, supported) => Some(serde_json::json!({
+                    "schema_version": found,
+                    "supported_schema_version": supported,
+                })),
                 _ => None,
             },
         }
@@ -0,0 +1,653 @@
+// Rust guideline compliant 2026-02-10
+
+//! Notifications on Pearl changes.
+//!
+//! Events are found by comparing the Pearls with a snapshot taken at the end
+//! of the previous dispatch, so every mutation is seen whether it came from
+//! the CLI, the MCP server, a Git hook, or a merge. Each event is delivered to
+//! the `[[events.hooks]]` whose filters match it, by running a shell command
+//! with the event JSON on standard input or by POSTing it to a URL. Failed
+//! deliveries are retried and then appended to `.pearls/events-dead.jsonl`.
+//! Both files are local to the clone and added to `.pearls/.gitignore`.
+//!
+//! `.pearls/config.toml` is shared through Git, so commands are only run for
+//! hooks from the global or `--config` file; repository hooks may only POST.
+
+use crate::error::{AppError, Result};
+use crate::ready::ready_queue;
+use crate::repo::RepoContext;
+use crate::time::unix_timestamp;
+use fs2::FileExt;
+use pearls_core::{Config, ConfigSource, EventHook, EventKind, EventsConfig, Pearl, Status};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Read, Seek, Write};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Name of the snapshot of the last dispatch inside `.pearls`.
+const STATE_FILE: &str = "events-state.json";
+
+/// Name of the dead-letter log inside `.pearls`.
+const DEAD_LETTER_FILE: &str = "events-dead.jsonl";
+
+/// Timeout for a single webhook request.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Interval at which a running hook command is checked against the deadline.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A change to a Pearl.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Event {
+    /// Kind of change.
+    pub event: EventKind,
+    /// Pearl ID.
+    pub id: String,
+    /// Pearl title.
+    pub title: String,
+    /// Status after the change.
+    pub status: Status,
+    /// Status before the change, for `closed` and `status_changed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_status: Option<Status>,
+    /// Pearl priority.
+    pub priority: u8,
+    /// Pearl labels.
+    pub labels: Vec<String>,
+    /// Unix timestamp of the dispatch.
+    pub timestamp: i64,
+}
+
+/// Outcome of a dispatch.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DispatchReport {
+    /// Events found since the previous dispatch.
+    pub events: Vec<Event>,
+    /// Deliveries that succeeded.
+    pub delivered: usize,
+    /// Deliveries written to the dead-letter log.
+    pub failed: usize,
+    /// Hooks whose command was not run because it came from the repository
+    /// config.
+    pub untrusted: usize,
+}
+
+/// What the snapshot records about a Pearl.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct PearlState {
+    title: String,
+    status: Status,
+    priority: u8,
+    labels: Vec<String>,
+    updated_at: i64,
+}
+
+/// Snapshot of the Pearls at the end of a dispatch.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Snapshot {
+    pearls: BTreeMap<String, PearlState>,
+    ready: BTreeSet<String>,
+}
+
+impl Snapshot {
+    fn capture(pearls: Vec<Pearl>) -> Result<Self> {
+        let states = pearls
+            .iter()
+            .map(|pearl| {
+                (
+                    pearl.id.clone(),
+                    PearlState {
+                        title: pearl.title.clone(),
+                        status: pearl.status,
+                        priority: pearl.priority,
+                        labels: pearl.labels.clone(),
+                        updated_at: pearl.updated_at,
+                    },
+                )
+            })
+            .collect();
+        let ready = ready_queue(pearls)?
+            .into_iter()
+            .map(|pearl| pearl.id)
+            .collect();
+        Ok(Self {
+            pearls: states,
+            ready,
+        })
+    }
+}
+
+/// One line of the dead-letter log.
+#[derive(Debug, Serialize)]
+struct DeadLetter<'a> {
+    timestamp: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    command: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<&'a str>,
+    attempts: u32,
+    error: &'a str,
+    event: &'a Event,
+}
+
+/// Finds the changes since the previous dispatch and notifies the hooks.
+///
+/// The command and the URL of a hook are delivered and retried separately.
+/// With a `budget`, deliveries stop at the deadline: commands still running
+/// are killed, and every delivery not completed is dead-lettered.
+///
+/// Without `[[events.hooks]]` nothing is done. The first dispatch after hooks
+/// are configured only records a snapshot. Commands of hooks set in
+/// `.pearls/config.toml` are skipped and counted as untrusted.
+///
+/// # Arguments
+///
+/// * `repo` - The repository
+/// * `budget` - Optional time limit for all deliveries
+///
+/// # Returns
+///
+/// The events found and the number of deliveries that succeeded and failed.
+///
+/// # Errors
+///
+/// Returns an error if the configuration, Pearls, or snapshot cannot be read,
+/// or if the snapshot or dead-letter log cannot be written. Failed deliveries
+/// are not errors.
+pub fn dispatch_events(repo: &RepoContext, budget: Option<Duration>) -> Result<DispatchReport> {
+    let deadline = budget.map(|budget| Instant::now() + budget);
+    let layered = Config::load_layered(Some(repo.pearls_dir()))?;
+    let trusted = matches!(
+        layered.source("events.hooks"),
+        ConfigSource::Global | ConfigSource::Explicit
+    );
+    let mut config = layered.config.events;
+    if config.hooks.is_empty() {
+        return Ok(DispatchReport::default());
+    }
+    let invalid = config
+        .hooks
+        .iter()
+        .position(|hook| hook.command.is_none() && hook.url.is_none());
+    if let Some(index) = invalid {
+        return Err(AppError::InvalidInput(format!(
+            "Event hook {} needs a command or a url",
+            index + 1
+        )));
+    }
+
+    let mut untrusted = 0;
+    if !trusted {
+        for hook in &mut config.hooks {
+            if hook.command.take().is_some() {
+                untrusted += 1;
+            }
+        }
+        config.hooks.retain(|hook| hook.url.is_some());
+    }
+
+    let state_path = repo.pearls_dir().join(STATE_FILE);
+    if !state_path.exists() {
+        repo.ignore_local_files()?;
+    }
+    let mut state_file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(state_path)?;
+    state_file.lock_exclusive()?;
+
+    let mut content = String::new();
+    state_file.read_to_string(&mut content)?;
+    let current = Snapshot::capture(repo.open_storage()?.load_all()?)?;
+    let previous: Option<Snapshot> = if content.trim().is_empty() {
+        None
+    } else {
+        serde_json::from_str(&content).ok()
+    };
+    let timestamp = unix_timestamp()?;
+    let events = previous
+        .map(|previous| diff(&previous, &current, timestamp))
+        .unwrap_or_default();
+
+    let content = serde_json::to_string(&current).map_err(pearls_core::Error::from)?;
+    state_file.set_len(0)?;
+    state_file.rewind()?;
+    state_file.write_all(content.as_bytes())?;
+    state_file.sync_all()?;
+    FileExt::unlock(&state_file)?;
+
+    let mut report = DispatchReport {
+        events,
+        untrusted,
+        ..DispatchReport::default()
+    };
+    for event in &report.events {
+        let payload = serde_json::to_string(event).map_err(pearls_core::Error::from)?;
+        for hook in config.hooks.iter().filter(|hook| matches(hook, event)) {
+            let targets = hook
+                .command
+                .as_deref()
+                .map(Target::Command)
+                .into_iter()
+                .chain(hook.url.as_deref().map(Target::Url));
+            for target in targets {
+                match deliver(&config, target, event, &payload, deadline) {
+                    Ok(()) => report.delivered += 1,
+                    Err(failure) => {
+                        report.failed += 1;
+                        write_dead_letter(repo, target, event, &failure)?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Lists the events between two snapshots, ordered by Pearl ID.
+fn diff(previous: &Snapshot, current: &Snapshot, timestamp: i64) -> Vec<Event> {
+    let event = |kind: EventKind, id: &str, state: &PearlState, previous_status| Event {
+        event: kind,
+        id: id.to_string(),
+        title: state.title.clone(),
+        status: state.status,
+        previous_status,
+        priority: state.priority,
+        labels: state.labels.clone(),
+        timestamp,
+    };
+
+    let mut events = Vec::new();
+    for (id, state) in &current.pearls {
+        match previous.pearls.get(id) {
+            None => events.push(event(EventKind::Created, id, state, None)),
+            Some(before) if before.status != state.status => {
+                let kind = if state.status == Status::Closed {
+                    EventKind::Closed
+                } else {
+                    EventKind::StatusChanged
+                };
+                events.push(event(kind, id, state, Some(before.status)));
+            }
+            Some(before) if before != state => {
+                events.push(event(EventKind::Updated, id, state, None));
+            }
+            Some(_) => {}
+        }
+        if current.ready.contains(id) && !previous.ready.contains(id) {
+            events.push(event(EventKind::Ready, id, state, None));
+        }
+    }
+    for (id, state) in &previous.pearls {
+        if !current.pearls.contains_key(id) {
+            events.push(event(EventKind::Deleted, id, state, None));
+        }
+    }
+    events
+}
+
+/// Whether an event passes the filters of a hook.
+fn matches(hook: &EventHook, event: &Event) -> bool {
+    (hook.events.is_empty() || hook.events.contains(&event.event))
+        && (hook.labels.is_empty() || event.labels.iter().any(|label| hook.labels.contains(label)))
+        && (hook.priorities.is_empty() || hook.priorities.contains(&event.priority))
+}
+
+/// Where a hook delivers events.
+#[derive(Debug, Clone, Copy)]
+enum Target<'a> {
+    /// A shell command.
+    Command(&'a str),
+    /// A webhook URL.
+    Url(&'a str),
+}
+
+/// A delivery that gave up.
+struct Failure {
+    error: String,
+    attempts: u32,
+}
+
+/// Delivers an event to one target, retrying with a doubling delay until
+/// `max_attempts` or the deadline is reached.
+fn deliver(
+    config: &EventsConfig,
+    target: Target<'_>,
+    event: &Event,
+    payload: &str,
+    deadline: Option<Instant>,
+) -> std::result::Result<(), Failure> {
+    let mut delay = Duration::from_millis(config.retry_delay_ms);
+    let mut attempts = 0;
+    loop {
+        let Some(left) = remaining(deadline) else {
+            return Err(Failure {
+                error: "Delivery time budget exhausted".to_string(),
+                attempts,
+            });
+        };
+        attempts += 1;
+        let result = match target {
+            Target::Command(command) => run_command(command, event, payload, deadline),
+            Target::Url(url) => post(url, payload, left.min(HTTP_TIMEOUT)),
+        };
+        let error = match result {
+            Ok(()) => return Ok(()),
+            Err(error) => error,
+        };
+        let out_of_time = remaining(deadline).is_none_or(|left| left <= delay);
+        if attempts >= config.max_attempts.max(1) || out_of_time {
+            return Err(Failure { error, attempts });
+        }
+        std::thread::sleep(delay);
+        delay = delay.saturating_mul(2);
+    }
+}
+
+/// Returns the time left before the deadline; unlimited without one, and
+/// `None` once it has passed.
+fn remaining(deadline: Option<Instant>) -> Option<Duration> {
+    match deadline {
+        Some(deadline) => deadline
+            .checked_duration_since(Instant::now())
+            .filter(|remaining| !remaining.is_zero()),
+        None => Some(Duration::MAX),
+    }
+}
+
+/// Runs a hook command with the event JSON on standard input.
+///
+/// The event kind and Pearl ID are also passed as `PEARLS_EVENT` and
+/// `PEARLS_ID`. A command still running at the deadline is killed.
+fn run_command(
+    command: &str,
+    event: &Event,
+    payload: &str,
+    deadline: Option<Instant>,
+) -> std::result::Result<(), String> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    let mut child = shell
+        .arg(command)
+        .env("PEARLS_EVENT", event_name(event.event))
+        .env("PEARLS_ID", &event.id)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Failed to run '{}': {}", command, err))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that does not read its input closes the pipe early.
+        let _ = stdin.write_all(payload.as_bytes());
+    }
+    // Drain stderr on a thread so a chatty command cannot block on the pipe.
+    let stderr = child.stderr.take().map(|mut pipe| {
+        std::thread::spawn(move || {
+            let mut output = String::new();
+            let _ = pipe.read_to_string(&mut output);
+            output
+        })
+    });
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if remaining(deadline).is_none() => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("'{}' timed out", command));
+            }
+            Ok(None) => std::thread::sleep(POLL_INTERVAL),
+            Err(err) => return Err(format!("Failed to run '{}': {}", command, err)),
+        }
+    };
+    if status.success() {
+        Ok(())
+    } else {
+        let stderr = stderr
+            .and_then(|handle| handle.join().ok())
+            .unwrap_or_default();
+        Err(format!(
+            "'{}' failed with {}: {}",
+            command,
+            status,
+            stderr.trim()
+        ))
+    }
+}
+
+/// POSTs the event JSON to a URL.
+fn post(url: &str, payload: &str, timeout: Duration) -> std::result::Result<(), String> {
+    ureq::post(url)
+        .timeout(timeout)
+        .set("Content-Type", "application/json")
+        .send_string(payload)
+        .map(|_| ())
+        .map_err(|err| format!("POST {} failed: {}", url, err))
+}
+
+/// Returns the serialized name of an event kind.
+fn event_name(kind: EventKind) -> String {
+    serde_json::to_value(kind)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Appends a failed delivery to the dead-letter log.
+fn write_dead_letter(
+    repo: &RepoContext,
+    target: Target<'_>,
+    event: &Event,
+    failure: &Failure,
+) -> Result<()> {
+    let (command, url) = match target {
+        Target::Command(command) => (Some(command), None),
+        Target::Url(url) => (None, Some(url)),
+    };
+    let entry = DeadLetter {
+        timestamp: unix_timestamp()?,
+        command,
+        url,
+        attempts: failure.attempts,
+        error: &failure.error,
+        event,
+    };
+    let mut line = serde_json::to_string(&entry).map_err(pearls_core::Error::from)?;
+    line.push('\n');
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(repo.pearls_dir().join(DEAD_LETTER_FILE))?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pearls_core::{Config, Storage};
+    use std::io::BufRead;
+    use std::net::TcpListener;
+    use tempfile::TempDir;
+
+    /// Accepts `count` requests, answering each with `status`, and returns
+    /// their bodies.
+    fn http_stub(status: u16, count: usize) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind");
+        let url = format!("http://{}/hook", listener.local_addr().expect("No address"));
+        let handle = std::thread::spawn(move || {
+            let mut bodies = Vec::new();
+            for stream in listener.incoming().take(count) {
+                let mut stream = stream.expect("Failed to accept");
+                let mut reader = std::io::BufReader::new(stream.try_clone().expect("Clone"));
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).expect("Failed to read header");
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().expect("Bad length");
+                        }
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).expect("Failed to read body");
+                bodies.push(String::from_utf8(body).expect("Body is not UTF-8"));
+                let response = format!(
+                    "HTTP/1.1 {} Stub\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                stream
+                    .write_all(response.as_bytes())
+                    .expect("Failed to respond");
+            }
+            bodies
+        });
+        (url, handle)
+    }
+
+    fn init_repo(hooks: Vec<EventHook>) -> (TempDir, RepoContext) {
+        let temp = TempDir::new().expect("Failed to create temp dir");
+        let pearls_dir = temp.path().join(".pearls");
+        std::fs::create_dir(&pearls_dir).expect("Failed to create .pearls dir");
+        std::fs::File::create(pearls_dir.join("issues.jsonl")).expect("Failed to create issues");
+        let config = Config {
+            events: EventsConfig {
+                hooks,
+                max_attempts: 2,
+                retry_delay_ms: 0,
+            },
+            ..Config::default()
+        };
+        config.save(&pearls_dir).expect("Failed to save config");
+        let repo = RepoContext::discover(Some(temp.path())).expect("Failed to discover repo");
+        (temp, repo)
+    }
+
+    fn save(repo: &RepoContext, pearl: &Pearl) {
+        let mut storage = Storage::new(repo.issues_path().to_path_buf()).expect("Storage");
+        storage.save(pearl).expect("Failed to save Pearl");
+    }
+
+    #[test]
+    fn test_dispatch_posts_filtered_events() {
+        let (url, stub) = http_stub(200, 2);
+        let (_temp, repo) = init_repo(vec![EventHook {
+            events: vec![EventKind::Closed, EventKind::Ready],
+            priorities: vec![0],
+            url: Some(url),
+            ..EventHook::default()
+        }]);
+        let mut urgent = Pearl::new("Urgent".to_string(), "author".to_string());
+        urgent.priority = 0;
+        let mut minor = Pearl::new("Minor".to_string(), "author".to_string());
+        minor.priority = 3;
+
+        assert!(dispatch_events(&repo, None)
+            .expect("Baseline failed")
+            .events
+            .is_empty());
+        save(&repo, &urgent);
+        save(&repo, &minor);
+        let report = dispatch_events(&repo, None).expect("Dispatch failed");
+        assert_eq!(report.events.len(), 4);
+        assert_eq!(report.delivered, 1);
+
+        urgent.status = Status::Closed;
+        save(&repo, &urgent);
+        let report = dispatch_events(&repo, None).expect("Dispatch failed");
+        assert_eq!(report.events[0].event, EventKind::Closed);
+        assert_eq!(report.events[0].previous_status, Some(Status::Open));
+        assert_eq!(report.delivered, 1);
+
+        let bodies = stub.join().expect("Stub panicked");
+        let first: serde_json::Value = serde_json::from_str(&bodies[0]).expect("Bad body");
+        assert_eq!(first["event"], "ready");
+        assert_eq!(first["id"], urgent.id.as_str());
+        let second: serde_json::Value = serde_json::from_str(&bodies[1]).expect("Bad body");
+        assert_eq!(second["event"], "closed");
+    }
+
+    #[test]
+    fn test_failed_delivery_is_retried_then_dead_lettered() {
+        let (url, stub) = http_stub(500, 2);
+        let (_temp, repo) = init_repo(vec![EventHook {
+            events: vec![EventKind::Created],
+            url: Some(url),
+            ..EventHook::default()
+        }]);
+        dispatch_events(&repo, None).expect("Baseline failed");
+        save(&repo, &Pearl::new("New".to_string(), "author".to_string()));
+
+        let report = dispatch_events(&repo, None).expect("Dispatch failed");
+        assert_eq!(report.failed, 1);
+        assert_eq!(stub.join().expect("Stub panicked").len(), 2);
+        let dead = std::fs::read_to_string(repo.pearls_dir().join(DEAD_LETTER_FILE))
+            .expect("Missing dead-letter log");
+        let entry: serde_json::Value =
+            serde_json::from_str(dead.lines().next().expect("Empty log")).expect("Bad entry");
+        assert_eq!(entry["attempts"], 2);
+        assert_eq!(entry["event"]["event"], "created");
+    }
+
+    #[test]
+    fn test_repository_commands_are_not_run() {
+        let temp = TempDir::new().expect("Failed to create temp dir");
+        let marker = temp.path().join("ran");
+        let (_repo_temp, repo) = init_repo(vec![EventHook {
+            command: Some(format!("touch '{}'", marker.display())),
+            ..EventHook::default()
+        }]);
+        dispatch_events(&repo, None).expect("Baseline failed");
+        save(&repo, &Pearl::new("New".to_string(), "author".to_string()));
+
+        let report = dispatch_events(&repo, None).expect("Dispatch failed");
+        assert_eq!(report.untrusted, 1);
+        assert_eq!(report.delivered, 0);
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn test_deliveries_stop_at_the_deadline() {
+        let (_temp, repo) = init_repo(vec![EventHook {
+            url: Some("http://127.0.0.1:9/unreachable".to_string()),
+            ..EventHook::default()
+        }]);
+        dispatch_events(&repo, None).expect("Baseline failed");
+        save(&repo, &Pearl::new("New".to_string(), "author".to_string()));
+
+        let report = dispatch_events(&repo, Some(Duration::ZERO)).expect("Dispatch failed");
+        assert_eq!(report.failed, 2);
+        let dead = std::fs::read_to_string(repo.pearls_dir().join(DEAD_LETTER_FILE))
+            .expect("Missing dead-letter log");
+        let entry: serde_json::Value =
+            serde_json::from_str(dead.lines().next().expect("Empty log")).expect("Bad entry");
+        assert_eq!(entry["attempts"], 0);
+
+        let event = report.events[0].clone();
+        let started = Instant::now();
+        let result = run_command(
+            "sleep 5",
+            &event,
+            "{}",
+            Some(Instant::now() + Duration::from_millis(100)),
+        );
+        assert!(result
+            .expect_err("Command should time out")
+            .contains("timed out"));
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+}
@@ -0,0 +1,11 @@
+// Rust guideline compliant 2026-02-10
+
+//! Export of Pearls to formats read by people and other tools.
+//!
+//! Exporters render a set of Pearls without touching the repository. Where a
+//! matching importer exists, the output can be imported again and updates the
+//! same Pearls.
+
+pub mod csv;
+pub mod html;
+pub mod markdown;
@@ -0,0 +1,248 @@
+// Rust guideline compliant 2026-02-10
+
+//! CSV export for spreadsheets.
+//!
+//! Columns are chosen by name. Lists such as labels and dependencies are
+//! flattened into a single cell joined by `;`, and `meta.<key>` columns hold
+//! metadata values. The default columns can be imported again with
+//! `prl import csv` and no mapping.
+
+use crate::error::{AppError, Result};
+use pearls_core::{DepType, Pearl};
+use serde_json::Value;
+
+/// Columns written when none are requested.
+pub const DEFAULT_COLUMNS: &[&str] = &[
+    "id",
+    "title",
+    "status",
+    "priority",
+    "labels",
+    "author",
+    "created_at",
+    "updated_at",
+    "deps",
+    "description",
+];
+
+/// Column names accepted in addition to `meta.<key>`.
+pub const KNOWN_COLUMNS: &[&str] = &[
+    "id",
+    "title",
+    "description",
+    "status",
+    "priority",
+    "author",
+    "labels",
+    "created_at",
+    "updated_at",
+    "deps",
+    "blocked_by",
+    "blocks",
+    "parent",
+    "related",
+    "comments",
+];
+
+/// A CSV column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsvColumn {
+    /// A Pearl field or derived list, named as in [`KNOWN_COLUMNS`].
+    Field(String),
+    /// A metadata value.
+    Meta(String),
+}
+
+impl CsvColumn {
+    fn header(&self) -> String {
+        match self {
+            CsvColumn::Field(name) => name.clone(),
+            CsvColumn::Meta(key) => format!("meta.{}", key),
+        }
+    }
+}
+
+/// Parses a comma-separated list of column names.
+///
+/// # Arguments
+///
+/// * `spec` - Column names, such as `id,title,meta.owner`
+///
+/// # Returns
+///
+/// The columns in order.
+///
+/// # Errors
+///
+/// Returns an error if a column is unknown or the list is empty.
+pub fn parse_columns(spec: &str) -> Result<Vec<CsvColumn>> {
+    let mut columns = Vec::new();
+    for name in spec
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        let column = match name.strip_prefix("meta.") {
+            Some(key) if !key.is_empty() => CsvColumn::Meta(key.to_string()),
+            _ if KNOWN_COLUMNS.contains(&name) => CsvColumn::Field(name.to_string()),
+            _ => {
+                return Err(AppError::InvalidInput(format!(
+                    "Unknown CSV column '{}'. Expected one of {} or meta.<key>",
+                    name,
+                    KNOWN_COLUMNS.join(", ")
+                )))
+            }
+        };
+        columns.push(column);
+    }
+    if columns.is_empty() {
+        return Err(AppError::InvalidInput(
+            "At least one CSV column is required".to_string(),
+        ));
+    }
+    Ok(columns)
+}
+
+/// Renders Pearls as CSV with a header row.
+///
+/// # Arguments
+///
+/// * `pearls` - Pearls to render, one per row
+/// * `columns` - Columns to write
+///
+/// # Returns
+///
+/// The CSV document.
+///
+/// # Errors
+///
+/// Returns an error if the CSV cannot be written.
+pub fn render_csv(pearls: &[Pearl], columns: &[CsvColumn]) -> Result<String> {
+    let mut writer = ::csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(columns.iter().map(CsvColumn::header))
+        .map_err(csv_error)?;
+    for pearl in pearls {
+        writer
+            .write_record(columns.iter().map(|column| cell(pearl, pearls, column)))
+            .map_err(csv_error)?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|err| AppError::InvalidInput(format!("Failed to write CSV: {err}")))?;
+    String::from_utf8(bytes)
+        .map_err(|err| AppError::InvalidInput(format!("Failed to write CSV: {err}")))
+}
+
+fn cell(pearl: &Pearl, pearls: &[Pearl], column: &CsvColumn) -> String {
+    let targets = |dep_type: DepType| {
+        pearl
+            .deps
+            .iter()
+            .filter(|dep| dep.dep_type == dep_type)
+            .map(|dep| dep.target_id.as_str())
+            .collect::<Vec<_>>()
+            .join(";")
+    };
+    let name = match column {
+        CsvColumn::Meta(key) => {
+            return match pearl.metadata.get(key) {
+                None | Some(Value::Null) => String::new(),
+                Some(Value::String(text)) => text.clone(),
+                Some(value) => value.to_string(),
+            }
+        }
+        CsvColumn::Field(name) => name.as_str(),
+    };
+    match name {
+        "id" => pearl.id.clone(),
+        "title" => pearl.title.clone(),
+        "description" => pearl.description.clone(),
+        "status" => enum_name(&pearl.status),
+        "priority" => pearl.priority.to_string(),
+        "author" => pearl.author.clone(),
+        "labels" => pearl.labels.join(";"),
+        "created_at" => timestamp(pearl.created_at),
+        "updated_at" => timestamp(pearl.updated_at),
+        "deps" => pearl
+            .deps
+            .iter()
+            .map(|dep| format!("{}:{}", enum_name(&dep.dep_type), dep.target_id))
+            .collect::<Vec<_>>()
+            .join(";"),
+        "blocked_by" => targets(DepType::Blocks),
+        "blocks" => pearls
+            .iter()
+            .filter(|other| {
+                other
+                    .deps
+                    .iter()
+                    .any(|dep| dep.dep_type == DepType::Blocks && dep.target_id == pearl.id)
+            })
+            .map(|other| other.id.as_str())
+            .collect::<Vec<_>>()
+            .join(";"),
+        "parent" => targets(DepType::ParentChild),
+        "related" => targets(DepType::Related),
+        "comments" => pearl.comments.len().to_string(),
+        _ => String::new(),
+    }
+}
+
+fn enum_name<T: serde::Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+fn timestamp(seconds: i64) -> String {
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_else(|| seconds.to_string())
+}
+
+fn csv_error(err: ::csv::Error) -> AppError {
+    AppError::InvalidInput(format!("Failed to write CSV: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::csv::{parse_csv, CsvMapping};
+    use pearls_core::{Dependency, Status};
+
+    #[test]
+    fn test_render_csv_round_trips_with_default_mapping() {
+        let mut blocker = Pearl::new("Schema, v2".to_string(), "alice".to_string());
+        blocker.id = "prl-aaa111".to_string();
+        blocker.status = Status::Closed;
+        blocker.labels = vec!["db".to_string(), "backend".to_string()];
+        blocker.metadata.insert("owner".to_string(), "dana".into());
+        let mut blocked = Pearl::new("API".to_string(), "bob".to_string());
+        blocked.id = "prl-bbb222".to_string();
+        blocked.description = "Line one\n\"quoted\"".to_string();
+        blocked.deps.push(Dependency {
+            target_id: blocker.id.clone(),
+            dep_type: DepType::Blocks,
+        });
+        let pearls = vec![blocker, blocked];
+
+        let columns = parse_columns("id,blocks,meta.owner").expect("columns");
+        let csv = render_csv(&pearls, &columns).expect("render");
+        assert_eq!(
+            csv,
+            "id,blocks,meta.owner\nprl-aaa111,prl-bbb222,dana\nprl-bbb222,,\n"
+        );
+        assert!(parse_columns("id,estimate").is_err());
+
+        let default_columns = parse_columns(&DEFAULT_COLUMNS.join(",")).expect("columns");
+        let csv = render_csv(&pearls, &default_columns).expect("render");
+        let import = parse_csv(csv.as_bytes(), &CsvMapping::default(), "x").expect("parse");
+        for (draft, original) in import.drafts.iter().zip(&pearls) {
+            let mut parsed = draft.pearl.clone();
+            parsed.metadata = original.metadata.clone();
+            assert_eq!(&parsed, original);
+        }
+    }
+}
@@ -0,0 +1,613 @@
+// Rust guideline compliant 2026-02-10
+
+//! Static HTML site export.
+//!
+//! Renders the tracker as read-only pages that can be published on any static
+//! host: an index with client-side filters, one page per Pearl with its
+//! Markdown description, comments, and dependencies in both directions, and a
+//! dependency graph drawn as inline SVG. Archived Pearls are included and
+//! marked as such. The site has no external assets.
+
+use pearls_core::{DepType, Pearl, Status};
+use pulldown_cmark::{html, Event, Options, Parser, Tag};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// A file of the generated site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiteFile {
+    /// Path relative to the site root, using `/` separators.
+    pub path: String,
+    /// File content.
+    pub content: String,
+}
+
+const STYLE: &str = r#"body { font-family: system-ui, sans-serif; margin: 0 auto; max-width: 72rem; padding: 1rem 2rem; color: #1f2328; }
+a { color: #0969da; text-decoration: none; }
+a:hover { text-decoration: underline; }
+nav { margin-bottom: 1rem; }
+nav a { margin-right: 1rem; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border-bottom: 1px solid #d0d7de; padding: 0.4rem; text-align: left; vertical-align: top; }
+code, .id { font-family: ui-monospace, monospace; }
+.badge { border-radius: 1rem; display: inline-block; font-size: 0.8rem; padding: 0 0.5rem; background: #eaeef2; }
+.status-open { background: #dafbe1; }
+.status-in_progress { background: #ddf4ff; }
+.status-blocked { background: #ffebe9; }
+.status-deferred { background: #fff8c5; }
+.status-closed { background: #eaeef2; color: #57606a; }
+.archived { opacity: 0.6; }
+.filters { display: flex; flex-wrap: wrap; gap: 0.5rem; margin-bottom: 1rem; }
+.comment { border-left: 3px solid #d0d7de; margin: 1rem 0; padding-left: 1rem; }
+.meta { color: #57606a; font-size: 0.9rem; }
+svg text { font-size: 12px; }
+"#;
+
+const FILTER_SCRIPT: &str = r#"<script>
+(function () {
+  var text = document.getElementById('filter-text');
+  var status = document.getElementById('filter-status');
+  var label = document.getElementById('filter-label');
+  var archived = document.getElementById('filter-archived');
+  function apply() {
+    var query = text.value.toLowerCase();
+    document.querySelectorAll('tbody tr').forEach(function (row) {
+      var visible = (!query || row.dataset.search.indexOf(query) !== -1)
+        && (!status.value || row.dataset.status === status.value)
+        && (!label.value || row.dataset.labels.split(' ').indexOf(label.value) !== -1)
+        && (archived.checked || row.dataset.archived !== 'true');
+      row.style.display = visible ? '' : 'none';
+    });
+  }
+  [text, status, label, archived].forEach(function (input) {
+    input.addEventListener('input', apply);
+  });
+  apply();
+})();
+</script>
+"#;
+
+/// Renders the tracker as a static site.
+///
+/// # Arguments
+///
+/// * `active` - Active Pearls from storage
+/// * `archived` - Pearls from the archive
+///
+/// # Returns
+///
+/// The site files: `index.html`, `graph.html`, `style.css`, and
+/// `pearls/<id>.html` for every Pearl.
+#[must_use]
+pub fn render_site(active: &[Pearl], archived: &[Pearl]) -> Vec<SiteFile> {
+    let site = Site::new(active, archived);
+    let mut files = vec![
+        SiteFile {
+            path: "style.css".to_string(),
+            content: STYLE.to_string(),
+        },
+        SiteFile {
+            path: "index.html".to_string(),
+            content: site.index(),
+        },
+        SiteFile {
+            path: "graph.html".to_string(),
+            content: site.graph(),
+        },
+    ];
+    for (pearl, is_archived) in &site.pearls {
+        files.push(SiteFile {
+            path: format!("pearls/{}.html", pearl.id),
+            content: site.pearl_page(pearl, *is_archived),
+        });
+    }
+    files
+}
+
+struct Site<'a> {
+    pearls: Vec<(&'a Pearl, bool)>,
+    by_id: HashMap<&'a str, &'a Pearl>,
+    dependents: HashMap<&'a str, Vec<(&'a Pearl, DepType)>>,
+}
+
+impl<'a> Site<'a> {
+    fn new(active: &'a [Pearl], archived: &'a [Pearl]) -> Self {
+        let pearls: Vec<(&Pearl, bool)> = active
+            .iter()
+            .map(|pearl| (pearl, false))
+            .chain(archived.iter().map(|pearl| (pearl, true)))
+            .collect();
+        let by_id = pearls
+            .iter()
+            .map(|(pearl, _)| (pearl.id.as_str(), *pearl))
+            .collect();
+        let mut dependents: HashMap<&str, Vec<(&Pearl, DepType)>> = HashMap::new();
+        for (pearl, _) in &pearls {
+            for dep in &pearl.deps {
+                dependents
+                    .entry(dep.target_id.as_str())
+                    .or_default()
+                    .push((pearl, dep.dep_type));
+            }
+        }
+        Self {
+            pearls,
+            by_id,
+            dependents,
+        }
+    }
+
+    fn index(&self) -> String {
+        let labels: BTreeSet<&str> = self
+            .pearls
+            .iter()
+            .flat_map(|(pearl, _)| pearl.labels.iter().map(String::as_str))
+            .collect();
+        let mut body = String::from("<h1>Pearls</h1>\n<div class=\"filters\">\n");
+        body.push_str(
+            "<input id=\"filter-text\" type=\"search\" placeholder=\"Filter by text\">\n",
+        );
+        body.push_str("<select id=\"filter-status\"><option value=\"\">All statuses</option>");
+        for status in [
+            Status::Open,
+            Status::InProgress,
+            Status::Blocked,
+            Status::Deferred,
+            Status::Closed,
+        ] {
+            body.push_str(&format!(
+                "<option value=\"{0}\">{0}</option>",
+                status_name(status)
+            ));
+        }
+        body.push_str(
+            "</select>\n<select id=\"filter-label\"><option value=\"\">All labels</option>",
+        );
+        for label in labels {
+            body.push_str(&format!(
+                "<option value=\"{0}\">{0}</option>",
+                escape(label)
+            ));
+        }
+        body.push_str(
+            "</select>\n<label><input id=\"filter-archived\" type=\"checkbox\"> Show archived</label>\n</div>\n",
+        );
+
+        body.push_str("<table>\n<thead><tr><th>ID</th><th>Title</th><th>Status</th><th>Priority</th><th>Labels</th><th>Updated</th></tr></thead>\n<tbody>\n");
+        for (pearl, is_archived) in &self.pearls {
+            let search =
+                format!("{} {} {}", pearl.id, pearl.title, pearl.labels.join(" ")).to_lowercase();
+            body.push_str(&format!(
+                "<tr data-status=\"{status}\" data-labels=\"{labels}\" data-archived=\"{archived}\" data-search=\"{search}\"{class}>\
+<td class=\"id\">{id}</td><td><a href=\"pearls/{id}.html\">{title}</a></td><td>{badge}</td><td>P{priority}</td><td>{labels}</td><td>{updated}</td></tr>\n",
+                status = status_name(pearl.status),
+                labels = escape(&pearl.labels.join(" ")),
+                archived = is_archived,
+                search = escape(&search),
+                class = if *is_archived { " class=\"archived\"" } else { "" },
+                id = escape(&pearl.id),
+                title = escape(&pearl.title),
+                badge = status_badge(pearl.status, *is_archived),
+                priority = pearl.priority,
+                updated = date(pearl.updated_at),
+            ));
+        }
+        body.push_str("</tbody>\n</table>\n");
+        body.push_str(FILTER_SCRIPT);
+        page("Pearls", "", &body)
+    }
+
+    fn pearl_page(&self, pearl: &Pearl, is_archived: bool) -> String {
+        let mut body = format!(
+            "<h1>{}</h1>\n<p class=\"meta\"><span class=\"id\">{}</span> &middot; {} &middot; P{} &middot; by {} &middot; created {} &middot; updated {}</p>\n",
+            escape(&pearl.title),
+            escape(&pearl.id),
+            status_badge(pearl.status, is_archived),
+            pearl.priority,
+            escape(&pearl.author),
+            date(pearl.created_at),
+            date(pearl.updated_at),
+        );
+        if !pearl.labels.is_empty() {
+            body.push_str("<p>");
+            for label in &pearl.labels {
+                body.push_str(&format!("<span class=\"badge\">{}</span> ", escape(label)));
+            }
+            body.push_str("</p>\n");
+        }
+        if !pearl.description.is_empty() {
+            body.push_str(&markdown(&pearl.description));
+        }
+
+        body.push_str("<h2>Dependencies</h2>\n");
+        if pearl.deps.is_empty() {
+            body.push_str("<p class=\"meta\">None</p>\n");
+        } else {
+            body.push_str("<ul>\n");
+            for dep in &pearl.deps {
+                body.push_str(&format!(
+                    "<li>{} {}</li>\n",
+                    dep_name(dep.dep_type),
+                    self.link(&dep.target_id)
+                ));
+            }
+            body.push_str("</ul>\n");
+        }
+
+        body.push_str("<h2>Dependents</h2>\n");
+        match self.dependents.get(pearl.id.as_str()) {
+            None => body.push_str("<p class=\"meta\">None</p>\n"),
+            Some(dependents) => {
+                body.push_str("<ul>\n");
+                for (dependent, dep_type) in dependents {
+                    body.push_str(&format!(
+                        "<li>{} {}</li>\n",
+                        self.link(&dependent.id),
+                        reverse_dep_name(*dep_type)
+                    ));
+                }
+                body.push_str("</ul>\n");
+            }
+        }
+
+        if !pearl.comments.is_empty() {
+            body.push_str(&format!("<h2>Comments ({})</h2>\n", pearl.comments.len()));
+            for comment in &pearl.comments {
+                let kind = if comment.kind.is_note() {
+                    String::new()
+                } else {
+                    format!(" &middot; {}", comment.kind.as_str())
+                };
+                body.push_str(&format!(
+                    "<div class=\"comment\"><p class=\"meta\">{} &middot; {}{}</p>\n{}</div>\n",
+                    escape(&comment.author),
+                    date(comment.created_at),
+                    kind,
+                    markdown(&comment.body)
+                ));
+            }
+        }
+        page(&pearl.title, "../", &body)
+    }
+
+    fn graph(&self) -> String {
+        let layers = self.layers();
+        let depth = layers.values().copied().max().unwrap_or(0);
+        let mut columns: Vec<Vec<&Pearl>> = vec![Vec::new(); depth + 1];
+        for (pearl, _) in &self.pearls {
+            columns[layers[pearl.id.as_str()]].push(pearl);
+        }
+
+        const WIDTH: usize = 220;
+        const HEIGHT: usize = 36;
+        const GAP_X: usize = 60;
+        const GAP_Y: usize = 14;
+        let mut positions = HashMap::new();
+        for (x, column) in columns.iter().enumerate() {
+            for (y, pearl) in column.iter().enumerate() {
+                positions.insert(
+                    pearl.id.as_str(),
+                    (10 + x * (WIDTH + GAP_X), 10 + y * (HEIGHT + GAP_Y)),
+                );
+            }
+        }
+        let rows = columns.iter().map(Vec::len).max().unwrap_or(0);
+        let svg_width = 20 + columns.len() * (WIDTH + GAP_X);
+        let svg_height = 20 + rows * (HEIGHT + GAP_Y);
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n\
+<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"6\" markerHeight=\"6\" orient=\"auto\"><path d=\"M0,0 L10,5 L0,10 z\" fill=\"#57606a\"/></marker></defs>\n",
+            svg_width, svg_height
+        );
+        for (pearl, _) in &self.pearls {
+            let (x, y) = positions[pearl.id.as_str()];
+            for dep in &pearl.deps {
+                let Some(&(tx, ty)) = positions.get(dep.target_id.as_str()) else {
+                    continue;
+                };
+                let dash = match dep.dep_type {
+                    DepType::Blocks => "",
+                    _ => " stroke-dasharray=\"4 3\"",
+                };
+                svg.push_str(&format!(
+                    "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#57606a\"{} marker-end=\"url(#arrow)\"><title>{}</title></line>\n",
+                    tx + WIDTH,
+                    ty + HEIGHT / 2,
+                    x,
+                    y + HEIGHT / 2,
+                    dash,
+                    dep_name(dep.dep_type)
+                ));
+            }
+        }
+        for (pearl, is_archived) in &self.pearls {
+            let (x, y) = positions[pearl.id.as_str()];
+            let title: String = pearl.title.chars().take(28).collect();
+            svg.push_str(&format!(
+                "<a href=\"pearls/{id}.html\"><rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" rx=\"6\" fill=\"{fill}\" stroke=\"#d0d7de\"{opacity}/>\
+<text x=\"{tx}\" y=\"{ty}\">{title}</text><title>{id}: {full}</title></a>\n",
+                id = escape(&pearl.id),
+                x = x,
+                y = y,
+                w = WIDTH,
+                h = HEIGHT,
+                fill = status_color(pearl.status),
+                opacity = if *is_archived { " opacity=\"0.6\"" } else { "" },
+                tx = x + 8,
+                ty = y + HEIGHT / 2 + 4,
+                title = escape(&title),
+                full = escape(&pearl.title),
+            ));
+        }
+        svg.push_str("</svg>\n");
+
+        let body = format!(
+            "<h1>Dependency graph</h1>\n<p class=\"meta\">Arrows point from a dependency to the Pearl that depends on it. Solid lines block; dashed lines are parent, related, or discovered-from links.</p>\n<div style=\"overflow:auto\">\n{}</div>\n",
+            svg
+        );
+        page("Dependency graph", "", &body)
+    }
+
+    /// Assigns each Pearl the length of its longest dependency chain.
+    fn layers(&self) -> HashMap<&'a str, usize> {
+        fn visit<'a>(
+            id: &'a str,
+            by_id: &HashMap<&'a str, &'a Pearl>,
+            layers: &mut HashMap<&'a str, usize>,
+            visiting: &mut HashSet<&'a str>,
+        ) -> usize {
+            if let Some(layer) = layers.get(id) {
+                return *layer;
+            }
+            if !visiting.insert(id) {
+                return 0;
+            }
+            let layer = by_id[id]
+                .deps
+                .iter()
+                .filter(|dep| by_id.contains_key(dep.target_id.as_str()))
+                .map(|dep| visit(dep.target_id.as_str(), by_id, layers, visiting) + 1)
+                .max()
+                .unwrap_or(0);
+            visiting.remove(id);
+            layers.insert(id, layer);
+            layer
+        }
+
+        let mut layers = HashMap::new();
+        let mut visiting = HashSet::new();
+        for (pearl, _) in &self.pearls {
+            visit(pearl.id.as_str(), &self.by_id, &mut layers, &mut visiting);
+        }
+        layers
+    }
+
+    fn link(&self, id: &str) -> String {
+        match self.by_id.get(id) {
+            Some(pearl) => format!(
+                "<a href=\"{0}.html\"><span class=\"id\">{0}</span></a> {1}",
+                escape(id),
+                escape(&pearl.title)
+            ),
+            None => format!("<span class=\"id\">{}</span>", escape(id)),
+        }
+    }
+}
+
+/// Allows relative URLs and the `http`, `https` and `mailto` schemes.
+///
+/// Leading control characters and embedded tabs or newlines are ignored, as
+/// browsers do when they parse the scheme.
+fn is_safe_url(url: &str) -> bool {
+    let url: String = url
+        .trim_start_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    match url.find([':', '/', '?', '#']) {
+        Some(end) if url[end..].starts_with(':') => matches!(
+            url[..end].to_ascii_lowercase().as_str(),
+            "http" | "https" | "mailto"
+        ),
+        _ => true,
+    }
+}
+
+fn page(title: &str, root: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{title}</title>\n<link rel=\"stylesheet\" href=\"{root}style.css\">\n</head>\n<body>\n<nav><a href=\"{root}index.html\">All Pearls</a><a href=\"{root}graph.html\">Graph</a></nav>\n{body}</body>\n</html>\n",
+        title = escape(title),
+        root = root,
+        body = body
+    )
+}
+
+/// Renders Markdown to HTML, showing raw HTML as text and dropping links that
+/// fail [`is_safe_url`].
+fn markdown(text: &str) -> String {
+    let parser =
+        Parser::new_ext(text, Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS).map(|event| {
+            match event {
+                Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+                Event::Start(Tag::Link {
+                    link_type,
+                    dest_url,
+                    title,
+                    id,
+                }) if !is_safe_url(&dest_url) => Event::Start(Tag::Link {
+                    link_type,
+                    dest_url: "#".into(),
+                    title,
+                    id,
+                }),
+                Event::Start(Tag::Image {
+                    link_type,
+                    dest_url,
+                    title,
+                    id,
+                }) if !is_safe_url(&dest_url) => Event::Start(Tag::Image {
+                    link_type,
+                    dest_url: "#".into(),
+                    title,
+                    id,
+                }),
+                other => other,
+            }
+        });
+    let mut out = String::new();
+    html::push_html(&mut out, parser);
+    out
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn date(seconds: i64) -> String {
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+fn status_name(status: Status) -> &'static str {
+    match status {
+        Status::Open => "open",
+        Status::InProgress => "in_progress",
+        Status::Blocked => "blocked",
+        Status::Deferred => "deferred",
+        Status::Closed => "closed",
+    }
+}
+
+fn status_badge(status: Status, is_archived: bool) -> String {
+    let name = status_name(status);
+    let suffix = if is_archived { " (archived)" } else { "" };
+    format!(
+        "<span class=\"badge status-{}\">{}{}</span>",
+        name, name, suffix
+    )
+}
+
+fn status_color(status: Status) -> &'static str {
+    match status {
+        Status::Open => "#dafbe1",
+        Status::InProgress => "#ddf4ff",
+        Status::Blocked => "#ffebe9",
+        Status::Deferred => "#fff8c5",
+        Status::Closed => "#eaeef2",
+    }
+}
+
+fn dep_name(dep_type: DepType) -> &'static str {
+    match dep_type {
+        DepType::Blocks => "Blocked by",
+        DepType::ParentChild => "Child of",
+        DepType::Related => "Related to",
+        DepType::DiscoveredFrom => "Discovered from",
+    }
+}
+
+fn reverse_dep_name(dep_type: DepType) -> &'static str {
+    match dep_type {
+        DepType::Blocks => "is blocked by this",
+        DepType::ParentChild => "is a child of this",
+        DepType::Related => "is related to this",
+        DepType::DiscoveredFrom => "was discovered from this",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pearls_core::Dependency;
+
+    #[test]
+    fn test_render_site() {
+        let mut blocker = Pearl::new("Schema <v2>".to_string(), "alice".to_string());
+        blocker.id = "prl-aaa111".to_string();
+        blocker.description =
+            "Uses **bold**, <script>alert(1)</script> and [a](javascript:alert(1))".to_string();
+        let mut blocked = Pearl::new("API".to_string(), "bob".to_string());
+        blocked.id = "prl-bbb222".to_string();
+        blocked.deps.push(Dependency {
+            target_id: blocker.id.clone(),
+            dep_type: DepType::Blocks,
+        });
+        blocked
+            .add_comment("carol".to_string(), "Looks *good*".to_string())
+            .expect("comment");
+        let mut old = Pearl::new("Old".to_string(), "alice".to_string());
+        old.id = "prl-ccc333".to_string();
+        old.status = Status::Closed;
+
+        let files = render_site(&[blocker, blocked], &[old]);
+        let file = |path: &str| {
+            &files
+                .iter()
+                .find(|file| file.path == path)
+                .expect("missing file")
+                .content
+        };
+
+        let index = file("index.html");
+        assert!(index.contains("Schema &lt;v2&gt;"));
+        assert!(index.contains("data-archived=\"true\""));
+        assert!(index.contains("href=\"pearls/prl-ccc333.html\""));
+
+        let schema = file("pearls/prl-aaa111.html");
+        assert!(schema.contains("<strong>bold</strong>"));
+        assert!(!schema.contains("<script>alert"));
+        assert!(!schema.contains("javascript:"));
+        assert!(schema.contains("href=\"prl-bbb222.html\""));
+        assert!(schema.contains("is blocked by this"));
+
+        let api = file("pearls/prl-bbb222.html");
+        assert!(api.contains("Blocked by <a href=\"prl-aaa111.html\""));
+        assert!(api.contains("<em>good</em>"));
+
+        assert!(file("graph.html").contains("<line"));
+        assert!(file("pearls/prl-ccc333.html").contains("closed (archived)"));
+    }
+
+    #[test]
+    fn test_markdown_allows_only_safe_links() {
+        for url in [
+            "https://example.com/a?b#c",
+            "HTTP://example.com",
+            "mailto:dev@example.com",
+            "../prl-aaa111.html",
+            "/docs/guide.md",
+            "#section",
+            "notes?page=a:b",
+        ] {
+            assert!(is_safe_url(url), "{url} should be allowed");
+        }
+        for url in [
+            "javascript:alert(1)",
+            " JavaScript:alert(1)",
+            "java\tscript:alert(1)",
+            "\u{1}javascript:alert(1)",
+            "data:text/html;base64,PHNjcmlwdD4=",
+            "vbscript:msgbox(1)",
+            "file:///etc/passwd",
+        ] {
+            assert!(!is_safe_url(url), "{url:?} should be blocked");
+        }
+
+        let html = markdown("[a](data:text/html,x) ![b](vbscript:x) [c](https://example.com)");
+        assert!(!html.contains("data:"));
+        assert!(!html.contains("vbscript:"));
+        assert!(html.contains("href=\"https://example.com\""));
+    }
+}
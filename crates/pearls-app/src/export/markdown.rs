@@ -0,0 +1,178 @@
+// Rust guideline compliant 2026-02-10
+
+//! Markdown plan export.
+//!
+//! Writes Pearls as a checklist in the format read by the Markdown importer:
+//! Pearls with children become `##` headings, their children become nested
+//! checklist items, and standalone Pearls are listed first. Every line ends
+//! with an HTML comment holding the Pearl ID, so importing the document again
+//! updates the same Pearls.
+
+use pearls_core::{DepType, Pearl, Status};
+use std::collections::{HashMap, HashSet};
+
+/// Renders Pearls as a Markdown plan.
+///
+/// # Arguments
+///
+/// * `pearls` - Pearls to render, in the order they should appear
+///
+/// # Returns
+///
+/// The Markdown document.
+#[must_use]
+pub fn render_markdown(pearls: &[Pearl]) -> String {
+    let ids: HashSet<&str> = pearls.iter().map(|pearl| pearl.id.as_str()).collect();
+    let mut children: HashMap<&str, Vec<&Pearl>> = HashMap::new();
+    let mut roots = Vec::new();
+    for pearl in pearls {
+        let parent = pearl.deps.iter().find(|dep| {
+            dep.dep_type == DepType::ParentChild && ids.contains(dep.target_id.as_str())
+        });
+        match parent {
+            Some(dep) => children
+                .entry(dep.target_id.as_str())
+                .or_default()
+                .push(pearl),
+            None => roots.push(pearl),
+        }
+    }
+
+    let mut out = String::from("<!-- Exported by prl. Keep the ID comments to re-import. -->\n");
+    let mut seen = HashSet::new();
+    let (parents, standalone): (Vec<&Pearl>, Vec<&Pearl>) = roots
+        .into_iter()
+        .partition(|pearl| children.contains_key(pearl.id.as_str()));
+
+    if !standalone.is_empty() {
+        out.push('\n');
+        for pearl in standalone {
+            render_item(&mut out, pearl, 0, &children, &mut seen);
+        }
+    }
+    for pearl in parents {
+        seen.insert(pearl.id.as_str());
+        out.push_str(&format!("\n## {}\n", entry_line(pearl, true)));
+        if !pearl.description.is_empty() {
+            out.push('\n');
+            for line in pearl.description.lines() {
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+        out.push('\n');
+        for child in &children[pearl.id.as_str()] {
+            render_item(&mut out, child, 0, &children, &mut seen);
+        }
+    }
+
+    // Pearls in a parent cycle have no root; list them so nothing is lost.
+    let orphans: Vec<&Pearl> = pearls
+        .iter()
+        .filter(|pearl| !seen.contains(pearl.id.as_str()))
+        .collect();
+    if !orphans.is_empty() {
+        out.push('\n');
+        for pearl in orphans {
+            render_item(&mut out, pearl, 0, &children, &mut seen);
+        }
+    }
+    out
+}
+
+fn render_item<'a>(
+    out: &mut String,
+    pearl: &'a Pearl,
+    depth: usize,
+    children: &HashMap<&str, Vec<&'a Pearl>>,
+    seen: &mut HashSet<&'a str>,
+) {
+    if !seen.insert(pearl.id.as_str()) {
+        return;
+    }
+    let indent = "  ".repeat(depth);
+    out.push_str(&format!("{}- {}\n", indent, entry_line(pearl, false)));
+    let mut blank = false;
+    for line in pearl.description.lines() {
+        if line.trim().is_empty() {
+            blank = true;
+            continue;
+        }
+        if blank {
+            out.push('\n');
+            blank = false;
+        }
+        out.push_str(&format!("{}  {}\n", indent, line.trim()));
+    }
+    if let Some(kids) = children.get(pearl.id.as_str()) {
+        for child in kids {
+            render_item(out, child, depth + 1, children, seen);
+        }
+    }
+}
+
+/// Formats the checkbox, title, tokens, and ID comment of a Pearl.
+fn entry_line(pearl: &Pearl, heading: bool) -> String {
+    let closed = pearl.status == Status::Closed;
+    let mut line = match (heading, closed) {
+        (true, false) => String::new(),
+        (_, true) => "[x] ".to_string(),
+        (false, false) => "[ ] ".to_string(),
+    };
+    line.push_str(&pearl.title);
+    line.push_str(&format!(" P{}", pearl.priority));
+    for label in &pearl.labels {
+        line.push_str(&format!(" #{}", label.replace(char::is_whitespace, "-")));
+    }
+    line.push_str(&format!(" <!-- {} -->", pearl.id));
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::markdown::parse_markdown;
+    use pearls_core::Dependency;
+
+    #[test]
+    fn test_render_markdown_round_trips() {
+        let mut epic = Pearl::new("Release".to_string(), "alice".to_string());
+        epic.id = "prl-aaa111".to_string();
+        epic.description = "Ship it.".to_string();
+        epic.labels = vec!["launch".to_string()];
+        let mut task = Pearl::new("Write docs".to_string(), "alice".to_string());
+        task.id = "prl-bbb222".to_string();
+        task.status = Status::Closed;
+        task.priority = 1;
+        task.description = "First line\n\nSecond paragraph".to_string();
+        task.deps.push(Dependency {
+            target_id: epic.id.clone(),
+            dep_type: DepType::ParentChild,
+        });
+        let mut loose = Pearl::new("Standalone".to_string(), "alice".to_string());
+        loose.id = "prl-ccc333".to_string();
+
+        let markdown = render_markdown(&[epic.clone(), task.clone(), loose.clone()]);
+        assert!(markdown.contains("## Release P2 #launch <!-- prl-aaa111 -->"));
+        assert!(markdown.contains("- [x] Write docs P1 <!-- prl-bbb222 -->"));
+
+        let import = parse_markdown("plan.md", &markdown, "bob").expect("parse");
+        let by_id = |id: &str| {
+            &import
+                .drafts
+                .iter()
+                .find(|draft| draft.source_id == id)
+                .expect("draft")
+                .pearl
+        };
+        for original in [&epic, &task, &loose] {
+            let parsed = by_id(&original.id);
+            assert_eq!(parsed.title, original.title);
+            assert_eq!(parsed.status, original.status);
+            assert_eq!(parsed.priority, original.priority);
+            assert_eq!(parsed.labels, original.labels);
+            assert_eq!(parsed.description, original.description);
+            assert_eq!(parsed.deps, original.deps);
+        }
+    }
+}
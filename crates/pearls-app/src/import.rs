@@ -0,0 +1,390 @@
+// Rust guideline compliant 2026-02-10
+
+//! Import of issues from other trackers.
+//!
+//! Each source format has an adapter that turns its records into
+//! [`ImportDraft`]s: Pearls whose `id` and dependency targets are still the
+//! identifiers used by the source. [`plan_import`] then assigns Pearl IDs,
+//! rewrites references, and matches drafts against existing Pearls through a
+//! metadata key holding the source ID, so importing the same data again updates
+//! Pearls instead of duplicating them.
+
+pub mod beads;
+pub mod csv;
+pub mod forge;
+pub mod markdown;
+
+use crate::error::Result;
+use pearls_core::identity::{generate_id, validate_id_format};
+use pearls_core::{Pearl, Storage};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// A Pearl read from an external source, before Pearl IDs are assigned.
+#[derive(Debug, Clone)]
+pub struct ImportDraft {
+    /// Identifier of the issue in the source, e.g. `bd-a1b2`.
+    pub source_id: String,
+    /// Pearl content. Its `id` and dependency targets are source IDs.
+    pub pearl: Pearl,
+}
+
+/// What an import does with a single draft.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportAction {
+    /// A new Pearl is created.
+    Create,
+    /// An existing Pearl is updated.
+    Update,
+    /// An existing Pearl already matches the source.
+    Unchanged,
+}
+
+/// A draft resolved against the repository.
+#[derive(Debug, Clone)]
+pub struct PlannedImport {
+    /// Identifier of the issue in the source.
+    pub source_id: String,
+    /// Action the import takes.
+    pub action: ImportAction,
+    /// Pearl as it will be stored.
+    pub pearl: Pearl,
+}
+
+/// Result of resolving drafts against the repository.
+#[derive(Debug, Clone, Default)]
+pub struct ImportPlan {
+    /// Planned Pearls in source order.
+    pub entries: Vec<PlannedImport>,
+    /// Problems that did not stop the import, such as unresolved references.
+    pub warnings: Vec<String>,
+}
+
+impl ImportPlan {
+    /// Returns the number of entries with the given action.
+    #[must_use]
+    pub fn count(&self, action: ImportAction) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.action == action)
+            .count()
+    }
+}
+
+/// Resolves drafts against existing Pearls.
+///
+/// A draft matches an existing Pearl whose `metadata[source_key]` equals the
+/// draft's source ID, or whose ID equals it. Matched Pearls keep their ID,
+/// creation time, comments, and metadata not provided by the source; new
+/// comments are appended. Unmatched drafts get a new hash-based ID, unless the
+/// source ID already is a valid Pearl ID. Dependency targets are rewritten to
+/// Pearl IDs and unresolved ones are dropped with a warning.
+///
+/// # Arguments
+///
+/// * `existing` - Pearls currently in the repository
+/// * `source_key` - Metadata key that records the source ID
+/// * `drafts` - Drafts produced by a source adapter
+/// * `rewrite_text` - Whether to replace source IDs mentioned in descriptions
+///   and comments with Pearl IDs
+///
+/// # Returns
+///
+/// The import plan.
+#[must_use]
+pub fn plan_import(
+    existing: &[Pearl],
+    source_key: &str,
+    drafts: Vec<ImportDraft>,
+    rewrite_text: bool,
+) -> ImportPlan {
+    let mut plan = ImportPlan::default();
+    let by_id: HashMap<&str, &Pearl> = existing
+        .iter()
+        .map(|pearl| (pearl.id.as_str(), pearl))
+        .collect();
+    let by_source: HashMap<&str, &Pearl> = existing
+        .iter()
+        .filter_map(|pearl| {
+            pearl
+                .metadata
+                .get(source_key)
+                .and_then(|value| value.as_str())
+                .map(|source_id| (source_id, pearl))
+        })
+        .collect();
+
+    let mut used: HashSet<String> = existing.iter().map(|pearl| pearl.id.clone()).collect();
+    let mut id_map: HashMap<String, String> = by_source
+        .iter()
+        .map(|(source_id, pearl)| (source_id.to_string(), pearl.id.clone()))
+        .collect();
+    let mut matches = Vec::with_capacity(drafts.len());
+
+    for draft in &drafts {
+        let matched = by_source
+            .get(draft.source_id.as_str())
+            .or_else(|| by_id.get(draft.source_id.as_str()))
+            .copied();
+        let id = match matched {
+            Some(pearl) => pearl.id.clone(),
+            None if validate_id_format(&draft.source_id).is_ok()
+                && !used.contains(&draft.source_id) =>
+            {
+                draft.source_id.clone()
+            }
+            None => unused_id(&draft.pearl, &used),
+        };
+        used.insert(id.clone());
+        id_map.insert(draft.source_id.clone(), id);
+        matches.push(matched);
+    }
+
+    let refs: Vec<(&str, &str)> = id_map
+        .iter()
+        .filter(|(source_id, id)| source_id != id)
+        .map(|(source_id, id)| (source_id.as_str(), id.as_str()))
+        .collect();
+
+    for (draft, matched) in drafts.into_iter().zip(matches) {
+        let ImportDraft {
+            source_id,
+            mut pearl,
+        } = draft;
+        pearl.id = id_map[&source_id].clone();
+        if pearl.id != source_id {
+            pearl
+                .metadata
+                .insert(source_key.to_string(), source_id.clone().into());
+        }
+
+        let mut deps = Vec::with_capacity(pearl.deps.len());
+        for mut dep in std::mem::take(&mut pearl.deps) {
+            let target = id_map.get(&dep.target_id).cloned().or_else(|| {
+                by_id
+                    .contains_key(dep.target_id.as_str())
+                    .then(|| dep.target_id.clone())
+            });
+            match target {
+                Some(target) if target != pearl.id => {
+                    dep.target_id = target;
+                    if !deps.contains(&dep) {
+                        deps.push(dep);
+                    }
+                }
+                Some(_) => {}
+                None => plan.warnings.push(format!(
+                    "{}: dropped reference to unknown issue {}",
+                    source_id, dep.target_id
+                )),
+            }
+        }
+        pearl.deps = deps;
+
+        if rewrite_text {
+            pearl.description = rewrite_refs(&pearl.description, &refs);
+            for comment in &mut pearl.comments {
+                comment.body = rewrite_refs(&comment.body, &refs);
+            }
+        }
+
+        let (action, pearl) = match matched {
+            None => (ImportAction::Create, pearl),
+            Some(current) => merge_existing(current, pearl),
+        };
+        plan.entries.push(PlannedImport {
+            source_id,
+            action,
+            pearl,
+        });
+    }
+
+    plan
+}
+
+/// Writes every created or updated Pearl in the plan.
+///
+/// # Arguments
+///
+/// * `storage` - Storage for the active Pearls
+/// * `plan` - Plan produced by [`plan_import`]
+///
+/// # Returns
+///
+/// The number of Pearls written.
+///
+/// # Errors
+///
+/// Returns an error if a Pearl fails validation or cannot be written.
+pub fn apply_import(storage: &mut Storage, plan: &ImportPlan) -> Result<usize> {
+    let mut written = 0usize;
+    for entry in &plan.entries {
+        if entry.action != ImportAction::Unchanged {
+            storage.save(&entry.pearl)?;
+            written += 1;
+        }
+    }
+    Ok(written)
+}
+
+/// Parses a source timestamp given as RFC 3339 text or Unix seconds.
+///
+/// # Arguments
+///
+/// * `value` - Timestamp value from the source
+///
+/// # Returns
+///
+/// The Unix timestamp, or `None` if the value is missing or malformed.
+#[must_use]
+pub fn parse_timestamp(value: &serde_json::Value) -> Option<i64> {
+    match value {
+        serde_json::Value::Number(number) => number.as_i64(),
+        serde_json::Value::String(text) => chrono::DateTime::parse_from_rfc3339(text)
+            .ok()
+            .map(|time| time.timestamp())
+            .or_else(|| text.parse().ok()),
+        _ => None,
+    }
+}
+
+/// Generates a Pearl ID for a draft that is not yet in `used`.
+fn unused_id(pearl: &Pearl, used: &HashSet<String>) -> String {
+    let mut nonce = 0u32;
+    loop {
+        let id = generate_id(&pearl.title, &pearl.author, pearl.created_at, nonce);
+        if !used.contains(&id) {
+            return id;
+        }
+        nonce = nonce.saturating_add(1);
+    }
+}
+
+/// Merges an imported Pearl into the existing one it matched.
+fn merge_existing(current: &Pearl, mut imported: Pearl) -> (ImportAction, Pearl) {
+    imported.created_at = current.created_at;
+    imported.extra = current.extra.clone();
+
+    let mut metadata = current.metadata.clone();
+    metadata.extend(imported.metadata);
+    imported.metadata = metadata;
+
+    let mut comments = current.comments.clone();
+    for comment in imported.comments {
+        let known = comments.iter().any(|existing| {
+            existing.author == comment.author
+                && existing.body == comment.body
+                && existing.created_at == comment.created_at
+        });
+        if !known {
+            comments.push(comment);
+        }
+    }
+    imported.comments = comments;
+
+    imported.updated_at = current.updated_at;
+    if imported == *current {
+        return (ImportAction::Unchanged, imported);
+    }
+    imported.updated_at = crate::time::unix_timestamp().unwrap_or(current.updated_at);
+    (ImportAction::Update, imported)
+}
+
+/// Replaces whole-token mentions of source IDs with Pearl IDs.
+fn rewrite_refs(text: &str, refs: &[(&str, &str)]) -> String {
+    if refs.is_empty() {
+        return text.to_string();
+    }
+
+    let is_token = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.');
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(is_token) {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find(|c: char| !is_token(c)).unwrap_or(rest.len());
+        let token = &rest[..end];
+        // Allow sentence punctuation after an ID, as in "see bd-a1b2."
+        let trimmed = token.trim_end_matches('.');
+        match refs.iter().find(|(source_id, _)| *source_id == trimmed) {
+            Some((_, id)) => {
+                out.push_str(id);
+                out.push_str(&token[trimmed.len()..]);
+            }
+            None => out.push_str(token),
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pearls_core::{DepType, Dependency};
+
+    fn draft(source_id: &str, title: &str, blocked_by: Option<&str>) -> ImportDraft {
+        let mut pearl = Pearl::new(title.to_string(), "author".to_string());
+        pearl.id = source_id.to_string();
+        pearl.created_at = 1000;
+        pearl.updated_at = 1000;
+        pearl.description = format!("Follows {}.", blocked_by.unwrap_or("nothing"));
+        pearl.deps = blocked_by
+            .map(|target| Dependency {
+                target_id: target.to_string(),
+                dep_type: DepType::Blocks,
+            })
+            .into_iter()
+            .collect();
+        ImportDraft {
+            source_id: source_id.to_string(),
+            pearl,
+        }
+    }
+
+    #[test]
+    fn test_plan_rewrites_ids_and_references() {
+        let drafts = vec![
+            draft("bd-a1", "First", None),
+            draft("bd-a1.2", "Second", Some("bd-a1")),
+            draft("bd-b2", "Third", Some("bd-zz")),
+        ];
+        let plan = plan_import(&[], "beads_id", drafts, true);
+
+        let first = &plan.entries[0].pearl;
+        let second = &plan.entries[1].pearl;
+        assert!(validate_id_format(&first.id).is_ok());
+        assert_eq!(first.metadata.get("beads_id"), Some(&"bd-a1".into()));
+        assert_eq!(second.deps[0].target_id, first.id);
+        assert_eq!(second.description, format!("Follows {}.", first.id));
+        assert!(plan.entries[2].pearl.deps.is_empty());
+        assert_eq!(plan.warnings.len(), 1);
+        assert_eq!(plan.count(ImportAction::Create), 3);
+    }
+
+    #[test]
+    fn test_plan_updates_previous_import() {
+        let first = plan_import(&[], "beads_id", vec![draft("bd-a1", "First", None)], true);
+        let existing: Vec<Pearl> = first.entries.into_iter().map(|entry| entry.pearl).collect();
+
+        let again = plan_import(
+            &existing,
+            "beads_id",
+            vec![draft("bd-a1", "First", None)],
+            true,
+        );
+        assert_eq!(again.entries[0].action, ImportAction::Unchanged);
+
+        let renamed = plan_import(
+            &existing,
+            "beads_id",
+            vec![draft("bd-a1", "Renamed", None)],
+            true,
+        );
+        assert_eq!(renamed.entries[0].action, ImportAction::Update);
+        assert_eq!(renamed.entries[0].pearl.id, existing[0].id);
+        assert_eq!(renamed.entries[0].pearl.title, "Renamed");
+    }
+}
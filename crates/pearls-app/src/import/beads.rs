@@ -0,0 +1,321 @@
+// Rust guideline compliant 2026-02-10
+
+//! Adapter for Beads JSONL exports.
+//!
+//! Maps Beads issues (`bd-` IDs, `issue_type`, `assignee`, `dependencies`
+//! with `depends_on_id`, and Beads status names) onto Pearls. Lines that are
+//! already in Pearl format are imported as-is.
+
+use super::{parse_timestamp, ImportDraft};
+use crate::error::Result;
+use pearls_core::identity::validate_id_format;
+use pearls_core::{DepType, Dependency, Pearl, Status};
+use serde::Deserialize;
+use serde_json::Value;
+use std::io::BufRead;
+
+/// Metadata key that records the Beads ID of an imported Pearl.
+pub const SOURCE_KEY: &str = "beads_id";
+
+/// Author used when a Beads issue does not name one.
+const DEFAULT_AUTHOR: &str = "beads";
+
+#[derive(Debug, Deserialize)]
+struct BeadsIssue {
+    id: String,
+    title: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    design: String,
+    #[serde(default)]
+    acceptance_criteria: String,
+    #[serde(default)]
+    notes: String,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    priority: Option<Value>,
+    #[serde(default)]
+    issue_type: Option<String>,
+    #[serde(default)]
+    assignee: Option<String>,
+    #[serde(default, alias = "created_by")]
+    author: Option<String>,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    created_at: Option<Value>,
+    #[serde(default)]
+    updated_at: Option<Value>,
+    #[serde(default)]
+    external_ref: Option<String>,
+    #[serde(default)]
+    dependencies: Vec<BeadsDependency>,
+    #[serde(default)]
+    comments: Vec<BeadsComment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BeadsDependency {
+    depends_on_id: String,
+    #[serde(default, rename = "type")]
+    dep_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BeadsComment {
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(alias = "body")]
+    text: String,
+    #[serde(default)]
+    created_at: Option<Value>,
+}
+
+/// Drafts parsed from a Beads export.
+#[derive(Debug, Default)]
+pub struct BeadsImport {
+    /// Drafts in file order.
+    pub drafts: Vec<ImportDraft>,
+    /// Number of lines that could not be parsed.
+    pub skipped: usize,
+    /// Problems found while mapping fields.
+    pub warnings: Vec<String>,
+}
+
+/// Parses a Beads JSONL export.
+///
+/// Statuses, priorities, dependency kinds, and comments are mapped to their
+/// Pearl equivalents; `issue_type`, `assignee`, and `external_ref` are kept in
+/// metadata. Unknown values fall back to a default with a warning, and
+/// malformed lines are skipped with a warning.
+///
+/// # Arguments
+///
+/// * `reader` - Reader over the JSONL export
+///
+/// # Returns
+///
+/// The parsed drafts.
+///
+/// # Errors
+///
+/// Returns an error if the reader fails.
+pub fn parse_beads(reader: impl BufRead) -> Result<BeadsImport> {
+    let mut import = BeadsImport::default();
+    let now = crate::time::unix_timestamp()?;
+
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Ok(pearl) = serde_json::from_str::<Pearl>(&line) {
+            if validate_id_format(&pearl.id).is_ok() {
+                import.drafts.push(ImportDraft {
+                    source_id: pearl.id.clone(),
+                    pearl,
+                });
+                continue;
+            }
+        }
+
+        match serde_json::from_str::<BeadsIssue>(&line) {
+            Ok(issue) => {
+                let draft = map_issue(issue, now, &mut import.warnings);
+                import.drafts.push(draft);
+            }
+            Err(err) => {
+                import.skipped += 1;
+                import.warnings.push(format!(
+                    "Line {}: skipped invalid Beads issue ({})",
+                    idx + 1,
+                    err
+                ));
+            }
+        }
+    }
+
+    Ok(import)
+}
+
+fn map_issue(issue: BeadsIssue, now: i64, warnings: &mut Vec<String>) -> ImportDraft {
+    let created_at = issue
+        .created_at
+        .as_ref()
+        .and_then(parse_timestamp)
+        .unwrap_or(now);
+    let updated_at = issue
+        .updated_at
+        .as_ref()
+        .and_then(parse_timestamp)
+        .unwrap_or(created_at);
+
+    let mut description = issue.description.trim().to_string();
+    for (heading, section) in [
+        ("Design", &issue.design),
+        ("Acceptance Criteria", &issue.acceptance_criteria),
+        ("Notes", &issue.notes),
+    ] {
+        if !section.trim().is_empty() {
+            if !description.is_empty() {
+                description.push_str("\n\n");
+            }
+            description.push_str(&format!("## {}\n\n{}", heading, section.trim()));
+        }
+    }
+
+    let mut pearl = Pearl::new(
+        issue.title,
+        issue
+            .author
+            .filter(|author| !author.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_AUTHOR.to_string()),
+    );
+    pearl.id = issue.id.clone();
+    pearl.description = description;
+    pearl.status = map_status(&issue.id, issue.status.as_deref(), warnings);
+    pearl.priority = map_priority(&issue.id, issue.priority.as_ref(), warnings);
+    pearl.created_at = created_at;
+    pearl.updated_at = updated_at;
+    pearl.labels = issue.labels;
+
+    for (key, value) in [
+        ("issue_type", issue.issue_type),
+        ("assignee", issue.assignee),
+        ("external_ref", issue.external_ref),
+    ] {
+        if let Some(value) = value.filter(|value| !value.trim().is_empty()) {
+            pearl.metadata.insert(key.to_string(), value.into());
+        }
+    }
+
+    for dep in issue.dependencies {
+        pearl.deps.push(Dependency {
+            target_id: dep.depends_on_id,
+            dep_type: map_dep_type(&issue.id, dep.dep_type.as_deref(), warnings),
+        });
+    }
+
+    for comment in issue.comments {
+        let author = comment
+            .author
+            .filter(|author| !author.trim().is_empty())
+            .unwrap_or_else(|| pearl.author.clone());
+        let created_at = comment
+            .created_at
+            .as_ref()
+            .and_then(parse_timestamp)
+            .unwrap_or(updated_at);
+        if let Err(err) = pearl.add_comment_at(author, comment.text, created_at) {
+            warnings.push(format!("{}: skipped comment ({})", issue.id, err));
+        }
+    }
+
+    ImportDraft {
+        source_id: issue.id,
+        pearl,
+    }
+}
+
+fn map_status(id: &str, status: Option<&str>, warnings: &mut Vec<String>) -> Status {
+    match status
+        .map(|status| status.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        None | Some("") | Some("open") => Status::Open,
+        Some("in_progress") | Some("in-progress") => Status::InProgress,
+        Some("blocked") => Status::Blocked,
+        Some("deferred") => Status::Deferred,
+        Some("closed") | Some("done") | Some("tombstone") => Status::Closed,
+        Some(other) => {
+            warnings.push(format!("{}: unknown status '{}', using open", id, other));
+            Status::Open
+        }
+    }
+}
+
+fn map_priority(id: &str, priority: Option<&Value>, warnings: &mut Vec<String>) -> u8 {
+    let parsed = match priority {
+        None | Some(Value::Null) => return 2,
+        Some(Value::Number(number)) => number.as_u64(),
+        Some(Value::String(text)) => text
+            .trim()
+            .trim_start_matches(['P', 'p'])
+            .parse::<u64>()
+            .ok(),
+        Some(_) => None,
+    };
+    match parsed {
+        Some(priority) if priority <= 4 => priority as u8,
+        Some(priority) => {
+            warnings.push(format!(
+                "{}: priority {} out of range, using 4",
+                id, priority
+            ));
+            4
+        }
+        None => {
+            warnings.push(format!("{}: invalid priority, using 2", id));
+            2
+        }
+    }
+}
+
+fn map_dep_type(id: &str, dep_type: Option<&str>, warnings: &mut Vec<String>) -> DepType {
+    match dep_type
+        .map(|dep_type| dep_type.trim().to_ascii_lowercase().replace('-', "_"))
+        .as_deref()
+    {
+        None | Some("") | Some("blocks") => DepType::Blocks,
+        Some("parent_child") => DepType::ParentChild,
+        Some("related") => DepType::Related,
+        Some("discovered_from") => DepType::DiscoveredFrom,
+        Some(other) => {
+            warnings.push(format!(
+                "{}: unknown dependency type '{}', using related",
+                id, other
+            ));
+            DepType::Related
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_beads_maps_fields() {
+        let export = concat!(
+            r#"{"id":"bd-a1b2","title":"Parent","status":"in_progress","priority":1,"issue_type":"epic","assignee":"alice","created_at":"2025-01-02T03:04:05Z","design":"Use a queue"}"#,
+            "\n",
+            r#"{"id":"bd-c3d4","title":"Child","status":"hooked","priority":"P9","dependencies":[{"issue_id":"bd-c3d4","depends_on_id":"bd-a1b2","type":"parent-child"}],"comments":[{"id":1,"author":"bob","text":"See bd-a1b2","created_at":"2025-01-03T00:00:00Z"}]}"#,
+            "\n",
+            "not json\n",
+        );
+
+        let import = parse_beads(export.as_bytes()).expect("parse");
+        assert_eq!(import.drafts.len(), 2);
+        assert_eq!(import.skipped, 1);
+
+        let parent = &import.drafts[0].pearl;
+        assert_eq!(parent.status, Status::InProgress);
+        assert_eq!(parent.priority, 1);
+        assert_eq!(parent.created_at, 1_735_787_045);
+        assert_eq!(parent.metadata.get("issue_type"), Some(&"epic".into()));
+        assert_eq!(parent.metadata.get("assignee"), Some(&"alice".into()));
+        assert!(parent.description.contains("## Design\n\nUse a queue"));
+
+        let child = &import.drafts[1].pearl;
+        assert_eq!(child.status, Status::Open);
+        assert_eq!(child.priority, 4);
+        assert_eq!(child.deps[0].target_id, "bd-a1b2");
+        assert_eq!(child.deps[0].dep_type, DepType::ParentChild);
+        assert_eq!(child.comments[0].author, "bob");
+        // Unknown status, out-of-range priority, and the malformed line.
+        assert_eq!(import.warnings.len(), 3);
+    }
+}
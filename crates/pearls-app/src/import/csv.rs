@@ -0,0 +1,502 @@
+// Rust guideline compliant 2026-02-10
+
+//! Adapter for CSV exports, including Jira's.
+//!
+//! A [`CsvMapping`], usually read from a TOML file, names the column holding
+//! each Pearl field and translates status and priority names. Without a
+//! mapping, the columns written by `prl export csv` are expected. Columns may
+//! repeat, as Jira does for labels and issue links; every non-empty cell is
+//! used for list fields and the first for single-valued ones.
+
+use super::{parse_timestamp, ImportDraft};
+use crate::error::{AppError, Result};
+use pearls_core::{DepType, Dependency, Pearl, Status};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Column names of the Pearl fields in a CSV file.
+///
+/// An empty name means the field is not imported.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CsvColumns {
+    /// Source ID, such as a Jira issue key.
+    pub id: String,
+    /// Title or summary.
+    pub title: String,
+    /// Description text.
+    pub description: String,
+    /// Status name, translated through [`CsvMapping::status`].
+    pub status: String,
+    /// Priority name, translated through [`CsvMapping::priority`].
+    pub priority: String,
+    /// Author or reporter.
+    pub author: String,
+    /// Labels; cells may hold several separated by `list_separator`.
+    pub labels: String,
+    /// Creation time.
+    pub created_at: String,
+    /// Last update time.
+    pub updated_at: String,
+    /// Flattened dependencies written as `type:id`.
+    pub deps: String,
+    /// Issues that block this one.
+    pub blocked_by: String,
+    /// Issues that this one blocks.
+    pub blocks: String,
+    /// Parent issue.
+    pub parent: String,
+    /// Metadata keys mapped to the column holding their value.
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl Default for CsvColumns {
+    fn default() -> Self {
+        Self {
+            id: "id".to_string(),
+            title: "title".to_string(),
+            description: "description".to_string(),
+            status: "status".to_string(),
+            priority: "priority".to_string(),
+            author: "author".to_string(),
+            labels: "labels".to_string(),
+            created_at: "created_at".to_string(),
+            updated_at: "updated_at".to_string(),
+            deps: "deps".to_string(),
+            blocked_by: "blocked_by".to_string(),
+            blocks: "blocks".to_string(),
+            parent: "parent".to_string(),
+            metadata: BTreeMap::new(),
+        }
+    }
+}
+
+/// Describes how the rows of a CSV file map onto Pearls.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CsvMapping {
+    /// Metadata key that records the source ID of each Pearl.
+    pub source_key: String,
+    /// Field delimiter.
+    pub delimiter: char,
+    /// Separator between several values in one cell.
+    pub list_separator: String,
+    /// `chrono` format of timestamps that are neither RFC 3339 nor Unix
+    /// seconds, such as Jira's `%d/%b/%y %I:%M %p`.
+    pub date_format: Option<String>,
+    /// Column names of the Pearl fields.
+    pub columns: CsvColumns,
+    /// Source status names mapped to Pearl statuses, matched ignoring case.
+    pub status: BTreeMap<String, Status>,
+    /// Source priority names mapped to Pearl priorities, matched ignoring case.
+    pub priority: BTreeMap<String, u8>,
+}
+
+impl Default for CsvMapping {
+    fn default() -> Self {
+        Self {
+            source_key: "csv_id".to_string(),
+            delimiter: ',',
+            list_separator: ";".to_string(),
+            date_format: None,
+            columns: CsvColumns::default(),
+            status: BTreeMap::new(),
+            priority: BTreeMap::new(),
+        }
+    }
+}
+
+impl CsvMapping {
+    /// Parses a mapping from TOML.
+    ///
+    /// Missing settings keep their defaults, which match the columns written
+    /// by `prl export csv`.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - TOML text
+    ///
+    /// # Returns
+    ///
+    /// The mapping.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TOML is malformed, names unknown settings, or
+    /// maps a priority outside 0-4.
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let mapping: Self = toml::from_str(content)
+            .map_err(|err| AppError::InvalidInput(format!("Invalid CSV mapping: {err}")))?;
+        if let Some((name, priority)) = mapping.priority.iter().find(|(_, value)| **value > 4) {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid CSV mapping: priority '{}' maps to {}, expected 0-4",
+                name, priority
+            )));
+        }
+        if mapping.columns.id.is_empty() || mapping.columns.title.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Invalid CSV mapping: the id and title columns are required".to_string(),
+            ));
+        }
+        Ok(mapping)
+    }
+}
+
+/// Drafts parsed from a CSV file.
+#[derive(Debug, Default)]
+pub struct CsvImport {
+    /// Drafts in row order.
+    pub drafts: Vec<ImportDraft>,
+    /// Number of rows that were skipped.
+    pub skipped: usize,
+    /// Problems found while mapping fields.
+    pub warnings: Vec<String>,
+}
+
+/// Parses a CSV file into drafts.
+///
+/// Rows without an ID or title are skipped with a warning. Unknown status and
+/// priority names fall back to `open` and 2 with a warning.
+///
+/// # Arguments
+///
+/// * `reader` - Reader over the CSV file, starting with the header row
+/// * `mapping` - Column mapping and translation tables
+/// * `default_author` - Author used when a row names none
+///
+/// # Returns
+///
+/// The parsed drafts.
+///
+/// # Errors
+///
+/// Returns an error if the CSV cannot be read or lacks the ID or title
+/// column.
+pub fn parse_csv(
+    reader: impl std::io::Read,
+    mapping: &CsvMapping,
+    default_author: &str,
+) -> Result<CsvImport> {
+    let delimiter = u8::try_from(mapping.delimiter).map_err(|_| {
+        AppError::InvalidInput(format!(
+            "CSV delimiter must be an ASCII character, got '{}'",
+            mapping.delimiter
+        ))
+    })?;
+    let mut csv = ::csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(reader);
+    let headers = csv.headers().map_err(csv_error)?.clone();
+    let mut indexes: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (idx, header) in headers.iter().enumerate() {
+        indexes.entry(header.trim()).or_default().push(idx);
+    }
+    for required in [&mapping.columns.id, &mapping.columns.title] {
+        if !indexes.contains_key(required.as_str()) {
+            return Err(AppError::InvalidInput(format!(
+                "CSV file has no '{}' column",
+                required
+            )));
+        }
+    }
+
+    let now = crate::time::unix_timestamp()?;
+    let mut import = CsvImport::default();
+    let mut blocks: Vec<(usize, Vec<String>)> = Vec::new();
+
+    for (row, record) in csv.records().enumerate() {
+        let line = row + 2;
+        let record = match record {
+            Ok(record) => record,
+            Err(err) => {
+                import.skipped += 1;
+                import
+                    .warnings
+                    .push(format!("Row {}: skipped unreadable row ({})", line, err));
+                continue;
+            }
+        };
+        let cells = |column: &str| -> Vec<&str> {
+            if column.is_empty() {
+                return Vec::new();
+            }
+            indexes
+                .get(column)
+                .map(|idxs| {
+                    idxs.iter()
+                        .filter_map(|idx| record.get(*idx))
+                        .map(str::trim)
+                        .filter(|cell| !cell.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let first = |column: &str| cells(column).first().map(|cell| cell.to_string());
+        let list = |column: &str| -> Vec<String> {
+            cells(column)
+                .into_iter()
+                .flat_map(|cell| cell.split(mapping.list_separator.as_str()))
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+                .collect()
+        };
+
+        let (Some(source_id), Some(title)) =
+            (first(&mapping.columns.id), first(&mapping.columns.title))
+        else {
+            import.skipped += 1;
+            import
+                .warnings
+                .push(format!("Row {}: skipped row without an ID or title", line));
+            continue;
+        };
+
+        let created_at = first(&mapping.columns.created_at)
+            .and_then(|value| parse_date(&value, mapping.date_format.as_deref()))
+            .unwrap_or(now);
+        let updated_at = first(&mapping.columns.updated_at)
+            .and_then(|value| parse_date(&value, mapping.date_format.as_deref()))
+            .unwrap_or(created_at);
+
+        let mut pearl = Pearl::new(
+            title,
+            first(&mapping.columns.author).unwrap_or_else(|| default_author.to_string()),
+        );
+        pearl.id = source_id.clone();
+        pearl.description = first(&mapping.columns.description).unwrap_or_default();
+        pearl.created_at = created_at;
+        pearl.updated_at = updated_at;
+        pearl.status = map_status(
+            &source_id,
+            first(&mapping.columns.status).as_deref(),
+            mapping,
+            &mut import.warnings,
+        );
+        pearl.priority = map_priority(
+            &source_id,
+            first(&mapping.columns.priority).as_deref(),
+            mapping,
+            &mut import.warnings,
+        );
+        for label in list(&mapping.columns.labels) {
+            if !pearl.labels.contains(&label) {
+                pearl.labels.push(label);
+            }
+        }
+
+        for (key, column) in &mapping.columns.metadata {
+            if let Some(value) = first(column) {
+                pearl.metadata.insert(key.clone(), value.into());
+            }
+        }
+
+        let mut deps = Vec::new();
+        for entry in list(&mapping.columns.deps) {
+            match entry.split_once(':') {
+                Some((kind, target)) => match crate::list::parse_dep_type(kind.trim()) {
+                    Ok(dep_type) => deps.push((target.trim().to_string(), dep_type)),
+                    Err(_) => import.warnings.push(format!(
+                        "{}: unknown dependency type '{}', skipped",
+                        source_id, kind
+                    )),
+                },
+                None => deps.push((entry, DepType::Blocks)),
+            }
+        }
+        deps.extend(
+            list(&mapping.columns.blocked_by)
+                .into_iter()
+                .map(|target| (target, DepType::Blocks)),
+        );
+        deps.extend(first(&mapping.columns.parent).map(|target| (target, DepType::ParentChild)));
+        for (target_id, dep_type) in deps {
+            let dep = Dependency {
+                target_id,
+                dep_type,
+            };
+            if !pearl.deps.contains(&dep) {
+                pearl.deps.push(dep);
+            }
+        }
+
+        blocks.push((import.drafts.len(), list(&mapping.columns.blocks)));
+        import.drafts.push(ImportDraft { source_id, pearl });
+    }
+
+    // "Blocks" columns describe the reverse edge, which lives on the target.
+    let rows: HashMap<String, usize> = import
+        .drafts
+        .iter()
+        .enumerate()
+        .map(|(idx, draft)| (draft.source_id.clone(), idx))
+        .collect();
+    for (idx, targets) in blocks {
+        for target in targets {
+            let Some(&target_idx) = rows.get(&target) else {
+                import.warnings.push(format!(
+                    "{}: cannot add 'blocks {}' for an issue outside the file",
+                    import.drafts[idx].source_id, target
+                ));
+                continue;
+            };
+            let dep = Dependency {
+                target_id: import.drafts[idx].source_id.clone(),
+                dep_type: DepType::Blocks,
+            };
+            let deps = &mut import.drafts[target_idx].pearl.deps;
+            if !deps.contains(&dep) {
+                deps.push(dep);
+            }
+        }
+    }
+
+    Ok(import)
+}
+
+fn csv_error(err: ::csv::Error) -> AppError {
+    AppError::InvalidInput(format!("Invalid CSV file: {err}"))
+}
+
+fn parse_date(value: &str, format: Option<&str>) -> Option<i64> {
+    parse_timestamp(&serde_json::Value::from(value)).or_else(|| {
+        let format = format?;
+        chrono::NaiveDateTime::parse_from_str(value, format)
+            .map(|time| time.and_utc().timestamp())
+            .or_else(|_| {
+                chrono::NaiveDate::parse_from_str(value, format).map(|date| {
+                    date.and_hms_opt(0, 0, 0)
+                        .unwrap_or_default()
+                        .and_utc()
+                        .timestamp()
+                })
+            })
+            .ok()
+    })
+}
+
+fn lookup<'a, T>(table: &'a BTreeMap<String, T>, name: &str) -> Option<&'a T> {
+    table
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value)
+}
+
+fn map_status(
+    id: &str,
+    status: Option<&str>,
+    mapping: &CsvMapping,
+    warnings: &mut Vec<String>,
+) -> Status {
+    let Some(status) = status else {
+        return Status::Open;
+    };
+    if let Some(mapped) = lookup(&mapping.status, status) {
+        return *mapped;
+    }
+    crate::list::parse_status(status).unwrap_or_else(|_| {
+        warnings.push(format!("{}: unknown status '{}', using open", id, status));
+        Status::Open
+    })
+}
+
+fn map_priority(
+    id: &str,
+    priority: Option<&str>,
+    mapping: &CsvMapping,
+    warnings: &mut Vec<String>,
+) -> u8 {
+    let Some(priority) = priority else {
+        return 2;
+    };
+    if let Some(mapped) = lookup(&mapping.priority, priority) {
+        return *mapped;
+    }
+    match priority.trim_start_matches(['P', 'p']).parse::<u8>() {
+        Ok(value) if value <= 4 => value,
+        _ => {
+            warnings.push(format!("{}: unknown priority '{}', using 2", id, priority));
+            2
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_jira_csv() {
+        let mapping = CsvMapping::from_toml(
+            r#"
+source_key = "jira_key"
+date_format = "%d/%b/%y %I:%M %p"
+
+[columns]
+id = "Issue key"
+title = "Summary"
+status = "Status"
+priority = "Priority"
+labels = "Labels"
+author = "Reporter"
+created_at = "Created"
+blocks = "Outward issue link (Blocks)"
+blocked_by = "Inward issue link (Blocks)"
+parent = "Parent"
+
+[columns.metadata]
+issue_type = "Issue Type"
+
+[status]
+"To Do" = "open"
+"In Review" = "in_progress"
+Done = "closed"
+
+[priority]
+Highest = 0
+Medium = 2
+"#,
+        )
+        .expect("mapping");
+
+        let export = "\
+Summary,Issue key,Issue Type,Status,Priority,Labels,Labels,Reporter,Created,Outward issue link (Blocks),Inward issue link (Blocks),Parent
+Epic,PROJ-1,Epic,To Do,Highest,launch,,alice,12/Mar/24 10:15 AM,,,
+Schema,PROJ-2,Task,done,Medium,db,backend,bob,13/Mar/24 9:00 AM,PROJ-3,,PROJ-1
+API,PROJ-3,Task,Waiting,Urgent,,,bob,,,,PROJ-1
+,PROJ-4,Task,To Do,,,,,,,,
+";
+        let import = parse_csv(export.as_bytes(), &mapping, "importer").expect("parse");
+        assert_eq!(import.drafts.len(), 3);
+        assert_eq!(import.skipped, 1);
+
+        let epic = &import.drafts[0].pearl;
+        assert_eq!(epic.priority, 0);
+        assert_eq!(epic.author, "alice");
+        assert_eq!(epic.created_at, 1_710_238_500);
+        assert_eq!(epic.metadata.get("issue_type"), Some(&"Epic".into()));
+
+        let schema = &import.drafts[1].pearl;
+        assert_eq!(schema.status, Status::Closed);
+        assert_eq!(schema.labels, vec!["db", "backend"]);
+
+        let api = &import.drafts[2].pearl;
+        assert_eq!(api.status, Status::Open);
+        assert_eq!(api.priority, 2);
+        let deps: Vec<(&str, DepType)> = api
+            .deps
+            .iter()
+            .map(|dep| (dep.target_id.as_str(), dep.dep_type))
+            .collect();
+        assert_eq!(
+            deps,
+            vec![
+                ("PROJ-1", DepType::ParentChild),
+                ("PROJ-2", DepType::Blocks)
+            ]
+        );
+        // Unknown status and priority on PROJ-3, and the row without a title.
+        assert_eq!(import.warnings.len(), 3);
+    }
+}
@@ -0,0 +1,555 @@
+// Rust guideline compliant 2026-02-10
+
+//! Adapter for GitHub and GitLab issue exports.
+//!
+//! Reads offline API dumps: either a JSON array of issues, or an object with an
+//! `issues` array and an optional `comments` (GitHub) or `notes` (GitLab)
+//! array. Issues may also embed their comments or notes as an array.
+//!
+//! Cross-references in issue bodies become dependencies:
+//! - "blocked by #12" and "depends on #12" make this issue blocked by #12
+//! - "blocks #12" makes #12 blocked by this issue
+//! - task-list items such as "- [ ] #12" make #12 a child of this issue
+
+use super::{parse_timestamp, ImportDraft};
+use crate::error::{AppError, Result};
+use pearls_core::{DepType, Dependency, Pearl, Status};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Metadata key that records the source URL of an imported Pearl.
+pub const SOURCE_KEY: &str = "source_url";
+
+/// Issue trackers with a supported export format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Forge {
+    /// GitHub REST API issue objects.
+    GitHub,
+    /// GitLab REST API issue objects.
+    GitLab,
+}
+
+impl Forge {
+    /// Returns the lowercase name of the forge.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Forge::GitHub => "github",
+            Forge::GitLab => "gitlab",
+        }
+    }
+
+    fn number_field(self) -> &'static str {
+        match self {
+            Forge::GitHub => "number",
+            Forge::GitLab => "iid",
+        }
+    }
+
+    fn url_field(self) -> &'static str {
+        match self {
+            Forge::GitHub => "html_url",
+            Forge::GitLab => "web_url",
+        }
+    }
+
+    fn body_field(self) -> &'static str {
+        match self {
+            Forge::GitHub => "body",
+            Forge::GitLab => "description",
+        }
+    }
+
+    fn comments_field(self) -> &'static str {
+        match self {
+            Forge::GitHub => "comments",
+            Forge::GitLab => "notes",
+        }
+    }
+
+    fn user_field(self) -> &'static str {
+        match self {
+            Forge::GitHub => "user",
+            Forge::GitLab => "author",
+        }
+    }
+
+    fn login_field(self) -> &'static str {
+        match self {
+            Forge::GitHub => "login",
+            Forge::GitLab => "username",
+        }
+    }
+}
+
+/// Drafts parsed from a forge export.
+#[derive(Debug, Default)]
+pub struct ForgeImport {
+    /// Drafts in export order.
+    pub drafts: Vec<ImportDraft>,
+    /// Number of entries that were skipped, such as pull requests.
+    pub skipped: usize,
+    /// Problems found while mapping fields.
+    pub warnings: Vec<String>,
+}
+
+/// Parses a GitHub or GitLab issue export.
+///
+/// Issues keep their labels, state, assignees, milestone, and comments. The
+/// source URL and issue number are stored in metadata under `source_url` and
+/// `source_number`. A `P0`-`P4` label also sets the priority.
+///
+/// # Arguments
+///
+/// * `forge` - Tracker that produced the export
+/// * `content` - JSON export text
+///
+/// # Returns
+///
+/// The parsed drafts.
+///
+/// # Errors
+///
+/// Returns an error if the content is not JSON or has no issue array.
+pub fn parse_forge(forge: Forge, content: &str) -> Result<ForgeImport> {
+    let export: Value = serde_json::from_str(content)
+        .map_err(|err| AppError::InvalidInput(format!("Invalid {} export: {err}", forge.name())))?;
+    let (issues, comments) = match &export {
+        Value::Array(issues) => (issues.as_slice(), &[][..]),
+        Value::Object(object) => (
+            object
+                .get("issues")
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .ok_or_else(|| {
+                    AppError::InvalidInput(format!("{} export has no 'issues' array", forge.name()))
+                })?,
+            object
+                .get(forge.comments_field())
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or_default(),
+        ),
+        _ => {
+            return Err(AppError::InvalidInput(format!(
+                "{} export must be an array or an object",
+                forge.name()
+            )))
+        }
+    };
+
+    let mut by_number: HashMap<u64, Vec<&Value>> = HashMap::new();
+    for comment in comments {
+        if let Some(number) = comment_issue_number(forge, comment) {
+            by_number.entry(number).or_default().push(comment);
+        }
+    }
+
+    let now = crate::time::unix_timestamp()?;
+    let mut import = ForgeImport::default();
+    let mut numbers: HashMap<u64, usize> = HashMap::new();
+    let mut refs = Vec::new();
+
+    for (idx, issue) in issues.iter().enumerate() {
+        if issue.get("pull_request").is_some() {
+            import.skipped += 1;
+            continue;
+        }
+        let Some(number) = issue.get(forge.number_field()).and_then(Value::as_u64) else {
+            import.skipped += 1;
+            import.warnings.push(format!(
+                "Issue {}: missing {}",
+                idx + 1,
+                forge.number_field()
+            ));
+            continue;
+        };
+        let title = str_field(issue, "title");
+        if title.trim().is_empty() {
+            import.skipped += 1;
+            import.warnings.push(format!("#{}: missing title", number));
+            continue;
+        }
+
+        let source_id = match issue.get(forge.url_field()).and_then(Value::as_str) {
+            Some(url) => url.to_string(),
+            None => format!("{}#{}", forge.name(), number),
+        };
+        let body = str_field(issue, forge.body_field());
+        let created_at = issue
+            .get("created_at")
+            .and_then(parse_timestamp)
+            .unwrap_or(now);
+        let updated_at = issue
+            .get("updated_at")
+            .and_then(parse_timestamp)
+            .unwrap_or(created_at);
+
+        let author = user_login(forge, issue.get(forge.user_field()))
+            .unwrap_or_else(|| forge.name().to_string());
+        let mut pearl = Pearl::new(title.trim().to_string(), author);
+        pearl.id = source_id.clone();
+        pearl.description = body.trim().to_string();
+        pearl.created_at = created_at;
+        pearl.updated_at = updated_at;
+        pearl.status = match str_field(issue, "state").as_str() {
+            "closed" => Status::Closed,
+            _ => Status::Open,
+        };
+        pearl.labels = labels(issue);
+        if let Some(priority) = pearl.labels.iter().find_map(|label| label_priority(label)) {
+            pearl.priority = priority;
+        }
+
+        pearl
+            .metadata
+            .insert("source_number".to_string(), Value::from(number));
+        let assignees = assignees(forge, issue);
+        if !assignees.is_empty() {
+            pearl
+                .metadata
+                .insert("assignees".to_string(), Value::from(assignees));
+        }
+        if let Some(milestone) = issue
+            .get("milestone")
+            .and_then(|milestone| milestone.get("title"))
+            .and_then(Value::as_str)
+        {
+            pearl
+                .metadata
+                .insert("milestone".to_string(), Value::from(milestone));
+        }
+
+        let embedded = issue
+            .get(forge.comments_field())
+            .and_then(Value::as_array)
+            .map(|comments| comments.iter().collect::<Vec<_>>())
+            .unwrap_or_default();
+        let attached = by_number.get(&number).cloned().unwrap_or_default();
+        for comment in embedded.into_iter().chain(attached) {
+            if comment.get("system").and_then(Value::as_bool) == Some(true) {
+                continue;
+            }
+            let author = user_login(forge, comment.get(forge.user_field()))
+                .unwrap_or_else(|| pearl.author.clone());
+            let created_at = comment
+                .get("created_at")
+                .and_then(parse_timestamp)
+                .unwrap_or(updated_at);
+            if let Err(err) = pearl.add_comment_at(author, str_field(comment, "body"), created_at) {
+                import
+                    .warnings
+                    .push(format!("#{}: skipped comment ({})", number, err));
+            }
+        }
+
+        refs.push((number, cross_references(&body)));
+        numbers.insert(number, import.drafts.len());
+        import.drafts.push(ImportDraft { source_id, pearl });
+    }
+
+    // Resolve "#N" against this export first, then against the URL pattern of
+    // the referencing issue so earlier imports are found by `source_url`.
+    for (number, references) in refs {
+        let from = numbers[&number];
+        for reference in references {
+            let target = match numbers.get(&reference.number) {
+                Some(&idx) => import.drafts[idx].source_id.clone(),
+                None => sibling_source_id(&import.drafts[from].source_id, reference.number),
+            };
+            match reference.kind {
+                Reference::BlockedBy => push_dep(&mut import.drafts[from], target, DepType::Blocks),
+                Reference::Blocks => match numbers.get(&reference.number) {
+                    Some(&idx) => {
+                        let source = import.drafts[from].source_id.clone();
+                        push_dep(&mut import.drafts[idx], source, DepType::Blocks);
+                    }
+                    None => import.warnings.push(format!(
+                        "#{}: cannot add 'blocks #{}' for an issue outside the export",
+                        number, reference.number
+                    )),
+                },
+                Reference::Child => match numbers.get(&reference.number) {
+                    Some(&idx) => {
+                        let source = import.drafts[from].source_id.clone();
+                        push_dep(&mut import.drafts[idx], source, DepType::ParentChild);
+                    }
+                    None => import.warnings.push(format!(
+                        "#{}: cannot link child #{} outside the export",
+                        number, reference.number
+                    )),
+                },
+            }
+        }
+    }
+
+    Ok(import)
+}
+
+/// Kind of a cross-reference found in an issue body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reference {
+    BlockedBy,
+    Blocks,
+    Child,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CrossReference {
+    kind: Reference,
+    number: u64,
+}
+
+/// Finds dependency phrases and task-list child issues in a Markdown body.
+fn cross_references(body: &str) -> Vec<CrossReference> {
+    let mut found = Vec::new();
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        let task = ["- [ ] ", "- [x] ", "- [X] ", "* [ ] ", "* [x] ", "* [X] "]
+            .iter()
+            .find_map(|prefix| trimmed.strip_prefix(prefix));
+        if let Some(item) = task {
+            if let Some(number) = item.trim().strip_prefix('#').and_then(leading_number) {
+                found.push(CrossReference {
+                    kind: Reference::Child,
+                    number,
+                });
+                continue;
+            }
+        }
+
+        let lower = line.to_ascii_lowercase();
+        for (phrase, kind) in [
+            ("blocked by", Reference::BlockedBy),
+            ("depends on", Reference::BlockedBy),
+            ("blocks", Reference::Blocks),
+        ] {
+            let mut rest = lower.as_str();
+            while let Some(pos) = rest.find(phrase) {
+                let before = &rest[..pos];
+                rest = &rest[pos + phrase.len()..];
+                // "blocks" inside "blocked by" never matches, but guard
+                // against words such as "unblocks".
+                if before
+                    .chars()
+                    .last()
+                    .is_some_and(|c| c.is_ascii_alphanumeric())
+                {
+                    continue;
+                }
+                for number in issue_list(rest) {
+                    found.push(CrossReference { kind, number });
+                }
+            }
+        }
+    }
+    found.dedup();
+    found
+}
+
+/// Reads "#1, #2 and #3" from the start of `text`.
+fn issue_list(text: &str) -> Vec<u64> {
+    let mut numbers = Vec::new();
+    let mut rest = text.trim_start_matches([':', ' ']);
+    while let Some(after) = rest.strip_prefix('#') {
+        let Some(number) = leading_number(after) else {
+            break;
+        };
+        numbers.push(number);
+        let digits = after.len() - after.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        rest = after[digits..].trim_start_matches([',', ' ']);
+        rest = rest.strip_prefix("and ").unwrap_or(rest).trim_start();
+    }
+    numbers
+}
+
+fn leading_number(text: &str) -> Option<u64> {
+    let digits: String = text.chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+/// Builds the URL of issue `number` from the URL of a sibling issue.
+fn sibling_source_id(source_id: &str, number: u64) -> String {
+    match source_id.rsplit_once('/') {
+        Some((base, last)) if last.parse::<u64>().is_ok() => format!("{}/{}", base, number),
+        _ => match source_id.rsplit_once('#') {
+            Some((forge, _)) => format!("{}#{}", forge, number),
+            None => format!("#{}", number),
+        },
+    }
+}
+
+fn push_dep(draft: &mut ImportDraft, target_id: String, dep_type: DepType) {
+    let dep = Dependency {
+        target_id,
+        dep_type,
+    };
+    if !draft.pearl.deps.contains(&dep) {
+        draft.pearl.deps.push(dep);
+    }
+}
+
+fn comment_issue_number(forge: Forge, comment: &Value) -> Option<u64> {
+    let direct = match forge {
+        Forge::GitHub => comment.get("issue_number"),
+        Forge::GitLab => comment.get("noteable_iid"),
+    };
+    direct.and_then(Value::as_u64).or_else(|| {
+        comment
+            .get("issue_url")
+            .and_then(Value::as_str)
+            .and_then(|url| url.rsplit('/').next())
+            .and_then(|number| number.parse().ok())
+    })
+}
+
+fn str_field(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn user_login(forge: Forge, user: Option<&Value>) -> Option<String> {
+    user.and_then(|user| user.get(forge.login_field()))
+        .and_then(Value::as_str)
+        .filter(|login| !login.trim().is_empty())
+        .map(str::to_string)
+}
+
+fn labels(issue: &Value) -> Vec<String> {
+    issue
+        .get("labels")
+        .and_then(Value::as_array)
+        .map(|labels| {
+            labels
+                .iter()
+                .filter_map(|label| {
+                    label
+                        .as_str()
+                        .or_else(|| label.get("name").and_then(Value::as_str))
+                })
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn assignees(forge: Forge, issue: &Value) -> Vec<String> {
+    let mut logins: Vec<String> = issue
+        .get("assignees")
+        .and_then(Value::as_array)
+        .map(|users| {
+            users
+                .iter()
+                .filter_map(|user| user_login(forge, Some(user)))
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(login) = user_login(forge, issue.get("assignee")) {
+        if !logins.contains(&login) {
+            logins.push(login);
+        }
+    }
+    logins
+}
+
+/// Reads a priority from labels such as `P1` or `priority: P1`.
+fn label_priority(label: &str) -> Option<u8> {
+    let label = label.trim();
+    let token = label
+        .rsplit(|c: char| c == ':' || c == '/' || c.is_whitespace())
+        .next()
+        .unwrap_or(label);
+    let digit = token.strip_prefix(['P', 'p'])?;
+    match digit.parse::<u8>() {
+        Ok(priority) if priority <= 4 && digit.len() == 1 => Some(priority),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_github_export() {
+        let export = r#"{
+            "issues": [
+                {"number": 1, "title": "Epic", "state": "open",
+                 "html_url": "https://github.com/o/r/issues/1",
+                 "body": "Tasks:\n- [ ] #2\n- [x] #3",
+                 "labels": [{"name": "P1"}, {"name": "epic"}],
+                 "user": {"login": "alice"},
+                 "assignees": [{"login": "bob"}],
+                 "milestone": {"title": "v1"},
+                 "created_at": "2024-01-01T00:00:00Z"},
+                {"number": 2, "title": "Child", "state": "open",
+                 "html_url": "https://github.com/o/r/issues/2",
+                 "body": "Blocked by #3 and #9"},
+                {"number": 3, "title": "Done", "state": "closed",
+                 "html_url": "https://github.com/o/r/issues/3", "body": null},
+                {"number": 4, "title": "A PR", "pull_request": {}}
+            ],
+            "comments": [
+                {"issue_url": "https://api.github.com/repos/o/r/issues/1",
+                 "user": {"login": "carol"}, "body": "Looks good",
+                 "created_at": "2024-01-02T00:00:00Z"}
+            ]
+        }"#;
+
+        let import = parse_forge(Forge::GitHub, export).expect("parse");
+        assert_eq!(import.drafts.len(), 3);
+        assert_eq!(import.skipped, 1);
+
+        let epic = &import.drafts[0].pearl;
+        assert_eq!(epic.author, "alice");
+        assert_eq!(epic.priority, 1);
+        assert_eq!(epic.metadata.get("milestone"), Some(&Value::from("v1")));
+        assert_eq!(epic.metadata.get("source_number"), Some(&Value::from(1)));
+        assert_eq!(epic.comments[0].author, "carol");
+
+        let child = &import.drafts[1].pearl;
+        let deps: Vec<(&str, DepType)> = child
+            .deps
+            .iter()
+            .map(|dep| (dep.target_id.as_str(), dep.dep_type))
+            .collect();
+        assert_eq!(
+            deps,
+            vec![
+                ("https://github.com/o/r/issues/1", DepType::ParentChild),
+                ("https://github.com/o/r/issues/3", DepType::Blocks),
+                ("https://github.com/o/r/issues/9", DepType::Blocks),
+            ]
+        );
+        assert_eq!(import.drafts[2].pearl.status, Status::Closed);
+    }
+
+    #[test]
+    fn test_parse_gitlab_export() {
+        let export = r#"[
+            {"iid": 7, "title": "Login fails", "state": "opened",
+             "web_url": "https://gitlab.com/g/p/-/issues/7",
+             "description": "This blocks #8",
+             "labels": ["bug"], "author": {"username": "dana"},
+             "notes": [
+                {"author": {"username": "erin"}, "body": "changed the description", "system": true},
+                {"author": {"username": "erin"}, "body": "Repro attached"}
+             ]},
+            {"iid": 8, "title": "Release", "state": "closed",
+             "web_url": "https://gitlab.com/g/p/-/issues/8"}
+        ]"#;
+
+        let import = parse_forge(Forge::GitLab, export).expect("parse");
+        let login = &import.drafts[0].pearl;
+        assert_eq!(login.author, "dana");
+        assert_eq!(login.status, Status::Open);
+        assert_eq!(login.comments.len(), 1);
+        assert_eq!(
+            import.drafts[1].pearl.deps[0].target_id,
+            "https://gitlab.com/g/p/-/issues/7"
+        );
+    }
+}
@@ -0,0 +1,426 @@
+// Rust guideline compliant 2026-02-10
+
+//! Adapter for Markdown plans and TODO lists.
+//!
+//! Headings become parent Pearls and checklist items (`- [ ]`, `- [x]`) become
+//! their children; items indented under another item are its children. Inline
+//! `#label` and `P0`-`P4` tokens set labels and priority, and other text under
+//! a heading or item becomes its description. An HTML comment holding a Pearl
+//! ID, as written by `prl export markdown`, ties a line to an existing Pearl.
+
+use super::ImportDraft;
+use crate::error::Result;
+use pearls_core::identity::validate_id_format;
+use pearls_core::{DepType, Dependency, Pearl, Status};
+use std::collections::HashSet;
+
+/// Metadata key that records the Markdown source ID of an imported Pearl.
+pub const SOURCE_KEY: &str = "markdown_id";
+
+/// Drafts parsed from a Markdown document.
+#[derive(Debug, Default)]
+pub struct MarkdownImport {
+    /// Drafts in document order.
+    pub drafts: Vec<ImportDraft>,
+    /// Number of headings or items that were skipped.
+    pub skipped: usize,
+    /// Problems found while parsing.
+    pub warnings: Vec<String>,
+}
+
+/// Title, labels, priority, and Pearl ID parsed from a heading or item.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Entry {
+    title: String,
+    labels: Vec<String>,
+    priority: Option<u8>,
+    id: Option<String>,
+}
+
+/// Parses a Markdown document into drafts.
+///
+/// Source IDs are the document name followed by the path of heading and item
+/// titles, so re-importing an edited document updates the same Pearls as long
+/// as titles and nesting are unchanged. Lines carrying a Pearl ID comment use
+/// that ID instead.
+///
+/// # Arguments
+///
+/// * `source` - Name of the document, such as its path in the repository
+/// * `content` - Markdown text
+/// * `author` - Author recorded on new Pearls
+///
+/// # Returns
+///
+/// The parsed drafts.
+///
+/// # Errors
+///
+/// Returns an error if the current time cannot be read.
+pub fn parse_markdown(source: &str, content: &str, author: &str) -> Result<MarkdownImport> {
+    let now = crate::time::unix_timestamp()?;
+    let mut import = MarkdownImport::default();
+    let mut used = HashSet::new();
+    // (heading level, draft index, slug path)
+    let mut headings: Vec<(usize, usize, String)> = Vec::new();
+    // (indent, draft index, slug path)
+    let mut items: Vec<(usize, usize, String)> = Vec::new();
+    let mut target: Option<usize> = None;
+    let mut blank_lines = 0usize;
+    let mut in_code = false;
+
+    for (idx, line) in content.lines().enumerate() {
+        let indent = indent_width(line);
+        let trimmed = line.trim();
+
+        let fence = trimmed.starts_with("```") || trimmed.starts_with("~~~");
+        let parsed = if in_code || fence {
+            Line::Text
+        } else {
+            classify(line)
+        };
+        if fence {
+            in_code = !in_code;
+        }
+
+        let (level_or_indent, checked, text, is_heading) = match parsed {
+            Line::Blank => {
+                blank_lines += 1;
+                continue;
+            }
+            Line::Text => {
+                let owner = items
+                    .iter()
+                    .rev()
+                    .find(|(item_indent, _, _)| *item_indent < indent)
+                    .or(headings.last())
+                    .map(|(_, draft, _)| *draft);
+                if let Some(owner) = owner {
+                    append_description(
+                        &mut import.drafts[owner].pearl,
+                        trimmed,
+                        target == Some(owner),
+                        blank_lines,
+                    );
+                    target = Some(owner);
+                }
+                blank_lines = 0;
+                continue;
+            }
+            Line::Heading {
+                level,
+                checked,
+                text,
+            } => (level, checked, text, true),
+            Line::Item { checked, text } => (indent, Some(checked), text, false),
+        };
+        blank_lines = 0;
+
+        let entry = parse_entry(text);
+        if entry.title.is_empty() {
+            import.skipped += 1;
+            import.warnings.push(format!(
+                "{}:{}: skipped entry without a title",
+                source,
+                idx + 1
+            ));
+            continue;
+        }
+
+        let parent = if is_heading {
+            while headings
+                .last()
+                .is_some_and(|(level, _, _)| *level >= level_or_indent)
+            {
+                headings.pop();
+            }
+            items.clear();
+            headings.last()
+        } else {
+            while items
+                .last()
+                .is_some_and(|(indent, _, _)| *indent >= level_or_indent)
+            {
+                items.pop();
+            }
+            items.last().or(headings.last())
+        };
+        let parent_path = parent.map(|(_, _, path)| path.clone()).unwrap_or_default();
+        let parent_source = parent.map(|(_, draft, _)| import.drafts[*draft].source_id.clone());
+
+        let path = format!("{}/{}", parent_path, slug(&entry.title));
+        let source_id = match &entry.id {
+            Some(id) => id.clone(),
+            None => unique(format!("{}#{}", source, &path[1..]), &mut used),
+        };
+        used.insert(source_id.clone());
+
+        let mut pearl = Pearl::new(entry.title, author.to_string());
+        pearl.id = source_id.clone();
+        pearl.created_at = now;
+        pearl.updated_at = now;
+        pearl.labels = entry.labels;
+        if let Some(priority) = entry.priority {
+            pearl.priority = priority;
+        }
+        if checked == Some(true) {
+            pearl.status = Status::Closed;
+        }
+        if let Some(parent) = parent_source {
+            pearl.deps.push(Dependency {
+                target_id: parent,
+                dep_type: DepType::ParentChild,
+            });
+        }
+
+        let draft = import.drafts.len();
+        import.drafts.push(ImportDraft { source_id, pearl });
+        target = Some(draft);
+        if is_heading {
+            headings.push((level_or_indent, draft, path));
+        } else {
+            items.push((level_or_indent, draft, path));
+        }
+    }
+
+    Ok(import)
+}
+
+/// Keeps fields that Markdown cannot express from the matching Pearls.
+///
+/// A checklist only distinguishes open from done, so an unchecked item must
+/// not reopen a Pearl that is in progress, blocked, or deferred. Matched
+/// drafts also keep the existing author and all dependencies other than
+/// parent-child links, which the document does express.
+///
+/// # Arguments
+///
+/// * `drafts` - Drafts produced by [`parse_markdown`]
+/// * `existing` - Pearls currently in the repository
+pub fn preserve_existing(drafts: &mut [ImportDraft], existing: &[Pearl]) {
+    for draft in drafts {
+        let matched = existing.iter().find(|pearl| {
+            pearl.id == draft.source_id
+                || pearl
+                    .metadata
+                    .get(SOURCE_KEY)
+                    .and_then(|value| value.as_str())
+                    == Some(draft.source_id.as_str())
+        });
+        let Some(current) = matched else {
+            continue;
+        };
+        draft.pearl.author = current.author.clone();
+        if draft.pearl.status == Status::Open && current.status != Status::Closed {
+            draft.pearl.status = current.status;
+        }
+        draft.pearl.deps.extend(
+            current
+                .deps
+                .iter()
+                .filter(|dep| dep.dep_type != DepType::ParentChild)
+                .cloned(),
+        );
+    }
+}
+
+enum Line<'a> {
+    Blank,
+    Text,
+    Heading {
+        level: usize,
+        checked: Option<bool>,
+        text: &'a str,
+    },
+    Item {
+        checked: bool,
+        text: &'a str,
+    },
+}
+
+fn classify(line: &str) -> Line<'_> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return Line::Blank;
+    }
+
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+        let text = trimmed[level..].trim();
+        return match checkbox(text) {
+            Some((checked, rest)) => Line::Heading {
+                level,
+                checked: Some(checked),
+                text: rest,
+            },
+            None => Line::Heading {
+                level,
+                checked: None,
+                text,
+            },
+        };
+    }
+
+    let marker = trimmed
+        .strip_prefix(['-', '*', '+'])
+        .or_else(|| {
+            let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+            (digits > 0)
+                .then(|| trimmed[digits..].strip_prefix(['.', ')']))
+                .flatten()
+        })
+        .and_then(|rest| rest.strip_prefix(' '));
+    match marker.and_then(|rest| checkbox(rest.trim_start())) {
+        Some((checked, text)) => Line::Item { checked, text },
+        None => Line::Text,
+    }
+}
+
+fn checkbox(text: &str) -> Option<(bool, &str)> {
+    let rest = text.strip_prefix('[')?;
+    let checked = match rest.chars().next()? {
+        ' ' => false,
+        'x' | 'X' => true,
+        _ => return None,
+    };
+    let rest = rest[1..].strip_prefix(']')?;
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some((checked, rest.trim()))
+}
+
+/// Splits a heading or item into its title and inline tokens.
+fn parse_entry(text: &str) -> Entry {
+    let mut entry = Entry::default();
+    let mut text = text.trim();
+    if let Some(start) = text.rfind("<!--") {
+        if let Some(comment) = text[start + 4..].strip_suffix("-->") {
+            let comment = comment.trim();
+            if validate_id_format(comment).is_ok() {
+                entry.id = Some(comment.to_string());
+                text = text[..start].trim_end();
+            }
+        }
+    }
+
+    let mut words = Vec::new();
+    for word in text.split_whitespace() {
+        if let Some(label) = word.strip_prefix('#') {
+            if label.starts_with(|c: char| c.is_alphabetic()) {
+                if !entry.labels.iter().any(|known| known == label) {
+                    entry.labels.push(label.to_string());
+                }
+                continue;
+            }
+        }
+        if let Some(digit) = word.strip_prefix('P') {
+            if let Ok(priority @ 0..=4) = digit.parse::<u8>() {
+                if digit.len() == 1 {
+                    entry.priority = Some(priority);
+                    continue;
+                }
+            }
+        }
+        words.push(word);
+    }
+    entry.title = words.join(" ");
+    entry
+}
+
+fn append_description(pearl: &mut Pearl, text: &str, continues: bool, blank_lines: usize) {
+    if !pearl.description.is_empty() {
+        let breaks = if continues { 1 + blank_lines.min(1) } else { 2 };
+        pearl.description.push_str(&"\n".repeat(breaks));
+    }
+    pearl.description.push_str(text);
+}
+
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+fn slug(title: &str) -> String {
+    let mut slug = String::with_capacity(title.len());
+    for c in title.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+fn unique(candidate: String, used: &mut HashSet<String>) -> String {
+    if !used.contains(&candidate) {
+        return candidate;
+    }
+    (2..)
+        .map(|n| format!("{}~{}", candidate, n))
+        .find(|id| !used.contains(id))
+        .unwrap_or(candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_markdown_plan() {
+        let plan = "\
+# Release P1 #launch
+
+Ship the first version.
+
+- [ ] Write docs #docs
+  - [x] Outline
+  - [ ] Examples P0
+    Cover the CLI.
+- [x] Tag release <!-- prl-abc123 -->
+
+## Later
+- [ ] Nice to have P4
+";
+        let import = parse_markdown("TODO.md", plan, "alice").expect("parse");
+        let titles: Vec<&str> = import
+            .drafts
+            .iter()
+            .map(|draft| draft.pearl.title.as_str())
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Release",
+                "Write docs",
+                "Outline",
+                "Examples",
+                "Tag release",
+                "Later",
+                "Nice to have"
+            ]
+        );
+
+        let release = &import.drafts[0];
+        assert_eq!(release.source_id, "TODO.md#release");
+        assert_eq!(release.pearl.priority, 1);
+        assert_eq!(release.pearl.labels, vec!["launch"]);
+        assert_eq!(release.pearl.description, "Ship the first version.");
+
+        let parent_of = |idx: usize| import.drafts[idx].pearl.deps[0].target_id.as_str();
+        assert_eq!(parent_of(1), "TODO.md#release");
+        assert_eq!(parent_of(2), "TODO.md#release/write-docs");
+        assert_eq!(parent_of(3), "TODO.md#release/write-docs");
+        assert_eq!(parent_of(5), "TODO.md#release");
+        assert_eq!(parent_of(6), "TODO.md#release/later");
+
+        assert_eq!(import.drafts[2].pearl.status, Status::Closed);
+        assert_eq!(import.drafts[3].pearl.priority, 0);
+        assert_eq!(import.drafts[3].pearl.description, "Cover the CLI.");
+        assert_eq!(import.drafts[4].source_id, "prl-abc123");
+        assert_eq!(import.drafts[4].pearl.status, Status::Closed);
+    }
+}
@@ -3,25 +3,50 @@
 //! Shared application services for Pearls.
 //!
 //! This crate provides reusable, non-CLI-specific helpers for repository
-//! checks, ID resolution, list filtering, transition validation, and
-//! standardized response envelopes.
+//! checks and repairs, author identity, compaction, ID resolution, importers
+//! and exporters, changelog generation, linked commits, change notifications,
+//! list filtering, search and statistics, agent sessions, the SQLite query
+//! cache, transition validation, and standardized response envelopes.
 
+pub mod author;
+pub mod cache;
+pub mod changelog;
+pub mod commits;
+pub mod compact;
+pub mod doctor;
 pub mod error;
+pub mod events;
+pub mod export;
 pub mod fsm;
 pub mod ids;
+pub mod import;
 pub mod list;
-pub mod compact;
 pub mod ready;
 pub mod repo;
 pub mod response;
+pub mod search;
+pub mod session;
+pub mod stats;
 pub mod time;
 
+pub use author::default_author;
+pub use cache::{drop_cache, QueryCache};
+pub use commits::{linked_commits, LinkedCommit};
+pub use compact::{compact, compact_closed, CompactReport};
+pub use doctor::{diagnose, DoctorReport, Finding, Severity};
 pub use error::{AppError, ErrorCode, Result};
+pub use events::{dispatch_events, DispatchReport, Event};
 pub use fsm::validate_transition;
 pub use ids::resolve_pearl_id;
-pub use list::{list_pearls, parse_dep_type, parse_status, ListOptions};
-pub use compact::compact_closed;
-pub use ready::ready_queue;
-pub use repo::RepoContext;
+pub use import::{apply_import, plan_import, ImportAction, ImportDraft, ImportPlan};
+pub use list::{list_pearls, load_candidates, parse_dep_type, parse_status, ListOptions};
+pub use ready::{load_ready_queue, ready_queue};
+pub use repo::{RepoContext, PEARLS_DIR_ENV, REPO_ENV};
 pub use response::{ErrorEnvelope, SuccessEnvelope};
+pub use search::search_pearls;
+pub use session::{
+    add_session_note, end_session, parse_comment_kind, resume, start_session, ResumeEntry, Session,
+    SessionInfo, SessionNote,
+};
+pub use stats::{compute_stats, Stats};
 pub use time::unix_timestamp;
@@ -3,8 +3,9 @@
 //! Listing and filtering helpers for Pearls.
 
 use crate::error::{AppError, Result};
-use pearls_core::{DepType, Pearl, Status};
+use pearls_core::{DepType, Pearl, Status, Storage};
 use rayon::prelude::*;
+use std::collections::BTreeSet;
 
 /// List options for filtering and sorting Pearls.
 #[derive(Debug, Clone, Default)]
@@ -94,6 +95,7 @@ pub fn parse_dep_type(value: &str) -> Result<DepType> {
 /// # Returns
 ///
 /// The filtered and sorted list of Pearls.
+#[allow(clippy::unnecessary_sort_by)]
 pub fn list_pearls(mut pearls: Vec<Pearl>, options: &ListOptions) -> Vec<Pearl> {
     pearls = apply_filters(pearls, options);
 
@@ -106,6 +108,50 @@ pub fn list_pearls(mut pearls: Vec<Pearl>, options: &ListOptions) -> Vec<Pearl>
     pearls
 }
 
+/// Loads the Pearls that may match `options` from storage.
+///
+/// When the storage has an index, its postings narrow the candidates by status,
+/// priority and labels so that only those records are parsed. Otherwise every
+/// Pearl is loaded. The result still needs to go through [`list_pearls`].
+///
+/// # Arguments
+///
+/// * `storage` - Storage to load from
+/// * `options` - List options
+///
+/// # Returns
+///
+/// Candidate Pearls for the listing.
+///
+/// # Errors
+///
+/// Returns an error if the storage cannot be read.
+pub fn load_candidates(storage: &Storage, options: &ListOptions) -> Result<Vec<Pearl>> {
+    let Some(postings) = storage.index().map(|index| index.postings()) else {
+        return Ok(storage.load_all()?);
+    };
+
+    let mut sets: Vec<BTreeSet<&str>> = Vec::new();
+    if let Some(status) = options.status {
+        sets.push(postings.with_status(status).collect());
+    }
+    if let Some(priority) = options.priority {
+        sets.push(postings.with_priority(priority).collect());
+    }
+    for label in &options.labels {
+        sets.push(postings.with_label(label).collect());
+    }
+
+    let Some(mut candidates) = sets.pop() else {
+        return Ok(storage.load_all()?);
+    };
+    for set in &sets {
+        candidates.retain(|id| set.contains(id));
+    }
+
+    Ok(storage.load_by_ids(candidates)?)
+}
+
 fn apply_filters(pearls: Vec<Pearl>, options: &ListOptions) -> Vec<Pearl> {
     const PARALLEL_THRESHOLD: usize = 1_000;
 
@@ -173,6 +219,7 @@ fn apply_filters(pearls: Vec<Pearl>, options: &ListOptions) -> Vec<Pearl> {
     }
 }
 
+#[allow(clippy::unnecessary_sort_by)]
 fn sort_pearls(pearls: &mut [Pearl], field: &str) {
     match field {
         "id" => pearls.sort_by(|a, b| a.id.cmp(&b.id)),
@@ -208,6 +255,7 @@ mod tests {
             }],
             metadata: Default::default(),
             comments: Vec::new(),
+            extra: Default::default(),
         }
     }
 
@@ -232,4 +280,31 @@ mod tests {
         let filtered = list_pearls(pearls, &options);
         assert_eq!(filtered.len(), 1);
     }
+
+    #[test]
+    fn test_load_candidates_uses_index_postings() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let mut closed = sample_pearl("prl-abc456", "core");
+        closed.status = Status::Closed;
+        let pearls = vec![
+            sample_pearl("prl-abc123", "Core"),
+            closed,
+            sample_pearl("prl-abc789", "ui"),
+        ];
+        let mut storage = Storage::with_index(
+            temp_dir.path().join("issues.jsonl"),
+            Some(temp_dir.path().join("index.bin")),
+        )
+        .expect("storage");
+        storage.save_all(&pearls).expect("save");
+
+        let options = ListOptions {
+            status: Some(Status::Open),
+            labels: vec!["core".to_string()],
+            ..ListOptions::default()
+        };
+        let candidates = load_candidates(&storage, &options).expect("candidates");
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].id, "prl-abc123");
+    }
 }
@@ -3,7 +3,8 @@
 //! Ready-queue helpers for Pearls.
 
 use crate::error::Result;
-use pearls_core::{IssueGraph, Pearl};
+use pearls_core::{DepType, Error, IssueGraph, Pearl, Status, Storage};
+use std::collections::BTreeSet;
 
 /// Computes the ready queue for the provided Pearls.
 ///
@@ -22,3 +23,147 @@ pub fn ready_queue(pearls: Vec<Pearl>) -> Result<Vec<Pearl>> {
     let graph = IssueGraph::from_pearls(pearls)?;
     Ok(graph.ready_queue().into_iter().cloned().collect())
 }
+
+/// Computes the ready queue directly from storage.
+///
+/// When the storage has an index, the queue is derived from the status postings
+/// and reverse dependency edges, and only the ready Pearls are parsed. Without an
+/// index, all Pearls are loaded and passed to [`ready_queue`].
+///
+/// # Arguments
+///
+/// * `storage` - Storage to load from
+///
+/// # Returns
+///
+/// Vector of ready Pearls, sorted by priority and recency.
+///
+/// # Errors
+///
+/// Returns an error if the storage cannot be read or the dependencies form a
+/// cycle.
+pub fn load_ready_queue(storage: &Storage) -> Result<Vec<Pearl>> {
+    let Some(postings) = storage.index().map(|index| index.postings()) else {
+        return ready_queue(storage.load_all()?);
+    };
+    if let Some(cycle) = postings.find_cycle() {
+        return Err(Error::CycleDetected(cycle).into());
+    }
+
+    let active = [Status::Open, Status::InProgress, Status::Blocked];
+    let mut blocked = BTreeSet::new();
+    for status in active.iter().chain([&Status::Deferred]) {
+        for id in postings.with_status(*status) {
+            blocked.extend(
+                postings
+                    .dependents(id)
+                    .filter(|(_, dep_type)| *dep_type == DepType::Blocks)
+                    .map(|(source, _)| source),
+            );
+        }
+    }
+
+    let candidates = active
+        .iter()
+        .flat_map(|status| postings.with_status(*status))
+        .filter(|id| !blocked.contains(id));
+
+    let mut ready = storage.load_by_ids(candidates)?;
+    ready.sort_by(|a, b| match a.priority.cmp(&b.priority) {
+        std::cmp::Ordering::Equal => b.updated_at.cmp(&a.updated_at),
+        other => other,
+    });
+    Ok(ready)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pearls_core::Dependency;
+
+    fn sample_pearl(id: &str, status: Status, priority: u8, blocked_by: Option<&str>) -> Pearl {
+        Pearl {
+            id: id.to_string(),
+            title: "Title".to_string(),
+            description: String::new(),
+            status,
+            priority,
+            created_at: 1000,
+            updated_at: 1000,
+            author: "author".to_string(),
+            labels: Vec::new(),
+            deps: blocked_by
+                .map(|target| Dependency {
+                    target_id: target.to_string(),
+                    dep_type: DepType::Blocks,
+                })
+                .into_iter()
+                .collect(),
+            metadata: Default::default(),
+            comments: Vec::new(),
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_indexed_ready_queue_matches_graph() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let pearls = vec![
+            sample_pearl("prl-aaa111", Status::Open, 1, None),
+            sample_pearl("prl-bbb222", Status::Open, 0, Some("prl-aaa111")),
+            sample_pearl("prl-ccc333", Status::Closed, 2, None),
+            sample_pearl("prl-ddd444", Status::InProgress, 3, Some("prl-ccc333")),
+            sample_pearl("prl-eee555", Status::Deferred, 0, None),
+            sample_pearl("prl-fff666", Status::Blocked, 2, Some("prl-eee555")),
+            sample_pearl("prl-abc777", Status::Open, 2, Some("prl-dead00")),
+        ];
+        let mut storage = Storage::with_index(
+            temp_dir.path().join("issues.jsonl"),
+            Some(temp_dir.path().join("index.bin")),
+        )
+        .expect("storage");
+        storage.save_all(&pearls).expect("save");
+
+        let expected: Vec<String> = ready_queue(pearls)
+            .expect("ready")
+            .into_iter()
+            .map(|pearl| pearl.id)
+            .collect();
+        let indexed: Vec<String> = load_ready_queue(&storage)
+            .expect("indexed ready")
+            .into_iter()
+            .map(|pearl| pearl.id)
+            .collect();
+        assert_eq!(indexed, expected);
+        assert_eq!(indexed, vec!["prl-aaa111", "prl-abc777", "prl-ddd444"]);
+    }
+
+    #[test]
+    fn test_indexed_ready_queue_rejects_cycles() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let pearls = vec![
+            sample_pearl("prl-aaa111", Status::Open, 1, Some("prl-ccc333")),
+            sample_pearl("prl-bbb222", Status::Closed, 1, Some("prl-aaa111")),
+            sample_pearl("prl-ccc333", Status::Open, 1, Some("prl-bbb222")),
+            sample_pearl("prl-ddd444", Status::Open, 1, None),
+        ];
+        let mut storage = Storage::with_index(
+            temp_dir.path().join("issues.jsonl"),
+            Some(temp_dir.path().join("index.bin")),
+        )
+        .expect("storage");
+        storage.save_all(&pearls).expect("save");
+
+        assert!(matches!(
+            ready_queue(pearls),
+            Err(crate::AppError::Core(Error::CycleDetected(_)))
+        ));
+        match load_ready_queue(&storage) {
+            Err(crate::AppError::Core(Error::CycleDetected(mut cycle))) => {
+                cycle.sort();
+                assert_eq!(cycle, vec!["prl-aaa111", "prl-bbb222", "prl-ccc333"]);
+            }
+            other => panic!("expected a cycle, got {:?}", other.map(|_| ())),
+        }
+    }
+}
@@ -14,14 +14,40 @@ pub struct RepoContext {
     issues_path: PathBuf,
     archive_path: PathBuf,
     config_path: PathBuf,
+    index_path: PathBuf,
+    cache_path: PathBuf,
 }
 
+/// Local, regenerable files that are kept out of version control.
+const LOCAL_FILES: [&str; 6] = [
+    "index.bin",
+    "cache.db*",
+    "current",
+    "audit.jsonl",
+    "events-state.json",
+    "events-dead.jsonl",
+];
+
+/// Environment variable naming the `.pearls` directory to use, bypassing
+/// discovery.
+pub const PEARLS_DIR_ENV: &str = "PEARLS_DIR";
+
+/// Environment variable naming the directory discovery starts from, set by
+/// `prl --repo`.
+pub const REPO_ENV: &str = "PEARLS_REPO";
+
 impl RepoContext {
-    /// Discovers a Pearls repository starting from an optional root.
+    /// Discovers a Pearls repository starting from an optional directory.
+    ///
+    /// `$PEARLS_DIR` names the `.pearls` directory directly. Otherwise the
+    /// search starts at `start`, `$PEARLS_REPO`, or the current directory and
+    /// moves up to the nearest `.pearls`, stopping at the Git root. A linked
+    /// worktree without its own `.pearls` uses the main worktree's, and a
+    /// submodule without one continues into its superproject.
     ///
     /// # Arguments
     ///
-    /// * `repo_root` - Optional repository root to pin discovery
+    /// * `start` - Optional directory to start discovery from
     ///
     /// # Returns
     ///
@@ -30,30 +56,76 @@ impl RepoContext {
     /// # Errors
     ///
     /// Returns an error if:
-    /// - The repository root cannot be resolved
-    /// - The `.pearls` directory is missing
-    pub fn discover(repo_root: Option<&Path>) -> Result<Self> {
-        let root = match repo_root {
-            Some(root) => root.to_path_buf(),
-            None => std::env::current_dir()?,
-        };
-        let pearls_dir = root.join(".pearls");
-        if !pearls_dir.exists() {
-            return Err(AppError::RepoNotInitialized {
-                path: pearls_dir.clone(),
-            });
+    /// - The current directory cannot be resolved
+    /// - No `.pearls` directory is found
+    pub fn discover(start: Option<&Path>) -> Result<Self> {
+        if let Some(pearls_dir) = env_path(PEARLS_DIR_ENV) {
+            let pearls_dir = std::env::current_dir()?.join(pearls_dir);
+            if !pearls_dir.is_dir() {
+                return Err(AppError::RepoNotInitialized { path: pearls_dir });
+            }
+            return Ok(Self::at(pearls_dir));
+        }
+
+        let start = Self::start_dir(start)?;
+        match find_pearls_dir(&start) {
+            Some(pearls_dir) => Ok(Self::at(pearls_dir)),
+            None => Err(AppError::RepoNotInitialized {
+                path: start.join(".pearls"),
+            }),
         }
+    }
 
-        Ok(Self {
+    /// Returns the repository `prl init` creates.
+    ///
+    /// This is `$PEARLS_DIR` when set, otherwise `.pearls` in `start`,
+    /// `$PEARLS_REPO`, or the current directory. The directory need not
+    /// exist yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Optional directory to create the repository in
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the current directory cannot be resolved.
+    pub fn init_target(start: Option<&Path>) -> Result<Self> {
+        let pearls_dir = match env_path(PEARLS_DIR_ENV) {
+            Some(pearls_dir) => std::env::current_dir()?.join(pearls_dir),
+            None => Self::start_dir(start)?.join(".pearls"),
+        };
+        Ok(Self::at(pearls_dir))
+    }
+
+    /// Resolves the absolute directory discovery starts from.
+    fn start_dir(start: Option<&Path>) -> Result<PathBuf> {
+        let cwd = std::env::current_dir()?;
+        Ok(
+            match start.map(Path::to_path_buf).or_else(|| env_path(REPO_ENV)) {
+                Some(start) => cwd.join(start),
+                None => cwd,
+            },
+        )
+    }
+
+    /// Builds the context for a `.pearls` directory.
+    fn at(pearls_dir: PathBuf) -> Self {
+        let root = pearls_dir
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| pearls_dir.clone());
+        Self {
             root,
             issues_path: pearls_dir.join("issues.jsonl"),
             archive_path: pearls_dir.join("archive.jsonl"),
             config_path: pearls_dir.join("config.toml"),
+            index_path: pearls_dir.join("index.bin"),
+            cache_path: pearls_dir.join("cache.db"),
             pearls_dir,
-        })
+        }
     }
 
-    /// Returns the repository root path.
+    /// Returns the repository root path, the directory holding `.pearls`.
     #[must_use]
     pub fn root(&self) -> &Path {
         self.root.as_path()
@@ -83,17 +155,68 @@ impl RepoContext {
         self.config_path.as_path()
     }
 
-    /// Opens storage for the active issues file.
+    /// Returns the binary index path.
+    #[must_use]
+    pub fn index_path(&self) -> &Path {
+        self.index_path.as_path()
+    }
+
+    /// Returns the SQLite query cache path.
+    #[must_use]
+    pub fn cache_path(&self) -> &Path {
+        self.cache_path.as_path()
+    }
+
+    /// Ensures `.pearls/.gitignore` excludes the index and query cache.
+    ///
+    /// Existing entries are preserved and missing ones are appended.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ignore file cannot be read or written.
+    pub fn ignore_local_files(&self) -> Result<()> {
+        let path = self.pearls_dir.join(".gitignore");
+        let mut content = if path.exists() {
+            std::fs::read_to_string(&path)?
+        } else {
+            String::new()
+        };
+
+        let missing: Vec<&str> = LOCAL_FILES
+            .into_iter()
+            .filter(|entry| !content.lines().any(|line| line.trim() == *entry))
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        for entry in missing {
+            content.push_str(entry);
+            content.push('\n');
+        }
+        std::fs::write(&path, content)?;
+        Ok(())
+    }
+
+    /// Opens storage for the active Pearls.
+    ///
+    /// Uses the layout from the configuration. With the JSONL layout and
+    /// `use_index` enabled, the storage is backed by `.pearls/index.bin`, which is
+    /// rebuilt if it is missing or stale.
     ///
     /// # Returns
     ///
-    /// A `Storage` instance for `issues.jsonl`.
+    /// A `Storage` instance for the active Pearls.
     ///
     /// # Errors
     ///
-    /// Returns an error if the storage cannot be initialized.
+    /// Returns an error if the configuration cannot be loaded or the storage
+    /// cannot be initialized.
     pub fn open_storage(&self) -> Result<Storage> {
-        Ok(Storage::new(self.issues_path.clone())?)
+        Ok(Storage::open(&self.pearls_dir, &self.load_config()?)?)
     }
 
     /// Opens storage for the archive file if it exists.
@@ -125,3 +248,115 @@ impl RepoContext {
         Ok(Config::load(self.pearls_dir())?)
     }
 }
+
+/// Reads a non-empty path from an environment variable.
+fn env_path(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Finds the nearest `.pearls` directory at or above `start`.
+fn find_pearls_dir(start: &Path) -> Option<PathBuf> {
+    let mut dir = start.to_path_buf();
+    let mut visited = Vec::new();
+    loop {
+        if visited.contains(&dir) {
+            return None;
+        }
+        let pearls_dir = dir.join(".pearls");
+        if pearls_dir.is_dir() {
+            return Some(pearls_dir);
+        }
+        let next = if dir.join(".git").exists() {
+            beyond_git_root(&dir)?
+        } else {
+            dir.parent()?.to_path_buf()
+        };
+        visited.push(dir);
+        dir = next;
+    }
+}
+
+/// Returns where discovery continues past the Git root `dir`.
+///
+/// A linked worktree continues at its main worktree and a submodule at its
+/// parent directory inside the superproject. Any other repository ends the
+/// search.
+fn beyond_git_root(dir: &Path) -> Option<PathBuf> {
+    // In worktrees and submodules `.git` is a file pointing at the Git dir.
+    let content = std::fs::read_to_string(dir.join(".git")).ok()?;
+    let git_dir = dir.join(content.trim().strip_prefix("gitdir:")?.trim());
+    if let Ok(common_dir) = std::fs::read_to_string(git_dir.join("commondir")) {
+        let common_dir = git_dir.join(common_dir.trim()).canonicalize().ok()?;
+        return common_dir.parent().map(Path::to_path_buf);
+    }
+    let in_modules = git_dir
+        .parent()
+        .and_then(Path::file_name)
+        .is_some_and(|name| name == "modules");
+    if in_modules {
+        dir.parent().map(Path::to_path_buf)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_discover_from_subdirectory_stops_at_git_root() {
+        let temp = TempDir::new().expect("Failed to create temp dir");
+        let outer = temp.path();
+        std::fs::create_dir(outer.join(".pearls")).expect("Failed to create .pearls");
+        let project = outer.join("project");
+        let nested = project.join("src").join("nested");
+        std::fs::create_dir_all(&nested).expect("Failed to create dirs");
+
+        let repo = RepoContext::discover(Some(&nested)).expect("Failed to discover repo");
+        assert_eq!(repo.root(), outer);
+
+        std::fs::create_dir(project.join(".git")).expect("Failed to create .git");
+        assert!(matches!(
+            RepoContext::discover(Some(&nested)),
+            Err(AppError::RepoNotInitialized { .. })
+        ));
+    }
+
+    #[test]
+    fn test_discover_shares_tracker_with_worktrees_and_submodules() {
+        let temp = TempDir::new().expect("Failed to create temp dir");
+        let main = temp.path().join("main");
+        std::fs::create_dir_all(main.join(".pearls")).expect("Failed to create .pearls");
+        let worktree_git_dir = main.join(".git").join("worktrees").join("feature");
+        std::fs::create_dir_all(&worktree_git_dir).expect("Failed to create git dir");
+        std::fs::write(worktree_git_dir.join("commondir"), "../..\n").expect("Write failed");
+        std::fs::create_dir_all(main.join(".git").join("modules").join("lib"))
+            .expect("Failed to create modules dir");
+
+        let worktree = temp.path().join("feature");
+        std::fs::create_dir(&worktree).expect("Failed to create worktree");
+        std::fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", worktree_git_dir.display()),
+        )
+        .expect("Write failed");
+        let repo = RepoContext::discover(Some(&worktree)).expect("Failed to discover repo");
+        assert_eq!(
+            repo.pearls_dir(),
+            main.join(".pearls")
+                .canonicalize()
+                .expect("Canonicalize failed")
+        );
+
+        let submodule = main.join("lib");
+        std::fs::create_dir(&submodule).expect("Failed to create submodule");
+        std::fs::write(submodule.join(".git"), "gitdir: ../.git/modules/lib\n")
+            .expect("Write failed");
+        let repo = RepoContext::discover(Some(&submodule)).expect("Failed to discover repo");
+        assert_eq!(repo.root(), main);
+    }
+}
@@ -0,0 +1,134 @@
+// Rust guideline compliant 2026-02-09
+
+//! Full-text search helpers for Pearls.
+
+use crate::error::{AppError, Result};
+use pearls_core::Pearl;
+use std::cmp::Reverse;
+
+/// Normalizes a search query for matching.
+///
+/// # Arguments
+///
+/// * `query` - Raw query string
+///
+/// # Returns
+///
+/// The trimmed, lowercased query.
+///
+/// # Errors
+///
+/// Returns an error if the query is empty.
+pub fn normalize_query(query: &str) -> Result<String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Search query cannot be empty".to_string(),
+        ));
+    }
+    Ok(query.to_lowercase())
+}
+
+/// Builds the lowercased text that a search query is matched against.
+///
+/// Covers the ID, title, description, labels, and comment bodies.
+///
+/// # Arguments
+///
+/// * `pearl` - Pearl to index
+///
+/// # Returns
+///
+/// The searchable text for the Pearl.
+#[must_use]
+pub fn search_text(pearl: &Pearl) -> String {
+    let mut text = format!("{}\n{}\n{}", pearl.id, pearl.title, pearl.description);
+    for label in &pearl.labels {
+        text.push('\n');
+        text.push_str(label);
+    }
+    for comment in &pearl.comments {
+        text.push('\n');
+        text.push_str(&comment.body);
+    }
+    text.to_lowercase()
+}
+
+/// Returns the Pearls whose text contains the query, most recently updated first.
+///
+/// Matching is a case-insensitive substring match against [`search_text`].
+///
+/// # Arguments
+///
+/// * `pearls` - Pearls to search
+/// * `query` - Search query
+///
+/// # Returns
+///
+/// The matching Pearls.
+///
+/// # Errors
+///
+/// Returns an error if the query is empty.
+pub fn search_pearls(pearls: Vec<Pearl>, query: &str) -> Result<Vec<Pearl>> {
+    let query = normalize_query(query)?;
+    let mut matches: Vec<Pearl> = pearls
+        .into_iter()
+        .filter(|pearl| search_text(pearl).contains(&query))
+        .collect();
+    matches.sort_by_key(|pearl| Reverse(pearl.updated_at));
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pearls_core::{Comment, CommentKind, Status};
+
+    fn sample_pearl(id: &str, title: &str, updated_at: i64) -> Pearl {
+        Pearl {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            status: Status::Open,
+            priority: 2,
+            created_at: 1000,
+            updated_at,
+            author: "author".to_string(),
+            labels: Vec::new(),
+            deps: Vec::new(),
+            metadata: Default::default(),
+            comments: Vec::new(),
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_search_matches_title_and_comments() {
+        let mut commented = sample_pearl("prl-abc222", "Unrelated", 3000);
+        commented.comments.push(Comment {
+            id: "cmt-abc123".to_string(),
+            author: "author".to_string(),
+            body: "The Parser panics on empty input".to_string(),
+            created_at: 3000,
+            kind: CommentKind::Note,
+        });
+        let pearls = vec![
+            sample_pearl("prl-abc111", "Fix parser bug", 2000),
+            commented,
+            sample_pearl("prl-abc333", "Write docs", 4000),
+        ];
+
+        let ids: Vec<String> = search_pearls(pearls, "  PARSER ")
+            .expect("search")
+            .into_iter()
+            .map(|pearl| pearl.id)
+            .collect();
+        assert_eq!(ids, vec!["prl-abc222", "prl-abc111"]);
+    }
+
+    #[test]
+    fn test_search_rejects_empty_query() {
+        assert!(search_pearls(Vec::new(), "   ").is_err());
+    }
+}
@@ -0,0 +1,468 @@
+// Rust guideline compliant 2026-02-10
+
+//! Agent sessions and handoff notes.
+//!
+//! A session records which agent claimed which Pearls under the `session`
+//! metadata key of each Pearl, so it is stored, synced, and merged like any
+//! other Pearl data. Progress notes are typed comments, and ending a session
+//! leaves a handoff comment on every claimed Pearl. [`resume`] collects what
+//! the next agent needs to pick up the work after a context reset.
+
+use crate::error::{AppError, Result};
+use crate::fsm::validate_transition;
+use crate::ids::resolve_pearl_id;
+use crate::repo::RepoContext;
+use crate::time::unix_timestamp;
+use pearls_core::{identity, Comment, CommentKind, IssueGraph, Pearl, Status};
+use serde::{Deserialize, Serialize};
+
+/// Metadata key holding the [`SessionInfo`] of the session claiming a Pearl.
+pub const SESSION_KEY: &str = "session";
+
+/// Agent session that claimed a Pearl.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionInfo {
+    /// Session identifier (format: ses-XXXXXX).
+    pub id: String,
+    /// Agent that started the session.
+    pub agent: String,
+    /// Unix timestamp of the start of the session.
+    pub started_at: i64,
+}
+
+impl SessionInfo {
+    /// Reads the session claiming a Pearl, if any.
+    #[must_use]
+    pub fn of(pearl: &Pearl) -> Option<Self> {
+        pearl
+            .metadata
+            .get(SESSION_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+}
+
+/// A session and the Pearls it claims.
+#[derive(Debug, Clone, Serialize)]
+pub struct Session {
+    /// The session.
+    pub session: SessionInfo,
+    /// Pearls claimed by the session.
+    pub pearls: Vec<Pearl>,
+}
+
+/// A note added to a Pearl during a session.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionNote {
+    /// Pearl ID.
+    pub id: String,
+    /// New comment ID.
+    pub comment_id: String,
+    /// Kind of the note.
+    pub kind: CommentKind,
+}
+
+/// What the next agent needs to pick up one Pearl.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResumeEntry {
+    /// The Pearl.
+    pub pearl: Pearl,
+    /// Session still claiming the Pearl, if it was not ended.
+    pub session: Option<SessionInfo>,
+    /// Latest handoff note.
+    pub handoff: Option<Comment>,
+    /// Progress, decision, and blocker notes, oldest first.
+    pub notes: Vec<Comment>,
+    /// Open Pearls blocking this one.
+    pub blockers: Vec<Pearl>,
+}
+
+/// Parses a comment kind string into a `CommentKind` value.
+///
+/// # Arguments
+///
+/// * `value` - Kind name, such as `progress` or `handoff`
+///
+/// # Returns
+///
+/// The parsed `CommentKind`.
+///
+/// # Errors
+///
+/// Returns an error if the kind is unknown.
+pub fn parse_comment_kind(value: &str) -> Result<CommentKind> {
+    match value.to_lowercase().as_str() {
+        "note" => Ok(CommentKind::Note),
+        "progress" => Ok(CommentKind::Progress),
+        "decision" => Ok(CommentKind::Decision),
+        "blocker" => Ok(CommentKind::Blocker),
+        "handoff" => Ok(CommentKind::Handoff),
+        _ => Err(AppError::InvalidInput(format!(
+            "Invalid comment kind: {} (expected note, progress, decision, blocker, or handoff)",
+            value
+        ))),
+    }
+}
+
+/// Starts a session in which an agent claims Pearls.
+///
+/// Open Pearls that are not blocked move to `in_progress`, if `allow_transition`
+/// permits it. A Pearl claimed by another agent's session is refused; end that
+/// session first. Nothing is saved unless every Pearl can be claimed.
+///
+/// # Arguments
+///
+/// * `repo` - The repository
+/// * `agent` - Agent identifier
+/// * `ids` - Pearl IDs (full or partial) to claim
+/// * `allow_transition` - Called before a Pearl moves to a new status; an error
+///   refuses the session
+///
+/// # Returns
+///
+/// The new session and the claimed Pearls.
+///
+/// # Errors
+///
+/// Returns an error if the agent or IDs are missing, an ID cannot be
+/// resolved, a Pearl is claimed by another agent, `allow_transition` refuses a
+/// status change, or storage fails.
+pub fn start_session(
+    repo: &RepoContext,
+    agent: &str,
+    ids: &[String],
+    allow_transition: &dyn Fn(&Pearl, Status) -> Result<()>,
+) -> Result<Session> {
+    let agent = agent.trim();
+    if agent.is_empty() {
+        return Err(AppError::InvalidInput("Agent cannot be empty".to_string()));
+    }
+    if ids.is_empty() {
+        return Err(AppError::InvalidInput(
+            "A session must claim at least one Pearl".to_string(),
+        ));
+    }
+
+    let mut storage = repo.open_storage()?;
+    let all = storage.load_all()?;
+    let mut full_ids = Vec::new();
+    for id in ids {
+        let full_id = resolve_pearl_id(id, &all)?;
+        if !full_ids.contains(&full_id) {
+            full_ids.push(full_id);
+        }
+    }
+
+    let now = unix_timestamp()?;
+    let session = SessionInfo {
+        id: identity::generate_id(agent, &full_ids.join(","), now, 0).replacen("prl-", "ses-", 1),
+        agent: agent.to_string(),
+        started_at: now,
+    };
+    let claim = serde_json::to_value(&session).map_err(pearls_core::Error::from)?;
+    let graph = IssueGraph::from_pearls(all.clone())?;
+
+    let mut pearls = Vec::new();
+    for full_id in &full_ids {
+        let mut pearl = storage.load_by_id(full_id)?;
+        if let Some(existing) = SessionInfo::of(&pearl) {
+            if existing.agent != session.agent {
+                return Err(AppError::InvalidInput(format!(
+                    "Pearl {} is claimed by {} in session {}",
+                    pearl.id, existing.agent, existing.id
+                )));
+            }
+        }
+        pearl
+            .metadata
+            .insert(SESSION_KEY.to_string(), claim.clone());
+        if pearl.status == Status::Open
+            && validate_transition(&pearl, Status::InProgress, &graph).is_ok()
+        {
+            allow_transition(&pearl, Status::InProgress)?;
+            pearl.status = Status::InProgress;
+        }
+        pearl.updated_at = now;
+        pearl.validate()?;
+        pearls.push(pearl);
+    }
+    for pearl in &pearls {
+        storage.save(pearl)?;
+    }
+
+    Ok(Session { session, pearls })
+}
+
+/// Adds a typed note to a Pearl claimed by a session.
+///
+/// # Arguments
+///
+/// * `repo` - The repository
+/// * `session_id` - Session identifier
+/// * `id` - Pearl ID (full or partial); may be omitted when the session
+///   claims a single Pearl
+/// * `kind` - Kind of note
+/// * `body` - Note text
+///
+/// # Returns
+///
+/// The Pearl ID and the new comment ID.
+///
+/// # Errors
+///
+/// Returns an error if the session is unknown, the Pearl is not claimed by
+/// it, the body is empty, or storage fails.
+pub fn add_session_note(
+    repo: &RepoContext,
+    session_id: &str,
+    id: Option<&str>,
+    kind: CommentKind,
+    body: &str,
+) -> Result<SessionNote> {
+    let mut storage = repo.open_storage()?;
+    let all = storage.load_all()?;
+    let claimed = claimed_by(&all, session_id)?;
+    let full_id = match id {
+        Some(id) => {
+            let full_id = resolve_pearl_id(id, &all)?;
+            if !claimed.iter().any(|pearl| pearl.id == full_id) {
+                return Err(AppError::InvalidInput(format!(
+                    "Pearl {} is not claimed by session {}",
+                    full_id, session_id
+                )));
+            }
+            full_id
+        }
+        None if claimed.len() == 1 => claimed[0].id.clone(),
+        None => {
+            return Err(AppError::InvalidInput(format!(
+                "Session {} claims {} Pearls; specify which one the note is for",
+                session_id,
+                claimed.len()
+            )))
+        }
+    };
+    let agent = SessionInfo::of(claimed[0])
+        .map(|session| session.agent)
+        .unwrap_or_default();
+
+    let mut pearl = storage.load_by_id(&full_id)?;
+    let comment_id = pearl.add_comment_of_kind(agent, body.to_string(), kind)?;
+    pearl.validate()?;
+    storage.save(&pearl)?;
+
+    Ok(SessionNote {
+        id: pearl.id,
+        comment_id,
+        kind,
+    })
+}
+
+/// Ends a session, leaving a handoff note on every Pearl it claimed.
+///
+/// # Arguments
+///
+/// * `repo` - The repository
+/// * `session_id` - Session identifier
+/// * `summary` - Handoff summary for the next agent
+///
+/// # Returns
+///
+/// The ended session and its Pearls, no longer claimed.
+///
+/// # Errors
+///
+/// Returns an error if the session is unknown, the summary is empty, or
+/// storage fails.
+pub fn end_session(repo: &RepoContext, session_id: &str, summary: &str) -> Result<Session> {
+    let mut storage = repo.open_storage()?;
+    let all = storage.load_all()?;
+    let claimed = claimed_by(&all, session_id)?;
+    let session = SessionInfo::of(claimed[0])
+        .ok_or_else(|| AppError::InvalidInput(format!("Unknown session: {}", session_id)))?;
+
+    let mut pearls = Vec::new();
+    for claimed in claimed {
+        let mut pearl = claimed.clone();
+        pearl.add_comment_of_kind(
+            session.agent.clone(),
+            summary.to_string(),
+            CommentKind::Handoff,
+        )?;
+        pearl.metadata.remove(SESSION_KEY);
+        pearl.validate()?;
+        pearls.push(pearl);
+    }
+    for pearl in &pearls {
+        storage.save(pearl)?;
+    }
+
+    Ok(Session { session, pearls })
+}
+
+/// Collects what the next agent needs to pick up work in flight.
+///
+/// Without an ID, every Pearl that is claimed by a session or in progress is
+/// returned. With an agent, only Pearls claimed by or handed off by that
+/// agent are returned.
+///
+/// # Arguments
+///
+/// * `repo` - The repository
+/// * `agent` - Optional agent identifier to filter by
+/// * `id` - Optional Pearl ID (full or partial) to resume
+///
+/// # Returns
+///
+/// One entry per Pearl, most recently updated first.
+///
+/// # Errors
+///
+/// Returns an error if the ID cannot be resolved or storage fails.
+pub fn resume(
+    repo: &RepoContext,
+    agent: Option<&str>,
+    id: Option<&str>,
+) -> Result<Vec<ResumeEntry>> {
+    let storage = repo.open_storage()?;
+    let all = storage.load_all()?;
+    let graph = IssueGraph::from_pearls(all.clone())?;
+    let full_id = id.map(|id| resolve_pearl_id(id, &all)).transpose()?;
+
+    let mut entries: Vec<ResumeEntry> = all
+        .iter()
+        .filter_map(|pearl| {
+            let session = SessionInfo::of(pearl);
+            let in_flight = match &full_id {
+                Some(full_id) => pearl.id == *full_id,
+                None => {
+                    pearl.status != Status::Closed
+                        && (session.is_some() || pearl.status == Status::InProgress)
+                }
+            };
+            if !in_flight {
+                return None;
+            }
+            let handoff = pearl
+                .comments
+                .iter()
+                .filter(|comment| comment.kind == CommentKind::Handoff)
+                .max_by_key(|comment| comment.created_at)
+                .cloned();
+            if let Some(agent) = agent {
+                let by_agent = match (&session, &handoff) {
+                    (Some(session), _) => session.agent == agent,
+                    (None, Some(handoff)) => handoff.author == agent,
+                    (None, None) => false,
+                };
+                if !by_agent {
+                    return None;
+                }
+            }
+            let mut notes: Vec<Comment> = pearl
+                .comments
+                .iter()
+                .filter(|comment| !comment.kind.is_note() && comment.kind != CommentKind::Handoff)
+                .cloned()
+                .collect();
+            notes.sort_by_key(|comment| comment.created_at);
+            Some(ResumeEntry {
+                pearl: pearl.clone(),
+                session,
+                handoff,
+                notes,
+                blockers: graph
+                    .blocking_deps(&pearl.id)
+                    .into_iter()
+                    .cloned()
+                    .collect(),
+            })
+        })
+        .collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.pearl.updated_at));
+    Ok(entries)
+}
+
+/// Returns the Pearls claimed by a session.
+fn claimed_by<'a>(pearls: &'a [Pearl], session_id: &str) -> Result<Vec<&'a Pearl>> {
+    let claimed: Vec<&Pearl> = pearls
+        .iter()
+        .filter(|pearl| SessionInfo::of(pearl).is_some_and(|session| session.id == session_id))
+        .collect();
+    if claimed.is_empty() {
+        return Err(AppError::InvalidInput(format!(
+            "No Pearls are claimed by session {}",
+            session_id
+        )));
+    }
+    Ok(claimed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_session_lifecycle() {
+        let temp = TempDir::new().expect("Failed to create temp dir");
+        let pearls_dir = temp.path().join(".pearls");
+        std::fs::create_dir(&pearls_dir).expect("Failed to create .pearls dir");
+        std::fs::File::create(pearls_dir.join("issues.jsonl")).expect("Failed to create issues");
+        pearls_core::Config::default()
+            .save(&pearls_dir)
+            .expect("Failed to save config");
+        let repo = RepoContext::discover(Some(temp.path())).expect("Failed to discover repo");
+        let pearl = Pearl::new("Parser".to_string(), "author".to_string());
+        repo.open_storage()
+            .expect("Failed to open storage")
+            .save(&pearl)
+            .expect("Failed to save Pearl");
+
+        let started = start_session(
+            &repo,
+            "agent-a",
+            std::slice::from_ref(&pearl.id),
+            &|_, _| Ok(()),
+        )
+        .expect("Start failed");
+        assert_eq!(started.pearls[0].status, Status::InProgress);
+        assert!(start_session(
+            &repo,
+            "agent-b",
+            std::slice::from_ref(&pearl.id),
+            &|_, _| Ok(())
+        )
+        .is_err());
+
+        let note = add_session_note(
+            &repo,
+            &started.session.id,
+            None,
+            parse_comment_kind("decision").expect("Kind failed"),
+            "Use a hand-written lexer",
+        )
+        .expect("Note failed");
+        assert_eq!(note.kind, CommentKind::Decision);
+
+        let resumed = resume(&repo, Some("agent-a"), None).expect("Resume failed");
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(resumed[0].session, Some(started.session.clone()));
+        assert_eq!(resumed[0].notes[0].body, "Use a hand-written lexer");
+        assert!(resume(&repo, Some("agent-b"), None)
+            .expect("Resume failed")
+            .is_empty());
+
+        end_session(&repo, &started.session.id, "Lexer half done").expect("End failed");
+        let resumed = resume(&repo, None, None).expect("Resume failed");
+        assert!(resumed[0].session.is_none());
+        assert_eq!(
+            resumed[0]
+                .handoff
+                .as_ref()
+                .map(|handoff| handoff.body.as_str()),
+            Some("Lexer half done")
+        );
+        assert!(end_session(&repo, &started.session.id, "Again").is_err());
+        assert!(parse_comment_kind("rant").is_err());
+    }
+}
@@ -0,0 +1,122 @@
+// Rust guideline compliant 2026-02-09
+
+//! Aggregate statistics for Pearls.
+
+use pearls_core::{DepType, Pearl, Status};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Aggregate counts across the active and archived Pearls.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Stats {
+    /// Number of active Pearls in `issues.jsonl`.
+    pub total: usize,
+    /// Number of archived Pearls in `archive.jsonl`.
+    pub archived: usize,
+    /// Active Pearls per status.
+    pub by_status: BTreeMap<Status, usize>,
+    /// Active Pearls per priority.
+    pub by_priority: BTreeMap<u8, usize>,
+    /// Active Pearls per label, with labels lowercased.
+    pub by_label: BTreeMap<String, usize>,
+    /// Active, non-closed Pearls blocked by a non-closed active Pearl.
+    pub blocked: usize,
+}
+
+/// Computes statistics for the provided Pearls.
+///
+/// # Arguments
+///
+/// * `active` - Pearls from `issues.jsonl`
+/// * `archived` - Number of archived Pearls
+///
+/// # Returns
+///
+/// The aggregate statistics.
+#[must_use]
+pub fn compute_stats(active: &[Pearl], archived: usize) -> Stats {
+    let statuses: HashMap<&str, Status> = active
+        .iter()
+        .map(|pearl| (pearl.id.as_str(), pearl.status))
+        .collect();
+
+    let mut stats = Stats {
+        total: active.len(),
+        archived,
+        ..Stats::default()
+    };
+
+    for pearl in active {
+        *stats.by_status.entry(pearl.status).or_default() += 1;
+        *stats.by_priority.entry(pearl.priority).or_default() += 1;
+
+        let labels: BTreeSet<String> = pearl
+            .labels
+            .iter()
+            .map(|label| label.to_ascii_lowercase())
+            .collect();
+        for label in labels {
+            *stats.by_label.entry(label).or_default() += 1;
+        }
+
+        let blocked = pearl.status != Status::Closed
+            && pearl.deps.iter().any(|dep| {
+                dep.dep_type == DepType::Blocks
+                    && statuses
+                        .get(dep.target_id.as_str())
+                        .is_some_and(|status| *status != Status::Closed)
+            });
+        if blocked {
+            stats.blocked += 1;
+        }
+    }
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pearls_core::Dependency;
+
+    fn sample_pearl(id: &str, status: Status, labels: &[&str], blocked_by: Option<&str>) -> Pearl {
+        Pearl {
+            id: id.to_string(),
+            title: "Title".to_string(),
+            description: String::new(),
+            status,
+            priority: 2,
+            created_at: 1000,
+            updated_at: 1000,
+            author: "author".to_string(),
+            labels: labels.iter().map(|label| label.to_string()).collect(),
+            deps: blocked_by
+                .map(|target| Dependency {
+                    target_id: target.to_string(),
+                    dep_type: DepType::Blocks,
+                })
+                .into_iter()
+                .collect(),
+            metadata: Default::default(),
+            comments: Vec::new(),
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_compute_stats_counts_labels_and_blocked() {
+        let pearls = vec![
+            sample_pearl("prl-aaa111", Status::Open, &["Core", "core"], None),
+            sample_pearl("prl-bbb222", Status::Open, &["core"], Some("prl-aaa111")),
+            sample_pearl("prl-ccc333", Status::Closed, &[], None),
+            sample_pearl("prl-ddd444", Status::Open, &[], Some("prl-ccc333")),
+        ];
+
+        let stats = compute_stats(&pearls, 5);
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.archived, 5);
+        assert_eq!(stats.by_status.get(&Status::Open), Some(&3));
+        assert_eq!(stats.by_label.get("core"), Some(&2));
+        assert_eq!(stats.blocked, 1);
+    }
+}
@@ -2,11 +2,16 @@
 
 //! Command implementations for the Pearls CLI.
 
+pub mod cache;
+pub mod changelog;
 pub mod close;
 pub mod comments;
 pub mod compact;
+pub mod config;
 pub mod create;
 pub mod doctor;
+pub mod export;
+pub mod finish;
 pub mod hooks;
 pub mod import;
 pub mod init;
@@ -14,8 +19,12 @@ pub mod link;
 pub mod list;
 pub mod merge;
 pub mod meta;
+pub mod migrate;
 pub mod ready;
+pub mod search;
 pub mod show;
+pub mod start;
+pub mod stats;
 pub mod status;
 pub mod sync;
 pub mod unlink;
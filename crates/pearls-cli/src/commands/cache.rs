@@ -0,0 +1,78 @@
+// Rust guideline compliant 2026-02-09
+
+//! Implementation of the `prl cache` command.
+//!
+//! Rebuilds or drops the SQLite query cache at `.pearls/cache.db`. The cache is
+//! a disposable projection of the JSONL files and can be removed at any time.
+
+use crate::output_mode::is_json_output;
+use anyhow::Result;
+use pearls_app::{drop_cache, QueryCache, RepoContext};
+
+/// Runs the requested cache action.
+///
+/// # Arguments
+///
+/// * `action` - Cache action to perform
+///
+/// # Returns
+///
+/// Ok if the action succeeded, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The repository is not initialized
+/// - The JSONL files cannot be read
+/// - The cache database cannot be written or removed
+pub fn execute(action: CacheAction) -> Result<()> {
+    let repo = RepoContext::discover(None)?;
+
+    match action {
+        CacheAction::Rebuild => {
+            let count = QueryCache::open_unchecked(&repo)?.rebuild()?;
+            if is_json_output() {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "status": "ok",
+                        "action": "cache_rebuild",
+                        "path": repo.cache_path(),
+                        "pearls": count
+                    }))?
+                );
+            } else {
+                println!("✓ Cache rebuilt with {} Pearls", count);
+            }
+        }
+        CacheAction::Drop => {
+            let removed = drop_cache(&repo)?;
+            if is_json_output() {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "status": "ok",
+                        "action": "cache_drop",
+                        "path": repo.cache_path(),
+                        "removed": removed
+                    }))?
+                );
+            } else if removed {
+                println!("✓ Cache dropped");
+            } else {
+                println!("No cache to drop");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Supported cache actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::Subcommand)]
+pub enum CacheAction {
+    /// Rebuild the cache from the JSONL files
+    Rebuild,
+    /// Delete the cache database
+    Drop,
+}
@@ -0,0 +1,391 @@
+// Rust guideline compliant 2026-02-10
+
+//! Implementation of the `prl changelog` command.
+//!
+//! Collects the Pearls closed between two points, groups them by label or
+//! type, and renders Markdown or Keep a Changelog output. A point is either a
+//! Git ref or a date. Close times come from the Git history of `.pearls`,
+//! falling back to `updated_at` for closes that are not committed yet.
+
+use crate::output_mode::is_json_output;
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use git2::{Commit, ObjectType, Oid, Repository, Sort, Tree};
+use pearls_app::changelog::{
+    date, group_changelog, render_changelog, Changelog, ClosedPearl, KEEP_A_CHANGELOG_TEMPLATE,
+    MARKDOWN_TEMPLATE,
+};
+use pearls_app::RepoContext;
+use pearls_core::Status;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// One end of the changelog range.
+enum Bound {
+    /// A commit; a Pearl is in range when its close is reachable from it.
+    Commit(Oid),
+    /// A Unix timestamp; a Pearl is in range when it closed at or after it.
+    Time(i64),
+}
+
+/// Executes the changelog command.
+///
+/// # Arguments
+///
+/// * `since` - Git ref or date (`YYYY-MM-DD` or RFC 3339) the range starts at
+/// * `until` - Git ref or date the range ends at; `HEAD` plus uncommitted
+///   changes when omitted
+/// * `style` - `markdown` or `keep-a-changelog`
+/// * `version` - Release name for the heading
+/// * `output` - File to write; the changelog is printed when omitted
+///
+/// # Returns
+///
+/// Ok if the changelog is generated, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The repository is not initialized
+/// - A bound is neither a date nor a Git ref
+/// - The Pearls, the Git history, or a template cannot be read
+/// - The output file cannot be written
+pub fn execute(
+    since: String,
+    until: Option<String>,
+    style: String,
+    version: String,
+    output: Option<String>,
+) -> Result<()> {
+    let repo = RepoContext::discover(None)?;
+    let config = repo.load_config()?;
+    let git = Repository::discover(repo.root()).ok();
+
+    let since_bound = parse_bound(&since, git.as_ref(), false)?;
+    let until_bound = match &until {
+        Some(until) => Some(parse_bound(until, git.as_ref(), true)?),
+        None => None,
+    };
+
+    let mut pearls = repo.open_storage()?.load_all()?;
+    if let Some(archive) = repo.open_archive_storage()? {
+        pearls.extend(archive.load_all()?);
+    }
+
+    let history = match &git {
+        Some(git) => Some(History::scan(git, repo.pearls_dir(), until_bound.as_ref())?),
+        None => None,
+    };
+    let closed_at_bound = |bound: Option<&Bound>| -> Result<Option<HashSet<String>>> {
+        match (bound, &git, &history) {
+            (Some(Bound::Commit(oid)), Some(git), Some(history)) => {
+                Ok(Some(history.closed_at_commit(git, *oid)?))
+            }
+            _ => Ok(None),
+        }
+    };
+    let closed_before = closed_at_bound(Some(&since_bound))?;
+    let closed_by_end = closed_at_bound(until_bound.as_ref())?;
+
+    let mut closed = Vec::new();
+    for pearl in pearls {
+        let at = history
+            .as_ref()
+            .and_then(|history| history.closed_at.get(&pearl.id).copied())
+            .unwrap_or(pearl.updated_at);
+        let is_closed = match &closed_by_end {
+            Some(set) => set.contains(&pearl.id),
+            None => pearl.status == Status::Closed,
+        };
+        let after_since = match (&since_bound, &closed_before) {
+            (Bound::Time(start), _) => at >= *start,
+            (Bound::Commit(_), Some(set)) => !set.contains(&pearl.id),
+            (Bound::Commit(_), None) => false,
+        };
+        let before_until = match &until_bound {
+            Some(Bound::Time(end)) => at < *end,
+            _ => true,
+        };
+        if is_closed && after_since && before_until {
+            closed.push(ClosedPearl {
+                pearl,
+                closed_at: at,
+            });
+        }
+    }
+
+    let release_date = match &until_bound {
+        Some(Bound::Time(end)) => date(end - 1),
+        Some(Bound::Commit(oid)) => git
+            .as_ref()
+            .and_then(|git| git.find_commit(*oid).ok())
+            .map(|commit| date(commit.time().seconds()))
+            .unwrap_or_default(),
+        None => Utc::now().format("%Y-%m-%d").to_string(),
+    };
+    let changelog = Changelog {
+        version,
+        date: release_date,
+        since,
+        until: until.unwrap_or_else(|| "HEAD".to_string()),
+        sections: group_changelog(&closed, &config.changelog),
+    };
+    let count: usize = changelog
+        .sections
+        .iter()
+        .map(|section| section.entries.len())
+        .sum();
+
+    let (template_name, builtin) = match style.as_str() {
+        "keep-a-changelog" => ("keep-a-changelog.md", KEEP_A_CHANGELOG_TEMPLATE),
+        _ => ("changelog.md", MARKDOWN_TEMPLATE),
+    };
+    let template_path = repo.pearls_dir().join("templates").join(template_name);
+    let template = if template_path.exists() {
+        std::fs::read_to_string(&template_path)
+            .with_context(|| format!("Failed to read {}", template_path.display()))?
+    } else {
+        builtin.to_string()
+    };
+    let document = render_changelog(&template, &changelog);
+
+    if let Some(output) = output.map(PathBuf::from) {
+        std::fs::write(&output, &document)?;
+        if is_json_output() {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "status": "ok",
+                    "action": "changelog",
+                    "entries": count,
+                    "output": output.display().to_string()
+                }))?
+            );
+        } else {
+            println!(
+                "✓ Wrote {} changelog entries to {}",
+                count,
+                output.display()
+            );
+        }
+    } else if is_json_output() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "status": "ok",
+                "action": "changelog",
+                "entries": count,
+                "changelog": changelog,
+                "content": document
+            }))?
+        );
+    } else {
+        print!("{}", document);
+    }
+    Ok(())
+}
+
+/// Parses a range bound as a date or a Git ref.
+///
+/// A date starts the range at the beginning of that day (UTC) and ends it at
+/// the end of that day, so `--since 2026-01-01 --until 2026-01-31` covers all
+/// of January.
+fn parse_bound(value: &str, git: Option<&Repository>, end: bool) -> Result<Bound> {
+    if let Ok(day) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let day = if end {
+            day.succ_opt().unwrap_or(day)
+        } else {
+            day
+        };
+        let start = day
+            .and_hms_opt(0, 0, 0)
+            .map(|time| time.and_utc().timestamp())
+            .unwrap_or_default();
+        return Ok(Bound::Time(start));
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(Bound::Time(time.timestamp()));
+    }
+    let git = git.with_context(|| {
+        format!(
+            "'{}' is not a date (YYYY-MM-DD or RFC 3339) and there is no Git repository to resolve it as a ref",
+            value
+        )
+    })?;
+    let commit = git
+        .revparse_single(value)
+        .and_then(|object| object.peel_to_commit())
+        .with_context(|| format!("'{}' is neither a date nor a Git ref", value))?;
+    Ok(Bound::Commit(commit.id()))
+}
+
+/// Changes to the set of closed Pearls made by one scanned commit.
+struct Delta {
+    /// Position of the commit in the scan.
+    position: usize,
+    /// Pearls closed by the commit.
+    closed: Vec<String>,
+    /// Pearls reopened or removed by the commit.
+    reopened: Vec<String>,
+}
+
+/// Close history of Pearls along the first-parent history of a commit.
+struct History {
+    /// Path of `.pearls` relative to the work tree.
+    pearls_path: PathBuf,
+    /// Time of the last transition to closed for each Pearl.
+    closed_at: HashMap<String, i64>,
+    /// Position of each scanned commit, oldest first.
+    positions: HashMap<Oid, usize>,
+    /// Changes to the closed set, in scan order; replayed to get the set at a
+    /// commit without storing a copy per commit.
+    deltas: Vec<Delta>,
+}
+
+impl History {
+    /// Scans the history leading to the end bound, or to `HEAD`.
+    fn scan(git: &Repository, pearls_dir: &Path, until: Option<&Bound>) -> Result<Self> {
+        let workdir = git
+            .workdir()
+            .context("Changelog history requires a non-bare Git repository")?;
+        let mut history = Self {
+            pearls_path: relative_to(pearls_dir, workdir),
+            closed_at: HashMap::new(),
+            positions: HashMap::new(),
+            deltas: Vec::new(),
+        };
+
+        let start = match until {
+            Some(Bound::Commit(oid)) => Some(*oid),
+            _ => git.head().ok().and_then(|head| head.target()),
+        };
+        let Some(start) = start else {
+            return Ok(history);
+        };
+
+        let mut walk = git.revwalk()?;
+        walk.push(start)?;
+        walk.simplify_first_parent()?;
+        walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+
+        let mut previous_tree = None;
+        let mut previous = HashSet::new();
+        for (position, oid) in walk.enumerate() {
+            let oid = oid?;
+            let commit = git.find_commit(oid)?;
+            let tree = history.pearls_tree(git, &commit)?;
+            let tree_id = tree.as_ref().map(Tree::id);
+            if tree_id != previous_tree {
+                let current = match &tree {
+                    Some(tree) => closed_ids(git, tree)?,
+                    None => HashSet::new(),
+                };
+                let closed: Vec<String> = current.difference(&previous).cloned().collect();
+                let reopened: Vec<String> = previous.difference(&current).cloned().collect();
+                for id in &closed {
+                    history
+                        .closed_at
+                        .insert(id.clone(), commit.time().seconds());
+                }
+                for id in &reopened {
+                    history.closed_at.remove(id);
+                }
+                if !closed.is_empty() || !reopened.is_empty() {
+                    history.deltas.push(Delta {
+                        position,
+                        closed,
+                        reopened,
+                    });
+                }
+                previous = current;
+                previous_tree = tree_id;
+            }
+            history.positions.insert(oid, position);
+        }
+        Ok(history)
+    }
+
+    /// Returns the IDs of the Pearls closed at a commit.
+    fn closed_at_commit(&self, git: &Repository, oid: Oid) -> Result<HashSet<String>> {
+        if let Some(&position) = self.positions.get(&oid) {
+            let mut closed = HashSet::new();
+            for delta in self
+                .deltas
+                .iter()
+                .take_while(|delta| delta.position <= position)
+            {
+                closed.extend(delta.closed.iter().cloned());
+                for id in &delta.reopened {
+                    closed.remove(id);
+                }
+            }
+            return Ok(closed);
+        }
+        // The commit is off the scanned path, such as a tag on another branch.
+        let commit = git.find_commit(oid)?;
+        match self.pearls_tree(git, &commit)? {
+            Some(tree) => closed_ids(git, &tree),
+            None => Ok(HashSet::new()),
+        }
+    }
+
+    /// Returns the `.pearls` tree of a commit, if it has one.
+    fn pearls_tree<'r>(
+        &self,
+        git: &'r Repository,
+        commit: &Commit<'r>,
+    ) -> Result<Option<Tree<'r>>> {
+        let Ok(entry) = commit.tree()?.get_path(&self.pearls_path) else {
+            return Ok(None);
+        };
+        if entry.kind() != Some(ObjectType::Tree) {
+            return Ok(None);
+        }
+        Ok(Some(git.find_tree(entry.id())?))
+    }
+}
+
+/// Collects the IDs of closed Pearls stored in a `.pearls` tree.
+///
+/// Reads the JSONL file, the archive, and the directory layout, so the history
+/// stays readable across `prl migrate` and `prl compact`.
+fn closed_ids(git: &Repository, tree: &Tree<'_>) -> Result<HashSet<String>> {
+    let mut documents = Vec::new();
+    for name in ["issues.jsonl", "archive.jsonl"] {
+        if let Some(entry) = tree.get_name(name) {
+            let blob = git.find_blob(entry.id())?;
+            documents.extend(
+                String::from_utf8_lossy(blob.content())
+                    .lines()
+                    .map(str::to_string)
+                    .collect::<Vec<_>>(),
+            );
+        }
+    }
+    if let Some(entry) = tree.get_name("issues") {
+        if entry.kind() == Some(ObjectType::Tree) {
+            for file in git.find_tree(entry.id())?.iter() {
+                if file.name().is_some_and(|name| name.ends_with(".json")) {
+                    let blob = git.find_blob(file.id())?;
+                    documents.push(String::from_utf8_lossy(blob.content()).into_owned());
+                }
+            }
+        }
+    }
+
+    Ok(documents
+        .iter()
+        .filter_map(|document| serde_json::from_str::<serde_json::Value>(document).ok())
+        .filter(|pearl| pearl.get("status").and_then(|status| status.as_str()) == Some("closed"))
+        .filter_map(|pearl| pearl.get("id")?.as_str().map(str::to_string))
+        .collect())
+}
+
+/// Returns `path` relative to `base`, resolving symlinks where possible.
+fn relative_to(path: &Path, base: &Path) -> PathBuf {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let base = base.canonicalize().unwrap_or_else(|_| base.to_path_buf());
+    path.strip_prefix(&base)
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|_| PathBuf::from(".pearls"))
+}
@@ -7,8 +7,8 @@
 
 use crate::output_mode::is_json_output;
 use anyhow::Result;
-use pearls_core::{Status, Storage};
-use std::path::Path;
+use pearls_app::RepoContext;
+use pearls_core::Status;
 
 /// Closes a Pearl by transitioning it to closed status.
 ///
@@ -28,15 +28,10 @@ use std::path::Path;
 /// - The Pearl has open blocking dependencies
 /// - The file cannot be written
 pub fn execute(id: String) -> Result<()> {
-    let pearls_dir = Path::new(".pearls");
-
-    // Verify .pearls directory exists
-    if !pearls_dir.exists() {
-        anyhow::bail!("Pearls repository not initialized. Run 'prl init' first.");
-    }
+    let repo = RepoContext::discover(None)?;
 
     // Load all Pearls to resolve partial ID and build graph
-    let mut storage = Storage::new(pearls_dir.join("issues.jsonl"))?;
+    let mut storage = repo.open_storage()?;
     let all_pearls = storage.load_all()?;
 
     // Resolve partial ID
@@ -6,8 +6,8 @@
 
 use crate::output_mode::is_json_output;
 use anyhow::Result;
-use pearls_core::{identity, Comment, Storage};
-use std::path::Path;
+use pearls_app::RepoContext;
+use pearls_core::{identity, Comment, CommentKind};
 
 /// Adds a comment to a Pearl.
 ///
@@ -16,25 +16,26 @@ use std::path::Path;
 /// * `id` - Pearl ID (full or partial)
 /// * `body` - Comment text
 /// * `author` - Optional comment author override
+/// * `kind` - Optional comment kind (defaults to `note`)
 ///
 /// # Returns
 ///
 /// Ok if the comment was added.
-pub fn add(id: String, body: String, author: Option<String>) -> Result<()> {
-    let pearls_dir = Path::new(".pearls");
-    if !pearls_dir.exists() {
-        anyhow::bail!("Pearls repository not initialized. Run 'prl init' first.");
-    }
-
-    let mut storage = Storage::new(pearls_dir.join("issues.jsonl"))?;
+pub fn add(id: String, body: String, author: Option<String>, kind: Option<String>) -> Result<()> {
+    let repo = RepoContext::discover(None)?;
+    let mut storage = repo.open_storage()?;
     let pearls = storage.load_all()?;
     let full_id = identity::resolve_partial_id(&id, &pearls)?;
     let mut pearl = storage.load_by_id(&full_id)?;
 
     let author = author
-        .or_else(default_author)
+        .or_else(crate::commands::create::get_default_author)
         .unwrap_or_else(|| "unknown".to_string());
-    let comment_id = pearl.add_comment(author, body)?;
+    let kind = match kind.as_deref() {
+        Some(kind) => pearls_app::parse_comment_kind(kind)?,
+        None => CommentKind::Note,
+    };
+    let comment_id = pearl.add_comment_of_kind(author, body, kind)?;
     pearl.validate()?;
     storage.save(&pearl)?;
 
@@ -65,12 +66,8 @@ pub fn add(id: String, body: String, author: Option<String>) -> Result<()> {
 ///
 /// Ok if comments were listed.
 pub fn list(id: String, json_output: bool) -> Result<()> {
-    let pearls_dir = Path::new(".pearls");
-    if !pearls_dir.exists() {
-        anyhow::bail!("Pearls repository not initialized. Run 'prl init' first.");
-    }
-
-    let mut storage = Storage::new(pearls_dir.join("issues.jsonl"))?;
+    let repo = RepoContext::discover(None)?;
+    let mut storage = repo.open_storage()?;
     let pearls = storage.load_all()?;
     let full_id = identity::resolve_partial_id(&id, &pearls)?;
     let pearl = storage.load_by_id(&full_id)?;
@@ -92,7 +89,13 @@ pub fn list(id: String, json_output: bool) -> Result<()> {
     }
 
     for comment in &pearl.comments {
-        println!("- {} [{}] {}", comment.id, comment.author, comment.body);
+        println!(
+            "- {} [{}] {}{}",
+            comment.id,
+            comment.author,
+            kind_prefix(comment.kind),
+            comment.body
+        );
     }
 
     Ok(())
@@ -109,12 +112,8 @@ pub fn list(id: String, json_output: bool) -> Result<()> {
 ///
 /// Ok if the comment was deleted.
 pub fn delete(id: String, comment_id: String) -> Result<()> {
-    let pearls_dir = Path::new(".pearls");
-    if !pearls_dir.exists() {
-        anyhow::bail!("Pearls repository not initialized. Run 'prl init' first.");
-    }
-
-    let mut storage = Storage::new(pearls_dir.join("issues.jsonl"))?;
+    let repo = RepoContext::discover(None)?;
+    let mut storage = repo.open_storage()?;
     let pearls = storage.load_all()?;
     let full_id = identity::resolve_partial_id(&id, &pearls)?;
     let mut pearl = storage.load_by_id(&full_id)?;
@@ -172,20 +171,13 @@ fn resolve_comment_id(partial: &str, comments: &[Comment]) -> Result<String> {
     }
 }
 
-fn default_author() -> Option<String> {
-    if let Ok(output) = std::process::Command::new("git")
-        .args(["config", "user.name"])
-        .output()
-    {
-        if output.status.success() {
-            let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if !name.is_empty() {
-                return Some(name);
-            }
-        }
+/// Formats the kind of a comment as a prefix for its body.
+///
+/// Plain notes have no prefix.
+pub(crate) fn kind_prefix(kind: CommentKind) -> String {
+    if kind.is_note() {
+        String::new()
+    } else {
+        format!("{}: ", kind.as_str())
     }
-
-    std::env::var("USER")
-        .ok()
-        .or_else(|| std::env::var("USERNAME").ok())
 }
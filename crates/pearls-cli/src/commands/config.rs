@@ -0,0 +1,210 @@
+// Rust guideline compliant 2026-02-10
+
+//! Implementation of `prl config` commands.
+//!
+//! Reads and writes the repository config at `.pearls/config.toml` or, with
+//! `--global`, the user's global config, and reports which layer each
+//! effective value came from.
+
+use crate::output_mode::is_json_output;
+use anyhow::Result;
+use pearls_app::RepoContext;
+use pearls_core::{Config, ConfigEntry, ConfigSource, REPO_ONLY_KEYS};
+use std::path::PathBuf;
+
+/// Lists configuration values and where they came from.
+///
+/// # Arguments
+///
+/// * `global` - List only the values in the global config file
+///
+/// # Returns
+///
+/// Ok if the values are printed, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if a config file cannot be read or is invalid.
+pub fn list(global: bool) -> Result<()> {
+    let entries = load_entries(global)?;
+    print_entries("config_list", &entries)
+}
+
+/// Prints a configuration value, or every value under a table, and where
+/// it came from.
+///
+/// # Arguments
+///
+/// * `key` - Dotted key, such as `user.name`, or a table, such as `mcp`
+/// * `global` - Read only the global config file
+///
+/// # Returns
+///
+/// Ok if the value is printed, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if a config file cannot be read or the key is not set.
+pub fn get(key: String, global: bool) -> Result<()> {
+    let prefix = format!("{}.", key);
+    let entries: Vec<ConfigEntry> = load_entries(global)?
+        .into_iter()
+        .filter(|entry| entry.key == key || entry.key.starts_with(&prefix))
+        .collect();
+    if entries.is_empty() {
+        anyhow::bail!("Config key '{}' is not set", key);
+    }
+    print_entries("config_get", &entries)
+}
+
+/// Sets a value in the repository or global config file.
+///
+/// # Arguments
+///
+/// * `key` - Dotted key, such as `user.name`
+/// * `value` - The value; read as TOML when possible and as a string otherwise
+/// * `global` - Write the global config file instead of `.pearls/config.toml`
+///
+/// # Returns
+///
+/// Ok if the value was written, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - Repository is not initialized and `global` is false
+/// - The key is `mcp.auth_token` and `global` is false
+/// - The key is one of [`REPO_ONLY_KEYS`] and `global` is true
+/// - The key is unknown or the value is invalid
+/// - The file cannot be written
+pub fn set(key: String, value: String, global: bool) -> Result<()> {
+    if key == "mcp.auth_token" && !global {
+        anyhow::bail!(
+            "mcp.auth_token is a secret and .pearls/config.toml is committed; \
+             use --global or PEARLS_MCP_AUTH_TOKEN"
+        );
+    }
+    if global && REPO_ONLY_KEYS.contains(&key.as_str()) {
+        anyhow::bail!(
+            "{} describes how a repository stores its Pearls and can only be set \
+             in .pearls/config.toml",
+            key
+        );
+    }
+    let path = config_path(global)?;
+    Config::set_file_value(&path, &key, &value)?;
+
+    if is_json_output() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "status": "ok",
+                "action": "config_set",
+                "key": key,
+                "path": path.display().to_string()
+            }))?
+        );
+    } else {
+        println!("✓ Set {} in {}", key, path.display());
+    }
+    Ok(())
+}
+
+/// Opens the repository or global config file in an editor.
+///
+/// The editor is `ui.editor`, `$VISUAL`, `$EDITOR`, or `vi`.
+///
+/// # Arguments
+///
+/// * `global` - Edit the global config file instead of `.pearls/config.toml`
+///
+/// # Returns
+///
+/// Ok if the editor exited successfully, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if the repository is not initialized and `global` is
+/// false, or if the editor cannot be started or fails.
+pub fn edit(global: bool) -> Result<()> {
+    let path = config_path(global)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let editor = load_layered()?
+        .config
+        .ui
+        .editor
+        .or_else(|| std::env::var("VISUAL").ok())
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let status = std::process::Command::new(program)
+        .args(words)
+        .arg(&path)
+        .status()
+        .map_err(|e| anyhow::anyhow!("Failed to start editor '{}': {}", editor, e))?;
+    if !status.success() {
+        anyhow::bail!("Editor '{}' exited with {}", editor, status);
+    }
+    Ok(())
+}
+
+/// Loads the effective configuration, with the repository layer when run
+/// inside a repository.
+fn load_layered() -> Result<pearls_core::LayeredConfig> {
+    let repo = RepoContext::discover(None).ok();
+    Ok(Config::load_layered(
+        repo.as_ref().map(RepoContext::pearls_dir),
+    )?)
+}
+
+/// Loads the effective values, or only those of the global file.
+fn load_entries(global: bool) -> Result<Vec<ConfigEntry>> {
+    if global {
+        Ok(Config::file_entries(
+            &config_path(true)?,
+            ConfigSource::Global,
+        )?)
+    } else {
+        Ok(load_layered()?.entries()?)
+    }
+}
+
+/// Returns the file `set` and `edit` write.
+fn config_path(global: bool) -> Result<PathBuf> {
+    if global {
+        return Config::global_path().ok_or_else(|| {
+            anyhow::anyhow!("No global config location; set HOME or PEARLS_GLOBAL_CONFIG")
+        });
+    }
+    let repo = RepoContext::discover(None)
+        .map_err(|_| anyhow::anyhow!("Pearls repository not initialized. Run 'prl init' first."))?;
+    Ok(repo.config_path().to_path_buf())
+}
+
+/// Prints entries as `key = value (source)` lines or as JSON.
+fn print_entries(action: &str, entries: &[ConfigEntry]) -> Result<()> {
+    if is_json_output() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "status": "ok",
+                "action": action,
+                "entries": entries
+            }))?
+        );
+    } else {
+        for entry in entries {
+            println!(
+                "{} = {} ({})",
+                entry.key,
+                entry.value,
+                entry.source.as_str()
+            );
+        }
+    }
+    Ok(())
+}
@@ -7,8 +7,8 @@
 
 use crate::output_mode::is_json_output;
 use anyhow::Result;
+use pearls_app::RepoContext;
 use pearls_core::{Config, Pearl, Storage};
-use std::path::Path;
 
 /// Creates a new Pearl with the specified parameters.
 ///
@@ -39,12 +39,8 @@ pub fn execute(
     labels: Vec<String>,
     author: Option<String>,
 ) -> Result<()> {
-    let pearls_dir = Path::new(".pearls");
-
-    // Verify .pearls directory exists
-    if !pearls_dir.exists() {
-        anyhow::bail!("Pearls repository not initialized. Run 'prl init' first.");
-    }
+    let repo = RepoContext::discover(None)?;
+    let pearls_dir = repo.pearls_dir();
 
     // Determine author
     let author = author
@@ -84,7 +80,7 @@ pub fn execute(
     pearl.validate()?;
 
     // Save to storage
-    let mut storage = Storage::new(pearls_dir.join("issues.jsonl"))?;
+    let mut storage = Storage::open(pearls_dir, &config)?;
     if !labels.is_empty() {
         suggest_labels(&storage, &labels)?;
     }
@@ -116,29 +112,15 @@ pub fn execute(
     Ok(())
 }
 
-/// Gets the default author from Git config or system username.
+/// Gets the default author from the Pearls config, Git config, or system
+/// username.
 ///
 /// # Returns
 ///
 /// The author name if available, None otherwise.
-fn get_default_author() -> Option<String> {
-    // Try to get from Git config
-    if let Ok(output) = std::process::Command::new("git")
-        .args(["config", "user.name"])
-        .output()
-    {
-        if output.status.success() {
-            let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if !name.is_empty() {
-                return Some(name);
-            }
-        }
-    }
-
-    // Fall back to system username
-    std::env::var("USER")
-        .ok()
-        .or_else(|| std::env::var("USERNAME").ok())
+pub(crate) fn get_default_author() -> Option<String> {
+    let repo = pearls_app::RepoContext::discover(None).ok();
+    pearls_app::default_author(repo.as_ref())
 }
 
 fn read_description_from_path(path: &str) -> Result<String> {
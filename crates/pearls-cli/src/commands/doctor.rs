@@ -2,29 +2,19 @@
 
 //! Implementation of the `prl doctor` command.
 //!
-//! Validates JSONL syntax, schema compliance, graph integrity, and common issues.
+//! Validates record syntax, schema compliance, graph integrity, index freshness,
+//! Git integration, and common issues for either storage layout.
 
+use crate::commands::init::{configure_merge_driver, MERGE_DRIVER};
 use crate::output_mode::is_json_output;
 use anyhow::Result;
-use pearls_core::{IssueGraph, Pearl, Status, Storage};
-use std::collections::HashSet;
-use std::io::BufRead;
+use git2::Repository;
+use pearls_app::{diagnose, Finding, RepoContext, Severity};
+use pearls_hooks::HookState;
 use std::path::Path;
 
 use crate::progress::ProgressReporter;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Severity {
-    Error,
-    Warning,
-    Info,
-}
-
-struct Finding {
-    severity: Severity,
-    message: String,
-}
-
 /// Executes the doctor command.
 ///
 /// # Arguments
@@ -39,145 +29,32 @@ struct Finding {
 ///
 /// Returns an error if:
 /// - The repository is not initialized
+/// - The configuration is invalid or from a newer schema
 /// - The issues file cannot be read
 pub fn execute(fix: bool) -> Result<()> {
-    let pearls_dir = Path::new(".pearls");
-    if !pearls_dir.exists() {
-        anyhow::bail!("Pearls repository not initialized. Run 'prl init' first.");
-    }
-
-    let issues_path = pearls_dir.join("issues.jsonl");
-    let mut findings = Vec::new();
-    let mut pearls = Vec::new();
-    let mut invalid_lines = 0usize;
-    let mut has_cycle_error = false;
-    let mut has_closed_blocked_error = false;
-
-    if issues_path.exists() {
-        let file = std::fs::File::open(&issues_path)?;
-        let reader = std::io::BufReader::with_capacity(64 * 1024, file);
-        let progress = ProgressReporter::new("Doctor scan", None, 1000);
-        let mut processed = 0usize;
-        for (idx, line) in reader.lines().enumerate() {
-            let line = line?;
-            if line.trim().is_empty() {
-                continue;
-            }
-            processed += 1;
-            progress.report(processed);
-            match serde_json::from_str::<Pearl>(&line) {
-                Ok(pearl) => {
-                    if let Err(err) = pearl.validate() {
-                        findings.push(Finding {
-                            severity: Severity::Error,
-                            message: format!("Line {}: {}", idx + 1, err),
-                        });
-                    }
-                    pearls.push(pearl);
-                }
-                Err(err) => {
-                    invalid_lines += 1;
-                    findings.push(Finding {
-                        severity: Severity::Error,
-                        message: format!("Line {}: Invalid JSON ({})", idx + 1, err),
-                    });
-                }
-            }
-        }
-        progress.finish(processed);
-    }
-
-    let (deduped, duplicate_ids) = dedupe_pearls(&pearls);
-    if !duplicate_ids.is_empty() {
-        findings.push(Finding {
-            severity: Severity::Error,
-            message: format!("Duplicate Pearl IDs detected: {}", duplicate_ids.join(", ")),
-        });
-    }
-
-    let orphaned = find_orphaned_deps(&deduped);
-    for (pearl_id, target_id) in &orphaned {
-        findings.push(Finding {
-            severity: Severity::Warning,
-            message: format!(
-                "Orphaned dependency: {} references missing {}",
-                pearl_id, target_id
-            ),
-        });
-    }
-
-    match IssueGraph::from_pearls(deduped.clone()) {
-        Ok(graph) => {
-            for pearl in &deduped {
-                if pearl.status == Status::Blocked && !graph.is_blocked(&pearl.id) {
-                    findings.push(Finding {
-                        severity: Severity::Warning,
-                        message: format!(
-                            "Pearl {} is marked blocked but has no open blockers",
-                            pearl.id
-                        ),
-                    });
-                }
-                if pearl.status == Status::Closed && graph.is_blocked(&pearl.id) {
-                    has_closed_blocked_error = true;
-                    findings.push(Finding {
-                        severity: Severity::Error,
-                        message: format!(
-                            "Pearl {} is closed but still blocked by open dependencies",
-                            pearl.id
-                        ),
-                    });
-                }
-            }
-        }
-        Err(err) => {
-            has_cycle_error = true;
-            findings.push(Finding {
-                severity: Severity::Error,
-                message: format!("Cycle detected: {}", err),
-            });
-        }
-    }
-
-    if fix {
-        let mut fixed = deduped.clone();
-        let removed = remove_orphaned_deps(&mut fixed);
-        let removed_dupes = duplicate_ids.len();
-        let removed_invalid = invalid_lines;
+    let repo = RepoContext::discover(None)?;
+    let progress = ProgressReporter::new("Doctor scan", None, 1000);
+    let mut report = diagnose(&repo, fix, &|count| progress.report(count))?;
+    progress.finish(report.records_scanned);
 
-        let mut storage = Storage::new(issues_path)?;
-        storage.save_all(&fixed)?;
-
-        findings.push(Finding {
-            severity: Severity::Info,
-            message: format!(
-                "Fix applied: removed {} orphaned deps, {} duplicate IDs, {} invalid lines",
-                removed, removed_dupes, removed_invalid
-            ),
-        });
-    }
+    check_git_integration(fix, &mut report.findings)?;
 
     if is_json_output() {
         println!(
             "{}",
             serde_json::to_string_pretty(&serde_json::json!({
-                "status": if findings.iter().any(|f| f.severity == Severity::Error) { "error" } else { "ok" },
+                "status": if report.has_errors() { "error" } else { "ok" },
                 "action": "doctor",
-                "fix_applied": fix,
-                "findings": findings.iter().map(|f| {
-                    serde_json::json!({
-                        "severity": severity_label(f.severity),
-                        "message": f.message
-                    })
-                }).collect::<Vec<_>>()
+                "fix_applied": report.fix_applied,
+                "findings": report.findings
             }))?
         );
     } else {
-        report_findings(&findings);
+        report_findings(&report.findings, fix);
     }
 
-    if findings.iter().any(|f| f.severity == Severity::Error) {
-        if fix && !has_cycle_error && !has_closed_blocked_error {
+    if report.has_errors() {
+        if fix && !report.unrepairable {
             return Ok(());
         }
         anyhow::bail!("Doctor found errors. Run with --fix to attempt repairs.");
@@ -186,7 +63,7 @@ pub fn execute(fix: bool) -> Result<()> {
     Ok(())
 }
 
-fn report_findings(findings: &[Finding]) {
+fn report_findings(findings: &[Finding], fix: bool) {
     if findings.is_empty() {
         println!("Doctor: no issues found.");
         return;
@@ -199,59 +76,129 @@ fn report_findings(findings: &[Finding]) {
             Severity::Warning => "WARN",
             Severity::Info => "INFO",
         };
-        println!("[{}] {}", label, finding.message);
+        match &finding.suggestion {
+            Some(suggestion) if finding.fixable && !fix => println!(
+                "[{}] {} (run with --fix to {})",
+                label,
+                finding.message,
+                lowercase_first(suggestion)
+            ),
+            Some(suggestion) if !finding.fixable => {
+                println!("[{}] {} ({})", label, finding.message, suggestion)
+            }
+            _ => println!("[{}] {}", label, finding.message),
+        }
     }
 }
 
-fn severity_label(severity: Severity) -> &'static str {
-    match severity {
-        Severity::Error => "error",
-        Severity::Warning => "warning",
-        Severity::Info => "info",
+fn lowercase_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
     }
 }
 
-fn dedupe_pearls(pearls: &[Pearl]) -> (Vec<Pearl>, Vec<String>) {
-    let mut seen = HashSet::new();
-    let mut dupes = Vec::new();
-    let mut unique = Vec::new();
-
-    for pearl in pearls {
-        if seen.contains(&pearl.id) {
-            dupes.push(pearl.id.clone());
-        } else {
-            seen.insert(pearl.id.clone());
-            unique.push(pearl.clone());
+/// Checks the merge driver config, `.gitattributes`, and the Pearls hooks.
+///
+/// Nothing is reported outside a Git work tree. With `fix`, missing or stale
+/// pieces are set up again.
+fn check_git_integration(fix: bool, findings: &mut Vec<Finding>) -> Result<()> {
+    let Ok(repo) = Repository::discover(".") else {
+        return Ok(());
+    };
+    let Some(workdir) = repo.workdir().map(Path::to_path_buf) else {
+        return Ok(());
+    };
+    let mut repaired = Vec::new();
+
+    let driver = repo.config()?.get_string("merge.pearls.driver").ok();
+    let driver_problem = match driver.as_deref() {
+        None => Some("Git merge driver is not configured".to_string()),
+        Some(MERGE_DRIVER) => None,
+        Some(other) => Some(format!(
+            "Git merge driver is stale: runs '{}' instead of '{}'",
+            other, MERGE_DRIVER
+        )),
+    };
+    if let Some(message) = driver_problem {
+        findings.push(
+            Finding::new(Severity::Warning, message)
+                .fixable(format!("Set merge.pearls.driver to '{}'", MERGE_DRIVER)),
+        );
+        if fix {
+            configure_merge_driver(&repo)?;
+            repaired.push("merge driver");
         }
     }
 
-    (unique, dupes)
-}
-
-fn find_orphaned_deps(pearls: &[Pearl]) -> Vec<(String, String)> {
-    let ids: HashSet<String> = pearls.iter().map(|pearl| pearl.id.clone()).collect();
-    let mut orphaned = Vec::new();
-
-    for pearl in pearls {
-        for dep in &pearl.deps {
-            if !ids.contains(&dep.target_id) {
-                orphaned.push((pearl.id.clone(), dep.target_id.clone()));
+    let gitattributes_path = workdir.join(".gitattributes");
+    let gitattributes = std::fs::read_to_string(&gitattributes_path).unwrap_or_default();
+    if !gitattributes.contains("merge=pearls") {
+        findings.push(
+            Finding::new(
+                Severity::Warning,
+                ".gitattributes does not use the Pearls merge driver",
+            )
+            .fixable("Add merge=pearls entries to .gitattributes"),
+        );
+        if fix {
+            let mut content = gitattributes;
+            if !content.is_empty() && !content.ends_with('\n') {
+                content.push('\n');
             }
+            content.push_str("issues.jsonl merge=pearls\narchive.jsonl merge=pearls\n");
+            std::fs::write(&gitattributes_path, content)?;
+            repaired.push(".gitattributes");
         }
     }
 
-    orphaned
-}
-
-fn remove_orphaned_deps(pearls: &mut [Pearl]) -> usize {
-    let ids: HashSet<String> = pearls.iter().map(|pearl| pearl.id.clone()).collect();
-    let mut removed = 0usize;
-
-    for pearl in pearls {
-        let before = pearl.deps.len();
-        pearl.deps.retain(|dep| ids.contains(&dep.target_id));
-        removed += before - pearl.deps.len();
+    let hooks_dir = match pearls_hooks::hooks_dir(&workdir) {
+        Ok(hooks_dir) => hooks_dir,
+        Err(err) => {
+            findings.push(Finding::new(
+                Severity::Warning,
+                format!("Could not locate the Git hooks directory: {}", err),
+            ));
+            return Ok(());
+        }
+    };
+    let states = pearls_hooks::hook_states(&hooks_dir)?;
+    for (state, label) in [(HookState::Missing, "missing"), (HookState::Stale, "stale")] {
+        let names: Vec<&str> = states
+            .iter()
+            .filter(|(_, hook_state)| *hook_state == state)
+            .map(|(name, _)| *name)
+            .collect();
+        if !names.is_empty() {
+            findings.push(
+                Finding::new(
+                    Severity::Warning,
+                    format!(
+                        "Git hooks {} in {}: {}",
+                        label,
+                        hooks_dir.display(),
+                        names.join(", ")
+                    ),
+                )
+                .fixable("Install the hooks, also done by 'prl hooks install'"),
+            );
+        }
+    }
+    if fix
+        && states
+            .iter()
+            .any(|(_, state)| *state != HookState::Installed)
+    {
+        pearls_hooks::install_hooks(&hooks_dir)?;
+        repaired.push("hooks");
     }
 
-    removed
+    if !repaired.is_empty() {
+        findings.push(Finding::new(
+            Severity::Info,
+            format!("Git integration repaired: {}", repaired.join(", ")),
+        ));
+    }
+    Ok(())
 }
@@ -0,0 +1,170 @@
+// Rust guideline compliant 2026-02-10
+
+//! Implementation of the `prl export` command.
+//!
+//! Writes the active Pearls in formats read by people and other tools.
+
+use crate::output_mode::is_json_output;
+use anyhow::Result;
+use pearls_app::export::csv::{parse_columns, render_csv, DEFAULT_COLUMNS};
+use pearls_app::export::html::render_site;
+use pearls_app::export::markdown::render_markdown;
+use pearls_app::RepoContext;
+use std::path::PathBuf;
+
+/// Exports the active Pearls as a Markdown plan.
+///
+/// Pearls with children become headings and their children nested checklist
+/// items. The document can be edited and imported again with
+/// `prl import markdown`.
+///
+/// # Arguments
+///
+/// * `output` - File to write; the document is printed when omitted
+///
+/// # Returns
+///
+/// Ok if the export succeeds, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The repository is not initialized
+/// - The Pearls cannot be read
+/// - The output file cannot be written
+pub fn markdown(output: Option<String>) -> Result<()> {
+    let repo = RepoContext::discover(None)?;
+    let pearls = repo.open_storage()?.load_all()?;
+    let document = render_markdown(&pearls);
+    write_export("markdown", pearls.len(), &document, output)
+}
+
+/// Exports the active Pearls as CSV.
+///
+/// Labels and dependencies are flattened into `;`-separated cells, and
+/// `meta.<key>` columns hold metadata values. The default columns can be
+/// imported again with `prl import csv`.
+///
+/// # Arguments
+///
+/// * `columns` - Comma-separated column names; the defaults when omitted
+/// * `output` - File to write; the document is printed when omitted
+///
+/// # Returns
+///
+/// Ok if the export succeeds, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The repository is not initialized
+/// - A column name is unknown
+/// - The Pearls cannot be read or the output file cannot be written
+pub fn csv(columns: Option<String>, output: Option<String>) -> Result<()> {
+    let columns = parse_columns(&columns.unwrap_or_else(|| DEFAULT_COLUMNS.join(",")))?;
+    let repo = RepoContext::discover(None)?;
+    let pearls = repo.open_storage()?.load_all()?;
+    let document = render_csv(&pearls, &columns)?;
+    write_export("csv", pearls.len(), &document, output)
+}
+
+/// Exports the tracker as a static HTML site.
+///
+/// Writes an index with filters, a dependency graph, and one page per active
+/// or archived Pearl into `out`, which is created if needed. The pages have no
+/// external assets and can be published on any static host.
+///
+/// # Arguments
+///
+/// * `out` - Directory to write the site to
+///
+/// # Returns
+///
+/// Ok if the export succeeds, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The repository is not initialized
+/// - The Pearls or the archive cannot be read
+/// - The site files cannot be written
+pub fn html(out: String) -> Result<()> {
+    let repo = RepoContext::discover(None)?;
+    let active = repo.open_storage()?.load_all()?;
+    let archived = match repo.open_archive_storage()? {
+        Some(archive) => archive.load_all()?,
+        None => Vec::new(),
+    };
+
+    let out = PathBuf::from(out);
+    let files = render_site(&active, &archived);
+    for file in &files {
+        let path = out.join(&file.path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, &file.content)?;
+    }
+
+    if is_json_output() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "status": "ok",
+                "action": "export",
+                "format": "html",
+                "exported": active.len() + archived.len(),
+                "active": active.len(),
+                "archived": archived.len(),
+                "files": files.len(),
+                "output": out.display().to_string()
+            }))?
+        );
+    } else {
+        println!(
+            "✓ Exported {} Pearls ({} archived) to {}",
+            active.len() + archived.len(),
+            archived.len(),
+            out.join("index.html").display()
+        );
+    }
+    Ok(())
+}
+
+/// Writes an exported document to a file or standard output.
+fn write_export(format: &str, count: usize, document: &str, output: Option<String>) -> Result<()> {
+    let Some(output) = output.map(PathBuf::from) else {
+        if is_json_output() {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "status": "ok",
+                    "action": "export",
+                    "format": format,
+                    "exported": count,
+                    "content": document
+                }))?
+            );
+        } else {
+            print!("{}", document);
+        }
+        return Ok(());
+    };
+
+    std::fs::write(&output, document)?;
+    if is_json_output() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "status": "ok",
+                "action": "export",
+                "format": format,
+                "exported": count,
+                "output": output.display().to_string()
+            }))?
+        );
+    } else {
+        println!("✓ Exported {} Pearls to {}", count, output.display());
+    }
+    Ok(())
+}
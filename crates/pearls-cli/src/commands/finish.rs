@@ -0,0 +1,122 @@
+// Rust guideline compliant 2026-02-10
+
+//! Implementation of the `prl finish` command.
+//!
+//! Finishes the Pearl of the current branch by closing it or handing it over
+//! for review.
+
+use crate::output_mode::is_json_output;
+use anyhow::Result;
+use git2::Repository;
+use pearls_app::RepoContext;
+use pearls_core::{workflow, IssueGraph, Status};
+
+/// Finishes work on a Pearl.
+///
+/// Without an ID, the Pearl is taken from the current branch name, or from the
+/// current Pearl of the clone when the branch names none. All blocking
+/// dependencies must be closed. The Pearl is then closed, or with `review`,
+/// stays in progress and gets the `workflow.review_label` label.
+///
+/// # Arguments
+///
+/// * `id` - The Pearl ID (full or partial); detected when omitted
+/// * `review` - Whether to mark the Pearl for review instead of closing it
+///
+/// # Returns
+///
+/// Ok if the Pearl was finished, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The repository is not initialized
+/// - No Pearl is given or detected, or it is not found
+/// - The Pearl has open blocking dependencies
+/// - The file cannot be written
+pub fn execute(id: Option<String>, review: bool) -> Result<()> {
+    let repo = RepoContext::discover(None)?;
+    let config = repo.load_config()?;
+    let mut storage = repo.open_storage()?;
+    let all_pearls = storage.load_all()?;
+
+    let branch = Repository::discover(repo.root()).ok().and_then(|git| {
+        git.head()
+            .ok()
+            .and_then(|head| head.shorthand().map(str::to_string))
+    });
+    let full_id = match id {
+        Some(id) => pearls_core::identity::resolve_partial_id(&id, &all_pearls)?,
+        None => workflow::active_pearl(repo.pearls_dir(), branch.as_deref(), &all_pearls)?
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No Pearl found for branch '{}'. Pass an ID: prl finish <id>",
+                    branch.as_deref().unwrap_or("HEAD")
+                )
+            })?,
+    };
+    let mut pearl = storage.load_by_id(&full_id)?;
+
+    let graph = IssueGraph::from_pearls(all_pearls)?;
+    let blockers: Vec<String> = graph
+        .blocking_deps(&pearl.id)
+        .into_iter()
+        .map(|blocker| format!("{} ({})", blocker.id, blocker.title))
+        .collect();
+    if !blockers.is_empty() {
+        anyhow::bail!(
+            "Cannot finish {}: blocked by open Pearls: {}",
+            pearl.id,
+            blockers.join(", ")
+        );
+    }
+
+    if review {
+        if pearl.status != Status::InProgress {
+            pearls_core::fsm::validate_transition(&pearl, Status::InProgress, &graph)?;
+            pearl.status = Status::InProgress;
+        }
+        let label = &config.workflow.review_label;
+        if !pearl.labels.contains(label) {
+            pearl.labels.push(label.clone());
+        }
+    } else {
+        pearls_core::fsm::validate_transition(&pearl, Status::Closed, &graph)?;
+        pearl.status = Status::Closed;
+        pearl
+            .labels
+            .retain(|label| *label != config.workflow.review_label);
+    }
+    pearl.updated_at = pearls_app::unix_timestamp()?;
+    pearl.validate()?;
+    storage.save(&pearl)?;
+
+    if !review && workflow::read_current(repo.pearls_dir())?.as_deref() == Some(&pearl.id) {
+        workflow::write_current(repo.pearls_dir(), None)?;
+    }
+
+    if is_json_output() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "status": "ok",
+                "action": "finish",
+                "review": review,
+                "pearl": pearl
+            }))?
+        );
+    } else {
+        if review {
+            println!("✓ Ready for review: {}", pearl.id);
+        } else {
+            println!("✓ Finished Pearl: {}", pearl.id);
+        }
+        println!("  Title: {}", pearl.title);
+        println!("  Status: {:?}", pearl.status);
+        if !pearl.labels.is_empty() {
+            println!("  Labels: {}", pearl.labels.join(", "));
+        }
+    }
+
+    Ok(())
+}
@@ -1,14 +1,17 @@
 // Rust guideline compliant 2026-02-06
 
-//! Hook command wrappers for invoking Pearls Git hooks from the CLI.
+//! Hook command wrappers for invoking Pearls Git hooks from the CLI, and for
+//! installing and removing the hook scripts.
 
+use crate::output_mode::is_json_output;
 use anyhow::Result;
+use pearls_app::RepoContext;
 
 /// Runs the requested hook action.
 ///
 /// # Arguments
 ///
-/// * `action` - Hook action name ("pre-commit" or "post-merge")
+/// * `action` - Hook action to run
 ///
 /// # Returns
 ///
@@ -16,20 +19,112 @@ use anyhow::Result;
 ///
 /// # Errors
 ///
-/// Returns an error if the action is invalid or the hook fails.
+/// Returns an error if the action is invalid, the hook fails, or the hook
+/// scripts cannot be written.
 pub fn execute(action: HookAction) -> Result<()> {
+    // Git runs hooks from the top of the work tree; installs follow discovery.
     let repo_path = std::env::current_dir()?;
     match action {
+        HookAction::Install => {
+            let repo_path = install_root(repo_path);
+            let hooks_dir = pearls_hooks::hooks_dir(&repo_path)?;
+            let written = pearls_hooks::install_hooks(&hooks_dir)?;
+            report("install", &hooks_dir, &written, "Installed")
+        }
+        HookAction::Uninstall => {
+            let repo_path = install_root(repo_path);
+            let hooks_dir = pearls_hooks::hooks_dir(&repo_path)?;
+            let removed = pearls_hooks::uninstall_hooks(&hooks_dir)?;
+            report("uninstall", &hooks_dir, &removed, "Removed")
+        }
         HookAction::PreCommit => pearls_hooks::pre_commit_hook(&repo_path),
+        HookAction::PrepareCommitMsg {
+            message_file,
+            source,
+            ..
+        } => pearls_hooks::prepare_commit_msg_hook(
+            &repo_path,
+            std::path::Path::new(&message_file),
+            source.as_deref(),
+        ),
+        HookAction::CommitMsg { message_file } => {
+            pearls_hooks::commit_msg_hook(&repo_path, std::path::Path::new(&message_file))
+        }
+        HookAction::PostCommit => pearls_hooks::post_commit_hook(&repo_path),
+        HookAction::PostCheckout { flag, .. } => {
+            pearls_hooks::post_checkout_hook(&repo_path, flag == "1")
+        }
         HookAction::PostMerge => pearls_hooks::post_merge_hook(&repo_path),
     }
 }
 
+/// Returns the directory whose hooks `install` and `uninstall` change: the
+/// discovered repository root, or `cwd` outside a Pearls repository.
+fn install_root(cwd: std::path::PathBuf) -> std::path::PathBuf {
+    match RepoContext::discover(None) {
+        Ok(repo) => repo.root().to_path_buf(),
+        Err(_) => cwd,
+    }
+}
+
+fn report(action: &str, hooks_dir: &std::path::Path, hooks: &[&str], verb: &str) -> Result<()> {
+    if is_json_output() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "status": "ok",
+                "action": format!("hooks {}", action),
+                "hooks_dir": hooks_dir.display().to_string(),
+                "hooks": hooks
+            }))?
+        );
+    } else if hooks.is_empty() {
+        println!("No hooks to change in {}", hooks_dir.display());
+    } else {
+        println!(
+            "✓ {} hooks in {}: {}",
+            verb,
+            hooks_dir.display(),
+            hooks.join(", ")
+        );
+    }
+    Ok(())
+}
+
 /// Supported hook actions.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::Subcommand)]
+#[derive(Debug, Clone, PartialEq, Eq, clap::Subcommand)]
 pub enum HookAction {
+    /// Install or refresh the Pearls hooks, keeping existing hooks
+    Install,
+    /// Remove the Pearls hooks, restoring any hooks they were chained to
+    Uninstall,
     /// Run the pre-commit hook
     PreCommit,
+    /// Run the prepare-commit-msg hook
+    PrepareCommitMsg {
+        /// Path to the commit message file
+        message_file: String,
+        /// Message source (message, template, merge, squash, or commit)
+        source: Option<String>,
+        /// Commit SHA for amended or reused messages
+        sha: Option<String>,
+    },
+    /// Run the commit-msg hook
+    CommitMsg {
+        /// Path to the commit message file
+        message_file: String,
+    },
+    /// Run the post-commit hook
+    PostCommit,
+    /// Run the post-checkout hook
+    PostCheckout {
+        /// Previous HEAD
+        previous: String,
+        /// New HEAD
+        new: String,
+        /// 1 for a branch checkout, 0 for a file checkout
+        flag: String,
+    },
     /// Run the post-merge hook
     PostMerge,
 }
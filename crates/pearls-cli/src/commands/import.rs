@@ -1,22 +1,31 @@
-// Rust guideline compliant 2026-02-06
+// Rust guideline compliant 2026-02-10
 
 //! Implementation of the `prl import` command.
 //!
-//! Imports Pearls data from Beads JSONL format.
+//! Imports issues from other trackers. Imports are idempotent: each Pearl
+//! records its source ID in metadata, and importing the same data again
+//! updates those Pearls instead of creating duplicates.
 
 use crate::output_mode::is_json_output;
 use anyhow::Result;
-use pearls_core::{Pearl, Storage};
-use std::io::BufRead;
+use pearls_app::import::beads;
+use pearls_app::import::csv::{self, CsvMapping};
+use pearls_app::import::forge::{self, Forge};
+use pearls_app::import::markdown;
+use pearls_app::{apply_import, plan_import, ImportAction, ImportPlan, RepoContext};
 use std::path::{Path, PathBuf};
 
-use crate::progress::ProgressReporter;
-
-/// Imports Pearls from a Beads JSONL file.
+/// Imports Pearls from a Beads JSONL export.
+///
+/// Beads statuses, priorities, dependency kinds, and comments are mapped to
+/// Pearls, `bd-` IDs are replaced with Pearl IDs (the original is kept in the
+/// `beads_id` metadata key), and references between issues are rewritten.
+/// Lines already in Pearl format are imported unchanged.
 ///
 /// # Arguments
 ///
 /// * `path` - Path to the Beads JSONL file
+/// * `dry_run` - Whether to report the planned changes without writing them
 ///
 /// # Returns
 ///
@@ -26,13 +35,10 @@ use crate::progress::ProgressReporter;
 ///
 /// Returns an error if:
 /// - The repository is not initialized
-/// - The source file cannot be read
+/// - The source file cannot be read or holds no valid issues
 /// - The destination file cannot be written
-pub fn import_beads(path: String) -> Result<()> {
-    let pearls_dir = Path::new(".pearls");
-    if !pearls_dir.exists() {
-        anyhow::bail!("Pearls repository not initialized. Run 'prl init' first.");
-    }
+pub fn import_beads(path: String, dry_run: bool) -> Result<()> {
+    let repo = RepoContext::discover(None)?;
 
     let beads_path = PathBuf::from(path);
     if !beads_path.exists() {
@@ -40,50 +46,220 @@ pub fn import_beads(path: String) -> Result<()> {
     }
 
     let file = std::fs::File::open(&beads_path)?;
-    let reader = std::io::BufReader::with_capacity(64 * 1024, file);
-    let mut pearls = Vec::new();
-    let mut skipped = 0usize;
-    let mut processed = 0usize;
-    let progress = ProgressReporter::new("Importing", None, 1000);
-
-    for (idx, line) in reader.lines().enumerate() {
-        let line = line?;
-        if line.trim().is_empty() {
-            continue;
-        }
-        processed += 1;
-        progress.report(processed);
-        match serde_json::from_str::<Pearl>(&line) {
-            Ok(pearl) => {
-                if let Err(err) = pearl.validate() {
-                    skipped += 1;
-                    eprintln!(
-                        "Warning: Skipping invalid Pearl on line {}: {}",
-                        idx + 1,
-                        err
-                    );
-                } else {
-                    pearls.push(pearl);
-                }
-            }
-            Err(err) => {
-                skipped += 1;
-                eprintln!(
-                    "Warning: Skipping invalid JSON on line {}: {}",
-                    idx + 1,
-                    err
-                );
-            }
-        }
+    let parsed = beads::parse_beads(std::io::BufReader::with_capacity(64 * 1024, file))?;
+    if parsed.drafts.is_empty() {
+        anyhow::bail!("No valid Pearls found in Beads file.");
     }
-    progress.finish(processed);
 
-    if pearls.is_empty() {
-        anyhow::bail!("No valid Pearls found in Beads file.");
+    let mut storage = repo.open_storage()?;
+    let mut plan = plan_import(&storage.load_all()?, beads::SOURCE_KEY, parsed.drafts, true);
+    let mut warnings = parsed.warnings;
+    warnings.append(&mut plan.warnings);
+    plan.warnings = warnings;
+
+    if !dry_run {
+        apply_import(&mut storage, &plan)?;
+    }
+
+    report_import(&beads_path, "beads", &plan, parsed.skipped, dry_run)
+}
+
+/// Imports Pearls from a GitHub or GitLab issue export.
+///
+/// Reads an offline API dump, so no network access is needed. Each Pearl keeps
+/// the issue URL in the `source_url` metadata key and the issue number in
+/// `source_number`; cross-references such as "blocked by #12" and task-list
+/// child issues become dependencies.
+///
+/// # Arguments
+///
+/// * `forge` - Tracker that produced the export
+/// * `path` - Path to the JSON export
+/// * `dry_run` - Whether to report the planned changes without writing them
+///
+/// # Returns
+///
+/// Ok if import succeeds, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The repository is not initialized
+/// - The export cannot be read or holds no issues
+/// - The destination file cannot be written
+pub fn import_forge(forge: Forge, path: String, dry_run: bool) -> Result<()> {
+    let repo = RepoContext::discover(None)?;
+
+    let export_path = PathBuf::from(path);
+    if !export_path.exists() {
+        anyhow::bail!("Export file not found: {}", export_path.display());
+    }
+
+    let parsed = forge::parse_forge(forge, &std::fs::read_to_string(&export_path)?)?;
+    if parsed.drafts.is_empty() {
+        anyhow::bail!("No issues found in {} export.", forge.name());
+    }
+
+    let mut storage = repo.open_storage()?;
+    let mut plan = plan_import(
+        &storage.load_all()?,
+        forge::SOURCE_KEY,
+        parsed.drafts,
+        false,
+    );
+    let mut warnings = parsed.warnings;
+    warnings.append(&mut plan.warnings);
+    plan.warnings = warnings;
+
+    if !dry_run {
+        apply_import(&mut storage, &plan)?;
     }
 
-    let mut storage = Storage::new(pearls_dir.join("issues.jsonl"))?;
-    storage.save_all(&pearls)?;
+    report_import(&export_path, forge.name(), &plan, parsed.skipped, dry_run)
+}
+
+/// Imports Pearls from a Markdown TODO file or a directory of plans.
+///
+/// Headings become parent Pearls and checklist items their children; checked
+/// items are closed, and inline `#label` and `P0`-`P4` tokens set labels and
+/// priority. Re-importing an edited document, or one written by
+/// `prl export markdown`, updates the same Pearls.
+///
+/// # Arguments
+///
+/// * `path` - Path to a Markdown file, or a directory whose `.md` files are
+///   imported in name order
+/// * `dry_run` - Whether to report the planned changes without writing them
+///
+/// # Returns
+///
+/// Ok if import succeeds, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The repository is not initialized
+/// - The source cannot be read or holds no headings or checklist items
+/// - The destination file cannot be written
+pub fn import_markdown(path: String, dry_run: bool) -> Result<()> {
+    let repo = RepoContext::discover(None)?;
+
+    let source_path = PathBuf::from(path);
+    if !source_path.exists() {
+        anyhow::bail!("Markdown source not found: {}", source_path.display());
+    }
+    let files = if source_path.is_dir() {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(&source_path)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![source_path.clone()]
+    };
+
+    let author =
+        crate::commands::create::get_default_author().unwrap_or_else(|| "markdown".to_string());
+    let mut drafts = Vec::new();
+    let mut warnings = Vec::new();
+    let mut skipped = 0;
+    for file in &files {
+        let name = file.to_string_lossy().replace('\\', "/");
+        let name = name.trim_start_matches("./");
+        let mut parsed = markdown::parse_markdown(name, &std::fs::read_to_string(file)?, &author)?;
+        drafts.append(&mut parsed.drafts);
+        warnings.append(&mut parsed.warnings);
+        skipped += parsed.skipped;
+    }
+    if drafts.is_empty() {
+        anyhow::bail!("No headings or checklist items found in Markdown source.");
+    }
+
+    let mut storage = repo.open_storage()?;
+    let existing = storage.load_all()?;
+    markdown::preserve_existing(&mut drafts, &existing);
+    let mut plan = plan_import(&existing, markdown::SOURCE_KEY, drafts, false);
+    warnings.append(&mut plan.warnings);
+    plan.warnings = warnings;
+
+    if !dry_run {
+        apply_import(&mut storage, &plan)?;
+    }
+
+    report_import(&source_path, "markdown", &plan, skipped, dry_run)
+}
+
+/// Imports Pearls from a CSV file, such as a Jira export.
+///
+/// The mapping file names the column of each field and translates status and
+/// priority names; without one, the columns written by `prl export csv` are
+/// expected. The source ID column is recorded in the mapping's `source_key`
+/// metadata key, so importing the file again updates the same Pearls.
+///
+/// # Arguments
+///
+/// * `path` - Path to the CSV file
+/// * `mapping` - Optional path to a TOML mapping file
+/// * `dry_run` - Whether to report the planned changes without writing them
+///
+/// # Returns
+///
+/// Ok if import succeeds, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The repository is not initialized
+/// - The CSV or mapping file cannot be read or is invalid
+/// - The destination file cannot be written
+pub fn import_csv(path: String, mapping: Option<String>, dry_run: bool) -> Result<()> {
+    let repo = RepoContext::discover(None)?;
+
+    let csv_path = PathBuf::from(path);
+    if !csv_path.exists() {
+        anyhow::bail!("CSV file not found: {}", csv_path.display());
+    }
+    let mapping = match mapping {
+        Some(mapping) => CsvMapping::from_toml(&std::fs::read_to_string(&mapping)?)?,
+        None => CsvMapping::default(),
+    };
+
+    let author = crate::commands::create::get_default_author().unwrap_or_else(|| "csv".to_string());
+    let parsed = csv::parse_csv(std::fs::File::open(&csv_path)?, &mapping, &author)?;
+    if parsed.drafts.is_empty() {
+        anyhow::bail!("No rows found in CSV file.");
+    }
+
+    let mut storage = repo.open_storage()?;
+    let mut plan = plan_import(
+        &storage.load_all()?,
+        &mapping.source_key,
+        parsed.drafts,
+        true,
+    );
+    let mut warnings = parsed.warnings;
+    warnings.append(&mut plan.warnings);
+    plan.warnings = warnings;
+
+    if !dry_run {
+        apply_import(&mut storage, &plan)?;
+    }
+
+    report_import(&csv_path, "csv", &plan, parsed.skipped, dry_run)
+}
+
+/// Prints the outcome of an import.
+fn report_import(
+    source: &Path,
+    format: &str,
+    plan: &ImportPlan,
+    skipped: usize,
+    dry_run: bool,
+) -> Result<()> {
+    let created = plan.count(ImportAction::Create);
+    let updated = plan.count(ImportAction::Update);
+    let unchanged = plan.count(ImportAction::Unchanged);
 
     if is_json_output() {
         println!(
@@ -91,16 +267,55 @@ pub fn import_beads(path: String) -> Result<()> {
             serde_json::to_string_pretty(&serde_json::json!({
                 "status": "ok",
                 "action": "import",
-                "source": beads_path.display().to_string(),
-                "imported": pearls.len(),
-                "skipped": skipped
+                "format": format,
+                "source": source.display().to_string(),
+                "dry_run": dry_run,
+                "imported": created + updated,
+                "created": created,
+                "updated": updated,
+                "unchanged": unchanged,
+                "skipped": skipped,
+                "items": plan.entries.iter().map(|entry| serde_json::json!({
+                    "source_id": entry.source_id,
+                    "id": entry.pearl.id,
+                    "action": entry.action,
+                    "title": entry.pearl.title
+                })).collect::<Vec<_>>(),
+                "warnings": plan.warnings
             }))?
         );
-    } else {
-        println!("Imported Pearls: {}", pearls.len());
-        if skipped > 0 {
-            println!("Skipped entries: {}", skipped);
+        return Ok(());
+    }
+
+    for warning in &plan.warnings {
+        eprintln!("Warning: {}", warning);
+    }
+
+    if dry_run {
+        for entry in &plan.entries {
+            let marker = match entry.action {
+                ImportAction::Create => '+',
+                ImportAction::Update => '~',
+                ImportAction::Unchanged => '=',
+            };
+            println!(
+                "{} {} -> {} {}",
+                marker, entry.source_id, entry.pearl.id, entry.pearl.title
+            );
         }
+        println!(
+            "Dry run: would create {}, update {}, leave {} unchanged",
+            created, updated, unchanged
+        );
+    } else {
+        println!("Imported Pearls: {}", created + updated);
+        println!(
+            "  Created: {}, Updated: {}, Unchanged: {}",
+            created, updated, unchanged
+        );
+    }
+    if skipped > 0 {
+        println!("Skipped entries: {}", skipped);
     }
 
     Ok(())
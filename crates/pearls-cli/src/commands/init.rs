@@ -3,12 +3,13 @@
 //! Implementation of the `prl init` command.
 //!
 //! Initializes a new Pearls repository by creating the `.pearls` directory,
-//! initializing the JSONL file, and setting up Git integration.
+//! initializing the issue storage, and setting up Git integration.
 
 use crate::output_mode::is_json_output;
 use anyhow::Result;
 use git2::Repository;
-use pearls_core::Config;
+use pearls_app::RepoContext;
+use pearls_core::{Config, StorageLayout};
 use std::fs;
 use std::path::Path;
 
@@ -25,21 +26,16 @@ use std::path::Path;
 ///
 /// Returns an error if:
 /// - The `.pearls` directory cannot be created
-/// - The `issues.jsonl` file cannot be created
+/// - The `issues.jsonl` file or `issues/` directory cannot be created
 /// - The configuration file cannot be written
 /// - Git configuration cannot be updated
 pub fn execute() -> Result<()> {
-    let pearls_dir = Path::new(".pearls");
+    let repo = RepoContext::init_target(None)?;
+    let pearls_dir = repo.pearls_dir();
 
     // Create .pearls directory (ignore if already exists)
     if !pearls_dir.exists() {
-        fs::create_dir(pearls_dir)?;
-    }
-
-    // Initialize empty issues.jsonl file (only if it doesn't exist)
-    let issues_path = pearls_dir.join("issues.jsonl");
-    if !issues_path.exists() {
-        fs::File::create(&issues_path)?;
+        fs::create_dir_all(pearls_dir)?;
     }
 
     // Create default config.toml (only if it doesn't exist)
@@ -49,8 +45,26 @@ pub fn execute() -> Result<()> {
         config.save(pearls_dir)?;
     }
 
+    // Initialize empty issue storage for the configured layout (only if it doesn't exist)
+    let issues_file = match Config::load(pearls_dir)?.layout {
+        StorageLayout::Jsonl => {
+            let issues_path = pearls_dir.join("issues.jsonl");
+            if !issues_path.exists() {
+                fs::File::create(&issues_path)?;
+            }
+            ".pearls/issues.jsonl"
+        }
+        StorageLayout::Dir => {
+            fs::create_dir_all(pearls_dir.join("issues"))?;
+            ".pearls/issues/"
+        }
+    };
+
+    // Keep the local index and query cache out of version control
+    repo.ignore_local_files()?;
+
     // Configure Git merge driver and hooks
-    setup_git_integration()?;
+    setup_git_integration(repo.root())?;
 
     if is_json_output() {
         println!(
@@ -58,25 +72,38 @@ pub fn execute() -> Result<()> {
             serde_json::to_string_pretty(&serde_json::json!({
                 "status": "ok",
                 "action": "init",
-                "path": ".pearls",
-                "files": [".pearls/issues.jsonl", ".pearls/config.toml"],
-                "git_merge_driver_configured": true
+                "path": pearls_dir.display().to_string(),
+                "files": [issues_file, ".pearls/config.toml", ".pearls/.gitignore"],
+                "git_merge_driver_configured": true,
+                "git_hooks_installed": true
             }))?
         );
     } else {
-        println!("✓ Pearls repository initialized at .pearls/");
-        println!("  - Created .pearls/issues.jsonl");
+        println!(
+            "✓ Pearls repository initialized at {}",
+            pearls_dir.display()
+        );
+        println!("  - Created {}", issues_file);
         println!("  - Created .pearls/config.toml");
+        println!("  - Created .pearls/.gitignore");
         println!("  - Configured Git merge driver");
+        println!("  - Installed Git hooks");
     }
 
     Ok(())
 }
 
+/// Command Git runs for the Pearls merge driver.
+pub(crate) const MERGE_DRIVER: &str = "prl merge %O %A %B";
+
 /// Sets up Git integration for Pearls.
 ///
 /// Configures the custom merge driver and installs Git hooks.
 ///
+/// # Arguments
+///
+/// * `root` - The directory holding `.pearls`
+///
 /// # Returns
 ///
 /// Ok if Git integration was set up successfully, Err otherwise.
@@ -84,39 +111,41 @@ pub fn execute() -> Result<()> {
 /// # Errors
 ///
 /// Returns an error if Git operations fail.
-fn setup_git_integration() -> Result<()> {
-    // Configure merge driver in .git/config
-    let repo = Repository::discover(".")
+fn setup_git_integration(root: &Path) -> Result<()> {
+    let repo = Repository::discover(root)
         .map_err(|_| anyhow::anyhow!("Not a git repository. Run 'git init' first."))?;
-    let mut config = repo.config()?;
-    config.set_str("merge.pearls.name", "Pearls JSONL merge driver")?;
-    config.set_str("merge.pearls.driver", "prl merge %O %A %B")?;
+    configure_merge_driver(&repo)?;
 
     // Create .gitattributes file
-    let gitattributes_path = Path::new(".gitattributes");
+    let gitattributes_path = root.join(".gitattributes");
     let gitattributes_content = "issues.jsonl merge=pearls\narchive.jsonl merge=pearls\n";
 
     if !gitattributes_path.exists() {
-        fs::write(gitattributes_path, gitattributes_content)?;
+        fs::write(&gitattributes_path, gitattributes_content)?;
     }
 
-    install_hook(".git/hooks/pre-commit", "prl hooks pre-commit")?;
-    install_hook(".git/hooks/post-merge", "prl hooks post-merge")?;
+    let hooks_dir = pearls_hooks::hooks_dir(root)?;
+    pearls_hooks::install_hooks(&hooks_dir)?;
 
     Ok(())
 }
 
-fn install_hook(path: &str, command: &str) -> Result<()> {
-    let hook_content = format!("#!/bin/sh\n{}\n", command);
-    fs::write(path, hook_content)?;
-
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let mut perms = fs::metadata(path)?.permissions();
-        perms.set_mode(0o755);
-        fs::set_permissions(path, perms)?;
-    }
-
+/// Registers the Pearls merge driver in the repository config.
+///
+/// # Arguments
+///
+/// * `repo` - The Git repository
+///
+/// # Returns
+///
+/// Ok if the config was written, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if the Git config cannot be updated.
+pub(crate) fn configure_merge_driver(repo: &Repository) -> Result<()> {
+    let mut config = repo.config()?;
+    config.set_str("merge.pearls.name", "Pearls JSONL merge driver")?;
+    config.set_str("merge.pearls.driver", MERGE_DRIVER)?;
     Ok(())
 }
@@ -6,8 +6,8 @@
 
 use crate::output_mode::is_json_output;
 use anyhow::Result;
-use pearls_core::{identity, DepType, Dependency, IssueGraph, Storage};
-use std::path::Path;
+use pearls_app::RepoContext;
+use pearls_core::{identity, DepType, Dependency, IssueGraph};
 
 /// Creates a dependency link between two Pearls.
 ///
@@ -30,13 +30,8 @@ use std::path::Path;
 /// - The dependency already exists
 /// - The file cannot be written
 pub fn execute(from: String, to: String, dep_type: String) -> Result<()> {
-    let pearls_dir = Path::new(".pearls");
-
-    if !pearls_dir.exists() {
-        anyhow::bail!("Pearls repository not initialized. Run 'prl init' first.");
-    }
-
-    let mut storage = Storage::new(pearls_dir.join("issues.jsonl"))?;
+    let repo = RepoContext::discover(None)?;
+    let mut storage = repo.open_storage()?;
     let mut pearls = storage.load_all()?;
 
     let from_id = resolve_id(&from, &pearls)?;
@@ -7,10 +7,17 @@
 
 use crate::OutputFormatter;
 use anyhow::Result;
-use pearls_app::{list_pearls, parse_dep_type, parse_status, ListOptions, RepoContext};
+use pearls_app::{
+    list_pearls, load_candidates, parse_dep_type, parse_status, ListOptions, QueryCache,
+    RepoContext,
+};
 
 /// Lists Pearls with optional filtering and sorting.
 ///
+/// When `use_cache` is enabled, the filters are answered by the SQLite query
+/// cache instead of scanning the JSONL files. Without `--status` or `--sort`,
+/// the `ui.list_status` and `ui.list_sort` settings apply.
+///
 /// # Arguments
 ///
 /// * `status_filter` - Optional status filter
@@ -46,27 +53,11 @@ pub fn execute(
     formatter: &dyn OutputFormatter,
 ) -> Result<()> {
     let repo = RepoContext::discover(None)?;
-
-    let storage = repo.open_storage()?;
-    let mut pearls = storage.load_all()?;
-
-    if include_archived {
-        if let Some(archive_storage) = repo.open_archive_storage()? {
-            if let Ok(archived) = archive_storage.load_all() {
-                let mut archived = archived;
-                for pearl in &mut archived {
-                    pearl
-                        .metadata
-                        .insert("archived".to_string(), serde_json::Value::Bool(true));
-                }
-                pearls.extend(archived);
-            }
-        }
-    }
+    let config = repo.load_config()?;
 
     let status_filter = match status_filter {
         Some(status) => Some(parse_status(&status)?),
-        None => None,
+        None => config.ui.list_status,
     };
 
     let dep_type_filter = match dep_type_filter {
@@ -84,9 +75,30 @@ pub fn execute(
         created_before,
         updated_after,
         updated_before,
-        sort: sort_field,
+        sort: sort_field.or_else(|| config.ui.list_sort.clone()),
     };
 
+    let use_cache = config.use_cache;
+    let mut pearls = if use_cache {
+        QueryCache::open(&repo)?.list(&options, include_archived)?
+    } else {
+        load_candidates(&repo.open_storage()?, &options)?
+    };
+
+    if include_archived && !use_cache {
+        if let Some(archive_storage) = repo.open_archive_storage()? {
+            if let Ok(archived) = archive_storage.load_all() {
+                let mut archived = archived;
+                for pearl in &mut archived {
+                    pearl
+                        .metadata
+                        .insert("archived".to_string(), serde_json::Value::Bool(true));
+                }
+                pearls.extend(archived);
+            }
+        }
+    }
+
     let pearls = list_pearls(pearls, &options);
 
     println!("{}", formatter.format_list(&pearls));
@@ -6,8 +6,8 @@
 
 use crate::output_mode::is_json_output;
 use anyhow::Result;
-use pearls_core::{identity, Storage};
-use std::path::Path;
+use pearls_app::RepoContext;
+use pearls_core::identity;
 
 /// Gets a metadata value for a Pearl.
 ///
@@ -27,12 +27,8 @@ use std::path::Path;
 /// - Pearl is not found
 /// - Metadata key does not exist
 pub fn get(id: String, key: String) -> Result<()> {
-    let pearls_dir = Path::new(".pearls");
-    if !pearls_dir.exists() {
-        anyhow::bail!("Pearls repository not initialized. Run 'prl init' first.");
-    }
-
-    let mut storage = Storage::new(pearls_dir.join("issues.jsonl"))?;
+    let repo = RepoContext::discover(None)?;
+    let mut storage = repo.open_storage()?;
     let pearls = storage.load_all()?;
     let full_id = identity::resolve_partial_id(&id, &pearls)?;
     let pearl = storage.load_by_id(&full_id)?;
@@ -78,12 +74,8 @@ pub fn get(id: String, key: String) -> Result<()> {
 /// - Pearl is not found
 /// - Metadata value is not valid JSON
 pub fn set(id: String, key: String, value: String) -> Result<()> {
-    let pearls_dir = Path::new(".pearls");
-    if !pearls_dir.exists() {
-        anyhow::bail!("Pearls repository not initialized. Run 'prl init' first.");
-    }
-
-    let mut storage = Storage::new(pearls_dir.join("issues.jsonl"))?;
+    let repo = RepoContext::discover(None)?;
+    let mut storage = repo.open_storage()?;
     let pearls = storage.load_all()?;
     let full_id = identity::resolve_partial_id(&id, &pearls)?;
     let mut pearl = storage.load_by_id(&full_id)?;
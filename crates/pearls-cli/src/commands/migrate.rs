@@ -0,0 +1,314 @@
+// Rust guideline compliant 2026-02-09
+
+//! Implementation of the `prl migrate` command.
+//!
+//! Upgrades stored Pearls to the current schema version and optionally
+//! converts them between the single-file JSONL layout and the
+//! one-file-per-Pearl directory layout. The command reads and writes only the
+//! repository's `config.toml`, so values from other configuration layers never
+//! affect or leak into it.
+
+use crate::output_mode::is_json_output;
+use anyhow::Result;
+use pearls_app::RepoContext;
+use pearls_core::migrations::{migrate_pearl, pending_migrations};
+use pearls_core::{Config, Pearl, Storage, StorageLayout, CURRENT_SCHEMA_VERSION};
+use std::fs;
+use std::path::Path;
+
+/// Outcome of the schema upgrade.
+struct SchemaReport {
+    from: u32,
+    steps: Vec<&'static str>,
+    /// Pearls whose stored form changed, as `(before, after)`.
+    changes: Vec<(Pearl, Pearl)>,
+}
+
+/// Outcome of the layout conversion.
+struct LayoutReport {
+    from: StorageLayout,
+    to: StorageLayout,
+    migrated: usize,
+}
+
+/// Upgrades the repository schema and optionally converts the storage layout.
+///
+/// With `dry_run`, nothing is written; the pending schema steps and a diff of
+/// every Pearl they would change are printed instead.
+///
+/// # Arguments
+///
+/// * `layout` - Target layout (`jsonl` or `dir`), if a conversion is requested
+/// * `dry_run` - Whether to preview changes without applying them
+///
+/// # Returns
+///
+/// Ok if the migration succeeded, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The repository is not initialized
+/// - The repository schema is newer than this build supports
+/// - The layout is unknown or the target already contains Pearls
+/// - The Pearls cannot be read, migrated, or written
+pub fn execute(layout: Option<String>, dry_run: bool) -> Result<()> {
+    let repo = RepoContext::discover(None)?;
+    let pearls_dir = repo.pearls_dir();
+
+    let target = layout.as_deref().map(parse_layout).transpose()?;
+    let mut config = Config::load_repo(pearls_dir)?;
+
+    let schema = upgrade_schema(pearls_dir, &mut config, dry_run)?;
+    let layout = match target {
+        Some(target) => Some(convert_layout(pearls_dir, &mut config, target, dry_run)?),
+        None => None,
+    };
+
+    if is_json_output() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "status": "ok",
+                "action": "migrate",
+                "dry_run": dry_run,
+                "schema": {
+                    "from": schema.from,
+                    "to": CURRENT_SCHEMA_VERSION,
+                    "steps": schema.steps,
+                    "changes": schema.changes.iter().map(|(before, after)| {
+                        serde_json::json!({
+                            "id": after.id,
+                            "before": before,
+                            "after": after
+                        })
+                    }).collect::<Vec<_>>()
+                },
+                "layout": layout.as_ref().map(|layout| serde_json::json!({
+                    "from": layout_name(layout.from),
+                    "to": layout_name(layout.to),
+                    "migrated": layout.migrated
+                }))
+            }))?
+        );
+    } else {
+        report_schema(&schema, dry_run)?;
+        if let Some(layout) = &layout {
+            report_layout(layout, dry_run);
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies pending schema migrations to the active and archived Pearls.
+fn upgrade_schema(pearls_dir: &Path, config: &mut Config, dry_run: bool) -> Result<SchemaReport> {
+    let from = config.schema_version;
+    let steps = pending_migrations(from)?;
+    let mut report = SchemaReport {
+        from,
+        steps: steps.iter().map(|step| step.description).collect(),
+        changes: Vec::new(),
+    };
+    if steps.is_empty() {
+        return Ok(report);
+    }
+
+    let mut active = Storage::open(
+        pearls_dir,
+        &Config {
+            use_index: false,
+            ..config.clone()
+        },
+    )?;
+    let archive_path = pearls_dir.join("archive.jsonl");
+    let mut archive = if archive_path.exists() {
+        Some(Storage::new(archive_path)?)
+    } else {
+        None
+    };
+
+    let mut stores: Vec<&mut Storage> = vec![&mut active];
+    stores.extend(archive.as_mut());
+    for storage in stores {
+        let mut changed = false;
+        let mut migrated = Vec::new();
+        for pearl in storage.load_all()? {
+            let upgraded = migrate_pearl(&pearl, from)?;
+            if upgraded != pearl {
+                changed = true;
+                report.changes.push((pearl, upgraded.clone()));
+            }
+            migrated.push(upgraded);
+        }
+        if changed && !dry_run {
+            storage.save_all(&migrated)?;
+        }
+    }
+
+    if !dry_run {
+        config.schema_version = CURRENT_SCHEMA_VERSION;
+        Config::set_file_value(
+            &pearls_dir.join("config.toml"),
+            "schema_version",
+            &CURRENT_SCHEMA_VERSION.to_string(),
+        )?;
+    }
+    Ok(report)
+}
+
+/// Moves the active Pearls to another storage layout.
+///
+/// The target must be empty. After the Pearls are written, the layout is saved
+/// to the configuration and the old files are removed.
+fn convert_layout(
+    pearls_dir: &Path,
+    config: &mut Config,
+    target: StorageLayout,
+    dry_run: bool,
+) -> Result<LayoutReport> {
+    let source = config.layout;
+    if source == target {
+        return Ok(LayoutReport {
+            from: source,
+            to: target,
+            migrated: 0,
+        });
+    }
+
+    let pearls = Storage::open(
+        pearls_dir,
+        &Config {
+            use_index: false,
+            ..config.clone()
+        },
+    )?
+    .load_all()?;
+
+    let mut target_storage = Storage::open(
+        pearls_dir,
+        &Config {
+            layout: target,
+            use_index: false,
+            ..config.clone()
+        },
+    )?;
+    if !target_storage.is_empty()? {
+        anyhow::bail!(
+            "Cannot migrate: {} already contains Pearls",
+            target_storage.path().display()
+        );
+    }
+
+    if !dry_run {
+        target_storage.save_all(&pearls)?;
+        config.layout = target;
+        Config::set_file_value(
+            &pearls_dir.join("config.toml"),
+            "layout",
+            layout_name(target),
+        )?;
+        remove_layout(pearls_dir, source)?;
+    }
+
+    Ok(LayoutReport {
+        from: source,
+        to: target,
+        migrated: pearls.len(),
+    })
+}
+
+fn report_schema(schema: &SchemaReport, dry_run: bool) -> Result<()> {
+    if schema.steps.is_empty() {
+        println!("Schema is up to date (version {})", CURRENT_SCHEMA_VERSION);
+        return Ok(());
+    }
+
+    if dry_run {
+        println!(
+            "Would upgrade schema from version {} to {}:",
+            schema.from, CURRENT_SCHEMA_VERSION
+        );
+    } else {
+        println!(
+            "✓ Upgraded schema from version {} to {}:",
+            schema.from, CURRENT_SCHEMA_VERSION
+        );
+    }
+    for step in &schema.steps {
+        println!("  - {}", step);
+    }
+
+    if dry_run {
+        for (before, after) in &schema.changes {
+            println!("~ {}", after.id);
+            println!("- {}", serde_json::to_string(before)?);
+            println!("+ {}", serde_json::to_string(after)?);
+        }
+    }
+    println!(
+        "{} Pearls {}",
+        schema.changes.len(),
+        if dry_run { "would change" } else { "changed" }
+    );
+    Ok(())
+}
+
+fn report_layout(layout: &LayoutReport, dry_run: bool) {
+    if layout.from == layout.to {
+        println!(
+            "Repository already uses the {} layout",
+            layout_name(layout.to)
+        );
+    } else if dry_run {
+        println!(
+            "Would migrate {} Pearls from {} to {} layout",
+            layout.migrated,
+            layout_name(layout.from),
+            layout_name(layout.to)
+        );
+    } else {
+        println!(
+            "✓ Migrated {} Pearls from {} to {} layout",
+            layout.migrated,
+            layout_name(layout.from),
+            layout_name(layout.to)
+        );
+    }
+}
+
+fn parse_layout(layout: &str) -> Result<StorageLayout> {
+    match layout {
+        "jsonl" => Ok(StorageLayout::Jsonl),
+        "dir" => Ok(StorageLayout::Dir),
+        other => anyhow::bail!("Unknown layout '{}'. Expected 'jsonl' or 'dir'.", other),
+    }
+}
+
+fn layout_name(layout: StorageLayout) -> &'static str {
+    match layout {
+        StorageLayout::Jsonl => "jsonl",
+        StorageLayout::Dir => "dir",
+    }
+}
+
+/// Removes the files of a layout that is no longer in use.
+fn remove_layout(pearls_dir: &Path, layout: StorageLayout) -> Result<()> {
+    match layout {
+        StorageLayout::Jsonl => {
+            for name in ["issues.jsonl", "index.bin"] {
+                let path = pearls_dir.join(name);
+                if path.exists() {
+                    fs::remove_file(path)?;
+                }
+            }
+        }
+        StorageLayout::Dir => {
+            let path = pearls_dir.join("issues");
+            if path.exists() {
+                fs::remove_dir_all(path)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -7,7 +7,7 @@
 
 use crate::output_mode::is_json_output;
 use anyhow::Result;
-use pearls_app::{ready_queue, RepoContext};
+use pearls_app::{load_ready_queue, QueryCache, RepoContext};
 
 /// Displays the ready queue of unblocked Pearls.
 ///
@@ -27,10 +27,19 @@ use pearls_app::{ready_queue, RepoContext};
 /// - The dependency graph contains cycles
 pub fn execute(limit: Option<usize>) -> Result<()> {
     let repo = RepoContext::discover(None)?;
-    let storage = repo.open_storage()?;
-    let pearls = storage.load_all()?;
+    let ready = if repo.load_config()?.use_cache {
+        let cache = QueryCache::open(&repo)?;
+        (cache.count()? > 0)
+            .then(|| cache.ready_queue())
+            .transpose()?
+    } else {
+        let storage = repo.open_storage()?;
+        (!storage.is_empty()?)
+            .then(|| load_ready_queue(&storage))
+            .transpose()?
+    };
 
-    if pearls.is_empty() {
+    let Some(ready) = ready else {
         if is_json_output() {
             println!(
                 "{}",
@@ -44,9 +53,7 @@ pub fn execute(limit: Option<usize>) -> Result<()> {
             println!("No Pearls found. Create one with 'prl create <title>'");
         }
         return Ok(());
-    }
-
-    let ready = ready_queue(pearls)?;
+    };
 
     if ready.is_empty() {
         if is_json_output() {
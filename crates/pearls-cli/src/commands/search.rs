@@ -0,0 +1,67 @@
+// Rust guideline compliant 2026-02-09
+
+//! Implementation of the `prl search` command.
+//!
+//! Finds Pearls whose ID, title, description, labels, or comments contain a
+//! query string.
+
+use crate::OutputFormatter;
+use anyhow::Result;
+use pearls_app::{search_pearls, QueryCache, RepoContext};
+
+/// Searches Pearls for a case-insensitive substring.
+///
+/// When `use_cache` is enabled, the search is answered by the SQLite query
+/// cache instead of scanning the JSONL files.
+///
+/// # Arguments
+///
+/// * `query` - Text to search for
+/// * `include_archived` - Whether to include archived Pearls
+/// * `limit` - Optional maximum number of results
+/// * `formatter` - The output formatter to use
+///
+/// # Returns
+///
+/// Ok if the results were displayed successfully, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The `.pearls` directory does not exist
+/// - The query is empty
+/// - The files or the cache cannot be read
+pub fn execute(
+    query: String,
+    include_archived: bool,
+    limit: Option<usize>,
+    formatter: &dyn OutputFormatter,
+) -> Result<()> {
+    let repo = RepoContext::discover(None)?;
+
+    let mut results = if repo.load_config()?.use_cache {
+        QueryCache::open(&repo)?.search(&query, include_archived)?
+    } else {
+        let mut pearls = repo.open_storage()?.load_all()?;
+        if include_archived {
+            if let Some(archive_storage) = repo.open_archive_storage()? {
+                let mut archived = archive_storage.load_all()?;
+                for pearl in &mut archived {
+                    pearl
+                        .metadata
+                        .insert("archived".to_string(), serde_json::Value::Bool(true));
+                }
+                pearls.extend(archived);
+            }
+        }
+        search_pearls(pearls, &query)?
+    };
+
+    if let Some(limit) = limit {
+        results.truncate(limit);
+    }
+
+    println!("{}", formatter.format_list(&results));
+
+    Ok(())
+}
@@ -8,8 +8,8 @@
 use crate::output_mode::is_json_output;
 use crate::OutputFormatter;
 use anyhow::Result;
+use pearls_app::RepoContext;
 use pearls_core::{identity, Storage};
-use std::path::Path;
 
 /// Shows details of a Pearl by ID.
 ///
@@ -34,14 +34,8 @@ use std::path::Path;
 /// - The Pearl ID is ambiguous (matches multiple Pearls)
 /// - The file cannot be read
 pub fn execute(id: String, include_archived: bool, formatter: &dyn OutputFormatter) -> Result<()> {
-    let pearls_dir = Path::new(".pearls");
-
-    // Verify .pearls directory exists
-    if !pearls_dir.exists() {
-        anyhow::bail!("Pearls repository not initialized. Run 'prl init' first.");
-    }
-
-    let mut storage = Storage::new(pearls_dir.join("issues.jsonl"))?;
+    let repo = RepoContext::discover(None)?;
+    let mut storage = repo.open_storage()?;
 
     // Try to resolve partial ID
     let full_id = resolve_id(&id, &storage, include_archived)?;
@@ -90,12 +84,28 @@ fn display_pearl(pearl: pearls_core::Pearl, formatter: &dyn OutputFormatter) ->
                 ));
             }
         }
+        let commits = pearls_app::linked_commits(&pearl);
+        if !commits.is_empty() {
+            output.push_str("\nCommits:\n");
+            for commit in &commits {
+                output.push_str(&format!(
+                    "  - {} {} ({}){}\n",
+                    &commit.sha[..commit.sha.len().min(7)],
+                    commit.subject,
+                    commit.author,
+                    if commit.closes { " [closes]" } else { "" }
+                ));
+            }
+        }
         if !pearl.comments.is_empty() {
             output.push_str("\nComments:\n");
             for comment in &pearl.comments {
                 output.push_str(&format!(
-                    "  - {} [{}] {}\n",
-                    comment.id, comment.author, comment.body
+                    "  - {} [{}] {}{}\n",
+                    comment.id,
+                    comment.author,
+                    super::comments::kind_prefix(comment.kind),
+                    comment.body
                 ));
             }
         }
@@ -0,0 +1,148 @@
+// Rust guideline compliant 2026-02-10
+
+//! Implementation of the `prl start` command.
+//!
+//! Starts work on a Pearl: moves it to in progress, assigns it, and creates
+//! and checks out its branch.
+
+use crate::commands::create::get_default_author;
+use crate::output_mode::is_json_output;
+use anyhow::{Context, Result};
+use git2::{build::CheckoutBuilder, BranchType, Repository};
+use pearls_app::RepoContext;
+use pearls_core::{workflow, IssueGraph, Status};
+
+/// Starts work on a Pearl.
+///
+/// Checks out the branch named by the `workflow.branch_template` config,
+/// creating it from `HEAD` if needed. The Pearl is then reloaded from the
+/// branch, moved from Open to InProgress, and assigned, so an existing branch
+/// keeps its own changes to the Pearl. The Pearl becomes the current Pearl of
+/// the clone.
+///
+/// # Arguments
+///
+/// * `id` - The Pearl ID (full or partial)
+/// * `assignee` - Assignee; defaults to the Git user name
+///
+/// # Returns
+///
+/// Ok if work was started, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The repository is not initialized or is not a Git repository
+/// - The Pearl is not found or cannot move to in progress
+/// - The branch name is invalid or the branch cannot be checked out
+pub fn execute(id: String, assignee: Option<String>) -> Result<()> {
+    let repo = RepoContext::discover(None)?;
+    let config = repo.load_config()?;
+    let git = Repository::discover(repo.root())
+        .map_err(|_| anyhow::anyhow!("Not a git repository. Run 'git init' first."))?;
+
+    let all_pearls = repo.open_storage()?.load_all()?;
+    let full_id = pearls_core::identity::resolve_partial_id(&id, &all_pearls)?;
+    let pearl = all_pearls
+        .iter()
+        .find(|pearl| pearl.id == full_id)
+        .cloned()
+        .ok_or_else(|| pearls_core::Error::NotFound(full_id.clone()))?;
+
+    let branch = workflow::branch_name(&config.workflow.branch_template, &pearl);
+    if !git2::Branch::name_is_valid(&branch)? {
+        anyhow::bail!(
+            "Branch template '{}' gives an invalid branch name '{}'",
+            config.workflow.branch_template,
+            branch
+        );
+    }
+    // Refuse before switching branches when the transition cannot succeed here.
+    if pearl.status != Status::InProgress {
+        let graph = IssueGraph::from_pearls(all_pearls)?;
+        pearls_core::fsm::validate_transition(&pearl, Status::InProgress, &graph)?;
+    }
+
+    // The branch may hold its own copy of the Pearls, so they are read again
+    // after the checkout and the update is applied to that copy.
+    let created = checkout_branch(&git, &branch)?;
+    let mut storage = repo.open_storage()?;
+    let all_pearls = storage.load_all()?;
+    let mut pearl = storage
+        .load_by_id(&full_id)
+        .with_context(|| format!("Pearl {} does not exist on branch '{}'", full_id, branch))?;
+
+    if pearl.status != Status::InProgress {
+        let graph = IssueGraph::from_pearls(all_pearls)?;
+        pearls_core::fsm::validate_transition(&pearl, Status::InProgress, &graph)?;
+        pearl.status = Status::InProgress;
+    }
+    if let Some(assignee) = assignee.or_else(get_default_author) {
+        pearl
+            .metadata
+            .insert("assignee".to_string(), serde_json::Value::String(assignee));
+    }
+    pearl.updated_at = pearls_app::unix_timestamp()?;
+    pearl.validate()?;
+    storage.save(&pearl)?;
+    repo.ignore_local_files()?;
+    workflow::write_current(repo.pearls_dir(), Some(&pearl.id))?;
+
+    if is_json_output() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "status": "ok",
+                "action": "start",
+                "branch": branch,
+                "created_branch": created,
+                "pearl": pearl
+            }))?
+        );
+    } else {
+        println!("✓ Started Pearl: {}", pearl.id);
+        println!("  Title: {}", pearl.title);
+        println!("  Status: {:?}", pearl.status);
+        if let Some(assignee) = pearl.metadata.get("assignee").and_then(|v| v.as_str()) {
+            println!("  Assignee: {}", assignee);
+        }
+        let verb = if created { "Created" } else { "Switched to" };
+        println!("  {} branch: {}", verb, branch);
+    }
+
+    Ok(())
+}
+
+/// Checks out a local branch, creating it from `HEAD` when it does not exist.
+///
+/// Returns whether the branch was created.
+fn checkout_branch(git: &Repository, name: &str) -> Result<bool> {
+    let (branch, created) = match git.find_branch(name, BranchType::Local) {
+        Ok(branch) => (branch, false),
+        Err(_) => {
+            let head = git
+                .head()
+                .and_then(|head| head.peel_to_commit())
+                .context("Cannot create a branch before the first commit")?;
+            (git.branch(name, &head, false)?, true)
+        }
+    };
+    let reference = branch.into_reference();
+    let refname = reference
+        .name()
+        .context("Branch reference name is not valid UTF-8")?;
+    if git
+        .head()
+        .ok()
+        .and_then(|head| head.name().map(str::to_string))
+        == Some(refname.into())
+    {
+        return Ok(created);
+    }
+
+    let target = reference.peel_to_commit()?;
+    git.checkout_tree(target.as_object(), Some(CheckoutBuilder::new().safe()))
+        .with_context(|| format!("Cannot check out '{}' over local changes", name))?;
+    git.set_head(refname)?;
+    Ok(created)
+}
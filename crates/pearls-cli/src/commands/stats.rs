@@ -0,0 +1,71 @@
+// Rust guideline compliant 2026-02-09
+
+//! Implementation of the `prl stats` command.
+//!
+//! Displays counts of Pearls by status, priority, and label.
+
+use crate::output_mode::is_json_output;
+use anyhow::Result;
+use pearls_app::{compute_stats, QueryCache, RepoContext};
+
+/// Displays aggregate statistics for the repository.
+///
+/// When `use_cache` is enabled, the statistics are answered by the SQLite
+/// query cache instead of scanning the JSONL files.
+///
+/// # Returns
+///
+/// Ok if the statistics were displayed successfully, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The `.pearls` directory does not exist
+/// - The files or the cache cannot be read
+pub fn execute() -> Result<()> {
+    let repo = RepoContext::discover(None)?;
+
+    let stats = if repo.load_config()?.use_cache {
+        QueryCache::open(&repo)?.stats()?
+    } else {
+        let pearls = repo.open_storage()?.load_all()?;
+        let archived = match repo.open_archive_storage()? {
+            Some(archive_storage) => archive_storage.load_all()?.len(),
+            None => 0,
+        };
+        compute_stats(&pearls, archived)
+    };
+
+    if is_json_output() {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+
+    println!("Pearls Statistics");
+    println!("-----------------");
+    println!("Active: {}", stats.total);
+    println!("Archived: {}", stats.archived);
+    println!("Blocked: {}", stats.blocked);
+
+    println!();
+    println!("By status:");
+    for (status, count) in &stats.by_status {
+        println!("  {:<12} {}", format!("{:?}", status), count);
+    }
+
+    println!();
+    println!("By priority:");
+    for (priority, count) in &stats.by_priority {
+        println!("  P{:<11} {}", priority, count);
+    }
+
+    if !stats.by_label.is_empty() {
+        println!();
+        println!("By label:");
+        for (label, count) in &stats.by_label {
+            println!("  {:<12} {}", label, count);
+        }
+    }
+
+    Ok(())
+}
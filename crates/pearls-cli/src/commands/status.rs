@@ -9,7 +9,8 @@ use crate::output_mode::is_json_output;
 use anyhow::Result;
 use chrono::{Duration, Utc};
 use git2::{BranchType, Repository};
-use pearls_core::{IssueGraph, Status, Storage};
+use pearls_app::RepoContext;
+use pearls_core::{IssueGraph, Status};
 use std::path::Path;
 
 /// Executes the status command.
@@ -29,16 +30,13 @@ use std::path::Path;
 /// - Git repository discovery fails
 /// - The issues file cannot be read
 pub fn execute(detailed: bool) -> Result<()> {
-    let pearls_dir = Path::new(".pearls");
-    if !pearls_dir.exists() {
-        anyhow::bail!("Pearls repository not initialized. Run 'prl init' first.");
-    }
+    let repo = RepoContext::discover(None)?;
 
-    let storage = Storage::new(pearls_dir.join("issues.jsonl"))?;
+    let storage = repo.open_storage()?;
     let pearls = storage.load_all()?;
     let graph = IssueGraph::from_pearls(pearls.clone())?;
 
-    let git_status = collect_git_status()?;
+    let git_status = collect_git_status(repo.root())?;
     let p0_open = pearls
         .iter()
         .filter(|pearl| pearl.priority == 0 && pearl.status != Status::Closed)
@@ -133,8 +131,8 @@ struct GitStatusSummary {
     tests_status: Option<String>,
 }
 
-fn collect_git_status() -> Result<GitStatusSummary> {
-    let repo = Repository::discover(".")?;
+fn collect_git_status(root: &Path) -> Result<GitStatusSummary> {
+    let repo = Repository::discover(root)?;
     let is_clean = working_tree_is_clean(&repo)?;
 
     let sync_status = resolve_sync_status(&repo);
@@ -8,8 +8,8 @@ use crate::git::ensure_clean_working_tree_for_sync;
 use crate::output_mode::is_json_output;
 use anyhow::Result;
 use git2::{Cred, FetchOptions, PushOptions, RemoteCallbacks, Repository, Signature};
-use pearls_core::{IssueGraph, Storage};
-use std::path::Path;
+use pearls_app::RepoContext;
+use pearls_core::IssueGraph;
 
 /// Syncs the repository with the remote using pull --rebase semantics.
 ///
@@ -28,10 +28,7 @@ use std::path::Path;
 /// - Git operations fail
 /// - Integrity checks fail after merge
 pub fn execute(dry_run: bool) -> Result<()> {
-    let pearls_dir = Path::new(".pearls");
-    if !pearls_dir.exists() {
-        anyhow::bail!("Pearls repository not initialized. Run 'prl init' first.");
-    }
+    let pearls = RepoContext::discover(None)?;
 
     if dry_run {
         if is_json_output() {
@@ -50,7 +47,7 @@ pub fn execute(dry_run: bool) -> Result<()> {
         return Ok(());
     }
 
-    let repo = Repository::discover(".")?;
+    let repo = Repository::discover(pearls.root())?;
     ensure_clean_working_tree_for_sync(&repo)?;
     let branch_name = current_branch_name(&repo)?;
 
@@ -59,7 +56,7 @@ pub fn execute(dry_run: bool) -> Result<()> {
         attempts += 1;
         fetch_origin(&repo)?;
         rebase_onto_upstream(&repo, &branch_name)?;
-        run_integrity_checks(pearls_dir)?;
+        run_integrity_checks(&pearls)?;
         if push_origin(&repo, &branch_name).is_ok() {
             break;
         }
@@ -165,8 +162,8 @@ fn push_origin(repo: &Repository, branch_name: &str) -> Result<()> {
     Ok(())
 }
 
-fn run_integrity_checks(pearls_dir: &Path) -> Result<()> {
-    let storage = Storage::new(pearls_dir.join("issues.jsonl"))?;
+fn run_integrity_checks(repo: &RepoContext) -> Result<()> {
+    let storage = repo.open_storage()?;
     let pearls = storage.load_all()?;
     let _graph = IssueGraph::from_pearls(pearls.clone())?;
     let ids: std::collections::HashSet<String> = pearls.iter().map(|p| p.id.clone()).collect();
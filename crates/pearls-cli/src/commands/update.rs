@@ -7,8 +7,8 @@
 
 use crate::output_mode::is_json_output;
 use anyhow::Result;
+use pearls_app::RepoContext;
 use pearls_core::Storage;
-use std::path::Path;
 
 /// Updates a Pearl with the specified field changes.
 ///
@@ -44,15 +44,10 @@ pub fn execute(
     add_labels: Vec<String>,
     remove_labels: Vec<String>,
 ) -> Result<()> {
-    let pearls_dir = Path::new(".pearls");
-
-    // Verify .pearls directory exists
-    if !pearls_dir.exists() {
-        anyhow::bail!("Pearls repository not initialized. Run 'prl init' first.");
-    }
+    let repo = RepoContext::discover(None)?;
 
     // Load all Pearls to resolve partial ID
-    let mut storage = Storage::new(pearls_dir.join("issues.jsonl"))?;
+    let mut storage = repo.open_storage()?;
     let all_pearls = storage.load_all()?;
 
     // Resolve partial ID
@@ -5,6 +5,8 @@
 //! Command-line interface for the Pearls issue tracking system.
 
 use clap::Parser;
+use pearls_app::import::forge::Forge;
+use pearls_core::ColorMode;
 
 pub mod commands;
 pub mod git;
@@ -42,10 +44,14 @@ struct Cli {
     #[arg(long, global = true)]
     absolute_time: bool,
 
-    /// Custom config file path
+    /// Config file applied over all other configuration
     #[arg(long, global = true)]
     config: Option<String>,
 
+    /// Directory to discover the repository from instead of the current one
+    #[arg(long, global = true)]
+    repo: Option<String>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -152,6 +158,23 @@ enum Commands {
         limit: Option<usize>,
     },
 
+    /// Search Pearls by text
+    Search {
+        /// Text to search for in IDs, titles, descriptions, labels, and comments
+        query: String,
+
+        /// Include archived Pearls
+        #[arg(long)]
+        include_archived: bool,
+
+        /// Maximum number of results to show
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+
+    /// Show counts by status, priority, and label
+    Stats,
+
     /// Update a Pearl
     Update {
         /// Pearl ID
@@ -192,6 +215,26 @@ enum Commands {
         id: String,
     },
 
+    /// Start a Pearl and check out its branch
+    Start {
+        /// Pearl ID
+        id: String,
+
+        /// Assignee (defaults to git user.name)
+        #[arg(long)]
+        assignee: Option<String>,
+    },
+
+    /// Finish the Pearl of the current branch
+    Finish {
+        /// Pearl ID (defaults to the Pearl of the current branch)
+        id: Option<String>,
+
+        /// Mark the Pearl for review instead of closing it
+        #[arg(long)]
+        review: bool,
+    },
+
     /// Link two Pearls with a dependency
     Link {
         /// Source Pearl ID
@@ -246,6 +289,23 @@ enum Commands {
         fix: bool,
     },
 
+    /// Upgrade the schema and optionally convert the storage layout
+    Migrate {
+        /// Target layout (jsonl, dir)
+        #[arg(long, value_parser = ["jsonl", "dir"])]
+        layout: Option<String>,
+
+        /// Preview changes without applying
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Manage the SQLite query cache
+    Cache {
+        #[command(subcommand)]
+        action: commands::cache::CacheAction,
+    },
+
     /// Run Pearls Git hooks
     Hooks {
         #[command(subcommand)]
@@ -270,10 +330,6 @@ enum Commands {
 
     /// Run Pearls MCP server over stdio
     Mcp {
-        /// Repository root path
-        #[arg(long)]
-        repo: Option<String>,
-
         /// Disable mutating tools
         #[arg(long)]
         read_only: bool,
@@ -285,6 +341,14 @@ enum Commands {
         /// Optional log file path
         #[arg(long)]
         log_file: Option<String>,
+
+        /// Serve the streamable HTTP transport on this address instead of stdio
+        #[arg(long, value_name = "ADDR")]
+        http: Option<std::net::SocketAddr>,
+
+        /// Access policy file to use instead of [mcp.policy] in the config
+        #[arg(long, value_name = "FILE")]
+        policy: Option<String>,
     },
 
     /// Import from other formats
@@ -293,6 +357,41 @@ enum Commands {
         source: ImportSource,
     },
 
+    /// Export Pearls to other formats
+    Export {
+        #[command(subcommand)]
+        format: ExportFormat,
+    },
+
+    /// Generate a changelog from closed Pearls
+    Changelog {
+        /// Start of the range: a Git ref or a date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        since: String,
+
+        /// End of the range: a Git ref or a date (defaults to HEAD plus uncommitted changes)
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Changelog style
+        #[arg(long, default_value = "markdown", value_parser = ["markdown", "keep-a-changelog"])]
+        style: String,
+
+        /// Release name used in the heading
+        #[arg(long, default_value = "Unreleased")]
+        version: String,
+
+        /// Path to output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Show and change configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
     /// Manage metadata
     Meta {
         #[command(subcommand)]
@@ -308,10 +407,126 @@ enum Commands {
 
 #[derive(Debug, clap::Subcommand)]
 enum ImportSource {
-    /// Import from Beads format
+    /// Import from a Beads JSONL export
     Beads {
         /// Path to Beads JSONL file
         path: String,
+
+        /// Preview changes without applying
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Import from a GitHub issues JSON export
+    Github {
+        /// Path to the exported JSON file
+        path: String,
+
+        /// Preview changes without applying
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Import from a GitLab issues JSON export
+    Gitlab {
+        /// Path to the exported JSON file
+        path: String,
+
+        /// Preview changes without applying
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Import from a CSV file, such as a Jira export
+    Csv {
+        /// Path to the CSV file
+        path: String,
+
+        /// TOML file mapping columns, statuses, and priorities
+        #[arg(long)]
+        mapping: Option<String>,
+
+        /// Preview changes without applying
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Import a Markdown TODO file or a directory of plans
+    Markdown {
+        /// Path to a Markdown file or a directory of .md files
+        path: String,
+
+        /// Preview changes without applying
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum ExportFormat {
+    /// Export a CSV table
+    Csv {
+        /// Comma-separated columns, e.g. id,title,status,blocked_by,meta.owner
+        #[arg(long)]
+        columns: Option<String>,
+
+        /// Path to output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Export a static HTML site
+    Html {
+        /// Directory to write the site to
+        #[arg(long, default_value = "site")]
+        out: String,
+    },
+
+    /// Export a Markdown plan
+    Markdown {
+        /// Path to output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum ConfigAction {
+    /// Show a value, or every value under a table, and where it came from
+    Get {
+        /// Dotted key, such as user.name
+        key: String,
+
+        /// Read the global config file only
+        #[arg(long)]
+        global: bool,
+    },
+
+    /// Set a value in .pearls/config.toml or the global config file
+    Set {
+        /// Dotted key, such as user.name
+        key: String,
+
+        /// Value (TOML, or a plain string)
+        value: String,
+
+        /// Write the global config file
+        #[arg(long)]
+        global: bool,
+    },
+
+    /// List every value and where it came from
+    List {
+        /// List the global config file only
+        #[arg(long)]
+        global: bool,
+    },
+
+    /// Open a config file in an editor
+    Edit {
+        /// Edit the global config file
+        #[arg(long)]
+        global: bool,
     },
 }
 
@@ -352,6 +567,10 @@ enum CommentAction {
         /// Comment author
         #[arg(long)]
         author: Option<String>,
+
+        /// Comment kind
+        #[arg(long, value_parser = ["note", "progress", "decision", "blocker", "handoff"])]
+        kind: Option<String>,
     },
 
     /// List comments for a Pearl
@@ -372,24 +591,37 @@ enum CommentAction {
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    if let Some(path) = &cli.config {
+        std::env::set_var(pearls_core::CONFIG_ENV, path);
+    }
+    if let Some(path) = &cli.repo {
+        std::env::set_var(pearls_app::REPO_ENV, path);
+    }
 
-    // Determine output format and color usage
-    let use_color = !cli.no_color && should_use_color();
+    // Determine output format and color usage, falling back to the config
+    let config = load_config();
+    let use_color = !cli.no_color
+        && match config.ui.color {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => should_use_color(),
+        };
     let format = match cli.format {
         Some(OutputFormat::Json) => "json",
         Some(OutputFormat::Table) => "table",
         Some(OutputFormat::Plain) => "plain",
-        None => {
-            if cli.json {
-                "json"
-            } else {
-                "table"
-            }
-        }
+        None if cli.json => "json",
+        None => match config.output_format {
+            pearls_core::OutputFormat::Json => "json",
+            pearls_core::OutputFormat::Table => "table",
+            pearls_core::OutputFormat::Plain => "plain",
+        },
     };
     let formatter = create_formatter(format, use_color, cli.absolute_time);
     set_json_output(format == "json");
 
+    let notify = cli.command.as_ref().is_some_and(changes_pearls);
+
     match cli.command {
         Some(Commands::Init) => {
             commands::init::execute()?;
@@ -448,6 +680,16 @@ fn main() -> anyhow::Result<()> {
         Some(Commands::Ready { limit }) => {
             commands::ready::execute(limit)?;
         }
+        Some(Commands::Search {
+            query,
+            include_archived,
+            limit,
+        }) => {
+            commands::search::execute(query, include_archived, limit, formatter.as_ref())?;
+        }
+        Some(Commands::Stats) => {
+            commands::stats::execute()?;
+        }
         Some(Commands::Update {
             id,
             title,
@@ -472,6 +714,12 @@ fn main() -> anyhow::Result<()> {
         Some(Commands::Close { id }) => {
             commands::close::execute(id)?;
         }
+        Some(Commands::Start { id, assignee }) => {
+            commands::start::execute(id, assignee)?;
+        }
+        Some(Commands::Finish { id, review }) => {
+            commands::finish::execute(id, review)?;
+        }
         Some(Commands::Link { from, to, dep_type }) => {
             commands::link::execute(from, to, dep_type)?;
         }
@@ -493,6 +741,12 @@ fn main() -> anyhow::Result<()> {
         Some(Commands::Doctor { fix }) => {
             commands::doctor::execute(fix)?;
         }
+        Some(Commands::Migrate { layout, dry_run }) => {
+            commands::migrate::execute(layout, dry_run)?;
+        }
+        Some(Commands::Cache { action }) => {
+            commands::cache::execute(action)?;
+        }
         Some(Commands::Hooks { action }) => {
             commands::hooks::execute(action)?;
         }
@@ -505,22 +759,75 @@ fn main() -> anyhow::Result<()> {
             commands::merge::execute(ancestor, current, other, output)?;
         }
         Some(Commands::Mcp {
-            repo,
             read_only,
             log_level,
             log_file,
+            http,
+            policy,
         }) => {
             let options = pearls_mcp::McpOptions {
-                repo: repo.map(std::path::PathBuf::from),
+                repo: cli.repo.map(std::path::PathBuf::from),
                 read_only,
                 log_level,
                 log_file: log_file.map(std::path::PathBuf::from),
+                http,
+                policy: policy.map(std::path::PathBuf::from),
             };
             pearls_mcp::run(options).map_err(|err| anyhow::anyhow!("{err}"))?;
         }
         Some(Commands::Import { source }) => match source {
-            ImportSource::Beads { path } => {
-                commands::import::import_beads(path)?;
+            ImportSource::Beads { path, dry_run } => {
+                commands::import::import_beads(path, dry_run)?;
+            }
+            ImportSource::Github { path, dry_run } => {
+                commands::import::import_forge(Forge::GitHub, path, dry_run)?;
+            }
+            ImportSource::Gitlab { path, dry_run } => {
+                commands::import::import_forge(Forge::GitLab, path, dry_run)?;
+            }
+            ImportSource::Csv {
+                path,
+                mapping,
+                dry_run,
+            } => {
+                commands::import::import_csv(path, mapping, dry_run)?;
+            }
+            ImportSource::Markdown { path, dry_run } => {
+                commands::import::import_markdown(path, dry_run)?;
+            }
+        },
+        Some(Commands::Export { format }) => match format {
+            ExportFormat::Csv { columns, output } => {
+                commands::export::csv(columns, output)?;
+            }
+            ExportFormat::Html { out } => {
+                commands::export::html(out)?;
+            }
+            ExportFormat::Markdown { output } => {
+                commands::export::markdown(output)?;
+            }
+        },
+        Some(Commands::Changelog {
+            since,
+            until,
+            style,
+            version,
+            output,
+        }) => {
+            commands::changelog::execute(since, until, style, version, output)?;
+        }
+        Some(Commands::Config { action }) => match action {
+            ConfigAction::Get { key, global } => {
+                commands::config::get(key, global)?;
+            }
+            ConfigAction::Set { key, value, global } => {
+                commands::config::set(key, value, global)?;
+            }
+            ConfigAction::List { global } => {
+                commands::config::list(global)?;
+            }
+            ConfigAction::Edit { global } => {
+                commands::config::edit(global)?;
             }
         },
         Some(Commands::Meta { action }) => match action {
@@ -532,8 +839,13 @@ fn main() -> anyhow::Result<()> {
             }
         },
         Some(Commands::Comments { action }) => match action {
-            CommentAction::Add { id, body, author } => {
-                commands::comments::add(id, body, author)?;
+            CommentAction::Add {
+                id,
+                body,
+                author,
+                kind,
+            } => {
+                commands::comments::add(id, body, author, kind)?;
             }
             CommentAction::List { id } => {
                 commands::comments::list(id, format == "json")?;
@@ -547,5 +859,91 @@ fn main() -> anyhow::Result<()> {
         }
     }
 
+    if notify {
+        notify_events();
+    }
+
     Ok(())
 }
+
+/// Loads the configuration for output settings.
+///
+/// Errors fall back to the defaults; commands that read the config report
+/// them.
+fn load_config() -> pearls_core::Config {
+    let repo = pearls_app::RepoContext::discover(None).ok();
+    pearls_core::Config::load_layered(repo.as_ref().map(pearls_app::RepoContext::pearls_dir))
+        .map(|layered| layered.config)
+        .unwrap_or_default()
+}
+
+/// Time a command may spend delivering events before giving up on them.
+const EVENT_DELIVERY_BUDGET: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Whether a command may change Pearls, so that events are dispatched after it.
+///
+/// Read-only commands and the commit-time hooks skip dispatch so they are
+/// never slowed by deliveries. The MCP server dispatches events after each of
+/// its own mutations.
+fn changes_pearls(command: &Commands) -> bool {
+    use commands::hooks::HookAction;
+    match command {
+        Commands::Show { .. }
+        | Commands::List { .. }
+        | Commands::Ready { .. }
+        | Commands::Search { .. }
+        | Commands::Status { .. }
+        | Commands::Doctor { fix: false }
+        | Commands::Cache { .. }
+        | Commands::Export { .. }
+        | Commands::Changelog { .. }
+        | Commands::Config { .. }
+        | Commands::Mcp { .. }
+        | Commands::Meta {
+            action: MetaAction::Get { .. },
+        }
+        | Commands::Comments {
+            action: CommentAction::List { .. },
+        }
+        | Commands::Hooks {
+            action:
+                HookAction::Install
+                | HookAction::Uninstall
+                | HookAction::PreCommit
+                | HookAction::PrepareCommitMsg { .. }
+                | HookAction::CommitMsg { .. },
+        } => false,
+        Commands::Compact { dry_run, .. }
+        | Commands::Migrate { dry_run, .. }
+        | Commands::Sync { dry_run } => !dry_run,
+        _ => true,
+    }
+}
+
+/// Delivers events for the changes made by a command.
+///
+/// Deliveries stop after [`EVENT_DELIVERY_BUDGET`], and problems are reported
+/// as warnings so they never fail the command.
+fn notify_events() {
+    let Ok(repo) = pearls_app::RepoContext::discover(None) else {
+        return;
+    };
+    match pearls_app::dispatch_events(&repo, Some(EVENT_DELIVERY_BUDGET)) {
+        Ok(report) => {
+            if report.untrusted > 0 {
+                eprintln!(
+                    "Warning: skipped {} event hook command(s) from .pearls/config.toml; \
+                     move them to the global config to run them",
+                    report.untrusted
+                );
+            }
+            if report.failed > 0 {
+                eprintln!(
+                    "Warning: {} event notification(s) failed; see .pearls/events-dead.jsonl",
+                    report.failed
+                );
+            }
+        }
+        Err(err) => eprintln!("Warning: event notifications failed: {}", err),
+    }
+}
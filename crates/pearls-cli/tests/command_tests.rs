@@ -3,6 +3,7 @@
 //! Integration tests for CLI commands.
 
 use git2::Repository;
+use pearls_app::import::forge::Forge;
 use pearls_cli::OutputFormatter;
 use pearls_core::{DepType, IssueGraph, Pearl, Storage};
 use std::fs;
@@ -799,6 +800,78 @@ fn test_doctor_fix_repairs_common_issues() {
     }
 }
 
+#[test]
+fn test_doctor_fix_restores_git_integration() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let _guard = enter_dir(temp_dir.path());
+    init_git_repo(temp_dir.path());
+    init_repo(temp_dir.path());
+    let hooks_dir = temp_dir.path().join(".git/hooks");
+    fs::create_dir_all(&hooks_dir).expect("Failed to create hooks dir");
+    fs::write(hooks_dir.join("pre-commit"), "#!/bin/sh\necho custom\n")
+        .expect("Failed to write hook");
+
+    pearls_cli::commands::doctor::execute(true).expect("Doctor fix failed");
+
+    let repo = Repository::open(temp_dir.path()).expect("Failed to open repo");
+    let driver = repo
+        .config()
+        .expect("Failed to read config")
+        .get_string("merge.pearls.driver")
+        .expect("Merge driver should be configured");
+    assert_eq!(driver, "prl merge %O %A %B");
+    let gitattributes =
+        fs::read_to_string(temp_dir.path().join(".gitattributes")).expect("Missing attributes");
+    assert!(gitattributes.contains("issues.jsonl merge=pearls"));
+    let pre_commit = fs::read_to_string(hooks_dir.join("pre-commit")).expect("Missing hook");
+    assert!(pre_commit.contains("prl hooks pre-commit || exit $?"));
+    assert!(pre_commit.ends_with("echo custom\n"));
+
+    pearls_cli::commands::hooks::execute(pearls_cli::commands::hooks::HookAction::Uninstall)
+        .expect("Uninstall failed");
+    assert_eq!(
+        fs::read_to_string(hooks_dir.join("pre-commit")).expect("Missing hook"),
+        "#!/bin/sh\necho custom\n"
+    );
+    assert!(!hooks_dir.join("post-merge").exists());
+}
+
+#[test]
+fn test_doctor_fix_rebuilds_stale_index() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let _guard = enter_dir(temp_dir.path());
+    let pearls_dir = init_repo(temp_dir.path());
+    let config = pearls_core::Config {
+        use_index: true,
+        ..pearls_core::Config::default()
+    };
+    config.save(&pearls_dir).expect("Failed to save config");
+
+    let issues_path = pearls_dir.join("issues.jsonl");
+    let index_path = pearls_dir.join("index.bin");
+    let mut storage = Storage::with_index(issues_path.clone(), Some(index_path.clone()))
+        .expect("Failed to create storage");
+    let first = pearls_core::Pearl::new("First".to_string(), "alice".to_string());
+    storage.save(&first).expect("Failed to save pearl");
+
+    // Simulate a checkout that changes the file behind the index's back.
+    let second = pearls_core::Pearl::new("Second".to_string(), "bob".to_string());
+    let mut content = fs::read_to_string(&issues_path).expect("Failed to read issues");
+    content.push_str(&serde_json::to_string(&second).unwrap());
+    content.push('\n');
+    fs::write(&issues_path, content).expect("Failed to write issues.jsonl");
+
+    let index = pearls_core::Index::load(index_path.clone()).expect("Failed to load index");
+    assert!(index.is_stale(&issues_path).expect("Failed to check index"));
+
+    pearls_cli::commands::doctor::execute(true).expect("Doctor fix failed");
+
+    let index = pearls_core::Index::load(index_path).expect("Failed to load index");
+    assert!(!index.is_stale(&issues_path).expect("Failed to check index"));
+    assert_eq!(index.len(), 2);
+    assert!(index.get(&second.id).is_some());
+}
+
 #[test]
 fn test_import_beads_writes_issues() {
     let temp_dir = TempDir::new().expect("Failed to create temp directory");
@@ -810,7 +883,7 @@ fn test_import_beads_writes_issues() {
     let line = serde_json::to_string(&pearl).unwrap();
     fs::write(&beads_path, format!("{}\n", line)).expect("Failed to write beads file");
 
-    pearls_cli::commands::import::import_beads(beads_path.to_string_lossy().to_string())
+    pearls_cli::commands::import::import_beads(beads_path.to_string_lossy().to_string(), false)
         .expect("Import failed");
 
     let storage = Storage::new(pearls_dir.join("issues.jsonl")).expect("Failed to create storage");
@@ -819,6 +892,401 @@ fn test_import_beads_writes_issues() {
     assert_eq!(pearls[0].id, pearl.id);
 }
 
+#[test]
+fn test_import_beads_maps_export_and_is_idempotent() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let _guard = enter_dir(temp_dir.path());
+    let pearls_dir = init_repo(temp_dir.path());
+
+    let beads_path = temp_dir.path().join("beads.jsonl");
+    let export = concat!(
+        r#"{"id":"bd-a1b2","title":"Blocker","status":"closed","priority":0,"issue_type":"bug"}"#,
+        "\n",
+        r#"{"id":"bd-c3d4","title":"Blocked","description":"Waits on bd-a1b2.","dependencies":[{"issue_id":"bd-c3d4","depends_on_id":"bd-a1b2","type":"blocks"}]}"#,
+        "\n",
+    );
+    fs::write(&beads_path, export).expect("Failed to write beads file");
+    let path = beads_path.to_string_lossy().to_string();
+
+    pearls_cli::commands::import::import_beads(path.clone(), true).expect("Dry run failed");
+    let storage = Storage::new(pearls_dir.join("issues.jsonl")).expect("Failed to create storage");
+    assert!(storage
+        .load_all()
+        .expect("Failed to load pearls")
+        .is_empty());
+
+    pearls_cli::commands::import::import_beads(path.clone(), false).expect("Import failed");
+    pearls_cli::commands::import::import_beads(path, false).expect("Re-import failed");
+
+    let pearls = storage.load_all().expect("Failed to load pearls");
+    assert_eq!(pearls.len(), 2);
+    let source_id = |pearl: &pearls_core::Pearl| pearl.metadata["beads_id"].clone();
+    let blocker = pearls
+        .iter()
+        .find(|pearl| source_id(pearl) == "bd-a1b2")
+        .expect("Missing blocker");
+    let blocked = pearls
+        .iter()
+        .find(|pearl| source_id(pearl) == "bd-c3d4")
+        .expect("Missing blocked");
+    assert_eq!(blocker.status, pearls_core::Status::Closed);
+    assert_eq!(blocker.priority, 0);
+    assert_eq!(blocked.deps[0].target_id, blocker.id);
+    assert_eq!(blocked.description, format!("Waits on {}.", blocker.id));
+}
+
+#[test]
+fn test_import_github_links_issues_and_is_idempotent() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let _guard = enter_dir(temp_dir.path());
+    let pearls_dir = init_repo(temp_dir.path());
+
+    let export_path = temp_dir.path().join("issues.json");
+    let export = r#"[
+        {"number": 12, "title": "Schema", "state": "closed",
+         "html_url": "https://github.com/o/r/issues/12",
+         "labels": [{"name": "db"}], "user": {"login": "alice"}},
+        {"number": 13, "title": "API", "state": "open",
+         "html_url": "https://github.com/o/r/issues/13",
+         "body": "Blocked by #12", "user": {"login": "bob"},
+         "comments": [{"user": {"login": "alice"}, "body": "On it",
+                       "created_at": "2024-03-01T00:00:00Z"}]}
+    ]"#;
+    fs::write(&export_path, export).expect("Failed to write export");
+    let path = export_path.to_string_lossy().to_string();
+
+    pearls_cli::commands::import::import_forge(Forge::GitHub, path.clone(), false)
+        .expect("Import failed");
+    pearls_cli::commands::import::import_forge(Forge::GitHub, path, false)
+        .expect("Re-import failed");
+
+    let storage = Storage::new(pearls_dir.join("issues.jsonl")).expect("Failed to create storage");
+    let pearls = storage.load_all().expect("Failed to load pearls");
+    assert_eq!(pearls.len(), 2);
+    let by_number = |number: u64| {
+        pearls
+            .iter()
+            .find(|pearl| pearl.metadata["source_number"] == number)
+            .expect("Missing imported issue")
+    };
+    let schema = by_number(12);
+    let api = by_number(13);
+    assert_eq!(
+        schema.metadata["source_url"],
+        "https://github.com/o/r/issues/12"
+    );
+    assert_eq!(schema.status, pearls_core::Status::Closed);
+    assert_eq!(api.deps[0].target_id, schema.id);
+    assert_eq!(api.comments.len(), 1);
+}
+
+#[test]
+fn test_import_markdown_and_export_round_trip() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let _guard = enter_dir(temp_dir.path());
+    let pearls_dir = init_repo(temp_dir.path());
+
+    let todo = "# Launch P1\n\n- [ ] Write docs #docs\n  - [x] Outline\n- [ ] Tag release P0\n";
+    fs::write(temp_dir.path().join("TODO.md"), todo).expect("Failed to write TODO");
+    pearls_cli::commands::import::import_markdown("TODO.md".to_string(), false)
+        .expect("Import failed");
+
+    let storage = Storage::new(pearls_dir.join("issues.jsonl")).expect("Failed to create storage");
+    let pearls = storage.load_all().expect("Failed to load pearls");
+    assert_eq!(pearls.len(), 4);
+    let find = |title: &str| {
+        pearls
+            .iter()
+            .find(|pearl| pearl.title == title)
+            .expect("Missing imported item")
+            .clone()
+    };
+    let launch = find("Launch");
+    let outline = find("Outline");
+    assert_eq!(launch.priority, 1);
+    assert_eq!(outline.status, pearls_core::Status::Closed);
+    assert_eq!(find("Write docs").deps[0].target_id, launch.id);
+    assert_eq!(find("Write docs").labels, vec!["docs"]);
+
+    let plan_path = temp_dir.path().join("plan.md");
+    pearls_cli::commands::export::markdown(Some(plan_path.to_string_lossy().to_string()))
+        .expect("Export failed");
+    let exported = fs::read_to_string(&plan_path).expect("Failed to read plan");
+    assert!(exported.contains(&format!("## Launch P1 <!-- {} -->", launch.id)));
+
+    pearls_cli::commands::import::import_markdown("plan.md".to_string(), false)
+        .expect("Re-import failed");
+    pearls_cli::commands::import::import_markdown("TODO.md".to_string(), false)
+        .expect("Re-import failed");
+    assert_eq!(storage.load_all().expect("Failed to load pearls"), pearls);
+}
+
+#[test]
+fn test_import_jira_csv_with_mapping_and_export_csv() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let _guard = enter_dir(temp_dir.path());
+    let pearls_dir = init_repo(temp_dir.path());
+
+    let mapping_path = temp_dir.path().join("mapping.toml");
+    fs::write(
+        &mapping_path,
+        r#"
+source_key = "jira_key"
+
+[columns]
+id = "Issue key"
+title = "Summary"
+status = "Status"
+priority = "Priority"
+labels = "Labels"
+blocked_by = "Inward issue link (Blocks)"
+
+[columns.metadata]
+assignee = "Assignee"
+
+[status]
+Done = "closed"
+
+[priority]
+High = 1
+"#,
+    )
+    .expect("Failed to write mapping");
+    let csv_path = temp_dir.path().join("jira.csv");
+    fs::write(
+        &csv_path,
+        "Issue key,Summary,Status,Priority,Labels,Labels,Assignee,Inward issue link (Blocks)\n\
+         PROJ-1,Schema,Done,High,db,backend,dana,\n\
+         PROJ-2,API,To Do,Low,,,,PROJ-1\n",
+    )
+    .expect("Failed to write CSV");
+    let csv = csv_path.to_string_lossy().to_string();
+    let mapping = Some(mapping_path.to_string_lossy().to_string());
+
+    pearls_cli::commands::import::import_csv(csv.clone(), mapping.clone(), false)
+        .expect("Import failed");
+    pearls_cli::commands::import::import_csv(csv, mapping, false).expect("Re-import failed");
+
+    let storage = Storage::new(pearls_dir.join("issues.jsonl")).expect("Failed to create storage");
+    let pearls = storage.load_all().expect("Failed to load pearls");
+    assert_eq!(pearls.len(), 2);
+    let by_key = |key: &str| {
+        pearls
+            .iter()
+            .find(|pearl| pearl.metadata["jira_key"] == key)
+            .expect("Missing imported row")
+    };
+    let schema = by_key("PROJ-1");
+    assert_eq!(schema.status, pearls_core::Status::Closed);
+    assert_eq!(schema.priority, 1);
+    assert_eq!(schema.labels, vec!["db", "backend"]);
+    assert_eq!(by_key("PROJ-2").deps[0].target_id, schema.id);
+
+    let out_path = temp_dir.path().join("out.csv");
+    pearls_cli::commands::export::csv(
+        Some("id,title,blocked_by,meta.assignee".to_string()),
+        Some(out_path.to_string_lossy().to_string()),
+    )
+    .expect("Export failed");
+    let exported = fs::read_to_string(&out_path).expect("Failed to read CSV");
+    assert!(exported.starts_with("id,title,blocked_by,meta.assignee\n"));
+    assert!(exported.contains(&format!("{},Schema,,dana", schema.id)));
+}
+
+#[test]
+fn test_export_html_writes_site_with_archive() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let _guard = enter_dir(temp_dir.path());
+    let pearls_dir = init_repo(temp_dir.path());
+
+    let active = Pearl::new("Active".to_string(), "alice".to_string());
+    let mut archived = Pearl::new("Archived".to_string(), "alice".to_string());
+    archived.status = pearls_core::Status::Closed;
+    Storage::new(pearls_dir.join("issues.jsonl"))
+        .expect("Failed to create storage")
+        .save(&active)
+        .expect("Failed to save pearl");
+    Storage::new(pearls_dir.join("archive.jsonl"))
+        .expect("Failed to create archive")
+        .save(&archived)
+        .expect("Failed to save archived pearl");
+
+    pearls_cli::commands::export::html("site".to_string()).expect("Export failed");
+
+    let site = temp_dir.path().join("site");
+    let index = fs::read_to_string(site.join("index.html")).expect("Missing index");
+    assert!(index.contains("Active"));
+    assert!(index.contains("Archived"));
+    assert!(site.join("graph.html").exists());
+    assert!(site.join("style.css").exists());
+    assert!(site.join(format!("pearls/{}.html", active.id)).exists());
+    assert!(site.join(format!("pearls/{}.html", archived.id)).exists());
+}
+
+#[test]
+fn test_changelog_collects_pearls_closed_since_ref() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let _guard = enter_dir(temp_dir.path());
+    init_git_repo(temp_dir.path());
+    let pearls_dir = init_repo(temp_dir.path());
+    let repo = Repository::open(temp_dir.path()).expect("Failed to open repo");
+
+    let mut storage =
+        Storage::new(pearls_dir.join("issues.jsonl")).expect("Failed to create storage");
+    let mut pearl = |id: &str, title: &str, labels: &[&str], closed: bool| {
+        let mut pearl = Pearl::new(title.to_string(), "tester".to_string());
+        pearl.id = id.to_string();
+        pearl.labels = labels.iter().map(|label| label.to_string()).collect();
+        if closed {
+            pearl.status = pearls_core::Status::Closed;
+        }
+        storage.save(&pearl).expect("Failed to save pearl");
+    };
+    pearl("prl-aaa111", "Old fix", &["bug"], true);
+    pearl("prl-bbb222", "Dark mode", &["feature"], false);
+    pearl("prl-ccc333", "Tidy CI", &["no-changelog"], false);
+    add_all_and_commit(&repo, "Release 1.0");
+    let head = repo.head().expect("HEAD").target().expect("HEAD target");
+    repo.reference("refs/tags/v1.0", head, false, "tag")
+        .expect("Failed to tag");
+
+    pearl("prl-bbb222", "Dark mode", &["feature"], true);
+    pearl("prl-ccc333", "Tidy CI", &["no-changelog"], true);
+    add_all_and_commit(&repo, "Close dark mode");
+
+    let out = temp_dir.path().join("CHANGELOG.md");
+    pearls_cli::commands::changelog::execute(
+        "v1.0".to_string(),
+        None,
+        "keep-a-changelog".to_string(),
+        "1.1.0".to_string(),
+        Some(out.to_string_lossy().to_string()),
+    )
+    .expect("Changelog failed");
+    let changelog = fs::read_to_string(&out).expect("Failed to read changelog");
+    assert!(changelog.starts_with("## [1.1.0] - "));
+    assert!(changelog.contains("### Added\n\n- Dark mode (prl-bbb222)\n"));
+    assert!(!changelog.contains("prl-aaa111"));
+    assert!(!changelog.contains("prl-ccc333"));
+
+    fs::create_dir(pearls_dir.join("templates")).expect("Failed to create templates");
+    fs::write(
+        pearls_dir.join("templates/changelog.md"),
+        "{{#sections}}\n{{#entries}}\n{{id}} {{title}}\n{{/entries}}\n{{/sections}}\n",
+    )
+    .expect("Failed to write template");
+    pearls_cli::commands::changelog::execute(
+        "2000-01-01".to_string(),
+        None,
+        "markdown".to_string(),
+        "Unreleased".to_string(),
+        Some(out.to_string_lossy().to_string()),
+    )
+    .expect("Changelog failed");
+    assert_eq!(
+        fs::read_to_string(&out).expect("Failed to read changelog"),
+        "prl-bbb222 Dark mode\nprl-aaa111 Old fix\n"
+    );
+}
+
+#[test]
+fn test_start_and_finish_branch_workflow() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let _guard = enter_dir(temp_dir.path());
+    init_git_repo(temp_dir.path());
+    let pearls_dir = init_repo(temp_dir.path());
+    let repo = Repository::open(temp_dir.path()).expect("Failed to open repo");
+
+    let mut storage =
+        Storage::new(pearls_dir.join("issues.jsonl")).expect("Failed to create storage");
+    let mut blocker = Pearl::new("Schema".to_string(), "tester".to_string());
+    blocker.id = "prl-aaa111".to_string();
+    let mut task = Pearl::new("Fix login: 2FA".to_string(), "tester".to_string());
+    task.id = "prl-bbb222".to_string();
+    storage.save(&blocker).expect("Failed to save pearl");
+    storage.save(&task).expect("Failed to save pearl");
+    add_all_and_commit(&repo, "Initial");
+
+    pearls_cli::commands::start::execute("prl-bbb".to_string(), Some("ada".to_string()))
+        .expect("Start failed");
+    let head = repo.head().expect("HEAD");
+    assert_eq!(head.shorthand(), Some("prl/prl-bbb222-fix-login-2fa"));
+    let started = storage.load_by_id("prl-bbb222").expect("Failed to load");
+    assert_eq!(started.status, pearls_core::Status::InProgress);
+    assert_eq!(started.metadata.get("assignee"), Some(&"ada".into()));
+    assert_eq!(
+        pearls_core::workflow::read_current(&pearls_dir).expect("current"),
+        Some("prl-bbb222".to_string())
+    );
+
+    let mut blocked = started.clone();
+    blocked.deps.push(pearls_core::Dependency {
+        target_id: blocker.id.clone(),
+        dep_type: DepType::Blocks,
+    });
+    storage.save(&blocked).expect("Failed to save pearl");
+    let err = pearls_cli::commands::finish::execute(None, false).expect_err("Blocked finish");
+    assert!(err.to_string().contains("prl-aaa111"));
+
+    pearls_cli::commands::close::execute("prl-aaa111".to_string()).expect("Close failed");
+    pearls_cli::commands::finish::execute(None, true).expect("Review failed");
+    let reviewed = storage.load_by_id("prl-bbb222").expect("Failed to load");
+    assert_eq!(reviewed.status, pearls_core::Status::InProgress);
+    assert_eq!(reviewed.labels, vec!["review"]);
+
+    pearls_cli::commands::finish::execute(None, false).expect("Finish failed");
+    let finished = storage.load_by_id("prl-bbb222").expect("Failed to load");
+    assert_eq!(finished.status, pearls_core::Status::Closed);
+    assert!(finished.labels.is_empty());
+    assert_eq!(
+        pearls_core::workflow::read_current(&pearls_dir).expect("current"),
+        None
+    );
+}
+
+#[test]
+fn test_start_updates_the_branch_copy_of_the_pearl() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let _guard = enter_dir(temp_dir.path());
+    init_git_repo(temp_dir.path());
+    let pearls_dir = init_repo(temp_dir.path());
+    let repo = Repository::open(temp_dir.path()).expect("Failed to open repo");
+
+    let mut storage =
+        Storage::new(pearls_dir.join("issues.jsonl")).expect("Failed to create storage");
+    let mut task = Pearl::new("Fix login".to_string(), "tester".to_string());
+    task.id = "prl-bbb222".to_string();
+    storage.save(&task).expect("Failed to save pearl");
+    add_all_and_commit(&repo, "Initial");
+    let main = repo.head().expect("HEAD").name().expect("name").to_string();
+
+    // The branch already exists and has its own edit of the Pearl.
+    let head = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .expect("HEAD commit");
+    repo.branch("prl/prl-bbb222-fix-login", &head, false)
+        .expect("Failed to create branch");
+    repo.set_head("refs/heads/prl/prl-bbb222-fix-login")
+        .expect("Failed to switch branch");
+    let mut edited = task.clone();
+    edited.description = "Edited on the branch".to_string();
+    storage.save(&edited).expect("Failed to save pearl");
+    add_all_and_commit(&repo, "Edit on branch");
+    repo.set_head(&main).expect("Failed to switch branch");
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+        .expect("Failed to check out main");
+
+    pearls_cli::commands::start::execute("prl-bbb222".to_string(), Some("ada".to_string()))
+        .expect("Start failed");
+    let started = Storage::new(pearls_dir.join("issues.jsonl"))
+        .expect("Failed to create storage")
+        .load_by_id("prl-bbb222")
+        .expect("Failed to load");
+    assert_eq!(started.description, "Edited on the branch");
+    assert_eq!(started.status, pearls_core::Status::InProgress);
+}
+
 #[test]
 fn test_meta_set_updates_metadata() {
     let temp_dir = TempDir::new().expect("Failed to create temp directory");
@@ -856,6 +1324,7 @@ fn test_comments_add_appends_comment() {
         pearl_id.clone(),
         "Looks good".to_string(),
         Some("reviewer".to_string()),
+        None,
     )
     .expect("Failed to add comment");
 
@@ -1297,3 +1766,178 @@ fn test_create_falls_back_to_system_username() {
         std::env::remove_var("USER");
     }
 }
+
+#[test]
+fn test_cached_queries_follow_jsonl() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let _guard = enter_dir(temp_dir.path());
+    let pearls_dir = init_repo(temp_dir.path());
+    let config = pearls_core::Config {
+        use_cache: true,
+        ..pearls_core::Config::default()
+    };
+    config.save(&pearls_dir).expect("Failed to save config");
+
+    let mut storage =
+        Storage::new(pearls_dir.join("issues.jsonl")).expect("Failed to create storage");
+    let first = pearls_core::Pearl::new("Cache parser".to_string(), "author".to_string());
+    storage.save(&first).expect("Failed to save pearl");
+
+    let captured = Arc::new(Mutex::new(Vec::new()));
+    let formatter = CaptureFormatter {
+        captured: Arc::clone(&captured),
+    };
+    pearls_cli::commands::search::execute("PARSER".to_string(), false, None, &formatter)
+        .expect("Search failed");
+    assert!(pearls_dir.join("cache.db").exists());
+    let ignore = fs::read_to_string(pearls_dir.join(".gitignore")).expect("Missing .gitignore");
+    assert!(ignore.lines().any(|line| line == "cache.db*"));
+
+    // Writes go to JSONL only; the next query must notice and rebuild.
+    let second = pearls_core::Pearl::new("Second parser".to_string(), "author".to_string());
+    storage.save(&second).expect("Failed to save pearl");
+    pearls_cli::commands::list::execute(
+        None,
+        None,
+        Vec::new(),
+        None,
+        false,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        &formatter,
+    )
+    .expect("List failed");
+
+    let captured = captured.lock().expect("capture lock");
+    let ids: Vec<&str> = captured.iter().map(|p| p.id.as_str()).collect();
+    assert_eq!(ids.len(), 3);
+    assert!(ids[1..].contains(&second.id.as_str()));
+
+    pearls_cli::commands::cache::execute(pearls_cli::commands::cache::CacheAction::Drop)
+        .expect("Cache drop failed");
+    assert!(!pearls_dir.join("cache.db").exists());
+}
+
+#[test]
+fn test_migrate_between_layouts() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let _guard = enter_dir(temp_dir.path());
+    let pearls_dir = init_repo(temp_dir.path());
+
+    let mut storage =
+        Storage::new(pearls_dir.join("issues.jsonl")).expect("Failed to create storage");
+    let pearl = pearls_core::Pearl::new("Split me".to_string(), "author".to_string());
+    storage.save(&pearl).expect("Failed to save pearl");
+
+    let global = temp_dir.path().join("global.toml");
+    fs::write(
+        &global,
+        "layout = \"dir\"\n[user]\nname = \"Global User\"\n",
+    )
+    .expect("Failed to write config");
+    std::env::set_var("PEARLS_GLOBAL_CONFIG", &global);
+    let migrated = pearls_cli::commands::migrate::execute(Some("dir".to_string()), false);
+    std::env::remove_var("PEARLS_GLOBAL_CONFIG");
+    migrated.expect("Migrate to dir failed");
+    let repo_config =
+        fs::read_to_string(pearls_dir.join("config.toml")).expect("Failed to read config");
+    assert!(
+        !repo_config.contains("Global User"),
+        "Global values must not be written to the repository config"
+    );
+    assert!(!pearls_dir.join("issues.jsonl").exists());
+    assert!(pearls_dir
+        .join("issues")
+        .join(format!("{}.json", pearl.id))
+        .exists());
+    let config = pearls_core::Config::load(&pearls_dir).expect("Failed to load config");
+    assert_eq!(config.layout, pearls_core::StorageLayout::Dir);
+
+    pearls_cli::commands::close::execute(pearl.id.clone()).expect("Close failed");
+    pearls_cli::commands::doctor::execute(false).expect("Doctor failed");
+
+    pearls_cli::commands::migrate::execute(Some("jsonl".to_string()), false)
+        .expect("Migrate to jsonl failed");
+    assert!(!pearls_dir.join("issues").exists());
+    let reloaded = Storage::new(pearls_dir.join("issues.jsonl"))
+        .expect("Failed to create storage")
+        .load_all()
+        .expect("Failed to load pearls");
+    assert_eq!(reloaded.len(), 1);
+    assert_eq!(reloaded[0].status, pearls_core::Status::Closed);
+}
+
+#[test]
+fn test_config_set_keeps_repository_keys_out_of_global_config() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let _guard = enter_dir(temp_dir.path());
+    let pearls_dir = init_repo(temp_dir.path());
+    let global = temp_dir.path().join("global.toml");
+
+    std::env::set_var("PEARLS_GLOBAL_CONFIG", &global);
+    let refused = pearls_cli::commands::config::set("layout".to_string(), "dir".to_string(), true);
+    let allowed =
+        pearls_cli::commands::config::set("user.name".to_string(), "Ada".to_string(), true);
+    let repo =
+        pearls_cli::commands::config::set("use_cache".to_string(), "true".to_string(), false);
+    std::env::remove_var("PEARLS_GLOBAL_CONFIG");
+
+    assert!(refused.is_err());
+    allowed.expect("Setting a global value failed");
+    repo.expect("Setting a repository value failed");
+    let global_config = fs::read_to_string(&global).expect("Failed to read config");
+    assert!(!global_config.contains("layout"));
+    assert!(
+        pearls_core::Config::load_repo(&pearls_dir)
+            .expect("Failed to load config")
+            .use_cache
+    );
+}
+
+#[test]
+fn test_migrate_upgrades_legacy_schema() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let _guard = enter_dir(temp_dir.path());
+    let pearls_dir = init_repo(temp_dir.path());
+    let legacy = pearls_core::Config {
+        schema_version: 1,
+        ..pearls_core::Config::default()
+    };
+    legacy.save(&pearls_dir).expect("Failed to save config");
+
+    let pearl = pearls_core::Pearl::new("Legacy".to_string(), "author".to_string());
+    Storage::new(pearls_dir.join("issues.jsonl"))
+        .expect("Failed to create storage")
+        .save(&pearl)
+        .expect("Failed to save pearl");
+    let before = fs::read_to_string(pearls_dir.join("issues.jsonl")).expect("Failed to read");
+
+    pearls_cli::commands::migrate::execute(None, true).expect("Dry run failed");
+    assert_eq!(
+        fs::read_to_string(pearls_dir.join("issues.jsonl")).expect("Failed to read"),
+        before
+    );
+    let config = pearls_core::Config::load(&pearls_dir).expect("Failed to load config");
+    assert_eq!(config.schema_version, 1);
+
+    pearls_cli::commands::migrate::execute(None, false).expect("Migrate failed");
+    let config = pearls_core::Config::load(&pearls_dir).expect("Failed to load config");
+    assert_eq!(config.schema_version, pearls_core::CURRENT_SCHEMA_VERSION);
+    let migrated = Storage::new(pearls_dir.join("issues.jsonl"))
+        .expect("Failed to create storage")
+        .load_by_id(&pearl.id)
+        .expect("Failed to load pearl");
+    assert_eq!(migrated, pearl);
+
+    let newer = pearls_core::Config {
+        schema_version: pearls_core::CURRENT_SCHEMA_VERSION + 1,
+        ..pearls_core::Config::default()
+    };
+    newer.save(&pearls_dir).expect("Failed to save config");
+    let err = pearls_cli::commands::migrate::execute(None, false).expect_err("Newer schema");
+    assert!(err.to_string().contains("Upgrade prl"));
+}
@@ -149,6 +149,21 @@ fn test_git_integration_setup() {
         hooks_dir.join("post-merge").exists(),
         "post-merge hook should exist"
     );
+    let commit_msg =
+        fs::read_to_string(hooks_dir.join("commit-msg")).expect("commit-msg hook should exist");
+    assert!(commit_msg.contains("prl hooks commit-msg \"$1\""));
+    assert!(
+        hooks_dir.join("post-commit").exists(),
+        "post-commit hook should exist"
+    );
+    assert!(
+        hooks_dir.join("post-checkout").exists(),
+        "post-checkout hook should exist"
+    );
+    assert!(
+        hooks_dir.join("prepare-commit-msg").exists(),
+        "prepare-commit-msg hook should exist"
+    );
 
     let contents = fs::read_to_string(gitattributes).expect("Failed to read .gitattributes");
     assert!(
@@ -19,6 +19,7 @@ fn create_test_pearl() -> Pearl {
         deps: vec![],
         metadata: HashMap::new(),
         comments: Vec::new(),
+        extra: Default::default(),
     }
 }
 
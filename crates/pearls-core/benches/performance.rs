@@ -66,6 +66,47 @@ fn bench_create(c: &mut Criterion) {
     });
 }
 
+fn bench_update_10k(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update_10k");
+    group.sample_size(20);
+
+    let (_temp_dir, mut storage) = setup_storage(10_000);
+    let mut target = storage.load_all().expect("Failed to load pearls")[5_000].clone();
+    group.bench_function("incremental_save", |b| {
+        b.iter(|| {
+            target.updated_at += 1;
+            black_box(storage.save(&target)).expect("Failed to save pearl");
+        })
+    });
+
+    let (temp_dir, storage) = setup_storage(10_000);
+    let mut indexed = Storage::with_index(
+        storage.path().to_path_buf(),
+        Some(temp_dir.path().join("index.bin")),
+    )
+    .expect("Failed to create indexed storage");
+    group.bench_function("incremental_save_indexed", |b| {
+        b.iter(|| {
+            target.updated_at += 1;
+            black_box(indexed.save(&target)).expect("Failed to save pearl");
+        })
+    });
+
+    let (_temp_dir, mut storage) = setup_storage(10_000);
+    group.bench_function("load_and_rewrite", |b| {
+        b.iter(|| {
+            target.updated_at += 1;
+            let mut pearls = storage.load_all().expect("Failed to load pearls");
+            if let Some(pos) = pearls.iter().position(|p| p.id == target.id) {
+                pearls[pos] = target.clone();
+            }
+            black_box(storage.save_all(&pearls)).expect("Failed to save pearls");
+        })
+    });
+
+    group.finish();
+}
+
 fn bench_ready_queue(c: &mut Criterion) {
     let pearls = build_pearls(1000);
     let graph = IssueGraph::from_pearls(pearls).expect("Failed to build graph");
@@ -79,6 +120,7 @@ criterion_group!(
     bench_load_all,
     bench_topological_sort,
     bench_create,
-    bench_ready_queue
+    bench_ready_queue,
+    bench_update_10k
 );
 criterion_main!(benches);
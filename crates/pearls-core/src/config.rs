@@ -1,10 +1,31 @@
 // Rust guideline compliant 2026-02-06
 
 //! Configuration management for Pearls.
+//!
+//! Configuration is layered: built-in defaults, the global config at
+//! `~/.config/pearls/config.toml`, the repository config at
+//! `.pearls/config.toml`, `PEARLS_*` environment variables, and finally an
+//! explicit file given with `prl --config`. Later layers override earlier ones
+//! key by key, and the layer of every value is recorded for `prl config`.
 
-use crate::Result;
+use crate::migrations::{check_schema_version, CURRENT_SCHEMA_VERSION, LEGACY_SCHEMA_VERSION};
+use crate::{Result, Status};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming an explicit config file, set by `prl --config`.
+pub const CONFIG_ENV: &str = "PEARLS_CONFIG";
+
+/// Top-level keys read only from the repository config.
+///
+/// They describe how one repository stores its Pearls, so the global config
+/// and an explicit `--config` file cannot set them.
+pub const REPO_ONLY_KEYS: [&str; 4] = ["schema_version", "layout", "use_index", "use_cache"];
+
+/// Environment variable overriding the location of the global config file;
+/// an empty value disables the global config.
+pub const GLOBAL_CONFIG_ENV: &str = "PEARLS_GLOBAL_CONFIG";
 
 /// Output format for command results.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -19,9 +40,389 @@ pub enum OutputFormat {
     Plain,
 }
 
+/// On-disk layout of the active Pearls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageLayout {
+    /// All Pearls in `.pearls/issues.jsonl`.
+    #[default]
+    Jsonl,
+    /// One `.pearls/issues/<id>.json` file per Pearl.
+    Dir,
+}
+
+/// A changelog section and the Pearls it collects.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangelogSection {
+    /// Section heading, such as "Fixed".
+    pub title: String,
+
+    /// Labels that place a Pearl in this section.
+    #[serde(default)]
+    pub labels: Vec<String>,
+
+    /// Issue types, read from the `type_key` metadata, that place a Pearl in
+    /// this section.
+    #[serde(default)]
+    pub types: Vec<String>,
+}
+
+impl ChangelogSection {
+    fn new(title: &str, labels: &[&str], types: &[&str]) -> Self {
+        Self {
+            title: title.to_string(),
+            labels: labels.iter().map(|label| label.to_string()).collect(),
+            types: types.iter().map(|kind| kind.to_string()).collect(),
+        }
+    }
+}
+
+/// Settings for `prl changelog`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChangelogConfig {
+    /// Label that keeps a Pearl out of the changelog.
+    pub exclude_label: String,
+
+    /// Metadata key holding the issue type of a Pearl.
+    pub type_key: String,
+
+    /// Section for Pearls that match no other section.
+    pub default_section: String,
+
+    /// Sections in output order; a Pearl goes into the first that matches.
+    pub sections: Vec<ChangelogSection>,
+}
+
+impl Default for ChangelogConfig {
+    fn default() -> Self {
+        Self {
+            exclude_label: "no-changelog".to_string(),
+            type_key: "issue_type".to_string(),
+            default_section: "Changed".to_string(),
+            sections: vec![
+                ChangelogSection::new("Added", &["feature", "enhancement"], &["feature"]),
+                ChangelogSection::new("Changed", &[], &[]),
+                ChangelogSection::new("Deprecated", &["deprecation"], &[]),
+                ChangelogSection::new("Removed", &["removal"], &[]),
+                ChangelogSection::new("Fixed", &["bug", "fix"], &["bug"]),
+                ChangelogSection::new("Security", &["security"], &[]),
+            ],
+        }
+    }
+}
+
+impl ChangelogConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Verbs that link commits to Pearls.
+///
+/// Matching is case-insensitive, so `Fixes prl-a1b2c3`, `fixes: prl-a1b2c3`,
+/// and `FIXES (prl-a1b2c3)` all match the `fixes` verb.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommitsConfig {
+    /// Verbs that link a commit to a Pearl, such as `Refs prl-a1b2c3`.
+    pub reference_verbs: Vec<String>,
+
+    /// Verbs that link a commit and close the Pearl when
+    /// `auto_close_on_commit` is set, such as `Fixes prl-a1b2c3`.
+    pub closing_verbs: Vec<String>,
+
+    /// Trailer key added by the prepare-commit-msg hook, such as
+    /// `Pearl: prl-a1b2c3`; the trailer links without closing. Empty disables
+    /// the trailer.
+    pub trailer: String,
+}
+
+impl Default for CommitsConfig {
+    fn default() -> Self {
+        let verbs = |verbs: &[&str]| verbs.iter().map(|verb| verb.to_string()).collect();
+        Self {
+            reference_verbs: verbs(&["refs", "references", "ref", "see"]),
+            closing_verbs: verbs(&[
+                "fixes", "fixed", "fix", "closes", "closed", "close", "resolves", "resolved",
+                "resolve",
+            ]),
+            trailer: "Pearl".to_string(),
+        }
+    }
+}
+
+impl CommitsConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Settings for the branch-per-Pearl workflow of `prl start` and `prl finish`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkflowConfig {
+    /// Branch name template; `{id}` is the Pearl ID and `{slug}` its title in
+    /// lowercase words joined by `-`.
+    pub branch_template: String,
+
+    /// Label added by `prl finish --review`.
+    pub review_label: String,
+}
+
+impl Default for WorkflowConfig {
+    fn default() -> Self {
+        Self {
+            branch_template: "prl/{id}-{slug}".to_string(),
+            review_label: "review".to_string(),
+        }
+    }
+}
+
+impl WorkflowConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Settings for `prl mcp`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct McpConfig {
+    /// Bearer token HTTP clients must send; requests are not authenticated
+    /// when unset. Set it in the global config or `PEARLS_MCP_AUTH_TOKEN`;
+    /// `prl mcp --http` refuses a token in the committed repository config.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_token: Option<String>,
+
+    /// Which tool calls agents may make.
+    #[serde(skip_serializing_if = "McpPolicy::is_default")]
+    pub policy: McpPolicy,
+}
+
+impl McpConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Access policy for MCP tool calls, from `[mcp.policy]` or `prl mcp --policy`.
+///
+/// The default allows every call.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct McpPolicy {
+    /// Tools that may be called; empty allows every tool.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allow_tools: Vec<String>,
+
+    /// Tools that may not be called, even when listed in `allow_tools`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deny_tools: Vec<String>,
+
+    /// Status changes that are refused.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deny_transitions: Vec<TransitionRule>,
+
+    /// Labels that may be added or removed; empty allows every label.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allow_labels: Vec<String>,
+
+    /// Labels that may not be added or removed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deny_labels: Vec<String>,
+
+    /// Maximum number of items in one `create`, `link`, or `unlink` call.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_batch: Option<usize>,
+}
+
+impl McpPolicy {
+    /// Loads a policy from a standalone TOML file.
+    ///
+    /// The file holds the keys of `[mcp.policy]` at the top level.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the policy file
+    ///
+    /// # Returns
+    ///
+    /// The parsed policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid policy.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        toml::from_str(&content).map_err(|e| {
+            crate::Error::InvalidPearl(format!("Invalid policy file {}: {}", path.display(), e))
+        })
+    }
+
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A status change refused by an [`McpPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransitionRule {
+    /// Status that may not be set.
+    pub to: Status,
+
+    /// Priorities the rule applies to; empty applies to every priority.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub priorities: Vec<u8>,
+}
+
+/// Kind of change to a Pearl that notifications react to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// A Pearl was created.
+    Created,
+    /// A Pearl changed without a status change.
+    Updated,
+    /// A Pearl moved to a status other than closed.
+    StatusChanged,
+    /// A Pearl was closed.
+    Closed,
+    /// A Pearl entered the ready queue.
+    Ready,
+    /// A Pearl was deleted or archived.
+    Deleted,
+}
+
+/// Notifications sent when Pearls change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventsConfig {
+    /// Commands and URLs to notify.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<EventHook>,
+
+    /// Attempts per delivery before it is written to the dead-letter log.
+    pub max_attempts: u32,
+
+    /// Delay before the first retry in milliseconds; doubled on each retry.
+    pub retry_delay_ms: u64,
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            hooks: Vec::new(),
+            max_attempts: 3,
+            retry_delay_ms: 500,
+        }
+    }
+}
+
+impl EventsConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A command or URL notified of matching events.
+///
+/// Filters left empty match every event.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventHook {
+    /// Event kinds to deliver.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<EventKind>,
+
+    /// Deliver only events for Pearls with one of these labels.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+
+    /// Deliver only events for Pearls with one of these priorities.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub priorities: Vec<u8>,
+
+    /// Shell command run with the event JSON on standard input.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
+    /// URL the event JSON is POSTed to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Identity recorded as the author of new Pearls and comments.
+///
+/// Unset fields fall back to Git's `user.name` and `user.email`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserConfig {
+    /// Author name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Author email, used as the author when no name is known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+impl UserConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// When the CLI colors its output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorMode {
+    /// Color when writing to a terminal and `NO_COLOR` is unset.
+    #[default]
+    Auto,
+    /// Always color.
+    Always,
+    /// Never color.
+    Never,
+}
+
+/// Terminal preferences of the CLI.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    /// Editor command for `prl config edit`; falls back to `$VISUAL`,
+    /// `$EDITOR`, and `vi`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+
+    /// When to color output; `--no-color` always disables it.
+    pub color: ColorMode,
+
+    /// Sort field `prl list` uses when `--sort` is not given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list_sort: Option<String>,
+
+    /// Status `prl list` shows when `--status` is not given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list_status: Option<Status>,
+}
+
+impl UiConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Configuration for Pearls behavior.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// Schema version of the stored Pearls.
+    ///
+    /// Configs written before versioning was introduced lack this field and
+    /// are read as version 1.
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
+
     /// Default priority for new Pearls (0-4).
     #[serde(default = "default_priority")]
     pub default_priority: u8,
@@ -30,17 +431,136 @@ pub struct Config {
     #[serde(default = "default_compact_threshold")]
     pub compact_threshold_days: u32,
 
+    /// On-disk layout of the active Pearls.
+    #[serde(default)]
+    pub layout: StorageLayout,
+
     /// Whether to use index file for large repositories.
     #[serde(default)]
     pub use_index: bool,
 
+    /// Whether to serve queries from the SQLite cache at `.pearls/cache.db`.
+    #[serde(default)]
+    pub use_cache: bool,
+
     /// Default output format for commands.
     #[serde(default)]
     pub output_format: OutputFormat,
 
-    /// Whether to auto-close Pearls on commit with "Fixes (prl-XXXXXX)" pattern.
+    /// Whether commits with a closing verb, such as `Fixes prl-XXXXXX`,
+    /// close the Pearls they reference.
     #[serde(default)]
     pub auto_close_on_commit: bool,
+
+    /// Verbs recognized in commit messages.
+    #[serde(default, skip_serializing_if = "CommitsConfig::is_default")]
+    pub commits: CommitsConfig,
+
+    /// Branch-per-Pearl workflow.
+    #[serde(default, skip_serializing_if = "WorkflowConfig::is_default")]
+    pub workflow: WorkflowConfig,
+
+    /// Grouping of `prl changelog` output.
+    #[serde(default, skip_serializing_if = "ChangelogConfig::is_default")]
+    pub changelog: ChangelogConfig,
+
+    /// MCP server settings.
+    #[serde(default, skip_serializing_if = "McpConfig::is_default")]
+    pub mcp: McpConfig,
+
+    /// Notifications on Pearl changes.
+    #[serde(default, skip_serializing_if = "EventsConfig::is_default")]
+    pub events: EventsConfig,
+
+    /// Author identity.
+    #[serde(default, skip_serializing_if = "UserConfig::is_default")]
+    pub user: UserConfig,
+
+    /// Terminal preferences.
+    #[serde(default, skip_serializing_if = "UiConfig::is_default")]
+    pub ui: UiConfig,
+}
+
+/// Configuration layer a value was set in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSource {
+    /// Built-in default.
+    Default,
+    /// The global config file.
+    Global,
+    /// `.pearls/config.toml`.
+    Repo,
+    /// A `PEARLS_*` environment variable.
+    Env,
+    /// The file given with `prl --config`.
+    Explicit,
+}
+
+impl ConfigSource {
+    /// Returns the name of the layer.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Global => "global",
+            Self::Repo => "repo",
+            Self::Env => "env",
+            Self::Explicit => "explicit",
+        }
+    }
+}
+
+/// A configuration value and the layer it was set in.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigEntry {
+    /// Dotted key, such as `user.name`.
+    pub key: String,
+    /// Value as TOML.
+    pub value: toml::Value,
+    /// Layer the value was set in.
+    pub source: ConfigSource,
+}
+
+/// A loaded configuration and the layer of each of its values.
+#[derive(Debug, Clone)]
+pub struct LayeredConfig {
+    /// The effective configuration.
+    pub config: Config,
+    sources: BTreeMap<String, ConfigSource>,
+}
+
+impl LayeredConfig {
+    /// Returns the layer a value was set in.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Dotted key, such as `events.hooks`
+    #[must_use]
+    pub fn source(&self, key: &str) -> ConfigSource {
+        self.sources
+            .get(key)
+            .copied()
+            .unwrap_or(ConfigSource::Default)
+    }
+
+    /// Lists every value of the effective configuration, sorted by key.
+    ///
+    /// Arrays, such as `events.hooks`, are listed as one value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration cannot be serialized.
+    pub fn entries(&self) -> Result<Vec<ConfigEntry>> {
+        let mut entries = Vec::new();
+        flatten(&to_table(&self.config)?, "", &mut |key, value| {
+            entries.push(ConfigEntry {
+                source: self.source(&key),
+                key,
+                value: value.clone(),
+            });
+        });
+        Ok(entries)
+    }
 }
 
 /// Default priority value (medium).
@@ -48,6 +568,11 @@ fn default_priority() -> u8 {
     2
 }
 
+/// Schema version assumed when the config does not record one.
+fn legacy_schema_version() -> u32 {
+    LEGACY_SCHEMA_VERSION
+}
+
 /// Default compaction threshold in days.
 fn default_compact_threshold() -> u32 {
     30
@@ -56,22 +581,34 @@ fn default_compact_threshold() -> u32 {
 impl Default for Config {
     fn default() -> Self {
         Self {
+            schema_version: CURRENT_SCHEMA_VERSION,
             default_priority: default_priority(),
             compact_threshold_days: default_compact_threshold(),
+            layout: StorageLayout::default(),
             use_index: false,
+            use_cache: false,
             output_format: OutputFormat::default(),
             auto_close_on_commit: false,
+            commits: CommitsConfig::default(),
+            workflow: WorkflowConfig::default(),
+            changelog: ChangelogConfig::default(),
+            mcp: McpConfig::default(),
+            events: EventsConfig::default(),
+            user: UserConfig::default(),
+            ui: UiConfig::default(),
         }
     }
 }
 
 impl Config {
-    /// Loads configuration from file and environment variables.
+    /// Loads configuration from files and environment variables.
     ///
     /// Configuration is loaded in the following order (later overrides earlier):
     /// 1. Default values
-    /// 2. Configuration file at `.pearls/config.toml`
-    /// 3. Environment variables with `PEARLS_` prefix
+    /// 2. Global configuration file (see [`Config::global_path`])
+    /// 3. Configuration file at `.pearls/config.toml`
+    /// 4. Environment variables with `PEARLS_` prefix
+    /// 5. The file named by `PEARLS_CONFIG`, set by `prl --config`
     ///
     /// # Arguments
     ///
@@ -88,34 +625,234 @@ impl Config {
     /// - Configuration file contains invalid TOML
     /// - Configuration values fail validation
     pub fn load(pearls_dir: &Path) -> Result<Self> {
-        let mut config = Self::default();
+        Ok(Self::load_layered(Some(pearls_dir))?.config)
+    }
 
-        // Try to load from config file
-        let config_path = pearls_dir.join("config.toml");
-        if config_path.exists() {
-            let content = std::fs::read_to_string(&config_path)?;
-            let file_config: Config = toml::from_str(&content)
-                .map_err(|e| crate::Error::InvalidPearl(format!("Invalid config file: {}", e)))?;
-            config = file_config;
+    /// Loads configuration and records the layer of each value.
+    ///
+    /// Layers are applied in the order described for [`Config::load`]. The
+    /// [`REPO_ONLY_KEYS`] are ignored in the global and explicit files, so
+    /// they come from the repository configuration, or for `use_index` and
+    /// `use_cache` from the environment.
+    ///
+    /// # Arguments
+    ///
+    /// * `pearls_dir` - Path to the `.pearls` directory; `None` outside a
+    ///   repository
+    ///
+    /// # Returns
+    ///
+    /// The effective configuration and the layer of each value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a configuration file cannot be read or contains
+    /// invalid TOML, if the explicit file does not exist, or if the resulting
+    /// values fail validation.
+    pub fn load_layered(pearls_dir: Option<&Path>) -> Result<LayeredConfig> {
+        let mut merged = to_table(&Self::default())?;
+        merged.remove("schema_version");
+        let mut sources = BTreeMap::new();
+        flatten(&merged, "", &mut |key, _| {
+            sources.insert(key, ConfigSource::Default);
+        });
+
+        if let Some(path) = Self::global_path() {
+            if let Some(mut table) = read_table(&path)? {
+                strip_repo_only(&mut table);
+                overlay(&mut merged, table, ConfigSource::Global, &mut sources);
+            }
         }
+        match pearls_dir.map(|dir| dir.join("config.toml")) {
+            Some(path) if path.exists() => {
+                let table = read_table(&path)?.unwrap_or_default();
+                overlay(&mut merged, table, ConfigSource::Repo, &mut sources);
+            }
+            _ => {
+                merged.insert(
+                    "schema_version".to_string(),
+                    toml::Value::Integer(i64::from(CURRENT_SCHEMA_VERSION)),
+                );
+            }
+        }
+        let mut config = from_table(merged)?;
 
-        // Apply environment variable overrides
+        let before = to_table(&config)?;
         config.apply_env_overrides()?;
+        let after = to_table(&config)?;
+        flatten(&after, "", &mut |key, value| {
+            if lookup(&before, &key) != Some(value) {
+                sources.insert(key, ConfigSource::Env);
+            }
+        });
+
+        if let Some(path) = Self::explicit_path() {
+            let mut table = read_table(&path)?
+                .ok_or_else(|| crate::Error::NotFound(format!("Config file {}", path.display())))?;
+            strip_repo_only(&mut table);
+            let mut merged = after;
+            overlay(&mut merged, table, ConfigSource::Explicit, &mut sources);
+            config = from_table(merged)?;
+        }
 
-        // Validate configuration
         config.validate()?;
+        Ok(LayeredConfig { config, sources })
+    }
 
+    /// Loads the repository configuration alone, without the other layers.
+    ///
+    /// # Arguments
+    ///
+    /// * `pearls_dir` - Path to the `.pearls` directory
+    ///
+    /// # Returns
+    ///
+    /// The values in `.pearls/config.toml` over the defaults; the defaults if
+    /// the file does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, contains invalid TOML, or
+    /// holds values that fail validation.
+    pub fn load_repo(pearls_dir: &Path) -> Result<Self> {
+        let config = match read_table(&pearls_dir.join("config.toml"))? {
+            Some(table) => from_table(table)?,
+            None => Self::default(),
+        };
+        config.validate()?;
         Ok(config)
     }
 
+    /// Returns the path of the global configuration file.
+    ///
+    /// This is `$PEARLS_GLOBAL_CONFIG` when set, otherwise
+    /// `pearls/config.toml` under `$XDG_CONFIG_HOME`, `~/.config`, or, on
+    /// Windows, `%APPDATA%`.
+    ///
+    /// # Returns
+    ///
+    /// The path, or `None` if the global config is disabled or no home
+    /// directory is known.
+    pub fn global_path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os(GLOBAL_CONFIG_ENV) {
+            return (!path.is_empty()).then(|| PathBuf::from(path));
+        }
+        let non_empty = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty());
+        let base = non_empty("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| non_empty("HOME").map(|home| Path::new(&home).join(".config")))
+            .or_else(|| non_empty("APPDATA").map(PathBuf::from))?;
+        Some(base.join("pearls").join("config.toml"))
+    }
+
+    /// Returns the explicit configuration file named by `PEARLS_CONFIG`.
+    pub fn explicit_path() -> Option<PathBuf> {
+        std::env::var_os(CONFIG_ENV)
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+    }
+
+    /// Lists the values set in one configuration file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The configuration file
+    /// * `source` - Layer to report for the values
+    ///
+    /// # Returns
+    ///
+    /// The values sorted by key; empty if the file does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or contains invalid TOML.
+    pub fn file_entries(path: &Path, source: ConfigSource) -> Result<Vec<ConfigEntry>> {
+        let mut entries = Vec::new();
+        if let Some(table) = read_table(path)? {
+            flatten(&table, "", &mut |key, value| {
+                entries.push(ConfigEntry {
+                    key,
+                    value: value.clone(),
+                    source,
+                });
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Sets one value in a configuration file, creating it if needed.
+    ///
+    /// The value is read as TOML when it parses as a TOML value, such as
+    /// `true`, `3`, or `["a", "b"]`, and as a string otherwise. Other values
+    /// in the file are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The configuration file
+    /// * `key` - Dotted key, such as `user.name`
+    /// * `value` - The new value
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is unknown, the value has the wrong type
+    /// or fails validation, or the file cannot be read or written.
+    pub fn set_file_value(path: &Path, key: &str, value: &str) -> Result<()> {
+        let invalid = |message: String| crate::Error::InvalidPearl(message);
+        let mut table = read_table(path)?.unwrap_or_default();
+        let value = toml::from_str::<toml::Table>(&format!("value = {}", value))
+            .ok()
+            .and_then(|mut parsed| parsed.remove("value"))
+            .unwrap_or_else(|| toml::Value::String(value.to_string()));
+
+        let mut parts: Vec<&str> = key.split('.').collect();
+        let last = parts
+            .pop()
+            .filter(|last| !last.is_empty())
+            .ok_or_else(|| invalid(format!("Invalid config key '{}'", key)))?;
+        let mut current = &mut table;
+        for part in parts {
+            current = current
+                .entry(part)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+                .as_table_mut()
+                .ok_or_else(|| invalid(format!("Config key '{}' is not a table", part)))?;
+        }
+        current.insert(last.to_string(), value);
+
+        let mut unknown = Vec::new();
+        let config: Config =
+            serde_ignored::deserialize(toml::Value::Table(table.clone()), |path| {
+                unknown.push(path.to_string());
+            })
+            .map_err(|e| invalid(format!("Invalid value for '{}': {}", key, e)))?;
+        if !unknown.is_empty() {
+            return Err(invalid(format!(
+                "Unknown config key '{}'",
+                unknown.join("', '")
+            )));
+        }
+        config.validate()?;
+
+        let content = toml::to_string_pretty(&table).map_err(|e| {
+            crate::Error::InvalidPearl(format!("Failed to serialize config: {}", e))
+        })?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
     /// Applies environment variable overrides to the configuration.
     ///
     /// Supported environment variables:
     /// - `PEARLS_DEFAULT_PRIORITY` - Default priority (0-4)
     /// - `PEARLS_COMPACT_THRESHOLD_DAYS` - Compaction threshold in days
     /// - `PEARLS_USE_INDEX` - Whether to use index file (true/false)
+    /// - `PEARLS_USE_CACHE` - Whether to use the SQLite query cache (true/false)
     /// - `PEARLS_OUTPUT_FORMAT` - Output format (json/table/plain)
     /// - `PEARLS_AUTO_CLOSE_ON_COMMIT` - Auto-close on commit (true/false)
+    /// - `PEARLS_MCP_AUTH_TOKEN` - Bearer token for the MCP HTTP transport
     ///
     /// # Returns
     ///
@@ -147,6 +884,12 @@ impl Config {
             })?;
         }
 
+        if let Ok(val) = std::env::var("PEARLS_USE_CACHE") {
+            self.use_cache = val.parse().map_err(|_| {
+                crate::Error::InvalidPearl("PEARLS_USE_CACHE must be true or false".to_string())
+            })?;
+        }
+
         if let Ok(val) = std::env::var("PEARLS_OUTPUT_FORMAT") {
             self.output_format = match val.as_str() {
                 "json" => OutputFormat::Json,
@@ -160,6 +903,10 @@ impl Config {
             };
         }
 
+        if let Ok(val) = std::env::var("PEARLS_MCP_AUTH_TOKEN") {
+            self.mcp.auth_token = Some(val).filter(|token| !token.is_empty());
+        }
+
         if let Ok(val) = std::env::var("PEARLS_AUTO_CLOSE_ON_COMMIT") {
             self.auto_close_on_commit = val.parse().map_err(|_| {
                 crate::Error::InvalidPearl(
@@ -182,7 +929,10 @@ impl Config {
     /// Returns an error if:
     /// - default_priority is out of range (0-4)
     /// - compact_threshold_days is zero
+    /// - schema_version is newer than this build supports
     fn validate(&self) -> Result<()> {
+        check_schema_version(self.schema_version)?;
+
         if self.default_priority > 4 {
             return Err(crate::Error::InvalidPearl(format!(
                 "default_priority must be 0-4, got {}",
@@ -224,6 +974,116 @@ impl Config {
     }
 }
 
+/// Serializes a configuration to a TOML table.
+fn to_table(config: &Config) -> Result<toml::Table> {
+    match toml::Value::try_from(config) {
+        Ok(toml::Value::Table(table)) => Ok(table),
+        Ok(_) => Ok(toml::Table::new()),
+        Err(e) => Err(crate::Error::InvalidPearl(format!(
+            "Failed to serialize config: {}",
+            e
+        ))),
+    }
+}
+
+/// Deserializes a configuration from a TOML table.
+fn from_table(table: toml::Table) -> Result<Config> {
+    toml::Value::Table(table)
+        .try_into()
+        .map_err(|e| crate::Error::InvalidPearl(format!("Invalid config file: {}", e)))
+}
+
+/// Removes the [`REPO_ONLY_KEYS`] from a layer other than the repository's.
+fn strip_repo_only(table: &mut toml::Table) {
+    for key in REPO_ONLY_KEYS {
+        table.remove(key);
+    }
+}
+
+/// Reads a configuration file as a TOML table; `None` if it does not exist.
+fn read_table(path: &Path) -> Result<Option<toml::Table>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path)?;
+    let table = toml::from_str(&content).map_err(|e| {
+        crate::Error::InvalidPearl(format!("Invalid config file {}: {}", path.display(), e))
+    })?;
+    Ok(Some(table))
+}
+
+/// Calls `visit` with the dotted key of every non-table value.
+fn flatten(table: &toml::Table, prefix: &str, visit: &mut dyn FnMut(String, &toml::Value)) {
+    for (name, value) in table {
+        let key = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        match value {
+            toml::Value::Table(table) => flatten(table, &key, visit),
+            value => visit(key, value),
+        }
+    }
+}
+
+/// Returns the value at a dotted key.
+fn lookup<'a>(table: &'a toml::Table, key: &str) -> Option<&'a toml::Value> {
+    let (head, rest) = match key.split_once('.') {
+        Some((head, rest)) => (head, Some(rest)),
+        None => (key, None),
+    };
+    match (table.get(head)?, rest) {
+        (toml::Value::Table(table), Some(rest)) => lookup(table, rest),
+        (value, None) => Some(value),
+        _ => None,
+    }
+}
+
+/// Merges `layer` into `base` key by key and records `source` for its values.
+fn overlay(
+    base: &mut toml::Table,
+    layer: toml::Table,
+    source: ConfigSource,
+    sources: &mut BTreeMap<String, ConfigSource>,
+) {
+    fn merge(
+        base: &mut toml::Table,
+        layer: toml::Table,
+        prefix: &str,
+        source: ConfigSource,
+        sources: &mut BTreeMap<String, ConfigSource>,
+    ) {
+        for (name, value) in layer {
+            let key = if prefix.is_empty() {
+                name.clone()
+            } else {
+                format!("{}.{}", prefix, name)
+            };
+            match (base.get_mut(&name), value) {
+                (Some(toml::Value::Table(existing)), toml::Value::Table(table)) => {
+                    merge(existing, table, &key, source, sources);
+                }
+                (_, value) => {
+                    sources.retain(|known, _| {
+                        known != &key && !known.starts_with(&format!("{}.", key))
+                    });
+                    match &value {
+                        toml::Value::Table(table) => flatten(table, &key, &mut |key, _| {
+                            sources.insert(key, source);
+                        }),
+                        _ => {
+                            sources.insert(key, source);
+                        }
+                    }
+                    base.insert(name, value);
+                }
+            }
+        }
+    }
+    merge(base, layer, "", source, sources);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -236,8 +1096,12 @@ mod tests {
         std::env::remove_var("PEARLS_DEFAULT_PRIORITY");
         std::env::remove_var("PEARLS_COMPACT_THRESHOLD_DAYS");
         std::env::remove_var("PEARLS_USE_INDEX");
+        std::env::remove_var("PEARLS_USE_CACHE");
         std::env::remove_var("PEARLS_OUTPUT_FORMAT");
         std::env::remove_var("PEARLS_AUTO_CLOSE_ON_COMMIT");
+        std::env::remove_var("PEARLS_MCP_AUTH_TOKEN");
+        std::env::remove_var(CONFIG_ENV);
+        std::env::set_var(GLOBAL_CONFIG_ENV, "");
     }
 
     fn run_env_test<F: FnOnce()>(f: F) {
@@ -251,9 +1115,12 @@ mod tests {
     fn test_default_config() {
         run_env_test(|| {
             let config = Config::default();
+            assert_eq!(config.schema_version, CURRENT_SCHEMA_VERSION);
             assert_eq!(config.default_priority, 2);
             assert_eq!(config.compact_threshold_days, 30);
+            assert_eq!(config.layout, StorageLayout::Jsonl);
             assert!(!config.use_index);
+            assert!(!config.use_cache);
             assert_eq!(config.output_format, OutputFormat::Table);
             assert!(!config.auto_close_on_commit);
         });
@@ -284,6 +1151,7 @@ auto_close_on_commit = true
             std::fs::write(&config_path, content).unwrap();
 
             let config = Config::load(temp_dir.path()).unwrap();
+            assert_eq!(config.schema_version, LEGACY_SCHEMA_VERSION);
             assert_eq!(config.default_priority, 1);
             assert_eq!(config.compact_threshold_days, 60);
             assert!(config.use_index);
@@ -292,6 +1160,33 @@ auto_close_on_commit = true
         });
     }
 
+    #[test]
+    fn test_config_load_changelog_sections() {
+        run_env_test(|| {
+            let temp_dir = TempDir::new().unwrap();
+            let config_path = temp_dir.path().join("config.toml");
+            let content = r#"
+[changelog]
+default_section = "Other"
+
+[[changelog.sections]]
+title = "Features"
+labels = ["feature"]
+"#;
+            std::fs::write(&config_path, content).unwrap();
+
+            let config = Config::load(temp_dir.path()).unwrap();
+            assert_eq!(config.changelog.exclude_label, "no-changelog");
+            assert_eq!(config.changelog.default_section, "Other");
+            assert_eq!(config.changelog.sections.len(), 1);
+            assert!(config.changelog.sections[0].types.is_empty());
+
+            Config::default().save(temp_dir.path()).unwrap();
+            let saved = std::fs::read_to_string(&config_path).unwrap();
+            assert!(!saved.contains("changelog"));
+        });
+    }
+
     #[test]
     fn test_config_validation_invalid_priority() {
         run_env_test(|| {
@@ -318,6 +1213,19 @@ auto_close_on_commit = true
         });
     }
 
+    #[test]
+    fn test_config_refuses_newer_schema() {
+        run_env_test(|| {
+            let temp_dir = TempDir::new().unwrap();
+            let config_path = temp_dir.path().join("config.toml");
+            let content = format!("schema_version = {}", CURRENT_SCHEMA_VERSION + 1);
+            std::fs::write(&config_path, content).unwrap();
+
+            let result = Config::load(temp_dir.path());
+            assert!(matches!(result, Err(crate::Error::UnsupportedSchema(_, _))));
+        });
+    }
+
     #[test]
     fn test_config_env_override_priority() {
         run_env_test(|| {
@@ -401,23 +1309,68 @@ auto_close_on_commit = true
             let temp_dir = TempDir::new().unwrap();
 
             let original = Config {
+                schema_version: LEGACY_SCHEMA_VERSION,
                 default_priority: 1,
                 compact_threshold_days: 45,
+                layout: StorageLayout::Dir,
                 use_index: true,
+                use_cache: true,
                 output_format: OutputFormat::Json,
                 auto_close_on_commit: true,
+                commits: CommitsConfig::default(),
+                workflow: WorkflowConfig::default(),
+                changelog: ChangelogConfig::default(),
+                mcp: McpConfig {
+                    auth_token: Some("secret".to_string()),
+                    policy: McpPolicy {
+                        deny_tools: vec!["compact".to_string()],
+                        deny_transitions: vec![TransitionRule {
+                            to: Status::Closed,
+                            priorities: vec![0],
+                        }],
+                        max_batch: Some(10),
+                        ..McpPolicy::default()
+                    },
+                },
+                events: EventsConfig {
+                    hooks: vec![EventHook {
+                        events: vec![EventKind::Closed, EventKind::Ready],
+                        priorities: vec![0, 1],
+                        url: Some("http://127.0.0.1:9000/hook".to_string()),
+                        ..EventHook::default()
+                    }],
+                    max_attempts: 5,
+                    ..EventsConfig::default()
+                },
+                user: UserConfig {
+                    name: Some("Ada".to_string()),
+                    email: None,
+                },
+                ui: UiConfig {
+                    editor: Some("nano".to_string()),
+                    color: ColorMode::Never,
+                    list_sort: Some("priority".to_string()),
+                    list_status: Some(Status::InProgress),
+                },
             };
 
             original.save(temp_dir.path()).unwrap();
             let loaded = Config::load(temp_dir.path()).unwrap();
 
+            assert_eq!(original.schema_version, loaded.schema_version);
             assert_eq!(original.default_priority, loaded.default_priority);
             assert_eq!(
                 original.compact_threshold_days,
                 loaded.compact_threshold_days
             );
+            assert_eq!(original.layout, loaded.layout);
             assert_eq!(original.use_index, loaded.use_index);
+            assert_eq!(original.use_cache, loaded.use_cache);
             assert_eq!(original.output_format, loaded.output_format);
+            assert_eq!(original.mcp, loaded.mcp);
+            assert_eq!(original.events, loaded.events);
+            assert_eq!(original.user, loaded.user);
+            assert_eq!(original.ui, loaded.ui);
             assert_eq!(original.auto_close_on_commit, loaded.auto_close_on_commit);
         });
     }
@@ -435,4 +1388,92 @@ auto_close_on_commit = true
             assert_eq!(config.default_priority, 3);
         });
     }
+
+    #[test]
+    fn test_config_layers_and_sources() {
+        run_env_test(|| {
+            let temp_dir = TempDir::new().unwrap();
+            let global = temp_dir.path().join("global.toml");
+            std::fs::write(
+                &global,
+                "schema_version = 9\nlayout = \"dir\"\nuse_cache = true\ndefault_priority = 1\n[user]\nname = \"Ada\"\n",
+            )
+            .unwrap();
+            let pearls_dir = temp_dir.path().join(".pearls");
+            std::fs::create_dir(&pearls_dir).unwrap();
+            std::fs::write(
+                pearls_dir.join("config.toml"),
+                "default_priority = 3\nuse_index = true\n",
+            )
+            .unwrap();
+            let explicit = temp_dir.path().join("explicit.toml");
+            std::fs::write(
+                &explicit,
+                "use_index = false\nschema_version = 9\ncompact_threshold_days = 7\n",
+            )
+            .unwrap();
+            std::env::set_var(GLOBAL_CONFIG_ENV, &global);
+            std::env::set_var(CONFIG_ENV, &explicit);
+
+            // Repository-only keys in the global and explicit files are ignored.
+            let layered = Config::load_layered(Some(&pearls_dir)).unwrap();
+            assert_eq!(layered.config.schema_version, LEGACY_SCHEMA_VERSION);
+            assert_eq!(layered.config.layout, StorageLayout::Jsonl);
+            assert!(!layered.config.use_cache);
+            assert!(layered.config.use_index);
+            assert_eq!(layered.config.compact_threshold_days, 7);
+
+            std::env::set_var("PEARLS_USE_CACHE", "true");
+            let layered = Config::load_layered(Some(&pearls_dir)).unwrap();
+            assert_eq!(layered.config.default_priority, 3);
+            assert_eq!(layered.config.user.name.as_deref(), Some("Ada"));
+            assert!(layered.config.use_cache);
+            assert!(layered.config.use_index);
+
+            let entries = layered.entries().unwrap();
+            let source = |key: &str| {
+                entries
+                    .iter()
+                    .find(|entry| entry.key == key)
+                    .map(|entry| entry.source)
+            };
+            assert_eq!(source("output_format"), Some(ConfigSource::Default));
+            assert_eq!(source("user.name"), Some(ConfigSource::Global));
+            assert_eq!(source("default_priority"), Some(ConfigSource::Repo));
+            assert_eq!(source("use_cache"), Some(ConfigSource::Env));
+            assert_eq!(source("use_index"), Some(ConfigSource::Repo));
+            assert_eq!(
+                source("compact_threshold_days"),
+                Some(ConfigSource::Explicit)
+            );
+            assert_eq!(Config::load_repo(&pearls_dir).unwrap().user.name, None);
+
+            std::env::remove_var(CONFIG_ENV);
+            let outside = Config::load_layered(None).unwrap();
+            assert_eq!(outside.config.schema_version, CURRENT_SCHEMA_VERSION);
+            assert_eq!(outside.config.default_priority, 1);
+        });
+    }
+
+    #[test]
+    fn test_set_file_value() {
+        run_env_test(|| {
+            let temp_dir = TempDir::new().unwrap();
+            let path = temp_dir.path().join("pearls").join("config.toml");
+
+            Config::set_file_value(&path, "user.name", "Ada Lovelace").unwrap();
+            Config::set_file_value(&path, "default_priority", "1").unwrap();
+            Config::set_file_value(&path, "ui.color", "never").unwrap();
+            assert!(Config::set_file_value(&path, "usr.name", "Ada").is_err());
+            assert!(Config::set_file_value(&path, "default_priority", "9").is_err());
+            assert!(Config::set_file_value(&path, "use_index", "yes").is_err());
+            assert!(Config::set_file_value(&path, "ui.list_status", "done").is_err());
+
+            let entries = Config::file_entries(&path, ConfigSource::Global).unwrap();
+            let keys: Vec<&str> = entries.iter().map(|entry| entry.key.as_str()).collect();
+            assert_eq!(keys, vec!["default_priority", "ui.color", "user.name"]);
+            assert_eq!(entries[2].value.as_str(), Some("Ada Lovelace"));
+            assert_eq!(entries[0].value.as_integer(), Some(1));
+        });
+    }
 }
@@ -41,4 +41,10 @@ pub enum Error {
     /// Git operation error.
     #[error("Git error: {0}")]
     Git(String),
+
+    /// Repository schema is newer than this build supports.
+    #[error(
+        "Unsupported schema version: repository uses version {0}, but this prl supports up to {1}. Upgrade prl to open it."
+    )]
+    UnsupportedSchema(u32, u32),
 }
@@ -0,0 +1,729 @@
+// Rust guideline compliant 2026-02-06
+
+//! Binary index for fast Pearl lookups and queries.
+//!
+//! The index maps Pearl IDs to byte offsets in the JSONL file. Since format
+//! version 2 it also stores postings for status, priority, labels and reverse
+//! dependency edges, together with a content hash of the JSONL file so that a
+//! stale index can be detected. The index is derived data and can always be
+//! rebuilt from the JSONL file.
+
+use crate::{DepType, Error, Pearl, Result, Status};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+const INDEX_MAGIC: [u8; 8] = *b"PRLIDX1\0";
+const INDEX_VERSION: u8 = 2;
+const LEGACY_INDEX_VERSION: u8 = 1;
+
+/// SHA-256 digest of a JSONL file's content.
+pub type ContentHash = [u8; 32];
+
+fn invalid_index_error(message: &str) -> Error {
+    Error::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        message,
+    ))
+}
+
+fn read_u8<R: std::io::Read>(reader: &mut R) -> Result<u8> {
+    let mut buf = [0u8; 1];
+    reader.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+fn read_u32<R: std::io::Read>(reader: &mut R) -> Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: std::io::Read>(reader: &mut R) -> Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_string<R: std::io::Read>(reader: &mut R) -> Result<String> {
+    let len = read_u32(reader)? as usize;
+    let mut bytes = vec![0u8; len];
+    reader.read_exact(&mut bytes)?;
+    String::from_utf8(bytes).map_err(|_| invalid_index_error("Index entry has invalid UTF-8"))
+}
+
+fn read_ordinal<R: std::io::Read>(reader: &mut R, ids: &[String]) -> Result<String> {
+    let ordinal = read_u32(reader)? as usize;
+    ids.get(ordinal)
+        .cloned()
+        .ok_or_else(|| invalid_index_error("Index posting refers to unknown entry"))
+}
+
+fn read_id_set<R: std::io::Read>(reader: &mut R, ids: &[String]) -> Result<BTreeSet<String>> {
+    let count = read_u64(reader)?;
+    let mut set = BTreeSet::new();
+    for _ in 0..count {
+        set.insert(read_ordinal(reader, ids)?);
+    }
+    Ok(set)
+}
+
+fn write_u32<W: std::io::Write>(writer: &mut W, value: u32) -> Result<()> {
+    writer.write_all(&value.to_le_bytes())?;
+    Ok(())
+}
+
+fn write_u64<W: std::io::Write>(writer: &mut W, value: u64) -> Result<()> {
+    writer.write_all(&value.to_le_bytes())?;
+    Ok(())
+}
+
+fn write_string<W: std::io::Write>(writer: &mut W, value: &str) -> Result<()> {
+    write_u32(writer, value.len() as u32)?;
+    writer.write_all(value.as_bytes())?;
+    Ok(())
+}
+
+fn write_ordinal<W: std::io::Write>(
+    writer: &mut W,
+    id: &str,
+    ordinals: &HashMap<&str, u32>,
+) -> Result<()> {
+    let ordinal = ordinals
+        .get(id)
+        .ok_or_else(|| invalid_index_error("Index posting refers to unknown entry"))?;
+    write_u32(writer, *ordinal)
+}
+
+fn write_id_set<W: std::io::Write>(
+    writer: &mut W,
+    ids: &BTreeSet<String>,
+    ordinals: &HashMap<&str, u32>,
+) -> Result<()> {
+    write_u64(writer, ids.len() as u64)?;
+    for id in ids {
+        write_ordinal(writer, id, ordinals)?;
+    }
+    Ok(())
+}
+
+fn status_tag(status: Status) -> u8 {
+    match status {
+        Status::Open => 0,
+        Status::InProgress => 1,
+        Status::Blocked => 2,
+        Status::Deferred => 3,
+        Status::Closed => 4,
+    }
+}
+
+fn status_from_tag(tag: u8) -> Result<Status> {
+    match tag {
+        0 => Ok(Status::Open),
+        1 => Ok(Status::InProgress),
+        2 => Ok(Status::Blocked),
+        3 => Ok(Status::Deferred),
+        4 => Ok(Status::Closed),
+        _ => Err(invalid_index_error("Index has unknown status tag")),
+    }
+}
+
+fn dep_type_tag(dep_type: DepType) -> u8 {
+    match dep_type {
+        DepType::Blocks => 0,
+        DepType::ParentChild => 1,
+        DepType::Related => 2,
+        DepType::DiscoveredFrom => 3,
+    }
+}
+
+fn dep_type_from_tag(tag: u8) -> Result<DepType> {
+    match tag {
+        0 => Ok(DepType::Blocks),
+        1 => Ok(DepType::ParentChild),
+        2 => Ok(DepType::Related),
+        3 => Ok(DepType::DiscoveredFrom),
+        _ => Err(invalid_index_error("Index has unknown dependency tag")),
+    }
+}
+
+/// Computes the content hash of a file.
+///
+/// # Arguments
+///
+/// * `path` - Path to the file
+///
+/// # Returns
+///
+/// The SHA-256 digest of the file content. A missing file hashes as empty content.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read.
+pub fn content_hash(path: &Path) -> Result<ContentHash> {
+    use std::fs::File;
+    use std::io::Read;
+
+    let mut hasher = Sha256::new();
+    if path.exists() {
+        let mut file = File::open(path)?;
+        let mut buf = vec![0u8; 64 * 1024];
+        loop {
+            let read = file.read(&mut buf)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buf[..read]);
+        }
+    }
+    Ok(hasher.finalize().into())
+}
+
+/// Inverted lists over the queryable fields of indexed Pearls.
+///
+/// Labels are keyed case-insensitively (ASCII), matching list filtering.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Postings {
+    /// Pearl IDs grouped by status.
+    status: BTreeMap<Status, BTreeSet<String>>,
+    /// Pearl IDs grouped by priority.
+    priority: BTreeMap<u8, BTreeSet<String>>,
+    /// Pearl IDs grouped by lowercased label.
+    labels: BTreeMap<String, BTreeSet<String>>,
+    /// Reverse dependency edges: target ID to dependent IDs and edge types.
+    dependents: BTreeMap<String, BTreeSet<(String, DepType)>>,
+}
+
+impl Postings {
+    /// Adds a Pearl to the postings, replacing any previous entry for its ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `pearl` - The Pearl to index
+    pub fn insert(&mut self, pearl: &Pearl) {
+        self.remove(&pearl.id);
+        self.status
+            .entry(pearl.status)
+            .or_default()
+            .insert(pearl.id.clone());
+        self.priority
+            .entry(pearl.priority)
+            .or_default()
+            .insert(pearl.id.clone());
+        for label in &pearl.labels {
+            self.labels
+                .entry(label.to_ascii_lowercase())
+                .or_default()
+                .insert(pearl.id.clone());
+        }
+        for dep in &pearl.deps {
+            self.dependents
+                .entry(dep.target_id.clone())
+                .or_default()
+                .insert((pearl.id.clone(), dep.dep_type));
+        }
+    }
+
+    /// Removes a Pearl ID from all postings.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The Pearl ID
+    pub fn remove(&mut self, id: &str) {
+        for ids in self.status.values_mut() {
+            ids.remove(id);
+        }
+        for ids in self.priority.values_mut() {
+            ids.remove(id);
+        }
+        for ids in self.labels.values_mut() {
+            ids.remove(id);
+        }
+        for edges in self.dependents.values_mut() {
+            edges.retain(|(source, _)| source != id);
+        }
+        self.status.retain(|_, ids| !ids.is_empty());
+        self.priority.retain(|_, ids| !ids.is_empty());
+        self.labels.retain(|_, ids| !ids.is_empty());
+        self.dependents.retain(|_, edges| !edges.is_empty());
+    }
+
+    /// Clears all postings.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Returns the IDs of Pearls with the given status.
+    pub fn with_status(&self, status: Status) -> impl Iterator<Item = &str> {
+        self.status
+            .get(&status)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// Returns the IDs of Pearls with the given priority.
+    pub fn with_priority(&self, priority: u8) -> impl Iterator<Item = &str> {
+        self.priority
+            .get(&priority)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// Returns the IDs of Pearls carrying the given label (case-insensitive).
+    pub fn with_label(&self, label: &str) -> impl Iterator<Item = &str> {
+        self.labels
+            .get(&label.to_ascii_lowercase())
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// Returns the status of an indexed Pearl.
+    pub fn status_of(&self, id: &str) -> Option<Status> {
+        self.status
+            .iter()
+            .find(|(_, ids)| ids.contains(id))
+            .map(|(status, _)| *status)
+    }
+
+    /// Returns the Pearls that depend on `target_id`, with the dependency type.
+    pub fn dependents(&self, target_id: &str) -> impl Iterator<Item = (&str, DepType)> {
+        self.dependents
+            .get(target_id)
+            .into_iter()
+            .flatten()
+            .map(|(source, dep_type)| (source.as_str(), *dep_type))
+    }
+
+    /// Finds a dependency cycle among the indexed edges.
+    ///
+    /// Walks the reverse dependency edges depth-first, so the check needs no
+    /// Pearl to be parsed.
+    ///
+    /// # Returns
+    ///
+    /// The Pearl IDs along a cycle, each depending on the next, or `None` if
+    /// the edges form a DAG.
+    pub fn find_cycle(&self) -> Option<Vec<String>> {
+        let mut done = BTreeSet::new();
+        for start in self.dependents.keys() {
+            if !done.insert(start.as_str()) {
+                continue;
+            }
+            let mut path = vec![start.as_str()];
+            let mut on_path = BTreeSet::from([start.as_str()]);
+            let mut stack = vec![self.dependents(start)];
+            while let Some(edges) = stack.last_mut() {
+                match edges.next() {
+                    Some((next, _)) if on_path.contains(next) => {
+                        let position = path.iter().position(|id| *id == next)?;
+                        return Some(
+                            path[position..]
+                                .iter()
+                                .rev()
+                                .map(|id| id.to_string())
+                                .collect(),
+                        );
+                    }
+                    Some((next, _)) => {
+                        if done.insert(next) {
+                            path.push(next);
+                            on_path.insert(next);
+                            stack.push(self.dependents(next));
+                        }
+                    }
+                    None => {
+                        stack.pop();
+                        if let Some(id) = path.pop() {
+                            on_path.remove(id);
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Writes the postings, referring to Pearls by their ordinal in the entry table.
+    fn write<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+        ordinals: &HashMap<&str, u32>,
+    ) -> Result<()> {
+        write_u32(writer, self.status.len() as u32)?;
+        for (status, ids) in &self.status {
+            writer.write_all(&[status_tag(*status)])?;
+            write_id_set(writer, ids, ordinals)?;
+        }
+
+        write_u32(writer, self.priority.len() as u32)?;
+        for (priority, ids) in &self.priority {
+            writer.write_all(&[*priority])?;
+            write_id_set(writer, ids, ordinals)?;
+        }
+
+        write_u32(writer, self.labels.len() as u32)?;
+        for (label, ids) in &self.labels {
+            write_string(writer, label)?;
+            write_id_set(writer, ids, ordinals)?;
+        }
+
+        // Targets are stored by name since they may reference missing Pearls.
+        write_u32(writer, self.dependents.len() as u32)?;
+        for (target, edges) in &self.dependents {
+            write_string(writer, target)?;
+            write_u64(writer, edges.len() as u64)?;
+            for (source, dep_type) in edges {
+                write_ordinal(writer, source, ordinals)?;
+                writer.write_all(&[dep_type_tag(*dep_type)])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads postings written by [`Postings::write`], resolving ordinals against `ids`.
+    fn read<R: std::io::Read>(reader: &mut R, ids: &[String]) -> Result<Self> {
+        let mut postings = Self::default();
+
+        for _ in 0..read_u32(reader)? {
+            let status = status_from_tag(read_u8(reader)?)?;
+            postings.status.insert(status, read_id_set(reader, ids)?);
+        }
+
+        for _ in 0..read_u32(reader)? {
+            let priority = read_u8(reader)?;
+            postings
+                .priority
+                .insert(priority, read_id_set(reader, ids)?);
+        }
+
+        for _ in 0..read_u32(reader)? {
+            let label = read_string(reader)?;
+            postings.labels.insert(label, read_id_set(reader, ids)?);
+        }
+
+        for _ in 0..read_u32(reader)? {
+            let target = read_string(reader)?;
+            let mut edges = BTreeSet::new();
+            for _ in 0..read_u64(reader)? {
+                let source = read_ordinal(reader, ids)?;
+                edges.insert((source, dep_type_from_tag(read_u8(reader)?)?));
+            }
+            postings.dependents.insert(target, edges);
+        }
+
+        Ok(postings)
+    }
+}
+
+/// Optional index for fast Pearl lookups by ID and by field.
+///
+/// Maps Pearl IDs to byte offsets in the JSONL file for O(log n) lookup performance,
+/// and keeps [`Postings`] so that queries can avoid parsing every Pearl.
+#[derive(Debug, Clone)]
+pub struct Index {
+    /// Mapping from Pearl ID to byte offset in the JSONL file.
+    map: HashMap<String, u64>,
+    /// Inverted lists over status, priority, labels and reverse dependencies.
+    postings: Postings,
+    /// Hash of the JSONL content the index was built from, if known.
+    content_hash: Option<ContentHash>,
+    /// Path to the index file.
+    path: PathBuf,
+}
+
+impl Index {
+    /// Creates a new Index instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the index file
+    ///
+    /// # Returns
+    ///
+    /// A new Index instance.
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            map: HashMap::new(),
+            postings: Postings::default(),
+            content_hash: None,
+            path,
+        }
+    }
+
+    /// Loads an Index from disk, or returns an empty Index if the file does not exist.
+    ///
+    /// Version 1 files (offsets only) are accepted; they carry no postings or
+    /// content hash and are therefore always reported as stale.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the index file
+    ///
+    /// # Returns
+    ///
+    /// An Index populated from disk, or empty if the file is missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but is invalid or unreadable.
+    pub fn load(path: PathBuf) -> Result<Self> {
+        use std::fs::File;
+        use std::io::{BufReader, Read};
+
+        if !path.exists() {
+            return Ok(Self::new(path));
+        }
+
+        let mut file = BufReader::new(File::open(&path)?);
+
+        let mut magic = [0u8; 8];
+        file.read_exact(&mut magic)?;
+        if magic != INDEX_MAGIC {
+            return Err(invalid_index_error("Invalid index magic header"));
+        }
+
+        let version = read_u8(&mut file)?;
+        if version != INDEX_VERSION && version != LEGACY_INDEX_VERSION {
+            return Err(invalid_index_error("Unsupported index version"));
+        }
+
+        let mut content_hash = None;
+        if version == INDEX_VERSION {
+            let mut hash = [0u8; 32];
+            file.read_exact(&mut hash)?;
+            content_hash = Some(hash);
+        }
+
+        let count = read_u64(&mut file)?;
+        let mut map = HashMap::with_capacity(count as usize);
+        let mut ids = Vec::with_capacity(count as usize);
+
+        for _ in 0..count {
+            let id = read_string(&mut file)?;
+            if id.is_empty() {
+                return Err(invalid_index_error("Index entry has empty ID"));
+            }
+            let offset = read_u64(&mut file)?;
+            map.insert(id.clone(), offset);
+            ids.push(id);
+        }
+
+        let postings = if version == INDEX_VERSION {
+            Postings::read(&mut file, &ids)?
+        } else {
+            Postings::default()
+        };
+
+        Ok(Self {
+            map,
+            postings,
+            content_hash,
+            path,
+        })
+    }
+
+    /// Writes the Index to disk using an atomic temp file + rename.
+    ///
+    /// # Returns
+    ///
+    /// Ok if the index was written successfully.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written or renamed.
+    pub fn save(&self) -> Result<()> {
+        use std::fs::File;
+        use std::io::{BufWriter, Write};
+
+        let temp_path = self.path.with_extension("bin.tmp");
+        let mut file = BufWriter::new(File::create(&temp_path)?);
+
+        file.write_all(&INDEX_MAGIC)?;
+        file.write_all(&[INDEX_VERSION])?;
+        file.write_all(&self.content_hash.unwrap_or_default())?;
+        write_u64(&mut file, self.map.len() as u64)?;
+
+        let mut entries: Vec<(&String, &u64)> = self.map.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut ordinals = HashMap::with_capacity(entries.len());
+        for (ordinal, (id, offset)) in entries.into_iter().enumerate() {
+            write_string(&mut file, id)?;
+            write_u64(&mut file, *offset)?;
+            ordinals.insert(id.as_str(), ordinal as u32);
+        }
+
+        self.postings.write(&mut file, &ordinals)?;
+
+        file.into_inner()
+            .map_err(|err| err.into_error())?
+            .sync_all()?;
+        std::fs::rename(&temp_path, &self.path)?;
+
+        Ok(())
+    }
+
+    /// Inserts a Pearl ID and its byte offset into the index.
+    ///
+    /// Only the offset is recorded; use [`Index::insert_pearl`] to keep the
+    /// postings in sync as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The Pearl ID
+    /// * `offset` - The byte offset in the JSONL file
+    pub fn insert(&mut self, id: String, offset: u64) {
+        self.map.insert(id, offset);
+    }
+
+    /// Inserts a Pearl's offset and postings into the index.
+    ///
+    /// # Arguments
+    ///
+    /// * `pearl` - The Pearl to index
+    /// * `offset` - The byte offset of its record in the JSONL file
+    pub fn insert_pearl(&mut self, pearl: &Pearl, offset: u64) {
+        self.map.insert(pearl.id.clone(), offset);
+        self.postings.insert(pearl);
+    }
+
+    /// Retrieves the byte offset for a Pearl ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The Pearl ID
+    ///
+    /// # Returns
+    ///
+    /// The byte offset if found, None otherwise.
+    pub fn get(&self, id: &str) -> Option<u64> {
+        self.map.get(id).copied()
+    }
+
+    /// Removes a Pearl ID from the index and its postings.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The Pearl ID
+    pub fn remove(&mut self, id: &str) {
+        self.map.remove(id);
+        self.postings.remove(id);
+    }
+
+    /// Rebuilds the Index by scanning the JSONL file.
+    ///
+    /// Every record is parsed to rebuild the postings, and the content hash is
+    /// recomputed from the bytes read. Blank and padding lines are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `jsonl_path` - Path to the JSONL file
+    ///
+    /// # Returns
+    ///
+    /// Ok if the index was rebuilt successfully.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSONL file cannot be read or contains invalid JSON.
+    pub fn rebuild(&mut self, jsonl_path: &Path) -> Result<()> {
+        use std::fs::File;
+        use std::io::{BufRead, BufReader};
+
+        self.clear();
+
+        let mut hasher = Sha256::new();
+        if !jsonl_path.exists() {
+            self.content_hash = Some(hasher.finalize().into());
+            return Ok(());
+        }
+
+        let file = File::open(jsonl_path)?;
+        let mut reader = BufReader::with_capacity(64 * 1024, file);
+        let mut buf = Vec::new();
+        let mut offset: u64 = 0;
+
+        loop {
+            buf.clear();
+            let bytes = reader.read_until(b'\n', &mut buf)?;
+            if bytes == 0 {
+                break;
+            }
+            hasher.update(&buf);
+
+            if !buf.iter().all(|b| b.is_ascii_whitespace()) {
+                let pearl: Pearl = serde_json::from_slice(&buf)?;
+                self.insert_pearl(&pearl, offset);
+            }
+            offset = offset.saturating_add(bytes as u64);
+        }
+
+        self.content_hash = Some(hasher.finalize().into());
+        Ok(())
+    }
+
+    /// Clears all entries from the index.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.postings.clear();
+        self.content_hash = None;
+    }
+
+    /// Returns the number of entries in the index.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns an iterator over index entries.
+    pub fn entries(&self) -> impl Iterator<Item = (&String, &u64)> {
+        self.map.iter()
+    }
+
+    /// Returns the postings for indexed Pearls.
+    pub fn postings(&self) -> &Postings {
+        &self.postings
+    }
+
+    /// Returns the hash of the JSONL content the index was built from, if known.
+    pub fn content_hash(&self) -> Option<&ContentHash> {
+        self.content_hash.as_ref()
+    }
+
+    /// Records the hash of the JSONL content the index now reflects.
+    ///
+    /// # Arguments
+    ///
+    /// * `hash` - The content hash
+    pub fn set_content_hash(&mut self, hash: ContentHash) {
+        self.content_hash = Some(hash);
+    }
+
+    /// Checks whether the index no longer matches the JSONL file.
+    ///
+    /// # Arguments
+    ///
+    /// * `jsonl_path` - Path to the JSONL file
+    ///
+    /// # Returns
+    ///
+    /// True if the index has no content hash or the hash differs from the file's.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSONL file cannot be read.
+    pub fn is_stale(&self, jsonl_path: &Path) -> Result<bool> {
+        match self.content_hash {
+            Some(hash) => Ok(hash != content_hash(jsonl_path)?),
+            None => Ok(true),
+        }
+    }
+}
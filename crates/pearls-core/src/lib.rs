@@ -4,10 +4,13 @@
 //!
 //! This crate provides the foundational components for the Pearls issue tracking system:
 //! - Data models (Pearl, Dependency, Status)
-//! - Storage engine (JSONL read/write, streaming, indexing)
+//! - Storage engine (JSONL or one-file-per-Pearl layouts, streaming, indexing)
+//! - Binary index with postings for field queries
 //! - Graph algorithms (DAG, cycle detection, topological sort)
 //! - FSM logic (state transitions, validation)
 //! - Hash ID generation and resolution
+//! - Schema versioning and migrations
+//! - Branch-per-Pearl workflow helpers
 //! - Error types and result handling
 
 pub mod config;
@@ -15,12 +18,22 @@ pub mod error;
 pub mod fsm;
 pub mod graph;
 pub mod identity;
+pub mod index;
+pub mod migrations;
 pub mod models;
 pub mod storage;
+pub mod workflow;
 
-pub use config::{Config, OutputFormat};
+pub use config::{
+    ChangelogConfig, ChangelogSection, ColorMode, CommitsConfig, Config, ConfigEntry, ConfigSource,
+    EventHook, EventKind, EventsConfig, LayeredConfig, McpConfig, McpPolicy, OutputFormat,
+    StorageLayout, TransitionRule, UiConfig, UserConfig, WorkflowConfig, CONFIG_ENV,
+    GLOBAL_CONFIG_ENV, REPO_ONLY_KEYS,
+};
 pub use error::{Error, Result};
 pub use fsm::validate_transition;
 pub use graph::IssueGraph;
-pub use models::{Comment, DepType, Dependency, Pearl, Status};
-pub use storage::Storage;
+pub use index::{Index, Postings};
+pub use migrations::CURRENT_SCHEMA_VERSION;
+pub use models::{Comment, CommentKind, DepType, Dependency, Pearl, Status};
+pub use storage::{Storage, StorageBackend};
@@ -0,0 +1,187 @@
+// Rust guideline compliant 2026-02-09
+
+//! Schema versioning and migrations for stored Pearls.
+//!
+//! The schema version of a repository is recorded as `schema_version` in
+//! `.pearls/config.toml`; a config without it predates versioning and is
+//! treated as version 1. Each [`Migration`] upgrades a single record from one
+//! version to the next and operates on the raw JSON object, so steps can rename
+//! or restructure fields that the current [`Pearl`] model no longer knows.
+
+use crate::{Error, Pearl, Result};
+use serde_json::{Map, Value};
+
+/// Schema version written by this build.
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// Schema version of repositories created before versioning was introduced.
+pub const LEGACY_SCHEMA_VERSION: u32 = 1;
+
+/// A single upgrade step between two adjacent schema versions.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// Version the step upgrades from; it produces `from + 1`.
+    pub from: u32,
+    /// Human-readable summary of the change.
+    pub description: &'static str,
+    /// Transforms one record in place.
+    pub apply: fn(&mut Map<String, Value>),
+}
+
+/// All migrations, ordered by `from` version with no gaps.
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    from: 1,
+    description: "Record the schema version in config.toml",
+    apply: record_schema_version,
+}];
+
+/// Checks that a repository's schema version can be opened by this build.
+///
+/// # Arguments
+///
+/// * `version` - Schema version recorded by the repository
+///
+/// # Errors
+///
+/// Returns `Error::UnsupportedSchema` if the repository was written by a newer
+/// version of Pearls, or `Error::InvalidPearl` if the version is zero.
+pub fn check_schema_version(version: u32) -> Result<()> {
+    if version == 0 {
+        return Err(Error::InvalidPearl(
+            "schema_version must be at least 1".to_string(),
+        ));
+    }
+    if version > CURRENT_SCHEMA_VERSION {
+        return Err(Error::UnsupportedSchema(version, CURRENT_SCHEMA_VERSION));
+    }
+    Ok(())
+}
+
+/// Returns the migrations needed to bring `version` up to date.
+///
+/// # Arguments
+///
+/// * `version` - Schema version recorded by the repository
+///
+/// # Returns
+///
+/// The pending steps in the order they must be applied; empty if the schema is
+/// current.
+///
+/// # Errors
+///
+/// Returns an error if the version is not supported by this build.
+pub fn pending_migrations(version: u32) -> Result<&'static [Migration]> {
+    check_schema_version(version)?;
+    let start = (version - LEGACY_SCHEMA_VERSION) as usize;
+    Ok(&MIGRATIONS[start..])
+}
+
+/// Upgrades a single Pearl from `version` to the current schema.
+///
+/// Fields unknown to the model are carried in [`Pearl::extra`], so steps see
+/// the record exactly as it was stored.
+///
+/// # Arguments
+///
+/// * `pearl` - Pearl as loaded from storage
+/// * `version` - Schema version the Pearl was written with
+///
+/// # Returns
+///
+/// The upgraded Pearl.
+///
+/// # Errors
+///
+/// Returns an error if the version is unsupported, the record cannot be
+/// converted, or the upgraded Pearl fails validation.
+pub fn migrate_pearl(pearl: &Pearl, version: u32) -> Result<Pearl> {
+    apply_steps(pearl, pending_migrations(version)?)
+}
+
+/// Applies `steps` to a Pearl in order and validates the result.
+fn apply_steps(pearl: &Pearl, steps: &[Migration]) -> Result<Pearl> {
+    let mut record = match serde_json::to_value(pearl)? {
+        Value::Object(record) => record,
+        _ => {
+            return Err(Error::InvalidPearl(format!(
+                "Pearl {} did not serialize to an object",
+                pearl.id
+            )))
+        }
+    };
+    for step in steps {
+        (step.apply)(&mut record);
+    }
+
+    let migrated: Pearl = serde_json::from_value(Value::Object(record))?;
+    migrated.validate()?;
+    Ok(migrated)
+}
+
+/// Version 1 to 2: records are unchanged. Version 2 only marks repositories
+/// that record `schema_version`, so later steps have a known starting point.
+fn record_schema_version(_record: &mut Map<String, Value>) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_are_ordered_and_complete() {
+        for (offset, step) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(step.from, LEGACY_SCHEMA_VERSION + offset as u32);
+        }
+        assert_eq!(
+            LEGACY_SCHEMA_VERSION + MIGRATIONS.len() as u32,
+            CURRENT_SCHEMA_VERSION
+        );
+        assert!(pending_migrations(CURRENT_SCHEMA_VERSION)
+            .expect("current")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_newer_schema_is_refused() {
+        let err = pending_migrations(CURRENT_SCHEMA_VERSION + 1).expect_err("newer");
+        assert!(matches!(
+            err,
+            Error::UnsupportedSchema(_, CURRENT_SCHEMA_VERSION)
+        ));
+        assert!(err.to_string().contains("Upgrade prl"));
+    }
+
+    #[test]
+    fn test_migrate_v1_keeps_records_unchanged() {
+        let mut pearl = Pearl::new("Legacy".to_string(), "author".to_string());
+        pearl.labels = vec!["ui".to_string(), "core".to_string()];
+        pearl.extra.insert("estimate".to_string(), Value::from(3));
+
+        let migrated = migrate_pearl(&pearl, LEGACY_SCHEMA_VERSION).expect("migrate");
+        assert_eq!(migrated, pearl);
+    }
+
+    #[test]
+    fn test_steps_see_raw_record_and_keep_unknown_fields() {
+        fn rename_estimate(record: &mut Map<String, Value>) {
+            if let Some(estimate) = record.remove("estimate") {
+                record.insert("points".to_string(), estimate);
+            }
+        }
+        let steps = [Migration {
+            from: LEGACY_SCHEMA_VERSION,
+            description: "Rename estimate to points",
+            apply: rename_estimate,
+        }];
+
+        let mut pearl = Pearl::new("Legacy".to_string(), "author".to_string());
+        pearl.extra.insert("estimate".to_string(), Value::from(3));
+        pearl.extra.insert("owner".to_string(), Value::from("ada"));
+
+        let migrated = apply_steps(&pearl, &steps).expect("migrate");
+        assert_eq!(migrated.extra.get("estimate"), None);
+        assert_eq!(migrated.extra.get("points"), Some(&Value::from(3)));
+        assert_eq!(migrated.extra.get("owner"), Some(&Value::from("ada")));
+        assert_eq!(migrated.title, pearl.title);
+    }
+}
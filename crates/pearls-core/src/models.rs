@@ -7,7 +7,7 @@ use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 
 /// Status of a Pearl in the finite state machine.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Status {
     /// Pearl is open and ready to be worked on.
@@ -23,7 +23,7 @@ pub enum Status {
 }
 
 /// Type of dependency relationship between Pearls.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum DepType {
     /// Blocking dependency - target must be closed before dependent can progress.
@@ -45,6 +45,43 @@ pub struct Dependency {
     pub dep_type: DepType,
 }
 
+/// Kind of a comment, used by agent sessions to structure their notes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentKind {
+    /// Free-form comment.
+    #[default]
+    Note,
+    /// Progress made on the Pearl.
+    Progress,
+    /// Decision taken and its rationale.
+    Decision,
+    /// Obstacle that stops the work.
+    Blocker,
+    /// Summary left for the next agent or person picking up the Pearl.
+    Handoff,
+}
+
+impl CommentKind {
+    /// Returns the serialized name of the kind.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CommentKind::Note => "note",
+            CommentKind::Progress => "progress",
+            CommentKind::Decision => "decision",
+            CommentKind::Blocker => "blocker",
+            CommentKind::Handoff => "handoff",
+        }
+    }
+
+    /// Whether this is a plain note.
+    #[must_use]
+    pub fn is_note(&self) -> bool {
+        *self == CommentKind::Note
+    }
+}
+
 /// A comment attached to a Pearl.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Comment {
@@ -56,6 +93,9 @@ pub struct Comment {
     pub body: String,
     /// Unix timestamp of creation.
     pub created_at: i64,
+    /// Kind of comment; plain notes are not serialized.
+    #[serde(default, skip_serializing_if = "CommentKind::is_note")]
+    pub kind: CommentKind,
 }
 
 /// A Pearl represents a single issue or task.
@@ -91,6 +131,9 @@ pub struct Pearl {
     /// Comments attached to this Pearl.
     #[serde(default)]
     pub comments: Vec<Comment>,
+    /// Fields not known to this version, preserved so they round-trip unchanged.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Default priority value (medium).
@@ -132,6 +175,7 @@ impl Pearl {
             deps: Vec::new(),
             metadata: HashMap::new(),
             comments: Vec::new(),
+            extra: serde_json::Map::new(),
         }
     }
 
@@ -201,6 +245,69 @@ impl Pearl {
     ///
     /// Returns an error if the author or body is empty.
     pub fn add_comment(&mut self, author: String, body: String) -> crate::Result<String> {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_else(|_| std::time::Duration::from_secs(0))
+            .as_secs() as i64;
+
+        let comment_id = self.add_comment_at(author, body, now)?;
+        self.updated_at = now;
+        Ok(comment_id)
+    }
+
+    /// Adds a comment of the given kind and returns the new comment ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `author` - Comment author
+    /// * `body` - Comment body
+    /// * `kind` - Kind of comment
+    ///
+    /// # Returns
+    ///
+    /// The new comment ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the author or body is empty.
+    pub fn add_comment_of_kind(
+        &mut self,
+        author: String,
+        body: String,
+        kind: CommentKind,
+    ) -> crate::Result<String> {
+        let comment_id = self.add_comment(author, body)?;
+        if let Some(comment) = self.comments.last_mut() {
+            comment.kind = kind;
+        }
+        Ok(comment_id)
+    }
+
+    /// Adds a comment with an explicit creation time and returns its ID.
+    ///
+    /// Used when importing comments from other trackers. `updated_at` is only
+    /// moved forward if the comment is newer.
+    ///
+    /// # Arguments
+    ///
+    /// * `author` - Comment author
+    /// * `body` - Comment body
+    /// * `created_at` - Unix timestamp of the comment
+    ///
+    /// # Returns
+    ///
+    /// The new comment ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the author or body is empty.
+    pub fn add_comment_at(
+        &mut self,
+        author: String,
+        body: String,
+        created_at: i64,
+    ) -> crate::Result<String> {
         let author = author.trim().to_string();
         let body = body.trim().to_string();
 
@@ -216,15 +323,9 @@ impl Pearl {
             ));
         }
 
-        use std::time::{SystemTime, UNIX_EPOCH};
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_else(|_| std::time::Duration::from_secs(0))
-            .as_secs() as i64;
-
         let mut nonce = 0u32;
         let comment_id = loop {
-            let id = generate_comment_id(&self.id, &author, &body, now, nonce);
+            let id = generate_comment_id(&self.id, &author, &body, created_at, nonce);
             if !self.comments.iter().any(|comment| comment.id == id) {
                 break id;
             }
@@ -235,9 +336,10 @@ impl Pearl {
             id: comment_id.clone(),
             author,
             body,
-            created_at: now,
+            created_at,
+            kind: CommentKind::Note,
         });
-        self.updated_at = now;
+        self.updated_at = self.updated_at.max(created_at);
 
         Ok(comment_id)
     }
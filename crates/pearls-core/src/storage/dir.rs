@@ -0,0 +1,228 @@
+// Rust guideline compliant 2026-02-06
+
+//! Directory storage backend.
+//!
+//! Stores each Pearl as pretty-printed JSON in its own `<id>.json` file, so that
+//! concurrent changes to different Pearls touch different files and merge with
+//! plain Git.
+
+use super::StorageBackend;
+use crate::identity::validate_id_format;
+use crate::index::ContentHash;
+use crate::{Error, Pearl, Result};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// File extension of Pearl records.
+const RECORD_EXTENSION: &str = "json";
+
+/// Storage backend for a directory with one JSON file per Pearl.
+pub struct DirBackend {
+    /// Path to the directory.
+    path: PathBuf,
+}
+
+impl DirBackend {
+    /// Creates a new directory backend.
+    ///
+    /// The directory is created on the first write.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the directory
+    ///
+    /// # Returns
+    ///
+    /// A new backend.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is empty.
+    pub fn new(path: PathBuf) -> Result<Self> {
+        if path.as_os_str().is_empty() {
+            return Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Path cannot be empty",
+            )));
+        }
+        Ok(Self { path })
+    }
+
+    /// Returns the path of the record file for `id`, or `None` if the ID is not
+    /// a valid Pearl ID (and so cannot name a record).
+    fn record_path(&self, id: &str) -> Option<PathBuf> {
+        validate_id_format(id).ok()?;
+        Some(self.path.join(format!("{}.{}", id, RECORD_EXTENSION)))
+    }
+
+    /// Lists record files sorted by name, which is also ID order.
+    fn record_files(&self) -> Result<Vec<PathBuf>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(&self.path)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == RECORD_EXTENSION) && path.is_file() {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    /// Reads a record file, returning `None` if it is missing.
+    fn read_record(path: &Path) -> Result<Option<Pearl>> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let pearl: Pearl = serde_json::from_str(&content)?;
+        pearl.validate()?;
+        Ok(Some(pearl))
+    }
+
+    /// Writes a record atomically, skipping the write if the content is unchanged.
+    fn write_record(&self, pearl: &Pearl) -> Result<()> {
+        let path = self
+            .record_path(&pearl.id)
+            .ok_or_else(|| Error::InvalidPearl(format!("Invalid Pearl ID: {}", pearl.id)))?;
+        let mut content = serde_json::to_string_pretty(pearl)?;
+        content.push('\n');
+
+        if std::fs::read(&path).is_ok_and(|existing| existing == content.as_bytes()) {
+            return Ok(());
+        }
+
+        std::fs::create_dir_all(&self.path)?;
+        let temp_path = self.path.join(format!(".{}.tmp", pearl.id));
+        {
+            use std::io::Write;
+
+            let mut file = std::fs::File::create(&temp_path)?;
+            file.write_all(content.as_bytes())?;
+            file.sync_all()?;
+        }
+        std::fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+}
+
+impl StorageBackend for DirBackend {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads all Pearls in ID order.
+    ///
+    /// Files with invalid JSON are skipped with a warning, matching the JSONL
+    /// backend's handling of malformed lines.
+    fn load_all(&self) -> Result<Vec<Pearl>> {
+        let mut pearls = Vec::new();
+        for path in self.record_files()? {
+            match Self::read_record(&path) {
+                Ok(Some(pearl)) => pearls.push(pearl),
+                Ok(None) => {}
+                Err(Error::Json(err)) => {
+                    eprintln!(
+                        "Warning: Skipping malformed JSON file {}: {}",
+                        path.display(),
+                        err
+                    );
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(pearls)
+    }
+
+    fn load_by_id(&mut self, id: &str) -> Result<Pearl> {
+        let path = self
+            .record_path(id)
+            .ok_or_else(|| Error::NotFound(id.to_string()))?;
+        Self::read_record(&path)?.ok_or_else(|| Error::NotFound(id.to_string()))
+    }
+
+    fn load_by_ids(&self, ids: &[&str]) -> Result<Vec<Pearl>> {
+        let mut wanted: Vec<&str> = ids.to_vec();
+        wanted.sort_unstable();
+        wanted.dedup();
+
+        let mut pearls = Vec::with_capacity(wanted.len());
+        for id in wanted {
+            if let Some(path) = self.record_path(id) {
+                if let Some(pearl) = Self::read_record(&path)? {
+                    pearls.push(pearl);
+                }
+            }
+        }
+        Ok(pearls)
+    }
+
+    fn is_empty(&self) -> Result<bool> {
+        Ok(self.record_files()?.is_empty())
+    }
+
+    fn save(&mut self, pearl: &Pearl) -> Result<()> {
+        pearl.validate()?;
+        self.write_record(pearl)
+    }
+
+    /// Writes every Pearl to its file and removes files for Pearls that are no
+    /// longer present. Unchanged files are left untouched.
+    fn save_all(&mut self, pearls: &[Pearl]) -> Result<()> {
+        for pearl in pearls {
+            pearl.validate()?;
+        }
+
+        for pearl in pearls {
+            self.write_record(pearl)?;
+        }
+
+        let keep: HashSet<PathBuf> = pearls
+            .iter()
+            .filter_map(|pearl| self.record_path(&pearl.id))
+            .collect();
+        for path in self.record_files()? {
+            if !keep.contains(&path) {
+                std::fs::remove_file(&path)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, id: &str) -> Result<()> {
+        let path = self
+            .record_path(id)
+            .ok_or_else(|| Error::NotFound(id.to_string()))?;
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                Err(Error::NotFound(id.to_string()))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Records are written in canonical form, so there is nothing to compact.
+    fn compact(&mut self) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Hashes the name and content of every record file in name order.
+    fn content_hash(&self) -> Result<ContentHash> {
+        let mut hasher = Sha256::new();
+        for path in self.record_files()? {
+            let content = std::fs::read(&path)?;
+            if let Some(name) = path.file_name() {
+                hasher.update(name.as_encoded_bytes());
+            }
+            hasher.update((content.len() as u64).to_le_bytes());
+            hasher.update(&content);
+        }
+        Ok(hasher.finalize().into())
+    }
+}
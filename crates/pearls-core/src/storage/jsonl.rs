@@ -0,0 +1,848 @@
+// Rust guideline compliant 2026-02-06
+
+//! JSONL storage backend.
+//!
+//! Stores all Pearls as one JSON object per line in a single file, with support
+//! for streaming, incremental writes, and an optional binary index.
+
+use super::StorageBackend;
+use crate::index::{content_hash, ContentHash, Index};
+use crate::{Error, Pearl, Result};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Number of out-of-order records tolerated before `save` compacts the file.
+const COMPACT_THRESHOLD: usize = 64;
+
+/// Minimal view of a JSONL record used to locate lines without a full parse.
+#[derive(Deserialize)]
+struct RecordId {
+    id: String,
+}
+
+/// Location of a single record within the JSONL file.
+#[derive(Debug, Clone)]
+struct LineSpan {
+    /// Pearl ID stored on the line.
+    id: String,
+    /// Byte offset of the start of the line.
+    offset: u64,
+    /// Length of the line in bytes, excluding the line terminator.
+    len: u64,
+}
+
+/// Reads a single line starting at the reader's position into `buf`.
+///
+/// Returns the number of bytes consumed and the span of the record, if the line
+/// holds one. Blank and padding-only lines yield `None`.
+fn read_span<R: std::io::BufRead>(
+    reader: &mut R,
+    offset: u64,
+    buf: &mut Vec<u8>,
+) -> Result<(usize, Option<LineSpan>)> {
+    buf.clear();
+    let bytes = reader.read_until(b'\n', buf)?;
+    if bytes == 0 {
+        return Ok((0, None));
+    }
+
+    let mut content: &[u8] = buf;
+    if let Some(rest) = content.strip_suffix(b"\n") {
+        content = rest;
+    }
+    if let Some(rest) = content.strip_suffix(b"\r") {
+        content = rest;
+    }
+    if content.iter().all(|b| b.is_ascii_whitespace()) {
+        return Ok((bytes, None));
+    }
+
+    let record: RecordId = serde_json::from_slice(content)?;
+    Ok((
+        bytes,
+        Some(LineSpan {
+            id: record.id,
+            offset,
+            len: content.len() as u64,
+        }),
+    ))
+}
+
+/// Scans the JSONL file and returns the span of every record, in file order.
+///
+/// Only the `id` field of each line is decoded. Lines that cannot be decoded are
+/// skipped and left untouched.
+fn scan_spans(path: &Path) -> Result<Vec<LineSpan>> {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let file = File::open(path)?;
+    let mut reader = BufReader::with_capacity(64 * 1024, file);
+    let mut buf = Vec::new();
+    let mut spans = Vec::new();
+    let mut offset: u64 = 0;
+
+    loop {
+        let (bytes, span) = match read_span(&mut reader, offset, &mut buf) {
+            Ok(result) => result,
+            Err(Error::Json(_)) => (buf.len(), None),
+            Err(err) => return Err(err),
+        };
+        if bytes == 0 {
+            break;
+        }
+        spans.extend(span);
+        offset = offset.saturating_add(bytes as u64);
+    }
+
+    Ok(spans)
+}
+
+/// Counts records that appear after a record with a greater ID.
+///
+/// A file in canonical order yields zero; every tail append of an ID that does
+/// not sort last adds one.
+fn displaced_records<'a>(ids: impl IntoIterator<Item = &'a str>) -> usize {
+    let mut max: Option<&str> = None;
+    let mut displaced = 0;
+    for id in ids {
+        match max {
+            Some(current) if id < current => displaced += 1,
+            _ => max = Some(id),
+        }
+    }
+    displaced
+}
+
+/// Serializes Pearls into JSONL bytes, returning the offset of each record.
+fn render_jsonl(pearls: &[&Pearl]) -> Result<(Vec<u8>, Vec<u64>)> {
+    let mut bytes = Vec::new();
+    let mut offsets = Vec::with_capacity(pearls.len());
+    for pearl in pearls {
+        offsets.push(bytes.len() as u64);
+        // Serialize to single line (no newlines within JSON)
+        serde_json::to_writer(&mut bytes, pearl)?;
+        bytes.push(b'\n');
+    }
+    Ok((bytes, offsets))
+}
+
+/// Storage backend for a single JSONL file.
+///
+/// Manages JSONL file operations with support for streaming and optional indexing.
+pub struct JsonlBackend {
+    /// Path to the JSONL file.
+    path: PathBuf,
+    /// Optional index for fast lookups.
+    index: Option<Index>,
+}
+
+impl JsonlBackend {
+    /// Creates a new JSONL backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the JSONL file
+    ///
+    /// # Returns
+    ///
+    /// A new backend with no index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is invalid.
+    pub fn new(path: PathBuf) -> Result<Self> {
+        Self::validate_path(&path)?;
+        Ok(Self { path, index: None })
+    }
+
+    /// Creates a new JSONL backend with an optional index.
+    ///
+    /// The index is rebuilt when it is missing, invalid, or its content hash no longer
+    /// matches the JSONL file (for example after a checkout or merge rewrote the file).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the JSONL file
+    /// * `index_path` - Optional path to the index file
+    ///
+    /// # Returns
+    ///
+    /// A new backend.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is invalid.
+    pub fn with_index(path: PathBuf, index_path: Option<PathBuf>) -> Result<Self> {
+        Self::validate_path(&path)?;
+        let mut index = None;
+
+        if let Some(index_path) = index_path {
+            let index_exists = index_path.exists();
+            let mut needs_save = !index_exists;
+            let mut loaded = match Index::load(index_path.clone()) {
+                Ok(index) => index,
+                Err(err) => {
+                    if matches!(err, Error::Io(ref io_err) if io_err.kind() == std::io::ErrorKind::InvalidData)
+                    {
+                        needs_save = true;
+                        Index::new(index_path.clone())
+                    } else {
+                        return Err(err);
+                    }
+                }
+            };
+
+            if path.exists() && (loaded.is_empty() || loaded.is_stale(&path)?) {
+                loaded.rebuild(&path)?;
+                needs_save = true;
+            }
+
+            if needs_save {
+                loaded.save()?;
+            }
+
+            index = Some(loaded);
+        }
+
+        Ok(Self { path, index })
+    }
+
+    /// Validates that the path is suitable for storage operations.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to validate
+    ///
+    /// # Returns
+    ///
+    /// Ok if the path is valid, Err otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is empty or contains invalid components.
+    fn validate_path(path: &Path) -> Result<()> {
+        if path.as_os_str().is_empty() {
+            return Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Path cannot be empty",
+            )));
+        }
+        Ok(())
+    }
+
+    fn load_by_offset(path: &Path, id: &str, offset: u64) -> Result<Pearl> {
+        use std::fs::File;
+        use std::io::{BufRead, BufReader, Seek, SeekFrom};
+
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(offset))?;
+
+        let mut reader = BufReader::new(file);
+        let mut line = String::new();
+        let bytes = reader.read_line(&mut line)?;
+        if bytes == 0 {
+            return Err(Error::NotFound(id.to_string()));
+        }
+
+        let line_trimmed = line.trim();
+        if line_trimmed.is_empty() {
+            return Err(Error::NotFound(id.to_string()));
+        }
+
+        let pearl: Pearl = serde_json::from_str(line_trimmed)?;
+        if pearl.id != id {
+            return Err(Error::NotFound(id.to_string()));
+        }
+        pearl.validate()?;
+        Ok(pearl)
+    }
+
+    /// Atomically replaces the JSONL file and resets the index to the given records.
+    fn replace_contents(&mut self, bytes: &[u8], pearls: &[&Pearl], offsets: &[u64]) -> Result<()> {
+        use std::fs::File;
+        use std::io::Write;
+
+        // Create temp file in the same directory for atomic rename
+        let temp_path = self.path.with_extension("jsonl.tmp");
+
+        {
+            let mut file = File::create(&temp_path)?;
+            file.write_all(bytes)?;
+            file.sync_all()?;
+        }
+
+        // Atomic rename
+        std::fs::rename(&temp_path, &self.path)?;
+
+        // Update index if enabled
+        if let Some(index) = self.index.as_mut() {
+            index.clear();
+            for (pearl, offset) in pearls.iter().zip(offsets) {
+                index.insert_pearl(pearl, *offset);
+            }
+            index.set_content_hash(Sha256::digest(bytes).into());
+            index.save()?;
+        }
+
+        Ok(())
+    }
+
+    /// Records the current content hash in the index and writes it to disk.
+    fn persist_index(&mut self) -> Result<()> {
+        if let Some(index) = self.index.as_mut() {
+            index.set_content_hash(content_hash(&self.path)?);
+            index.save()?;
+        }
+        Ok(())
+    }
+
+    /// Finds the line holding `id` using the index, rebuilding it once if stale.
+    fn locate_indexed(&mut self, id: &str) -> Result<Option<LineSpan>> {
+        let Some(index) = self.index.as_mut() else {
+            return Ok(scan_spans(&self.path)?
+                .into_iter()
+                .find(|span| span.id == id));
+        };
+
+        if let Some(offset) = index.get(id) {
+            if let Some(span) = Self::span_at(&self.path, offset)? {
+                if span.id == id {
+                    return Ok(Some(span));
+                }
+            }
+
+            // Index appears out of sync; rebuild and retry once.
+            index.rebuild(&self.path)?;
+            index.save()?;
+            if let Some(offset) = index.get(id) {
+                return Self::span_at(&self.path, offset);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Reads the record span starting at `offset`, if any.
+    fn span_at(path: &Path, offset: u64) -> Result<Option<LineSpan>> {
+        use std::fs::File;
+        use std::io::{BufReader, Seek, SeekFrom};
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut reader = BufReader::new(file);
+        let mut buf = Vec::new();
+        match read_span(&mut reader, offset, &mut buf) {
+            Ok((_, span)) => Ok(span),
+            Err(Error::Json(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Overwrites the line at `offset` with `content`, padding to `len` bytes.
+    fn overwrite_line(&self, offset: u64, len: u64, content: &str) -> Result<()> {
+        use std::fs::OpenOptions;
+        use std::io::{Seek, SeekFrom, Write};
+
+        let mut bytes = content.as_bytes().to_vec();
+        bytes.resize(len as usize, b' ');
+
+        let mut file = OpenOptions::new().write(true).open(&self.path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        file.write_all(&bytes)?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    /// Appends a line to the end of the file and returns its offset.
+    fn append_line(&self, content: &str) -> Result<u64> {
+        use std::fs::OpenOptions;
+        use std::io::{Read, Seek, SeekFrom, Write};
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&self.path)?;
+
+        let mut offset = file.metadata()?.len();
+        let mut bytes = Vec::with_capacity(content.len() + 2);
+        if offset > 0 {
+            // Guard against a file whose last line lacks a terminator.
+            let mut last = [0u8; 1];
+            file.seek(SeekFrom::Start(offset - 1))?;
+            file.read_exact(&mut last)?;
+            if last[0] != b'\n' {
+                bytes.push(b'\n');
+                offset += 1;
+            }
+        }
+        bytes.extend_from_slice(content.as_bytes());
+        bytes.push(b'\n');
+
+        file.write_all(&bytes)?;
+        file.sync_data()?;
+        Ok(offset)
+    }
+}
+
+impl StorageBackend for JsonlBackend {
+    /// Returns a reference to the JSONL file path.
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns a reference to the index if present.
+    fn index(&self) -> Option<&Index> {
+        self.index.as_ref()
+    }
+
+    /// Returns a mutable reference to the index if present.
+    fn index_mut(&mut self) -> Option<&mut Index> {
+        self.index.as_mut()
+    }
+
+    /// Enables indexing with the given index path.
+    ///
+    /// # Arguments
+    ///
+    /// * `index_path` - Path to the index file
+    fn enable_index(&mut self, index_path: PathBuf) -> Result<()> {
+        let mut index = Index::new(index_path);
+        if self.path.exists() {
+            index.rebuild(&self.path)?;
+        }
+        index.save()?;
+        self.index = Some(index);
+        Ok(())
+    }
+
+    /// Disables indexing.
+    fn disable_index(&mut self) {
+        self.index = None;
+    }
+
+    /// Loads all Pearls from the JSONL file using streaming deserialization.
+    ///
+    /// # Returns
+    ///
+    /// A vector of all Pearls in the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file cannot be opened
+    /// - The file contains invalid JSON
+    /// - A Pearl fails validation
+    fn load_all(&self) -> Result<Vec<Pearl>> {
+        use std::fs::File;
+        use std::io::BufReader;
+
+        // Handle empty file case
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = File::open(&self.path)?;
+        let reader = BufReader::with_capacity(64 * 1024, file);
+        let mut pearls = Vec::new();
+
+        // Use streaming deserializer for memory efficiency
+        let stream = serde_json::Deserializer::from_reader(reader).into_iter::<Pearl>();
+
+        for result in stream {
+            match result {
+                Ok(pearl) => {
+                    pearl.validate()?;
+                    pearls.push(pearl);
+                }
+                Err(e) => {
+                    // Log malformed JSON but continue processing
+                    eprintln!("Warning: Skipping malformed JSON line: {}", e);
+                }
+            }
+        }
+
+        Ok(pearls)
+    }
+
+    /// Loads a single Pearl by ID from the JSONL file with early termination.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The Pearl ID to search for
+    ///
+    /// # Returns
+    ///
+    /// The Pearl if found.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file cannot be opened
+    /// - The Pearl is not found
+    /// - The file contains invalid JSON
+    fn load_by_id(&mut self, id: &str) -> Result<Pearl> {
+        use std::fs::File;
+        use std::io::BufReader;
+
+        // Check index first if available
+        if let Some(index) = self.index.as_mut() {
+            if let Some(offset) = index.get(id) {
+                if let Ok(pearl) = Self::load_by_offset(&self.path, id, offset) {
+                    return Ok(pearl);
+                }
+
+                // Index appears out of sync; rebuild and retry once.
+                index.rebuild(&self.path)?;
+                index.save()?;
+
+                if let Some(rebuilt_offset) = index.get(id) {
+                    if let Ok(pearl) = Self::load_by_offset(&self.path, id, rebuilt_offset) {
+                        return Ok(pearl);
+                    }
+                }
+            }
+        }
+
+        if !self.path.exists() {
+            return Err(Error::NotFound(id.to_string()));
+        }
+
+        let file = File::open(&self.path)?;
+        let reader = BufReader::with_capacity(64 * 1024, file);
+        let stream = serde_json::Deserializer::from_reader(reader).into_iter::<Pearl>();
+
+        for result in stream {
+            match result {
+                Ok(pearl) => {
+                    if pearl.id == id {
+                        pearl.validate()?;
+                        return Ok(pearl);
+                    }
+                }
+                Err(e) => {
+                    // Skip malformed JSON lines
+                    eprintln!("Warning: Skipping malformed JSON line: {}", e);
+                }
+            }
+        }
+
+        Err(Error::NotFound(id.to_string()))
+    }
+
+    /// Loads the Pearls with the given IDs.
+    ///
+    /// With an index, each record is read directly at its offset and no other line
+    /// is parsed. Without an index, the file is streamed once.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The Pearl IDs to load
+    ///
+    /// # Returns
+    ///
+    /// The matching Pearls in file order. IDs that are not present are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file cannot be read
+    /// - An indexed record contains invalid JSON or fails validation
+    fn load_by_ids(&self, ids: &[&str]) -> Result<Vec<Pearl>> {
+        use std::collections::HashSet;
+        use std::fs::File;
+        use std::io::{BufRead, BufReader, Seek, SeekFrom};
+
+        let wanted: HashSet<&str> = ids.iter().copied().collect();
+
+        let Some(index) = self.index.as_ref() else {
+            let mut pearls = self.load_all()?;
+            pearls.retain(|pearl| wanted.contains(pearl.id.as_str()));
+            return Ok(pearls);
+        };
+
+        let mut offsets: Vec<(u64, &str)> = wanted
+            .iter()
+            .filter_map(|id| index.get(id).map(|offset| (offset, *id)))
+            .collect();
+        offsets.sort_unstable();
+
+        if offsets.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut reader = BufReader::new(File::open(&self.path)?);
+        let mut line = String::new();
+        let mut pearls = Vec::with_capacity(offsets.len());
+        for (offset, id) in offsets {
+            reader.seek(SeekFrom::Start(offset))?;
+            line.clear();
+            reader.read_line(&mut line)?;
+            let pearl: Pearl = serde_json::from_str(line.trim())?;
+            if pearl.id != id {
+                return Err(Error::NotFound(id.to_string()));
+            }
+            pearl.validate()?;
+            pearls.push(pearl);
+        }
+
+        Ok(pearls)
+    }
+
+    /// Checks whether the file holds no Pearls.
+    ///
+    /// Uses the index when available; otherwise reads only until the first record.
+    ///
+    /// # Returns
+    ///
+    /// True if there are no records.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    fn is_empty(&self) -> Result<bool> {
+        use std::fs::File;
+        use std::io::{BufRead, BufReader};
+
+        if let Some(index) = self.index.as_ref() {
+            return Ok(index.is_empty());
+        }
+        if !self.path.exists() {
+            return Ok(true);
+        }
+
+        let reader = BufReader::new(File::open(&self.path)?);
+        for line in reader.split(b'\n') {
+            if !line?.iter().all(|b| b.is_ascii_whitespace()) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Saves a single Pearl to the JSONL file.
+    ///
+    /// Writes are incremental: if the Pearl already exists (by ID) and its new
+    /// serialization fits in the existing line, the line is overwritten in place and
+    /// padded with spaces. Otherwise the record is appended to the end of the file
+    /// and any previous line is blanked. Only record IDs are decoded to locate the
+    /// line, and the index (if enabled) is consulted instead of scanning.
+    ///
+    /// Appends leave the file out of canonical order; once more than a small number
+    /// of records are displaced, the file is compacted (see [`JsonlBackend::compact`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `pearl` - The Pearl to save
+    ///
+    /// # Returns
+    ///
+    /// Ok if the save was successful.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The Pearl fails validation
+    /// - The file cannot be read or written
+    fn save(&mut self, pearl: &Pearl) -> Result<()> {
+        pearl.validate()?;
+        let line = serde_json::to_string(pearl)?;
+
+        let mut spans = match self.index.is_some() {
+            true => None,
+            false => Some(scan_spans(&self.path)?),
+        };
+        let existing = match spans.as_ref() {
+            Some(spans) => spans.iter().find(|span| span.id == pearl.id).cloned(),
+            None => self.locate_indexed(&pearl.id)?,
+        };
+
+        let offset = match &existing {
+            Some(span) if line.len() as u64 <= span.len => {
+                self.overwrite_line(span.offset, span.len, &line)?;
+                span.offset
+            }
+            Some(span) => {
+                // Append first so a crash never loses the record; compaction
+                // resolves the duplicate by keeping the last occurrence.
+                let offset = self.append_line(&line)?;
+                self.overwrite_line(span.offset, span.len, "")?;
+                offset
+            }
+            None => self.append_line(&line)?,
+        };
+
+        let appended = existing.as_ref().is_none_or(|span| span.offset != offset);
+        if let Some(index) = self.index.as_mut() {
+            index.insert_pearl(pearl, offset);
+        }
+
+        // An in-place overwrite keeps the record order, so only an append can
+        // push the file past the compaction threshold.
+        let displaced = match (appended, spans.as_mut(), self.index.as_ref()) {
+            (false, _, _) => 0,
+            (true, Some(spans), _) => {
+                spans.retain(|span| span.id != pearl.id);
+                spans.push(LineSpan {
+                    id: pearl.id.clone(),
+                    offset,
+                    len: line.len() as u64,
+                });
+                displaced_records(spans.iter().map(|span| span.id.as_str()))
+            }
+            (true, None, Some(index)) => {
+                let mut entries: Vec<(&String, &u64)> = index.entries().collect();
+                entries.sort_by_key(|(_, offset)| **offset);
+                displaced_records(entries.into_iter().map(|(id, _)| id.as_str()))
+            }
+            (true, None, None) => 0,
+        };
+
+        if displaced > COMPACT_THRESHOLD {
+            self.compact()?;
+        } else {
+            self.persist_index()?;
+        }
+
+        Ok(())
+    }
+
+    /// Saves multiple Pearls to the JSONL file.
+    ///
+    /// Replaces the entire file with the provided Pearls, written in canonical order
+    /// (sorted by ID) so that the on-disk layout is deterministic.
+    /// Uses atomic write operations (temp file + rename) to ensure consistency.
+    ///
+    /// # Arguments
+    ///
+    /// * `pearls` - The Pearls to save
+    ///
+    /// # Returns
+    ///
+    /// Ok if the save was successful.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Any Pearl fails validation
+    /// - The file cannot be written
+    /// - The atomic write operation fails
+    fn save_all(&mut self, pearls: &[Pearl]) -> Result<()> {
+        // Validate all Pearls first
+        for pearl in pearls {
+            pearl.validate()?;
+        }
+
+        let mut ordered: Vec<&Pearl> = pearls.iter().collect();
+        ordered.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let (bytes, offsets) = render_jsonl(&ordered)?;
+        self.replace_contents(&bytes, &ordered, &offsets)
+    }
+
+    /// Rewrites the JSONL file in canonical form.
+    ///
+    /// Canonical form holds one record per ID (the last occurrence wins), sorted by ID,
+    /// with no padding or blank lines. The file is only rewritten if its content
+    /// differs from the canonical form.
+    ///
+    /// # Returns
+    ///
+    /// True if the file was rewritten, false if it was already canonical.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file cannot be read or written
+    /// - A Pearl fails validation
+    fn compact(&mut self) -> Result<bool> {
+        if !self.path.exists() {
+            return Ok(false);
+        }
+
+        let latest: BTreeMap<String, Pearl> = self
+            .load_all()?
+            .into_iter()
+            .map(|pearl| (pearl.id.clone(), pearl))
+            .collect();
+
+        let ordered: Vec<&Pearl> = latest.values().collect();
+        let (bytes, offsets) = render_jsonl(&ordered)?;
+        if std::fs::read(&self.path)? == bytes {
+            return Ok(false);
+        }
+
+        self.replace_contents(&bytes, &ordered, &offsets)?;
+        Ok(true)
+    }
+
+    /// Deletes a Pearl from the JSONL file by ID.
+    ///
+    /// Blanks the Pearl's line in place; the next compaction drops it.
+    /// Updates the index if enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the Pearl to delete
+    ///
+    /// # Returns
+    ///
+    /// Ok if the delete was successful.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file cannot be read or written
+    /// - The Pearl is not found
+    fn delete(&mut self, id: &str) -> Result<()> {
+        let span = self
+            .locate_indexed(id)?
+            .ok_or_else(|| Error::NotFound(id.to_string()))?;
+
+        self.overwrite_line(span.offset, span.len, "")?;
+
+        if let Some(index) = self.index.as_mut() {
+            index.remove(id);
+        }
+        self.persist_index()?;
+
+        Ok(())
+    }
+
+    /// Rebuilds the index from the JSONL file if indexing is enabled.
+    ///
+    /// # Returns
+    ///
+    /// Ok if the index was rebuilt successfully.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if indexing is disabled or the JSONL file cannot be read.
+    fn rebuild_index(&mut self) -> Result<()> {
+        if let Some(index) = self.index.as_mut() {
+            index.rebuild(&self.path)?;
+            index.save()?;
+            Ok(())
+        } else {
+            Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Indexing is not enabled",
+            )))
+        }
+    }
+
+    fn content_hash(&self) -> Result<ContentHash> {
+        content_hash(&self.path)
+    }
+}
@@ -0,0 +1,223 @@
+// Rust guideline compliant 2026-02-10
+
+//! Branch-per-Pearl workflow helpers.
+//!
+//! Builds branch names from a template, finds the Pearl a branch belongs to,
+//! and tracks the "current" Pearl of a clone in `.pearls/current`. The current
+//! Pearl is local state and is not committed.
+
+use crate::{Pearl, Result};
+use std::path::Path;
+
+/// File in `.pearls` holding the ID of the current Pearl.
+pub const CURRENT_FILE: &str = "current";
+
+/// Converts a title into lowercase words joined by `-`.
+///
+/// # Arguments
+///
+/// * `title` - The text to convert
+///
+/// # Returns
+///
+/// The slug, cut at a word boundary to at most 40 characters.
+#[must_use]
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for word in title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        if !slug.is_empty() && slug.len() + word.len() >= 40 {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word.to_ascii_lowercase());
+    }
+    slug.truncate(40);
+    slug
+}
+
+/// Builds the branch name of a Pearl.
+///
+/// # Arguments
+///
+/// * `template` - Template with `{id}` and `{slug}` placeholders
+/// * `pearl` - The Pearl to name the branch after
+///
+/// # Returns
+///
+/// The branch name. A `{slug}` of an empty title is dropped along with the
+/// separator next to it.
+#[must_use]
+pub fn branch_name(template: &str, pearl: &Pearl) -> String {
+    let slug = slugify(&pearl.title);
+    if !slug.is_empty() {
+        return template.replace("{slug}", &slug).replace("{id}", &pearl.id);
+    }
+    let is_separator = |c: char| matches!(c, '-' | '_' | '/');
+    template
+        .replace("-{slug}", "")
+        .replace("_{slug}", "")
+        .replace("/{slug}", "")
+        .replace("{slug}", "")
+        .trim_matches(is_separator)
+        .replace("{id}", &pearl.id)
+}
+
+/// Finds the Pearl a branch belongs to.
+///
+/// Looks for `prl-` followed by hexadecimal characters anywhere in the branch
+/// name, so any template that contains `{id}` is recognized.
+///
+/// # Arguments
+///
+/// * `branch` - Branch name, such as `prl/prl-a1b2c3-fix-login`
+/// * `pearls` - Known Pearls
+///
+/// # Returns
+///
+/// The ID of the matching Pearl, if any.
+#[must_use]
+pub fn pearl_id_from_branch(branch: &str, pearls: &[Pearl]) -> Option<String> {
+    let mut rest = branch;
+    while let Some(start) = rest.find("prl-") {
+        let candidate = &rest[start..];
+        let hex = candidate[4..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || ('a'..='f').contains(c))
+            .count()
+            .min(8);
+        // A slug may start with hex letters, so prefer the longest known ID.
+        for len in (6..=hex).rev() {
+            let id = &candidate[..4 + len];
+            if pearls.iter().any(|pearl| pearl.id == id) {
+                return Some(id.to_string());
+            }
+        }
+        rest = &candidate[4..];
+    }
+    None
+}
+
+/// Finds the Pearl being worked on.
+///
+/// The Pearl named in the branch wins; otherwise the current Pearl of the
+/// clone is used, as long as it still exists.
+///
+/// # Arguments
+///
+/// * `pearls_dir` - Path to the `.pearls` directory
+/// * `branch` - Checked-out branch, or `None` on a detached `HEAD`
+/// * `pearls` - Known Pearls
+///
+/// # Returns
+///
+/// The active Pearl ID, if any.
+///
+/// # Errors
+///
+/// Returns an error if the current Pearl file cannot be read.
+pub fn active_pearl(
+    pearls_dir: &Path,
+    branch: Option<&str>,
+    pearls: &[Pearl],
+) -> Result<Option<String>> {
+    if let Some(id) = branch.and_then(|branch| pearl_id_from_branch(branch, pearls)) {
+        return Ok(Some(id));
+    }
+    Ok(read_current(pearls_dir)?.filter(|id| pearls.iter().any(|pearl| pearl.id == *id)))
+}
+
+/// Reads the current Pearl of a clone.
+///
+/// # Arguments
+///
+/// * `pearls_dir` - Path to the `.pearls` directory
+///
+/// # Returns
+///
+/// The current Pearl ID, or `None` if none is set.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read.
+pub fn read_current(pearls_dir: &Path) -> Result<Option<String>> {
+    let path = pearls_dir.join(CURRENT_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let id = std::fs::read_to_string(path)?.trim().to_string();
+    Ok((!id.is_empty()).then_some(id))
+}
+
+/// Sets or clears the current Pearl of a clone.
+///
+/// # Arguments
+///
+/// * `pearls_dir` - Path to the `.pearls` directory
+/// * `id` - The Pearl ID, or `None` to clear it
+///
+/// # Returns
+///
+/// Ok if the file was written or removed.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written or removed.
+pub fn write_current(pearls_dir: &Path, id: Option<&str>) -> Result<()> {
+    let path = pearls_dir.join(CURRENT_FILE);
+    match id {
+        Some(id) => std::fs::write(path, format!("{}\n", id))?,
+        None if path.exists() => std::fs::remove_file(path)?,
+        None => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn pearl(id: &str, title: &str) -> Pearl {
+        let mut pearl = Pearl::new(title.to_string(), "tester".to_string());
+        pearl.id = id.to_string();
+        pearl
+    }
+
+    #[test]
+    fn test_branch_name_round_trips() {
+        let fix = pearl("prl-a1b2c3", "Fix login: handle 2FA tokens!");
+        let decaf = pearl("prl-a1b2c3de", "Decaf");
+        let untitled = pearl("prl-0f0f0f", "");
+
+        let branch = branch_name("prl/{id}-{slug}", &fix);
+        assert_eq!(branch, "prl/prl-a1b2c3-fix-login-handle-2fa-tokens");
+        assert_eq!(branch_name("{slug}/{id}", &untitled), "prl-0f0f0f");
+        assert!(slugify(&"word ".repeat(20)).len() <= 40);
+
+        let pearls = vec![fix.clone(), decaf.clone(), untitled];
+        assert_eq!(
+            pearl_id_from_branch(&branch, &pearls),
+            Some("prl-a1b2c3".to_string())
+        );
+        assert_eq!(
+            pearl_id_from_branch("feature/prl-a1b2c3de-decaf", &pearls),
+            Some("prl-a1b2c3de".to_string())
+        );
+        assert_eq!(
+            pearl_id_from_branch("prl/prl-a1b2c3-ad-hoc", &pearls[..1]),
+            Some("prl-a1b2c3".to_string())
+        );
+        assert_eq!(pearl_id_from_branch("main", &pearls), None);
+
+        let temp_dir = TempDir::new().unwrap();
+        write_current(temp_dir.path(), Some(&fix.id)).unwrap();
+        assert_eq!(read_current(temp_dir.path()).unwrap(), Some(fix.id));
+        write_current(temp_dir.path(), None).unwrap();
+        assert_eq!(read_current(temp_dir.path()).unwrap(), None);
+    }
+}
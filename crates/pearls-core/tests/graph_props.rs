@@ -50,6 +50,7 @@ fn arb_pearl() -> impl Strategy<Value = Pearl> {
                 deps: Vec::new(),
                 metadata: HashMap::new(),
                 comments: Vec::new(),
+                extra: Default::default(),
             },
         )
 }
@@ -23,6 +23,7 @@ fn create_pearl(id: &str, status: Status) -> Pearl {
         deps: Vec::new(),
         metadata: HashMap::new(),
         comments: Vec::new(),
+        extra: Default::default(),
     }
 }
 
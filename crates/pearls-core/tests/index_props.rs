@@ -35,6 +35,7 @@ fn pearls_from_ids(ids: Vec<String>) -> Vec<Pearl> {
             deps: Vec::new(),
             metadata: Default::default(),
             comments: Vec::new(),
+            extra: Default::default(),
         })
         .collect()
 }
@@ -0,0 +1,237 @@
+// Rust guideline compliant 2026-02-06
+
+//! Unit tests for the index module.
+//!
+//! These tests validate postings maintenance, persistence, and staleness detection.
+
+use pearls_core::{DepType, Dependency, Index, Pearl, Postings, Status, Storage};
+use std::fs;
+use tempfile::TempDir;
+
+/// Helper to create a test Pearl.
+fn create_test_pearl(id: &str, status: Status, labels: &[&str]) -> Pearl {
+    Pearl {
+        id: id.to_string(),
+        title: format!("Pearl {}", id),
+        description: String::new(),
+        status,
+        priority: 2,
+        created_at: 1000,
+        updated_at: 1000,
+        author: "test-author".to_string(),
+        labels: labels.iter().map(|label| label.to_string()).collect(),
+        deps: vec![],
+        metadata: Default::default(),
+        comments: Vec::new(),
+        extra: Default::default(),
+    }
+}
+
+fn sorted(ids: impl Iterator<Item = String>) -> Vec<String> {
+    let mut ids: Vec<String> = ids.collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn test_postings_round_trip() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let storage_path = temp_dir.path().join("issues.jsonl");
+    let index_path = temp_dir.path().join("index.bin");
+
+    let mut blocked = create_test_pearl("prl-222222", Status::Open, &["Backend"]);
+    blocked.deps.push(Dependency {
+        target_id: "prl-111111".to_string(),
+        dep_type: DepType::Blocks,
+    });
+    let pearls = vec![
+        create_test_pearl("prl-111111", Status::InProgress, &["backend", "urgent"]),
+        blocked,
+        create_test_pearl("prl-333333", Status::Closed, &[]),
+    ];
+
+    let mut storage = Storage::with_index(storage_path.clone(), Some(index_path.clone()))
+        .expect("Failed to create storage with index");
+    storage.save_all(&pearls).expect("Failed to save pearls");
+
+    let index = Index::load(index_path).expect("Failed to load index");
+    assert!(!index
+        .is_stale(&storage_path)
+        .expect("Failed to check index"));
+
+    let postings = index.postings();
+    assert_eq!(
+        sorted(postings.with_label("BACKEND").map(str::to_string)),
+        vec!["prl-111111", "prl-222222"]
+    );
+    assert_eq!(
+        sorted(postings.with_status(Status::Closed).map(str::to_string)),
+        vec!["prl-333333"]
+    );
+    assert_eq!(postings.with_priority(2).count(), 3);
+    assert_eq!(
+        postings.dependents("prl-111111").collect::<Vec<_>>(),
+        vec![("prl-222222", DepType::Blocks)]
+    );
+    assert_eq!(postings, storage.index().expect("Index enabled").postings());
+}
+
+#[test]
+fn test_save_updates_postings_incrementally() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let storage_path = temp_dir.path().join("issues.jsonl");
+    let index_path = temp_dir.path().join("index.bin");
+
+    let mut storage = Storage::with_index(storage_path.clone(), Some(index_path))
+        .expect("Failed to create storage with index");
+    let mut pearl = create_test_pearl("prl-111111", Status::Open, &["ui"]);
+    storage.save(&pearl).expect("Failed to save pearl");
+
+    pearl.status = Status::Closed;
+    pearl.labels = vec!["done".to_string()];
+    storage.save(&pearl).expect("Failed to update pearl");
+
+    let index = storage.index().expect("Index enabled");
+    let postings = index.postings();
+    assert_eq!(postings.status_of("prl-111111"), Some(Status::Closed));
+    assert_eq!(postings.with_status(Status::Open).count(), 0);
+    assert_eq!(postings.with_label("ui").count(), 0);
+    assert_eq!(postings.with_label("done").count(), 1);
+    assert!(!index
+        .is_stale(&storage_path)
+        .expect("Failed to check index"));
+
+    storage
+        .delete("prl-111111")
+        .expect("Failed to delete pearl");
+    let postings = storage.index().expect("Index enabled").postings();
+    assert_eq!(postings.status_of("prl-111111"), None);
+}
+
+#[test]
+fn test_stale_index_is_rebuilt_on_open() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let storage_path = temp_dir.path().join("issues.jsonl");
+    let index_path = temp_dir.path().join("index.bin");
+
+    let mut storage = Storage::with_index(storage_path.clone(), Some(index_path.clone()))
+        .expect("Failed to create storage with index");
+    storage
+        .save(&create_test_pearl("prl-111111", Status::Open, &[]))
+        .expect("Failed to save pearl");
+
+    let mut other = Storage::new(storage_path.clone()).expect("Failed to create storage");
+    other
+        .save(&create_test_pearl("prl-222222", Status::Open, &[]))
+        .expect("Failed to save pearl");
+
+    let index = Index::load(index_path.clone()).expect("Failed to load index");
+    assert!(index
+        .is_stale(&storage_path)
+        .expect("Failed to check index"));
+
+    let reopened = Storage::with_index(storage_path.clone(), Some(index_path))
+        .expect("Failed to reopen storage");
+    let index = reopened.index().expect("Index enabled");
+    assert_eq!(index.len(), 2);
+    assert!(!index
+        .is_stale(&storage_path)
+        .expect("Failed to check index"));
+}
+
+#[test]
+fn test_legacy_index_is_upgraded() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let storage_path = temp_dir.path().join("issues.jsonl");
+    let index_path = temp_dir.path().join("index.bin");
+
+    let mut storage = Storage::new(storage_path.clone()).expect("Failed to create storage");
+    storage
+        .save(&create_test_pearl("prl-111111", Status::Open, &["core"]))
+        .expect("Failed to save pearl");
+
+    // Version 1 layout: magic, version, count, then (id_len, id, offset) entries.
+    let mut legacy = b"PRLIDX1\0".to_vec();
+    legacy.push(1);
+    legacy.extend_from_slice(&1u64.to_le_bytes());
+    legacy.extend_from_slice(&10u32.to_le_bytes());
+    legacy.extend_from_slice(b"prl-111111");
+    legacy.extend_from_slice(&0u64.to_le_bytes());
+    fs::write(&index_path, legacy).expect("Failed to write legacy index");
+
+    let index = Index::load(index_path.clone()).expect("Failed to load legacy index");
+    assert_eq!(index.get("prl-111111"), Some(0));
+    assert!(index
+        .is_stale(&storage_path)
+        .expect("Failed to check index"));
+
+    let upgraded =
+        Storage::with_index(storage_path, Some(index_path)).expect("Failed to open storage");
+    let postings = upgraded.index().expect("Index enabled").postings();
+    assert_eq!(postings.with_label("core").count(), 1);
+}
+
+#[test]
+fn test_load_by_ids_reads_only_requested() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let storage_path = temp_dir.path().join("issues.jsonl");
+    let index_path = temp_dir.path().join("index.bin");
+
+    let pearls: Vec<Pearl> = (0..10)
+        .map(|i| create_test_pearl(&format!("prl-{:06}", i), Status::Open, &[]))
+        .collect();
+    let mut storage = Storage::with_index(storage_path.clone(), Some(index_path))
+        .expect("Failed to create storage with index");
+    storage.save_all(&pearls).expect("Failed to save pearls");
+
+    let loaded = storage
+        .load_by_ids(["prl-000007", "prl-000002", "prl-missing"])
+        .expect("Failed to load pearls");
+    let ids: Vec<&str> = loaded.iter().map(|pearl| pearl.id.as_str()).collect();
+    assert_eq!(ids, vec!["prl-000002", "prl-000007"]);
+
+    let unindexed = Storage::new(storage_path).expect("Failed to create storage");
+    assert_eq!(
+        unindexed
+            .load_by_ids(["prl-000007", "prl-000002"])
+            .expect("Failed to load pearls"),
+        loaded
+    );
+}
+
+#[test]
+fn test_postings_find_cycle() {
+    let blocks = |target: &str| Dependency {
+        target_id: target.to_string(),
+        dep_type: DepType::Blocks,
+    };
+    let mut a = create_test_pearl("prl-aaaaaa", Status::Open, &[]);
+    let mut b = create_test_pearl("prl-bbbbbb", Status::Open, &[]);
+    let mut c = create_test_pearl("prl-cccccc", Status::Open, &[]);
+    a.deps.push(blocks("prl-bbbbbb"));
+    b.deps.push(blocks("prl-cccccc"));
+    c.deps.push(blocks("prl-missing"));
+
+    let mut postings = Postings::default();
+    for pearl in [&a, &b, &c] {
+        postings.insert(pearl);
+    }
+    assert_eq!(postings.find_cycle(), None);
+
+    c.deps.push(blocks("prl-aaaaaa"));
+    postings.insert(&c);
+    assert_eq!(
+        postings.find_cycle(),
+        Some(vec![
+            "prl-bbbbbb".to_string(),
+            "prl-cccccc".to_string(),
+            "prl-aaaaaa".to_string()
+        ])
+    );
+
+    let mut solo = create_test_pearl("prl-dddddd", Status::Open, &[]);
+    solo.deps.push(blocks("prl-dddddd"));
+    let mut self_loop = Postings::default();
+    self_loop.insert(&solo);
+    assert_eq!(self_loop.find_cycle(), Some(vec!["prl-dddddd".to_string()]));
+}
@@ -56,7 +56,7 @@ fn arb_pearl() -> impl Strategy<Value = Pearl> {
         prop::collection::vec(arb_dependency(), 0..5),
         prop::collection::hash_map(
             prop::string::string_regex("[a-z_]{1,20}").unwrap(),
-            any::<String>().prop_map(|s| serde_json::Value::String(s)),
+            any::<String>().prop_map(serde_json::Value::String),
             0..5,
         ),
     )
@@ -87,6 +87,7 @@ fn arb_pearl() -> impl Strategy<Value = Pearl> {
                     deps,
                     metadata,
                     comments: Vec::new(),
+                    extra: Default::default(),
                 }
             },
         )
@@ -215,6 +216,7 @@ proptest! {
             deps: Vec::new(),
             metadata: Default::default(),
             comments: Vec::new(),
+            extra: Default::default(),
         };
         let json = serde_json::to_string(&pearl).expect("Serialization failed");
         let deserialized: Pearl = serde_json::from_str(&json).expect("Deserialization failed");
@@ -239,6 +241,7 @@ proptest! {
             deps: Vec::new(),
             metadata: Default::default(),
             comments: Vec::new(),
+            extra: Default::default(),
         };
         let json = serde_json::to_string(&pearl).expect("Serialization failed");
         let deserialized: Pearl = serde_json::from_str(&json).expect("Deserialization failed");
@@ -264,6 +267,7 @@ proptest! {
             deps: Vec::new(),
             metadata: Default::default(),
             comments: Vec::new(),
+            extra: Default::default(),
         };
         let json = serde_json::to_string(&pearl).expect("Serialization failed");
         let deserialized: Pearl = serde_json::from_str(&json).expect("Deserialization failed");
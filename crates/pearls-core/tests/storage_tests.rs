@@ -23,6 +23,7 @@ fn create_test_pearl(id: &str, title: &str) -> Pearl {
         deps: vec![],
         metadata: Default::default(),
         comments: Vec::new(),
+        extra: Default::default(),
     }
 }
 
@@ -291,3 +292,239 @@ fn test_storage_path_validation() {
     let result = Storage::new("".into());
     assert!(result.is_err(), "Empty path should fail validation");
 }
+
+#[test]
+fn test_update_in_place_preserves_layout() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let storage_path = temp_dir.path().join("test.jsonl");
+
+    let mut storage = Storage::new(storage_path.clone()).expect("Failed to create storage");
+    let pearls = vec![
+        create_test_pearl("prl-111111", "Pearl 1"),
+        create_test_pearl("prl-222222", "A longer title"),
+        create_test_pearl("prl-333333", "Pearl 3"),
+    ];
+    storage.save_all(&pearls).expect("Failed to save pearls");
+    let before = fs::read_to_string(&storage_path).expect("Failed to read file");
+
+    let mut updated = pearls[1].clone();
+    updated.title = "Shorter".to_string();
+    storage.save(&updated).expect("Failed to update pearl");
+
+    let after = fs::read_to_string(&storage_path).expect("Failed to read file");
+    assert_eq!(
+        after.len(),
+        before.len(),
+        "In-place update should not resize file"
+    );
+    let before_lines: Vec<&str> = before.lines().collect();
+    let after_lines: Vec<&str> = after.lines().collect();
+    assert_eq!(after_lines[0], before_lines[0]);
+    assert_eq!(after_lines[2], before_lines[2]);
+    assert_eq!(after_lines[1].len(), before_lines[1].len());
+
+    let loaded = storage
+        .load_by_id("prl-222222")
+        .expect("Failed to load pearl");
+    assert_eq!(loaded.title, "Shorter");
+}
+
+#[test]
+fn test_growing_update_appends_and_compacts() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let storage_path = temp_dir.path().join("test.jsonl");
+
+    let mut storage = Storage::new(storage_path.clone()).expect("Failed to create storage");
+    let mut pearl1 = create_test_pearl("prl-111111", "Pearl 1");
+    let pearl2 = create_test_pearl("prl-222222", "Pearl 2");
+    storage
+        .save_all(&[pearl1.clone(), pearl2.clone()])
+        .expect("Failed to save pearls");
+
+    pearl1.title = "A considerably longer title than before".to_string();
+    storage.save(&pearl1).expect("Failed to update pearl");
+
+    let content = fs::read_to_string(&storage_path).expect("Failed to read file");
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines.len(), 3, "Growing update should append a line");
+    assert!(lines[0].trim().is_empty(), "Old line should be blanked");
+
+    let all_pearls = storage.load_all().expect("Failed to load all pearls");
+    assert_eq!(all_pearls.len(), 2);
+
+    assert!(storage.compact().expect("Failed to compact"));
+    assert!(
+        !storage.compact().expect("Failed to compact"),
+        "Canonical file should not be rewritten"
+    );
+
+    let mut canonical =
+        Storage::new(temp_dir.path().join("canonical.jsonl")).expect("Failed to create storage");
+    canonical
+        .save_all(&[pearl2, pearl1])
+        .expect("Failed to save pearls");
+    assert_eq!(
+        fs::read(&storage_path).expect("Failed to read file"),
+        fs::read(canonical.path()).expect("Failed to read file"),
+        "Compaction should produce the same bytes as a sorted rewrite"
+    );
+}
+
+#[test]
+fn test_save_compacts_displaced_records() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let storage_path = temp_dir.path().join("test.jsonl");
+
+    let mut storage = Storage::new(storage_path).expect("Failed to create storage");
+    for i in (0..66).rev() {
+        let pearl = create_test_pearl(&format!("prl-{:06}", i), &format!("Pearl {}", i));
+        storage.save(&pearl).expect("Failed to save pearl");
+    }
+
+    let ids: Vec<String> = storage
+        .load_all()
+        .expect("Failed to load pearls")
+        .into_iter()
+        .map(|pearl| pearl.id)
+        .collect();
+    let mut sorted = ids.clone();
+    sorted.sort();
+    assert_eq!(
+        ids, sorted,
+        "Save should compact once many records are displaced"
+    );
+}
+
+#[test]
+fn test_indexed_save_updates_offsets() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let storage_path = temp_dir.path().join("test.jsonl");
+    let index_path = temp_dir.path().join("test.idx");
+
+    let mut storage = Storage::with_index(storage_path.clone(), Some(index_path.clone()))
+        .expect("Failed to create storage with index");
+    let mut pearl1 = create_test_pearl("prl-111111", "Pearl 1");
+    let pearl2 = create_test_pearl("prl-222222", "Pearl 2");
+    storage
+        .save_all(&[pearl1.clone(), pearl2])
+        .expect("Failed to save pearls");
+
+    pearl1.title = "A considerably longer title than before".to_string();
+    storage.save(&pearl1).expect("Failed to update pearl");
+    storage
+        .save(&create_test_pearl("prl-000000", "Pearl 0"))
+        .expect("Failed to save pearl");
+
+    let content = fs::read(&storage_path).expect("Failed to read file");
+    let reloaded = pearls_core::storage::Index::load(index_path).expect("Failed to load index");
+    assert_eq!(reloaded.len(), 3);
+    for (id, offset) in reloaded.entries() {
+        let line = content[*offset as usize..]
+            .split(|b| *b == b'\n')
+            .next()
+            .expect("Offset should point at a line");
+        let pearl: Pearl = serde_json::from_slice(line).expect("Offset should point at JSON");
+        assert_eq!(&pearl.id, id);
+    }
+
+    storage
+        .delete("prl-222222")
+        .expect("Failed to delete pearl");
+    assert!(storage
+        .index()
+        .expect("Index should be enabled")
+        .get("prl-222222")
+        .is_none());
+    assert_eq!(storage.load_all().expect("Failed to load pearls").len(), 2);
+}
+
+#[test]
+fn test_dir_backend_round_trip() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let issues_dir = temp_dir.path().join("issues");
+    let mut storage = Storage::new_dir(issues_dir.clone()).expect("Failed to create storage");
+    assert!(storage.is_empty().expect("Failed to check storage"));
+
+    storage
+        .save_all(&[
+            create_test_pearl("prl-222222", "Pearl 2"),
+            create_test_pearl("prl-111111", "Pearl 1"),
+        ])
+        .expect("Failed to save pearls");
+    assert!(issues_dir.join("prl-111111.json").exists());
+    assert!(issues_dir.join("prl-222222.json").exists());
+
+    let ids: Vec<String> = storage
+        .load_all()
+        .expect("Failed to load pearls")
+        .into_iter()
+        .map(|pearl| pearl.id)
+        .collect();
+    assert_eq!(ids, vec!["prl-111111", "prl-222222"]);
+
+    let hash = storage.content_hash().expect("Failed to hash storage");
+    let mut pearl = storage
+        .load_by_id("prl-111111")
+        .expect("Failed to load pearl");
+    pearl.title = "Updated".to_string();
+    storage.save(&pearl).expect("Failed to update pearl");
+    assert_ne!(
+        storage.content_hash().expect("Failed to hash storage"),
+        hash
+    );
+    assert_eq!(
+        storage
+            .load_by_id("prl-111111")
+            .expect("Failed to load pearl")
+            .title,
+        "Updated"
+    );
+
+    storage
+        .save_all(&[pearl])
+        .expect("Failed to replace pearls");
+    assert!(!issues_dir.join("prl-222222.json").exists());
+
+    storage
+        .delete("prl-111111")
+        .expect("Failed to delete pearl");
+    assert!(storage.delete("prl-111111").is_err());
+    assert!(storage.is_empty().expect("Failed to check storage"));
+}
+
+#[test]
+fn test_unknown_fields_round_trip() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let storage_path = temp_dir.path().join("test.jsonl");
+    let mut value = serde_json::to_value(create_test_pearl("prl-111111", "Pearl 1"))
+        .expect("Failed to serialize pearl");
+    value["estimate"] = serde_json::json!({"points": 3});
+    fs::write(&storage_path, format!("{}\n", value)).expect("Failed to write file");
+
+    let mut storage = Storage::new(storage_path.clone()).expect("Failed to create storage");
+    let mut pearl = storage
+        .load_by_id("prl-111111")
+        .expect("Failed to load pearl");
+    assert_eq!(
+        pearl.extra.get("estimate"),
+        Some(&serde_json::json!({"points": 3}))
+    );
+
+    pearl.title = "Renamed".to_string();
+    storage.save(&pearl).expect("Failed to save pearl");
+    storage
+        .save(&create_test_pearl("prl-222222", "Pearl 2"))
+        .expect("Failed to save pearl");
+    storage.compact().expect("Failed to compact");
+
+    let content = fs::read_to_string(&storage_path).expect("Failed to read file");
+    let first: serde_json::Value = serde_json::from_str(
+        content
+            .lines()
+            .next()
+            .expect("File should have a first line"),
+    )
+    .expect("Line should be JSON");
+    assert_eq!(first["title"], "Renamed");
+    assert_eq!(first["estimate"], serde_json::json!({"points": 3}));
+}
@@ -0,0 +1,74 @@
+// Rust guideline compliant 2026-02-10
+
+//! Commit-msg hook implementation.
+//!
+//! Checks the Pearl references in the message being committed, so typos and
+//! closes that the FSM would reject are caught before the commit is made.
+
+use crate::commit_refs::parse_commit_refs;
+use crate::git::pearls_dir;
+use anyhow::Result;
+use pearls_core::{Config, IssueGraph, Status, Storage};
+use std::path::Path;
+
+/// Runs the commit-msg hook.
+///
+/// Every referenced Pearl must exist. When `auto_close_on_commit` is set,
+/// Pearls referenced with a closing verb must also be allowed to close, so a
+/// commit cannot claim to fix a Pearl that is still blocked.
+///
+/// # Arguments
+///
+/// * `repo_path` - Path to the Git repository
+/// * `message_path` - Path to the commit message file passed by Git
+///
+/// # Returns
+///
+/// Ok if the references are valid, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The message or the Pearls cannot be read
+/// - A referenced Pearl does not exist
+/// - A Pearl to be closed cannot transition to closed
+pub fn commit_msg_hook(repo_path: &Path, message_path: &Path) -> Result<()> {
+    let pearls_dir = pearls_dir(repo_path);
+    let config = Config::load(&pearls_dir)?;
+    let message = std::fs::read_to_string(message_path)?;
+    let refs = parse_commit_refs(&message, &config.commits);
+    if refs.is_empty() {
+        return Ok(());
+    }
+
+    let storage = Storage::open(&pearls_dir, &config)?;
+    let pearls = storage.load_all()?;
+    let missing: Vec<&str> = refs
+        .iter()
+        .filter(|commit_ref| !pearls.iter().any(|pearl| pearl.id == commit_ref.id))
+        .map(|commit_ref| commit_ref.id.as_str())
+        .collect();
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "Commit message references unknown Pearls: {}",
+            missing.join(", ")
+        );
+    }
+
+    if config.auto_close_on_commit {
+        let graph = IssueGraph::from_pearls(pearls.clone())?;
+        let closing = pearls.iter().filter(|pearl| {
+            refs.iter()
+                .any(|commit_ref| commit_ref.closes && commit_ref.id == pearl.id)
+        });
+        for pearl in closing {
+            if pearl.status != Status::Closed {
+                pearls_core::fsm::validate_transition(pearl, Status::Closed, &graph).map_err(
+                    |err| anyhow::anyhow!("Commit message closes {}: {}", pearl.id, err),
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+// Rust guideline compliant 2026-02-10
+
+//! Pearl references in commit messages.
+//!
+//! A reference is a verb followed by one or more Pearl IDs, such as
+//! `Refs prl-a1b2c3` or `Fixes prl-a1b2c3, prl-d4e5f6`. Verbs come from the
+//! `[commits]` table of the config. Linked commits are recorded with
+//! [`pearls_app::commits`].
+
+use pearls_core::{identity, CommitsConfig};
+
+/// A Pearl referenced by a commit message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitRef {
+    /// Referenced Pearl ID.
+    pub id: String,
+    /// Whether the reference used a closing verb.
+    pub closes: bool,
+}
+
+/// Parses the Pearl references in a commit message.
+///
+/// Each line is read on its own, and lines starting with `#` are skipped as
+/// Git comments. After a verb, every following Pearl ID is referenced until a
+/// word that is neither an ID nor `and`. Punctuation around words is ignored,
+/// so the older `Fixes (prl-a1b2c3)` form is still recognized. A line starting
+/// with the configured trailer, such as `Pearl: prl-a1b2c3`, references the
+/// Pearls it lists.
+///
+/// # Arguments
+///
+/// * `message` - Commit message
+/// * `config` - Recognized verbs
+///
+/// # Returns
+///
+/// The referenced Pearls in order of first mention. A Pearl referenced by both
+/// kinds of verb is reported as closing.
+#[must_use]
+pub fn parse_commit_refs(message: &str, config: &CommitsConfig) -> Vec<CommitRef> {
+    let matches =
+        |verbs: &[String], word: &str| verbs.iter().any(|verb| verb.eq_ignore_ascii_case(word));
+
+    let mut refs: Vec<CommitRef> = Vec::new();
+    for line in message.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+        let mut closes = None;
+        let mut line = line;
+        if let Some(rest) = strip_trailer(line, &config.trailer) {
+            closes = Some(false);
+            line = rest;
+        }
+        for word in line.split(|c: char| c.is_whitespace() || c == ',') {
+            let word = word.trim_matches(|c: char| !c.is_alphanumeric() && c != '-');
+            if word.is_empty() {
+                continue;
+            }
+            if matches(&config.closing_verbs, word) {
+                closes = Some(true);
+            } else if matches(&config.reference_verbs, word) {
+                closes = Some(false);
+            } else if let Some(closing) = closes {
+                if identity::validate_id_format(word).is_ok() {
+                    match refs.iter_mut().find(|existing| existing.id == word) {
+                        Some(existing) => existing.closes |= closing,
+                        None => refs.push(CommitRef {
+                            id: word.to_string(),
+                            closes: closing,
+                        }),
+                    }
+                } else if !word.eq_ignore_ascii_case("and") {
+                    closes = None;
+                }
+            }
+        }
+    }
+    refs
+}
+
+/// Returns the value of a `Key: value` trailer line, matching the key
+/// case-insensitively.
+pub(crate) fn strip_trailer<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    if key.is_empty() {
+        return None;
+    }
+    let (name, value) = line.split_once(':')?;
+    name.trim().eq_ignore_ascii_case(key).then_some(value)
+}
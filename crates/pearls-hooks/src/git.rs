@@ -0,0 +1,31 @@
+// Rust guideline compliant 2026-02-10
+
+//! Git and repository helpers shared by the hooks.
+
+use anyhow::Result;
+use pearls_app::RepoContext;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Returns the checked-out branch, or `None` on a detached `HEAD`.
+pub(crate) fn current_branch(repo_path: &Path) -> Result<Option<String>> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(repo_path)
+        .output()?;
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || branch.is_empty() || branch == "HEAD" {
+        return Ok(None);
+    }
+    Ok(Some(branch))
+}
+
+/// Returns the `.pearls` directory serving the work tree at `repo_path`.
+///
+/// Uses the same discovery as the CLI, so worktrees and submodules share
+/// their tracker. Falls back to `.pearls` in `repo_path` when none is found.
+pub(crate) fn pearls_dir(repo_path: &Path) -> PathBuf {
+    RepoContext::discover(Some(repo_path))
+        .map(|repo| repo.pearls_dir().to_path_buf())
+        .unwrap_or_else(|_| repo_path.join(".pearls"))
+}
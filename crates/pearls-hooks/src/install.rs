@@ -0,0 +1,335 @@
+// Rust guideline compliant 2026-02-10
+
+//! Installation of the Pearls Git hooks.
+//!
+//! Each hook script gets a managed block between marker comments, placed right
+//! after the shebang so it runs before any `exec` in an existing script. Hook
+//! scripts that already exist keep their content. A hook that is not a shell
+//! script, such as a compiled binary, is moved aside to `<hook>.pre-pearls`
+//! and chained from the block. The hooks directory comes from
+//! `git rev-parse --git-path hooks`, which honors `core.hooksPath` and linked
+//! worktrees.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// First line of the managed block.
+pub const BLOCK_START: &str = "# >>> pearls hooks >>>";
+
+/// Last line of the managed block.
+pub const BLOCK_END: &str = "# <<< pearls hooks <<<";
+
+/// Suffix of a hook moved aside so it can be chained.
+pub const CHAINED_SUFFIX: &str = ".pre-pearls";
+
+/// Hooks installed by Pearls, with the command each one runs and whether a
+/// failure aborts the Git operation.
+pub const HOOKS: &[(&str, &str, bool)] = &[
+    ("pre-commit", "prl hooks pre-commit", true),
+    (
+        "prepare-commit-msg",
+        "prl hooks prepare-commit-msg \"$1\" \"$2\" \"$3\"",
+        true,
+    ),
+    ("commit-msg", "prl hooks commit-msg \"$1\"", true),
+    ("post-commit", "prl hooks post-commit", false),
+    (
+        "post-checkout",
+        "prl hooks post-checkout \"$1\" \"$2\" \"$3\"",
+        false,
+    ),
+    ("post-merge", "prl hooks post-merge", false),
+];
+
+/// Installation state of one hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookState {
+    /// The hook runs the current Pearls command.
+    Installed,
+    /// The hook does not run Pearls.
+    Missing,
+    /// The hook runs an outdated Pearls command or is not executable.
+    Stale,
+}
+
+/// Returns the hooks directory of a repository.
+///
+/// # Arguments
+///
+/// * `repo_path` - Path inside the Git work tree
+///
+/// # Returns
+///
+/// The absolute hooks directory.
+///
+/// # Errors
+///
+/// Returns an error if `repo_path` is not inside a Git repository.
+pub fn hooks_dir(repo_path: &Path) -> Result<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-path", "hooks"])
+        .current_dir(repo_path)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!("Not a git repository. Run 'git init' first.");
+    }
+    let path = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Ok(if path.is_absolute() {
+        path
+    } else {
+        repo_path.join(path)
+    })
+}
+
+/// Installs or refreshes every Pearls hook.
+///
+/// # Arguments
+///
+/// * `hooks_dir` - Hooks directory, see [`hooks_dir`]
+///
+/// # Returns
+///
+/// The names of the hooks that were written; hooks already up to date are
+/// left alone.
+///
+/// # Errors
+///
+/// Returns an error if a hook cannot be read, moved, or written.
+pub fn install_hooks(hooks_dir: &Path) -> Result<Vec<&'static str>> {
+    fs::create_dir_all(hooks_dir)
+        .with_context(|| format!("Failed to create {}", hooks_dir.display()))?;
+    let mut written = Vec::new();
+    for &(name, command, blocking) in HOOKS {
+        if hook_state(hooks_dir, name, command, blocking)? == HookState::Installed {
+            continue;
+        }
+        let path = hooks_dir.join(name);
+        let existing = read_hook(&path)?;
+        let body = match existing.as_deref() {
+            Some(content) if is_legacy(content, command) => String::new(),
+            Some(content) if is_shell_script(content) => remove_block(content),
+            Some(_) => {
+                let chained = chained_path(hooks_dir, name);
+                if chained.exists() {
+                    bail!(
+                        "Cannot chain {}: {} already exists",
+                        path.display(),
+                        chained.display()
+                    );
+                }
+                fs::rename(&path, &chained)
+                    .with_context(|| format!("Failed to move {} aside", path.display()))?;
+                String::new()
+            }
+            None => String::new(),
+        };
+        let block = managed_block(hooks_dir, name, command, blocking);
+        let content = insert_block(&body, &block);
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+        make_executable(&path)?;
+        written.push(name);
+    }
+    Ok(written)
+}
+
+/// Removes every Pearls hook.
+///
+/// Managed blocks are cut out of the scripts that hold them; a script left
+/// with nothing but its shebang is deleted, and a chained hook is moved back.
+///
+/// # Arguments
+///
+/// * `hooks_dir` - Hooks directory, see [`hooks_dir`]
+///
+/// # Returns
+///
+/// The names of the hooks that were changed.
+///
+/// # Errors
+///
+/// Returns an error if a hook cannot be read, moved, written, or removed.
+pub fn uninstall_hooks(hooks_dir: &Path) -> Result<Vec<&'static str>> {
+    let mut removed = Vec::new();
+    for &(name, command, _) in HOOKS {
+        let path = hooks_dir.join(name);
+        let Some(content) = read_hook(&path)? else {
+            continue;
+        };
+        let rest = if is_legacy(&content, command) {
+            String::new()
+        } else if content.contains(BLOCK_START) {
+            remove_block(&content)
+        } else {
+            continue;
+        };
+
+        let chained = chained_path(hooks_dir, name);
+        if is_empty_script(&rest) {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            if chained.exists() {
+                fs::rename(&chained, &path)
+                    .with_context(|| format!("Failed to restore {}", path.display()))?;
+            }
+        } else {
+            fs::write(&path, rest)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        removed.push(name);
+    }
+    Ok(removed)
+}
+
+/// Reports the state of every Pearls hook.
+///
+/// # Arguments
+///
+/// * `hooks_dir` - Hooks directory, see [`hooks_dir`]
+///
+/// # Returns
+///
+/// Each hook name with its state, in installation order.
+///
+/// # Errors
+///
+/// Returns an error if a hook exists but cannot be read.
+pub fn hook_states(hooks_dir: &Path) -> Result<Vec<(&'static str, HookState)>> {
+    HOOKS
+        .iter()
+        .map(|&(name, command, blocking)| {
+            Ok((name, hook_state(hooks_dir, name, command, blocking)?))
+        })
+        .collect()
+}
+
+fn hook_state(hooks_dir: &Path, name: &str, command: &str, blocking: bool) -> Result<HookState> {
+    let path = hooks_dir.join(name);
+    let Some(content) = read_hook(&path)? else {
+        return Ok(HookState::Missing);
+    };
+    if is_legacy(&content, command) {
+        return Ok(HookState::Stale);
+    }
+    let Some(block) = extract_block(&content) else {
+        return Ok(HookState::Missing);
+    };
+    if block != managed_block(hooks_dir, name, command, blocking) || !is_executable(&path)? {
+        return Ok(HookState::Stale);
+    }
+    Ok(HookState::Installed)
+}
+
+fn managed_block(hooks_dir: &Path, name: &str, command: &str, blocking: bool) -> String {
+    let mut block = format!(
+        "{}\n# Managed by Pearls; remove with 'prl hooks uninstall'.\n",
+        BLOCK_START
+    );
+    if blocking {
+        block.push_str(&format!("{} || exit $?\n", command));
+    } else {
+        block.push_str(&format!("{}\n", command));
+    }
+    if chained_path(hooks_dir, name).exists() {
+        block.push_str(&format!(
+            "\"$(dirname \"$0\")/{}{}\" \"$@\" || exit $?\n",
+            name, CHAINED_SUFFIX
+        ));
+    }
+    block.push_str(BLOCK_END);
+    block.push('\n');
+    block
+}
+
+fn chained_path(hooks_dir: &Path, name: &str) -> PathBuf {
+    hooks_dir.join(format!("{}{}", name, CHAINED_SUFFIX))
+}
+
+/// Reads a hook script, returning `None` if it does not exist.
+fn read_hook(path: &Path) -> Result<Option<String>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(Some(String::from_utf8_lossy(&bytes).into_owned()))
+}
+
+/// Whether a script is the single-command hook written by older versions.
+fn is_legacy(content: &str, command: &str) -> bool {
+    content == format!("#!/bin/sh\n{}\n", command)
+}
+
+fn is_shell_script(content: &str) -> bool {
+    let Some(shebang) = content.lines().next().filter(|line| line.starts_with("#!")) else {
+        return false;
+    };
+    shebang
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .any(|word| matches!(word, "sh" | "bash" | "dash" | "zsh" | "ksh"))
+}
+
+fn is_empty_script(content: &str) -> bool {
+    content
+        .lines()
+        .all(|line| line.trim().is_empty() || line.starts_with("#!"))
+}
+
+fn extract_block(content: &str) -> Option<String> {
+    let start = content.find(BLOCK_START)?;
+    let end = content[start..].find(BLOCK_END)? + start + BLOCK_END.len();
+    Some(format!("{}\n", &content[start..end]))
+}
+
+fn remove_block(content: &str) -> String {
+    let Some(start) = content.find(BLOCK_START) else {
+        return content.to_string();
+    };
+    let Some(end) = content[start..].find(BLOCK_END) else {
+        return content.to_string();
+    };
+    let mut end = start + end + BLOCK_END.len();
+    if content[end..].starts_with('\n') {
+        end += 1;
+    }
+    format!("{}{}", &content[..start], &content[end..])
+}
+
+/// Places the block after the shebang of `body`, or starts a new script.
+fn insert_block(body: &str, block: &str) -> String {
+    if body.trim().is_empty() {
+        return format!("#!/bin/sh\n{}", block);
+    }
+    match body.split_once('\n') {
+        Some((shebang, rest)) if shebang.starts_with("#!") => {
+            format!("{}\n{}{}", shebang, block, rest)
+        }
+        _ => format!("#!/bin/sh\n{}{}", block, body),
+    }
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut perms = fs::metadata(path)?.permissions();
+    perms.set_mode(perms.mode() | 0o755);
+    fs::set_permissions(path, perms)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> Result<bool> {
+    use std::os::unix::fs::PermissionsExt;
+    Ok(fs::metadata(path)?.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> Result<bool> {
+    Ok(true)
+}
@@ -1,13 +1,33 @@
-// Rust guideline compliant 2026-02-06
+// Rust guideline compliant 2026-02-10
 
 //! Pearls Git Hooks
 //!
 //! This crate provides Git hook implementations for Pearls:
 //! - Pre-commit validation
+//! - Prepare-commit-msg trailers naming the active Pearl
+//! - Commit-msg checks of Pearl references
+//! - Post-commit linking and auto-close
+//! - Post-checkout tracking of the current Pearl
 //! - Post-merge integrity checks
+//!
+//! The [`install`] module writes the hook scripts that run them.
 
+pub mod commit_msg;
+pub mod commit_refs;
+mod git;
+pub mod install;
+pub mod post_checkout;
+pub mod post_commit;
 pub mod post_merge;
 pub mod pre_commit;
+pub mod prepare_commit_msg;
 
+pub use commit_msg::commit_msg_hook;
+pub use commit_refs::{parse_commit_refs, CommitRef};
+pub use install::{hook_states, hooks_dir, install_hooks, uninstall_hooks, HookState};
+pub use pearls_app::commits::{linked_commits, LinkedCommit};
+pub use post_checkout::post_checkout_hook;
+pub use post_commit::post_commit_hook;
 pub use post_merge::post_merge_hook;
 pub use pre_commit::pre_commit_hook;
+pub use prepare_commit_msg::prepare_commit_msg_hook;
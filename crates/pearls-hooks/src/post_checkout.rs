@@ -0,0 +1,48 @@
+// Rust guideline compliant 2026-02-10
+
+//! Post-checkout hook implementation.
+//!
+//! Marks the Pearl of the checked-out branch as the current Pearl.
+
+use crate::git::{current_branch, pearls_dir};
+use anyhow::Result;
+use pearls_core::{workflow, Config, Storage};
+use std::path::Path;
+
+/// Runs the post-checkout hook.
+///
+/// On a branch checkout, the Pearl named in the branch, such as
+/// `prl/prl-a1b2c3-fix-login`, becomes the current Pearl of the clone. A
+/// branch that names no Pearl clears the current Pearl. File checkouts are
+/// ignored.
+///
+/// # Arguments
+///
+/// * `repo_path` - Path to the Git repository
+/// * `branch_checkout` - Whether Git reported a branch checkout (flag `1`)
+///
+/// # Returns
+///
+/// Ok if the current Pearl was updated or left alone, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if the Pearls cannot be read or the current Pearl cannot
+/// be written.
+pub fn post_checkout_hook(repo_path: &Path, branch_checkout: bool) -> Result<()> {
+    let pearls_dir = pearls_dir(repo_path);
+    if !branch_checkout || !pearls_dir.exists() {
+        return Ok(());
+    }
+
+    let Some(branch) = current_branch(repo_path)? else {
+        // Detached HEAD keeps the current Pearl.
+        return Ok(());
+    };
+
+    let storage = Storage::open(&pearls_dir, &Config::load(&pearls_dir)?)?;
+    let pearls = storage.load_all()?;
+    let id = workflow::pearl_id_from_branch(&branch, &pearls);
+    workflow::write_current(&pearls_dir, id.as_deref())?;
+    Ok(())
+}
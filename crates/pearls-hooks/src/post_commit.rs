@@ -0,0 +1,129 @@
+// Rust guideline compliant 2026-02-10
+
+//! Post-commit hook implementation.
+//!
+//! Links the new commit to the Pearls its message references and closes the
+//! Pearls referenced with a closing verb.
+
+use crate::commit_refs::parse_commit_refs;
+use crate::git::pearls_dir;
+use anyhow::Result;
+use pearls_app::commits::{link_commit, LinkedCommit};
+use pearls_core::{Config, IssueGraph, Status, Storage};
+use std::path::Path;
+use std::process::Command;
+
+/// Runs the post-commit hook.
+///
+/// Records the SHA, author, and subject of `HEAD` on every referenced Pearl.
+/// When `auto_close_on_commit` is set, Pearls referenced with a closing verb
+/// are closed as well. The commit already exists, so problems such as an
+/// unknown ID or a blocked Pearl are reported as warnings. The updated Pearls
+/// are left in the working tree for the next commit.
+///
+/// # Arguments
+///
+/// * `repo_path` - Path to the Git repository
+///
+/// # Returns
+///
+/// Ok if the commit was processed, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The commit cannot be read from Git
+/// - The Pearls cannot be read or written
+pub fn post_commit_hook(repo_path: &Path) -> Result<()> {
+    let pearls_dir = pearls_dir(repo_path);
+    let config = Config::load(&pearls_dir)?;
+    let (commit, message) = read_head_commit(repo_path)?;
+    let refs = parse_commit_refs(&message, &config.commits);
+    if refs.is_empty() {
+        return Ok(());
+    }
+
+    let mut storage = Storage::open(&pearls_dir, &config)?;
+    let pearls = storage.load_all()?;
+    let graph = IssueGraph::from_pearls(pearls.clone())?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs() as i64;
+
+    for commit_ref in refs {
+        let Some(mut pearl) = pearls
+            .iter()
+            .find(|pearl| pearl.id == commit_ref.id)
+            .cloned()
+        else {
+            eprintln!(
+                "Warning: commit {} references unknown Pearl {}",
+                short_sha(&commit.sha),
+                commit_ref.id
+            );
+            continue;
+        };
+
+        let mut changed = link_commit(
+            &mut pearl,
+            LinkedCommit {
+                closes: commit_ref.closes,
+                ..commit.clone()
+            },
+        );
+        if commit_ref.closes && config.auto_close_on_commit && pearl.status != Status::Closed {
+            match pearls_core::fsm::validate_transition(&pearl, Status::Closed, &graph) {
+                Ok(()) => {
+                    pearl.status = Status::Closed;
+                    changed = true;
+                }
+                Err(err) => eprintln!("Warning: not closing {}: {}", pearl.id, err),
+            }
+        }
+        if changed {
+            pearl.updated_at = now;
+            pearl.validate()?;
+            storage.save(&pearl)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads `HEAD` as a linked commit plus its full message.
+fn read_head_commit(repo_path: &Path) -> Result<(LinkedCommit, String)> {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%H%x00%an <%ae>%x00%B"])
+        .current_dir(repo_path)
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to read HEAD commit: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut fields = text.splitn(3, '\0');
+    let sha = fields.next().unwrap_or_default().trim().to_string();
+    let author = fields.next().unwrap_or_default().to_string();
+    let message = fields.next().unwrap_or_default().to_string();
+    let subject = message
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    Ok((
+        LinkedCommit {
+            sha,
+            author,
+            subject,
+            closes: false,
+        },
+        message,
+    ))
+}
+
+fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
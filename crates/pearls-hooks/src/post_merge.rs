@@ -4,8 +4,9 @@
 //!
 //! Validates graph integrity after merge operations.
 
+use crate::git::pearls_dir;
 use anyhow::Result;
-use pearls_core::{IssueGraph, Storage};
+use pearls_core::{Config, IssueGraph, Storage};
 use std::collections::HashSet;
 use std::path::Path;
 
@@ -25,8 +26,8 @@ use std::path::Path;
 /// - Cycles are detected in the dependency graph
 /// - Orphaned dependencies are found
 pub fn post_merge_hook(repo_path: &Path) -> Result<()> {
-    let pearls_path = repo_path.join(".pearls/issues.jsonl");
-    let storage = Storage::new(pearls_path)?;
+    let pearls_dir = pearls_dir(repo_path);
+    let storage = Storage::open(&pearls_dir, &Config::load(&pearls_dir)?)?;
     let pearls = storage.load_all()?;
     let graph = IssueGraph::from_pearls(pearls.clone())?;
 
@@ -2,14 +2,25 @@
 
 //! Pre-commit hook implementation.
 //!
-//! Validates JSONL syntax, schema compliance, and handles auto-close on commit messages.
+//! Validates JSONL syntax and schema compliance, and normalizes the JSONL file
+//! to canonical order before it is committed. Commit message references are
+//! handled by the commit-msg and post-commit hooks.
 
+use crate::git::pearls_dir;
 use anyhow::Result;
-use pearls_core::{IssueGraph, Status, Storage};
+use pearls_core::{Config, Storage};
 use std::path::Path;
+use std::process::Command;
 
 /// Runs the pre-commit hook.
 ///
+/// Works with either storage layout. Incremental writes may leave padded,
+/// blanked, or appended lines in the JSONL file. When the file is staged and
+/// has no unstaged changes, it is compacted and re-staged so that commits
+/// contain the canonical, sorted layout; a partially staged file is left alone
+/// so that unrelated hunks are never staged. Directory records are always
+/// written canonically.
+///
 /// # Arguments
 ///
 /// * `repo_path` - Path to the Git repository
@@ -25,8 +36,8 @@ use std::path::Path;
 /// - Pearl schema validation fails
 /// - Duplicate IDs are detected
 pub fn pre_commit_hook(repo_path: &Path) -> Result<()> {
-    let pearls_path = repo_path.join(".pearls/issues.jsonl");
-    let mut storage = Storage::new(pearls_path)?;
+    let pearls_dir = pearls_dir(repo_path);
+    let mut storage = Storage::open(&pearls_dir, &Config::load(&pearls_dir)?)?;
     let pearls = storage.load_all()?;
 
     for pearl in &pearls {
@@ -40,52 +51,45 @@ pub fn pre_commit_hook(repo_path: &Path) -> Result<()> {
         }
     }
 
-    if let Some(fix_ids) = extract_fix_ids(&repo_path.join(".git/COMMIT_EDITMSG"))? {
-        if !fix_ids.is_empty() {
-            auto_close(&mut storage, &pearls, &fix_ids)?;
-        }
+    let issues_file = pearls_dir.join("issues.jsonl");
+    if is_staged(repo_path, &issues_file)
+        && !has_unstaged_changes(repo_path, &issues_file)
+        && storage.compact()?
+    {
+        restage(repo_path, &issues_file)?;
     }
 
     Ok(())
 }
 
-fn extract_fix_ids(path: &Path) -> Result<Option<Vec<String>>> {
-    if !path.exists() {
-        return Ok(None);
-    }
-    let content = std::fs::read_to_string(path)?;
-    let mut ids = Vec::new();
-    let needle = "Fixes (prl-";
-    let mut rest = content.as_str();
-    while let Some(idx) = rest.find(needle) {
-        let start = idx + needle.len();
-        if let Some(end) = rest[start..].find(')') {
-            let id = &rest[start..start + end];
-            ids.push(format!("prl-{}", id));
-            rest = &rest[start + end + 1..];
-        } else {
-            break;
-        }
-    }
-    Ok(Some(ids))
+fn is_staged(repo_path: &Path, file: &Path) -> bool {
+    Command::new("git")
+        .args(["diff", "--cached", "--name-only", "--"])
+        .arg(file)
+        .current_dir(repo_path)
+        .output()
+        .map(|output| output.status.success() && !output.stdout.trim_ascii().is_empty())
+        .unwrap_or(false)
 }
 
-fn auto_close(storage: &mut Storage, pearls: &[pearls_core::Pearl], ids: &[String]) -> Result<()> {
-    let graph = IssueGraph::from_pearls(pearls.to_vec())?;
-    for id in ids {
-        let mut pearl = storage
-            .load_by_id(id)
-            .map_err(|_| anyhow::anyhow!("Pearl '{}' not found for auto-close", id))?;
-        if pearl.status == Status::Closed {
-            continue;
-        }
-        pearls_core::fsm::validate_transition(&pearl, Status::Closed, &graph)?;
-        pearl.status = Status::Closed;
-        use std::time::{SystemTime, UNIX_EPOCH};
-        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
-        pearl.updated_at = now;
-        pearl.validate()?;
-        storage.save(&pearl)?;
+fn has_unstaged_changes(repo_path: &Path, file: &Path) -> bool {
+    Command::new("git")
+        .args(["diff", "--quiet", "--"])
+        .arg(file)
+        .current_dir(repo_path)
+        .status()
+        .map(|status| !status.success())
+        .unwrap_or(true)
+}
+
+fn restage(repo_path: &Path, file: &Path) -> Result<()> {
+    let status = Command::new("git")
+        .args(["add", "--"])
+        .arg(file)
+        .current_dir(repo_path)
+        .status()?;
+    if !status.success() {
+        anyhow::bail!("Failed to re-stage {} after normalization", file.display());
     }
     Ok(())
 }
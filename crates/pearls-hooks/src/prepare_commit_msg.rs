@@ -0,0 +1,105 @@
+// Rust guideline compliant 2026-02-10
+
+//! Prepare-commit-msg hook implementation.
+//!
+//! Adds a trailer naming the active Pearl, such as `Pearl: prl-a1b2c3`, to the
+//! message template so commits stay traceable without typing the ID.
+
+use crate::commit_refs::strip_trailer;
+use crate::git::{current_branch, pearls_dir};
+use anyhow::Result;
+use pearls_core::{workflow, Config, Storage};
+use std::path::Path;
+
+/// Runs the prepare-commit-msg hook.
+///
+/// The active Pearl is the one named in the branch, or else the current Pearl
+/// of the clone. Merge and squash messages are left alone, as are messages
+/// that already carry the trailer. The trailer goes after the message and
+/// before Git's `#` comment lines; in the editor template, which has only
+/// comments, it leaves an empty subject line above it.
+///
+/// # Arguments
+///
+/// * `repo_path` - Path to the Git repository
+/// * `message_path` - Path to the commit message file passed by Git
+/// * `source` - Message source passed by Git (`message`, `template`,
+///   `merge`, `squash`, or `commit`), if any
+///
+/// # Returns
+///
+/// Ok if the message was updated or left alone, Err otherwise.
+///
+/// # Errors
+///
+/// Returns an error if the message, the config, or the Pearls cannot be read,
+/// or the message cannot be written.
+pub fn prepare_commit_msg_hook(
+    repo_path: &Path,
+    message_path: &Path,
+    source: Option<&str>,
+) -> Result<()> {
+    let pearls_dir = pearls_dir(repo_path);
+    if matches!(source, Some("merge" | "squash")) || !pearls_dir.exists() {
+        return Ok(());
+    }
+    let config = Config::load(&pearls_dir)?;
+    let key = &config.commits.trailer;
+    if key.is_empty() {
+        return Ok(());
+    }
+
+    let message = std::fs::read_to_string(message_path)?;
+    if message
+        .lines()
+        .any(|line| !line.starts_with('#') && strip_trailer(line, key).is_some())
+    {
+        return Ok(());
+    }
+
+    let pearls = Storage::open(&pearls_dir, &config)?.load_all()?;
+    let branch = current_branch(repo_path)?;
+    let Some(id) = workflow::active_pearl(&pearls_dir, branch.as_deref(), &pearls)? else {
+        return Ok(());
+    };
+
+    std::fs::write(
+        message_path,
+        add_trailer(&message, &format!("{}: {}", key, id)),
+    )?;
+    Ok(())
+}
+
+/// Inserts a trailer line after the message body and before the comments.
+///
+/// The trailer joins an existing trailer block such as `Signed-off-by:` lines,
+/// and is otherwise separated from the body by a blank line. Without a body,
+/// an empty subject line is left above it.
+fn add_trailer(message: &str, trailer: &str) -> String {
+    let mut body_len = 0;
+    for line in message.split_inclusive('\n') {
+        if line.starts_with('#') {
+            break;
+        }
+        body_len += line.len();
+    }
+    let (body, comments) = message.split_at(body_len);
+    let body = body.trim_end();
+
+    let is_trailer = |line: &str| {
+        line.split_once(':')
+            .is_some_and(|(key, _)| !key.is_empty() && !key.contains(' '))
+    };
+    let joins_trailers = match body.rsplit_once("\n\n") {
+        Some((_, last)) => last.lines().all(is_trailer),
+        None => false,
+    };
+
+    let separator = if joins_trailers { "\n" } else { "\n\n" };
+    let mut out = format!("{}{}{}\n", body, separator, trailer);
+    if !comments.is_empty() {
+        out.push('\n');
+        out.push_str(comments);
+    }
+    out
+}
@@ -2,8 +2,16 @@
 
 //! Integration tests for Pearls hooks.
 
-use pearls_core::{DepType, Dependency, Pearl, Status, Storage};
-use pearls_hooks::{post_merge_hook, pre_commit_hook};
+use pearls_core::workflow::{read_current, write_current};
+use pearls_core::{
+    CommitsConfig, Config, DepType, Dependency, Pearl, Status, Storage, StorageLayout,
+};
+use pearls_hooks::{
+    commit_msg_hook, hook_states, hooks_dir, install_hooks, linked_commits, parse_commit_refs,
+    post_checkout_hook, post_commit_hook, post_merge_hook, pre_commit_hook,
+    prepare_commit_msg_hook, uninstall_hooks, HookState, LinkedCommit,
+};
+use std::path::Path;
 use tempfile::TempDir;
 
 fn create_pearl(id: &str) -> Pearl {
@@ -20,30 +28,286 @@ fn create_pearl(id: &str) -> Pearl {
         deps: Vec::new(),
         metadata: Default::default(),
         comments: Vec::new(),
+        extra: Default::default(),
     }
 }
 
+fn git(repo_path: &Path, args: &[&str]) -> String {
+    let output = std::process::Command::new("git")
+        .args([
+            "-c",
+            "user.name=Tester",
+            "-c",
+            "user.email=tester@example.com",
+        ])
+        .args(args)
+        .current_dir(repo_path)
+        .output()
+        .expect("Failed to run git");
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8(output.stdout).expect("Invalid git output")
+}
+
+fn init_auto_close_repo(repo_path: &Path, layout: StorageLayout) -> Storage {
+    git(repo_path, &["init", "-q"]);
+    let pearls_dir = repo_path.join(".pearls");
+    std::fs::create_dir(&pearls_dir).expect("Failed to create .pearls");
+    let config = Config {
+        layout,
+        auto_close_on_commit: true,
+        ..Config::default()
+    };
+    config.save(&pearls_dir).expect("Failed to save config");
+    Storage::open(&pearls_dir, &config).expect("Failed to create storage")
+}
+
 #[test]
-fn test_pre_commit_auto_close() {
+fn test_parse_commit_refs() {
+    let message = "Add login form\n\n\
+                   Refs prl-aaa111 and prl-bbb222.\n\
+                   Fixes: prl-ccc333, prl-bbb222\n\
+                   Fixes (prl-ddd444)\n\
+                   Mentions prl-eee555 without a verb\n\
+                   # Closes prl-fff666\n";
+    let refs = parse_commit_refs(message, &CommitsConfig::default());
+    let summary: Vec<(&str, bool)> = refs
+        .iter()
+        .map(|commit_ref| (commit_ref.id.as_str(), commit_ref.closes))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("prl-aaa111", false),
+            ("prl-bbb222", true),
+            ("prl-ccc333", true),
+            ("prl-ddd444", true),
+        ]
+    );
+
+    let config = CommitsConfig {
+        reference_verbs: vec!["towards".to_string()],
+        closing_verbs: vec!["implements".to_string()],
+        ..CommitsConfig::default()
+    };
+    let refs = parse_commit_refs("Towards prl-aaa111; Fixes prl-ccc333", &config);
+    assert_eq!(refs.len(), 1);
+    assert!(!refs[0].closes);
+    assert!(parse_commit_refs("IMPLEMENTS prl-bbb222", &config)[0].closes);
+}
+
+#[test]
+fn test_commit_msg_rejects_unknown_and_blocked_pearls() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
     let repo_path = temp_dir.path();
-    std::fs::create_dir(repo_path.join(".git")).expect("Failed to create .git");
-    std::fs::create_dir(repo_path.join(".pearls")).expect("Failed to create .pearls");
+    let mut storage = init_auto_close_repo(repo_path, StorageLayout::Jsonl);
+    storage
+        .save(&create_pearl("prl-aaa111"))
+        .expect("Failed to save pearl");
+    let mut blocked = create_pearl("prl-bbb222");
+    blocked.deps.push(Dependency {
+        target_id: "prl-aaa111".to_string(),
+        dep_type: DepType::Blocks,
+    });
+    storage.save(&blocked).expect("Failed to save pearl");
 
-    let mut storage =
-        Storage::new(repo_path.join(".pearls/issues.jsonl")).expect("Failed to create storage");
-    let pearl = create_pearl("prl-abc123");
-    storage.save(&pearl).expect("Failed to save pearl");
+    let message_path = repo_path.join("MSG");
+    let check = |message: &str| {
+        std::fs::write(&message_path, message).expect("Failed to write message");
+        commit_msg_hook(repo_path, &message_path)
+    };
+    check("Fixes prl-aaa111, refs prl-bbb222").expect("Valid references rejected");
+    assert!(check("Refs prl-ccc333").is_err());
+    assert!(check("Closes prl-bbb222").is_err());
+}
 
-    std::fs::write(repo_path.join(".git/COMMIT_EDITMSG"), "Fixes (prl-abc123)")
-        .expect("Failed to write commit message");
+#[test]
+fn test_post_commit_links_and_closes() {
+    for layout in [StorageLayout::Jsonl, StorageLayout::Dir] {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo_path = temp_dir.path();
+        let mut storage = init_auto_close_repo(repo_path, layout);
+        storage
+            .save(&create_pearl("prl-abc123"))
+            .expect("Failed to save pearl");
+        storage
+            .save(&create_pearl("prl-def456"))
+            .expect("Failed to save pearl");
+        git(repo_path, &["add", "-A"]);
+        git(
+            repo_path,
+            &[
+                "commit",
+                "-q",
+                "-m",
+                "Add login\n\nFixes prl-abc123\nRefs prl-def456 and prl-999999",
+            ],
+        );
+        let sha = git(repo_path, &["rev-parse", "HEAD"]).trim().to_string();
 
-    pre_commit_hook(repo_path).expect("Pre-commit hook failed");
+        post_commit_hook(repo_path).expect("Post-commit hook failed");
+        post_commit_hook(repo_path).expect("Post-commit hook is not idempotent");
 
-    let updated = storage
-        .load_by_id("prl-abc123")
-        .expect("Failed to load pearl");
-    assert_eq!(updated.status, Status::Closed);
+        let fixed = storage.load_by_id("prl-abc123").expect("Failed to load");
+        assert_eq!(fixed.status, Status::Closed);
+        assert_eq!(
+            linked_commits(&fixed),
+            vec![LinkedCommit {
+                sha: sha.clone(),
+                author: "Tester <tester@example.com>".to_string(),
+                subject: "Add login".to_string(),
+                closes: true,
+            }]
+        );
+        let referenced = storage.load_by_id("prl-def456").expect("Failed to load");
+        assert_eq!(referenced.status, Status::Open);
+        assert_eq!(linked_commits(&referenced).len(), 1);
+        assert!(!linked_commits(&referenced)[0].closes);
+    }
+}
+
+#[test]
+fn test_post_checkout_tracks_current_pearl() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let repo_path = temp_dir.path();
+    let pearls_dir = repo_path.join(".pearls");
+    let mut storage = init_auto_close_repo(repo_path, StorageLayout::Jsonl);
+    storage
+        .save(&create_pearl("prl-abc123"))
+        .expect("Failed to save pearl");
+    git(repo_path, &["add", "-A"]);
+    git(repo_path, &["commit", "-q", "-m", "Initial"]);
+
+    git(repo_path, &["checkout", "-q", "-b", "prl/prl-abc123-test"]);
+    post_checkout_hook(repo_path, false).expect("Post-checkout hook failed");
+    assert_eq!(read_current(&pearls_dir).expect("current"), None);
+    post_checkout_hook(repo_path, true).expect("Post-checkout hook failed");
+    assert_eq!(
+        read_current(&pearls_dir).expect("current"),
+        Some("prl-abc123".to_string())
+    );
+
+    git(repo_path, &["checkout", "-q", "-b", "docs"]);
+    post_checkout_hook(repo_path, true).expect("Post-checkout hook failed");
+    assert_eq!(read_current(&pearls_dir).expect("current"), None);
+}
+
+#[test]
+fn test_prepare_commit_msg_adds_active_pearl_trailer() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let repo_path = temp_dir.path();
+    let pearls_dir = repo_path.join(".pearls");
+    let mut storage = init_auto_close_repo(repo_path, StorageLayout::Jsonl);
+    storage
+        .save(&create_pearl("prl-abc123"))
+        .expect("Failed to save pearl");
+    storage
+        .save(&create_pearl("prl-def456"))
+        .expect("Failed to save pearl");
+    git(repo_path, &["add", "-A"]);
+    git(repo_path, &["commit", "-q", "-m", "Initial"]);
+
+    let message_path = repo_path.join("COMMIT_EDITMSG");
+    let prepare = |message: &str, source: Option<&str>| {
+        std::fs::write(&message_path, message).expect("Failed to write message");
+        prepare_commit_msg_hook(repo_path, &message_path, source)
+            .expect("Prepare-commit-msg hook failed");
+        std::fs::read_to_string(&message_path).expect("Failed to read message")
+    };
+
+    // No active Pearl on the default branch.
+    assert_eq!(prepare("Fix login\n", Some("message")), "Fix login\n");
+
+    write_current(&pearls_dir, Some("prl-def456")).expect("Failed to set current");
+    assert_eq!(
+        prepare("Fix login\n# Please enter the commit message\n", None),
+        "Fix login\n\nPearl: prl-def456\n\n# Please enter the commit message\n"
+    );
+    // The editor template has only comments; the subject line stays empty.
+    assert_eq!(
+        prepare(
+            "\n# Please enter the commit message\n# On branch main\n",
+            None
+        ),
+        "\n\nPearl: prl-def456\n\n# Please enter the commit message\n# On branch main\n"
+    );
+    assert_eq!(
+        prepare("# Please enter the commit message\n", Some("template")),
+        "\n\nPearl: prl-def456\n\n# Please enter the commit message\n"
+    );
+
+    git(repo_path, &["checkout", "-q", "-b", "prl/prl-abc123-login"]);
+    assert_eq!(
+        prepare(
+            "Fix login\n\nSigned-off-by: Ada <ada@example.com>\n",
+            Some("message")
+        ),
+        "Fix login\n\nSigned-off-by: Ada <ada@example.com>\nPearl: prl-abc123\n"
+    );
+    assert_eq!(
+        prepare("Fix login\n\npearl: prl-def456\n", Some("message")),
+        "Fix login\n\npearl: prl-def456\n"
+    );
+    assert_eq!(
+        prepare("Merge branch 'main'\n", Some("merge")),
+        "Merge branch 'main'\n"
+    );
+    assert_eq!(prepare("Squash\n", Some("squash")), "Squash\n");
+
+    let refs = parse_commit_refs(
+        "Fix login\n\nPearl: prl-abc123\n",
+        &CommitsConfig::default(),
+    );
+    assert_eq!(refs.len(), 1);
+    assert!(!refs[0].closes);
+}
+
+#[test]
+fn test_install_chains_existing_hooks_and_uninstall_restores_them() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let repo_path = temp_dir.path();
+    git(repo_path, &["init", "-q"]);
+    git(repo_path, &["config", "core.hooksPath", ".githooks"]);
+
+    let dir = hooks_dir(repo_path).expect("Failed to resolve hooks dir");
+    assert!(dir.ends_with(".githooks"));
+    std::fs::create_dir_all(&dir).expect("Failed to create hooks dir");
+    let husky = "#!/usr/bin/env sh\necho lint\nexec true\n";
+    let python = "#!/usr/bin/env python3\nprint('checked')\n";
+    std::fs::write(dir.join("pre-commit"), husky).expect("Failed to write hook");
+    std::fs::write(dir.join("commit-msg"), python).expect("Failed to write hook");
+
+    let written = install_hooks(&dir).expect("Install failed");
+    assert_eq!(written.len(), 6);
+    assert!(install_hooks(&dir).expect("Install failed").is_empty());
+    assert!(hook_states(&dir)
+        .expect("Failed to read hook states")
+        .iter()
+        .all(|(_, state)| *state == HookState::Installed));
+
+    let pre_commit = std::fs::read_to_string(dir.join("pre-commit")).expect("Missing hook");
+    assert!(pre_commit.starts_with("#!/usr/bin/env sh\n# >>> pearls hooks >>>"));
+    assert!(pre_commit.ends_with("echo lint\nexec true\n"));
+    let commit_msg = std::fs::read_to_string(dir.join("commit-msg")).expect("Missing hook");
+    assert!(commit_msg.contains("commit-msg.pre-pearls\" \"$@\""));
+    assert!(dir.join("commit-msg.pre-pearls").exists());
+
+    std::fs::write(dir.join("post-merge"), "#!/bin/sh\nprl hooks post-merge\n")
+        .expect("Failed to write hook");
+    let states = hook_states(&dir).expect("Failed to read hook states");
+    assert!(states.contains(&("post-merge", HookState::Stale)));
+
+    uninstall_hooks(&dir).expect("Uninstall failed");
+    assert_eq!(
+        std::fs::read_to_string(dir.join("pre-commit")).expect("Missing hook"),
+        husky
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("commit-msg")).expect("Missing hook"),
+        python
+    );
+    assert!(!dir.join("commit-msg.pre-pearls").exists());
+    assert!(!dir.join("post-commit").exists());
+    assert!(!dir.join("post-merge").exists());
 }
 
 #[test]
@@ -65,3 +329,56 @@ fn test_post_merge_detects_orphaned_deps() {
     // Orphaned deps should warn but not error.
     post_merge_hook(repo_path).expect("Post-merge hook failed");
 }
+
+#[test]
+fn test_pre_commit_normalizes_staged_issues() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let repo_path = temp_dir.path();
+    git(repo_path, &["init", "-q"]);
+    std::fs::create_dir(repo_path.join(".pearls")).expect("Failed to create .pearls");
+
+    // A hand-edited file: out of order, padded, with a blank line.
+    let issues = repo_path.join(".pearls/issues.jsonl");
+    let line = |id: &str| serde_json::to_string(&create_pearl(id)).expect("Failed to serialize");
+    std::fs::write(
+        &issues,
+        format!("{}   \n\n{}\n", line("prl-bbbbbb"), line("prl-aaaaaa")),
+    )
+    .expect("Failed to write issues");
+    git(repo_path, &["add", ".pearls/issues.jsonl"]);
+
+    pre_commit_hook(repo_path).expect("Pre-commit hook failed");
+
+    let staged = git(repo_path, &["show", ":.pearls/issues.jsonl"]);
+    let on_disk = std::fs::read_to_string(&issues).expect("Failed to read");
+    assert_eq!(staged, on_disk, "Normalized file should be re-staged");
+    assert_eq!(
+        on_disk,
+        format!("{}\n{}\n", line("prl-aaaaaa"), line("prl-bbbbbb"))
+    );
+}
+
+#[test]
+fn test_pre_commit_leaves_partially_staged_issues() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let repo_path = temp_dir.path();
+    git(repo_path, &["init", "-q"]);
+    std::fs::create_dir(repo_path.join(".pearls")).expect("Failed to create .pearls");
+
+    let issues = repo_path.join(".pearls/issues.jsonl");
+    let line = |id: &str| serde_json::to_string(&create_pearl(id)).expect("Failed to serialize");
+    let staged = format!("{}\n{}\n", line("prl-bbbbbb"), line("prl-aaaaaa"));
+    std::fs::write(&issues, &staged).expect("Failed to write issues");
+    git(repo_path, &["add", ".pearls/issues.jsonl"]);
+    let unstaged = format!("{}{}\n", staged, line("prl-cccccc"));
+    std::fs::write(&issues, &unstaged).expect("Failed to write issues");
+
+    pre_commit_hook(repo_path).expect("Pre-commit hook failed");
+
+    assert_eq!(git(repo_path, &["show", ":.pearls/issues.jsonl"]), staged);
+    assert_eq!(
+        std::fs::read_to_string(&issues).expect("Failed to read"),
+        unstaged,
+        "Unstaged changes should not be rewritten or staged"
+    );
+}
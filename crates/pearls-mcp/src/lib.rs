@@ -2,7 +2,11 @@
 
 //! MCP server implementation for Pearls.
 
+mod page;
+mod policy;
+mod prompts;
 mod server;
 mod types;
+mod watch;
 
-pub use server::{run, McpOptions};
+pub use server::{run, serve_http, McpOptions, McpServerError};
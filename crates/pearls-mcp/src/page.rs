@@ -0,0 +1,217 @@
+// Rust guideline compliant 2026-02-10
+
+//! Cursor pagination and field projection for MCP list responses.
+//!
+//! Cursors are opaque to clients; they name the last item of the previous page,
+//! so items added or removed before it do not shift the next page.
+//! Projections keep only the requested Pearl fields and shorten long
+//! descriptions, so agents can scan many Pearls and `show` the ones they need.
+
+use pearls_app::AppError;
+use pearls_core::Pearl;
+
+/// Fields a projection may select.
+const PEARL_FIELDS: [&str; 12] = [
+    "id",
+    "title",
+    "description",
+    "status",
+    "priority",
+    "created_at",
+    "updated_at",
+    "author",
+    "labels",
+    "deps",
+    "metadata",
+    "comments",
+];
+
+/// One page of a list.
+pub(crate) struct Page<T> {
+    /// Items on this page.
+    pub(crate) items: Vec<T>,
+    /// Number of items across all pages.
+    pub(crate) total: usize,
+    /// Cursor of the next page, if there is one.
+    pub(crate) next_cursor: Option<String>,
+}
+
+/// Returns the page of `items` following `cursor`.
+///
+/// The cursor records the key of the last item returned and the position just
+/// after it. The next page starts after that item; if it is gone, it starts at
+/// the position the item held, which its successor now occupies.
+///
+/// # Arguments
+///
+/// * `items` - Every item of the list, in order
+/// * `cursor` - Cursor from a previous page; `None` starts at the beginning
+/// * `limit` - Maximum items on the page; `None` returns the rest
+/// * `key` - Unique key of an item, such as its ID
+///
+/// # Errors
+///
+/// Returns an error if `limit` is zero or the cursor is not one issued by this
+/// server.
+pub(crate) fn paginate<T>(
+    items: Vec<T>,
+    cursor: Option<&str>,
+    limit: Option<usize>,
+    key: impl Fn(&T) -> &str,
+) -> Result<Page<T>, AppError> {
+    if limit == Some(0) {
+        return Err(AppError::InvalidInput(
+            "limit must be at least 1".to_string(),
+        ));
+    }
+    let start = match cursor {
+        Some(cursor) => {
+            let (position, last) = cursor
+                .split_once(':')
+                .and_then(|(position, last)| Some((position.parse::<usize>().ok()?, last)))
+                .ok_or_else(|| AppError::InvalidInput(format!("Invalid cursor '{}'", cursor)))?;
+            items
+                .iter()
+                .position(|item| key(item) == last)
+                .map_or(position.saturating_sub(1), |found| found + 1)
+        }
+        None => 0,
+    };
+    let total = items.len();
+    let items: Vec<T> = items
+        .into_iter()
+        .skip(start)
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+    let end = start.saturating_add(items.len());
+    let next_cursor = match items.last() {
+        Some(last) if end < total => Some(format!("{}:{}", end, key(last))),
+        _ => None,
+    };
+    Ok(Page {
+        items,
+        total,
+        next_cursor,
+    })
+}
+
+/// Selection of Pearl fields for list responses.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Projection {
+    fields: Option<Vec<String>>,
+    max_description: Option<usize>,
+}
+
+impl Projection {
+    /// Creates a projection.
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - Fields to keep; `None` keeps all, and `id` is always kept
+    /// * `max_description` - Maximum description length in characters
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a field is not a Pearl field.
+    pub(crate) fn new(
+        fields: Option<Vec<String>>,
+        max_description: Option<usize>,
+    ) -> Result<Self, AppError> {
+        if let Some(fields) = &fields {
+            if let Some(unknown) = fields
+                .iter()
+                .find(|field| !PEARL_FIELDS.contains(&field.as_str()))
+            {
+                return Err(AppError::InvalidInput(format!(
+                    "Unknown field '{}'; expected one of: {}",
+                    unknown,
+                    PEARL_FIELDS.join(", ")
+                )));
+            }
+        }
+        Ok(Self {
+            fields,
+            max_description,
+        })
+    }
+
+    /// Projects a Pearl to a JSON object.
+    ///
+    /// A shortened description is marked with `"more": true`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Pearl cannot be serialized.
+    pub(crate) fn apply(&self, pearl: &Pearl) -> Result<serde_json::Value, AppError> {
+        let value = serde_json::to_value(pearl).map_err(pearls_core::Error::from)?;
+        let serde_json::Value::Object(mut object) = value else {
+            return Ok(value);
+        };
+        if let Some(fields) = &self.fields {
+            object.retain(|key, _| key == "id" || fields.iter().any(|field| field == key));
+        }
+        if let Some(max) = self.max_description {
+            if let Some(serde_json::Value::String(description)) = object.get_mut("description") {
+                if let Some((cut, _)) = description.char_indices().nth(max) {
+                    description.truncate(cut);
+                    object.insert("more".to_string(), serde_json::Value::Bool(true));
+                }
+            }
+        }
+        Ok(serde_json::Value::Object(object))
+    }
+
+    /// Projects a list of Pearls.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a Pearl cannot be serialized.
+    pub(crate) fn apply_all(&self, pearls: &[Pearl]) -> Result<Vec<serde_json::Value>, AppError> {
+        pearls.iter().map(|pearl| self.apply(pearl)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginate_and_project() {
+        let ids = |ids: &[&'static str]| ids.to_vec();
+        let first = paginate(ids(&["a", "b", "c", "d", "e"]), None, Some(2), |id| id)
+            .expect("First page failed");
+        assert_eq!(first.items, vec!["a", "b"]);
+        assert_eq!(first.total, 5);
+        let cursor = first.next_cursor.expect("Missing cursor");
+        // An item removed before the cursor does not shift the next page.
+        let last = paginate(ids(&["b", "c", "d", "e"]), Some(&cursor), Some(3), |id| id)
+            .expect("Last page failed");
+        assert_eq!(last.items, vec!["c", "d", "e"]);
+        assert!(last.next_cursor.is_none());
+        // When the last item itself is gone, its successor is not skipped.
+        let resumed = paginate(ids(&["a", "c", "d", "e"]), Some(&cursor), None, |id| id)
+            .expect("Resumed page failed");
+        assert_eq!(resumed.items, vec!["c", "d", "e"]);
+        assert!(paginate(ids(&["a"]), Some("nope"), None, |id| id).is_err());
+        assert!(paginate(ids(&["a"]), None, Some(0), |id| id).is_err());
+
+        let mut pearl = Pearl::new("Title".to_string(), "author".to_string());
+        pearl.description = "Long description".to_string();
+        let projection = Projection::new(
+            Some(vec!["title".to_string(), "description".to_string()]),
+            Some(4),
+        )
+        .expect("Projection failed");
+        let value = projection.apply(&pearl).expect("Apply failed");
+        let object = value.as_object().expect("Expected object");
+        assert_eq!(object.len(), 4);
+        assert_eq!(object["id"], pearl.id.as_str());
+        assert_eq!(object["description"], "Long");
+        assert_eq!(object["more"], true);
+
+        let full = Projection::default().apply(&pearl).expect("Apply failed");
+        assert!(full.get("more").is_none());
+        assert_eq!(full["author"], "author");
+        assert!(Projection::new(Some(vec!["bogus".to_string()]), None).is_err());
+    }
+}
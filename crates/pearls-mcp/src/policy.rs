@@ -0,0 +1,214 @@
+// Rust guideline compliant 2026-02-10
+
+//! Access policy checks and the audit log for MCP tool calls.
+//!
+//! Tool-level rules are applied to every call before it reaches the tool.
+//! Transition, label, and batch rules are applied by the tools themselves,
+//! since they depend on the arguments and the stored Pearls. Every mutating
+//! call, whether it succeeded, failed, or was denied, is appended to
+//! `.pearls/audit.jsonl` together with the client name the agent reported at
+//! initialization.
+
+use pearls_app::{AppError, RepoContext};
+use pearls_core::{McpPolicy, Pearl, Status};
+use rmcp::model::JsonObject;
+use serde::Serialize;
+use std::io::Write;
+
+/// Name of the audit log inside `.pearls`.
+const AUDIT_FILE: &str = "audit.jsonl";
+
+/// Tools that always change the repository.
+const MUTATING_TOOLS: [&str; 13] = [
+    "create",
+    "update",
+    "close",
+    "comments_add",
+    "comments_delete",
+    "link",
+    "unlink",
+    "transition_safe",
+    "meta_set",
+    "meta_delete",
+    "session_start",
+    "session_note",
+    "session_end",
+];
+
+/// Whether a tool call changes the repository.
+///
+/// `doctor` only writes with `fix`, and `compact` only without `dry_run`.
+pub(crate) fn is_mutating(tool: &str, arguments: Option<&JsonObject>) -> bool {
+    let flag = |name: &str| {
+        arguments
+            .and_then(|arguments| arguments.get(name))
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+    };
+    match tool {
+        "doctor" => flag("fix"),
+        "compact" => !flag("dry_run"),
+        _ => MUTATING_TOOLS.contains(&tool),
+    }
+}
+
+/// Refuses tools that are denied or not allowed.
+pub(crate) fn check_tool(policy: &McpPolicy, tool: &str) -> Result<(), AppError> {
+    let listed = |tools: &[String]| tools.iter().any(|name| name == tool);
+    if listed(&policy.deny_tools)
+        || (!policy.allow_tools.is_empty() && !listed(&policy.allow_tools))
+    {
+        return Err(AppError::PolicyDenied(format!(
+            "Tool '{}' is not allowed",
+            tool
+        )));
+    }
+    Ok(())
+}
+
+/// Refuses moving a Pearl to a status denied for its priority.
+///
+/// `priorities` holds the priority of the Pearl before and after the call, so
+/// a rule cannot be sidestepped by changing both in one update.
+pub(crate) fn check_transition(
+    policy: &McpPolicy,
+    pearl: &Pearl,
+    priorities: &[u8],
+    to: Status,
+) -> Result<(), AppError> {
+    let denied = policy.deny_transitions.iter().any(|rule| {
+        rule.to == to
+            && (rule.priorities.is_empty()
+                || priorities
+                    .iter()
+                    .any(|priority| rule.priorities.contains(priority)))
+    });
+    if denied {
+        return Err(AppError::PolicyDenied(format!(
+            "Moving P{} Pearl {} to {} is not allowed",
+            pearl.priority,
+            pearl.id,
+            crate::server::status_key(to)
+        )));
+    }
+    Ok(())
+}
+
+/// Refuses adding or removing labels that are denied or not allowed.
+pub(crate) fn check_labels<'a>(
+    policy: &McpPolicy,
+    labels: impl IntoIterator<Item = &'a String>,
+) -> Result<(), AppError> {
+    for label in labels {
+        let listed = |labels: &[String]| labels.iter().any(|name| name == label);
+        if listed(&policy.deny_labels)
+            || (!policy.allow_labels.is_empty() && !listed(&policy.allow_labels))
+        {
+            return Err(AppError::PolicyDenied(format!(
+                "Label '{}' may not be changed",
+                label
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Refuses batches larger than `max_batch`.
+pub(crate) fn check_batch(policy: &McpPolicy, tool: &str, count: usize) -> Result<(), AppError> {
+    match policy.max_batch {
+        Some(max) if count > max => Err(AppError::PolicyDenied(format!(
+            "Tool '{}' accepts at most {} items, got {}",
+            tool, max, count
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// One line of the audit log.
+#[derive(Debug, Serialize)]
+pub(crate) struct AuditEntry<'a> {
+    /// Unix timestamp of the call.
+    pub(crate) timestamp: i64,
+    /// Client name from the MCP initialize request.
+    pub(crate) client: &'a str,
+    /// Server session that received the call.
+    pub(crate) session: u64,
+    /// Tool name.
+    pub(crate) tool: &'a str,
+    /// Tool arguments.
+    pub(crate) arguments: Option<&'a JsonObject>,
+    /// `ok`, or the error code of a failed or denied call.
+    pub(crate) outcome: &'a str,
+    /// Error message of a failed or denied call.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) message: Option<&'a str>,
+}
+
+/// Appends an entry to the audit log.
+///
+/// The log is added to `.pearls/.gitignore` when it is first created.
+///
+/// # Errors
+///
+/// Returns an error if the log cannot be opened or written.
+pub(crate) fn append_audit(repo: &RepoContext, entry: &AuditEntry<'_>) -> Result<(), AppError> {
+    let path = repo.pearls_dir().join(AUDIT_FILE);
+    if !path.exists() {
+        repo.ignore_local_files()?;
+    }
+    let mut line = serde_json::to_string(entry).map_err(pearls_core::Error::from)?;
+    line.push('\n');
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pearls_core::TransitionRule;
+
+    #[test]
+    fn test_policy_checks() {
+        let policy = McpPolicy {
+            allow_tools: vec!["list".to_string(), "close".to_string()],
+            deny_tools: vec!["close".to_string()],
+            deny_transitions: vec![TransitionRule {
+                to: Status::Closed,
+                priorities: vec![0],
+            }],
+            deny_labels: vec!["security".to_string()],
+            max_batch: Some(2),
+            ..McpPolicy::default()
+        };
+
+        assert!(check_tool(&policy, "list").is_ok());
+        assert!(check_tool(&policy, "close").is_err());
+        assert!(check_tool(&policy, "create").is_err());
+        assert!(check_tool(&McpPolicy::default(), "compact").is_ok());
+
+        let mut pearl = Pearl::new("Urgent".to_string(), "author".to_string());
+        pearl.priority = 0;
+        assert!(check_transition(&policy, &pearl, &[0], Status::Closed).is_err());
+        assert!(check_transition(&policy, &pearl, &[0], Status::InProgress).is_ok());
+        assert!(check_transition(&policy, &pearl, &[1], Status::Closed).is_ok());
+
+        let labels = ["ui".to_string(), "security".to_string()];
+        assert!(check_labels(&policy, &labels[..1]).is_ok());
+        let err = check_labels(&policy, &labels).expect_err("label should be denied");
+        assert_eq!(err.code(), pearls_app::ErrorCode::PolicyDenied);
+
+        assert!(check_batch(&policy, "create", 2).is_ok());
+        assert!(check_batch(&policy, "create", 3).is_err());
+
+        let arguments = serde_json::json!({ "dry_run": true });
+        assert!(!is_mutating("compact", arguments.as_object()));
+        assert!(is_mutating("compact", None));
+        assert!(!is_mutating("doctor", None));
+        assert!(is_mutating("meta_set", None));
+        assert!(!is_mutating("list", None));
+    }
+}
@@ -0,0 +1,600 @@
+// Rust guideline compliant 2026-02-10
+
+//! MCP prompt templates for common agent workflows.
+//!
+//! The built-in prompts are filled with live repository data when requested.
+//! Teams can add their own templates as Markdown files in `.pearls/prompts/`;
+//! the file name without `.md` is the prompt name, and a template with the
+//! same name as a built-in prompt replaces it. Placeholders such as `{{id}}`
+//! become required arguments, except for the data placeholders
+//! `{{open_pearls}}`, `{{ready_pearls}}`, and `{{pearl}}` (the Pearl named by
+//! the `id` argument), which are filled from the repository.
+
+use crate::server::status_key;
+use pearls_app::{resolve_pearl_id, AppError, RepoContext};
+use pearls_core::{DepType, IssueGraph, Pearl, Status};
+use rmcp::model::{GetPromptResult, Prompt, PromptArgument, PromptMessage, PromptMessageRole};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Directory of custom prompt templates inside `.pearls`.
+const PROMPTS_DIR: &str = "prompts";
+
+/// Placeholders filled from the repository rather than by arguments.
+const DATA_PLACEHOLDERS: [&str; 3] = ["open_pearls", "ready_pearls", "pearl"];
+
+/// Prompt arguments as `(name, description)`.
+type Arguments = &'static [(&'static str, &'static str)];
+
+/// Built-in prompts as `(name, description, arguments)`.
+const BUILTIN: &[(&str, &str, Arguments)] = &[
+    (
+        "triage",
+        "Triage the open Pearls: priorities, labels, duplicates, and what to defer.",
+        &[],
+    ),
+    (
+        "plan_epic",
+        "Plan the work for an epic from its subtree and blockers.",
+        &[("id", "Epic Pearl ID (full or partial)")],
+    ),
+    (
+        "handoff",
+        "Write a handoff summary for a Pearl from its description, comments, and commits.",
+        &[("id", "Pearl ID (full or partial)")],
+    ),
+    (
+        "breakdown",
+        "Break a Pearl into subtasks.",
+        &[("id", "Pearl ID (full or partial)")],
+    ),
+];
+
+/// Lists the built-in and custom prompts.
+///
+/// # Arguments
+///
+/// * `repo` - Repository whose custom templates are included
+///
+/// # Returns
+///
+/// The prompts sorted by name.
+///
+/// # Errors
+///
+/// Returns an error if the prompts directory cannot be read.
+pub(crate) fn list_prompts(repo: &RepoContext) -> Result<Vec<Prompt>, AppError> {
+    let custom = load_custom(repo)?;
+    let mut prompts: Vec<Prompt> = BUILTIN
+        .iter()
+        .filter(|(name, _, _)| !custom.contains_key(*name))
+        .map(|(name, description, arguments)| {
+            Prompt::new(
+                *name,
+                Some(*description),
+                Some(
+                    arguments
+                        .iter()
+                        .map(|(name, description)| argument(name, Some(description)))
+                        .collect(),
+                ),
+            )
+        })
+        .collect();
+    prompts.extend(custom.iter().map(|(name, template)| {
+        Prompt::new(
+            name.as_str(),
+            template_description(template),
+            Some(
+                template_arguments(template)
+                    .into_iter()
+                    .map(|name| argument(&name, None))
+                    .collect(),
+            ),
+        )
+    }));
+    prompts.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(prompts)
+}
+
+/// Fills a prompt with repository data.
+///
+/// # Arguments
+///
+/// * `repo` - Repository to read
+/// * `name` - Prompt name
+/// * `arguments` - Prompt arguments by name
+///
+/// # Returns
+///
+/// The prompt as a single user message.
+///
+/// # Errors
+///
+/// Returns an error if the prompt does not exist, a required argument is
+/// missing, a Pearl ID cannot be resolved, or the repository cannot be read.
+pub(crate) fn get_prompt(
+    repo: &RepoContext,
+    name: &str,
+    arguments: &HashMap<String, String>,
+) -> Result<GetPromptResult, AppError> {
+    let pearls = repo.open_storage()?.load_all()?;
+    let custom = load_custom(repo)?;
+
+    let (description, text) = if let Some(template) = custom.get(name) {
+        (
+            template_description(template),
+            fill_template(template, arguments, &pearls)?,
+        )
+    } else {
+        let (_, description, _) = BUILTIN
+            .iter()
+            .find(|(builtin, _, _)| *builtin == name)
+            .ok_or_else(|| AppError::InvalidInput(format!("Prompt '{}' not found", name)))?;
+        let text = match name {
+            "triage" => triage(&pearls)?,
+            "plan_epic" => plan_epic(&pearls, required(arguments, "id")?)?,
+            "handoff" => handoff(&pearls, required(arguments, "id")?)?,
+            _ => breakdown(&pearls, required(arguments, "id")?)?,
+        };
+        (Some(description.to_string()), text)
+    };
+
+    Ok(GetPromptResult {
+        description,
+        messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+    })
+}
+
+fn argument(name: &str, description: Option<&str>) -> PromptArgument {
+    PromptArgument {
+        name: name.to_string(),
+        title: None,
+        description: description.map(str::to_string),
+        required: Some(true),
+    }
+}
+
+fn required<'a>(arguments: &'a HashMap<String, String>, name: &str) -> Result<&'a str, AppError> {
+    arguments
+        .get(name)
+        .map(String::as_str)
+        .filter(|value| !value.trim().is_empty())
+        .ok_or_else(|| AppError::InvalidInput(format!("Missing prompt argument '{}'", name)))
+}
+
+fn triage(pearls: &[Pearl]) -> Result<String, AppError> {
+    let graph = IssueGraph::from_pearls(pearls.to_vec())?;
+    let mut text = String::from(
+        "Triage the open Pearls below. For each one, suggest a priority (0 is \
+         most urgent, 4 least), labels, and whether it duplicates another Pearl, \
+         should be deferred, or is ready to start. Apply the changes you are sure \
+         about with the `update` and `link` tools and list the rest as questions.\n\n",
+    );
+    text.push_str("## Open Pearls\n\n");
+    text.push_str(&pearl_lines(open_pearls(pearls), &graph));
+    Ok(text)
+}
+
+fn plan_epic(pearls: &[Pearl], id: &str) -> Result<String, AppError> {
+    let graph = IssueGraph::from_pearls(pearls.to_vec())?;
+    let epic = find(pearls, id)?;
+    let mut text = format!(
+        "Plan the work for the epic {} below. Propose an order for the open \
+         subtasks, name tasks that are missing, and call out blockers that need \
+         attention first. Create missing subtasks with `create` and link them to \
+         the epic with `link` using the `parent_child` type.\n\n",
+        epic.id
+    );
+    text.push_str(&pearl_detail(epic));
+
+    let subtree = descendants(pearls, &epic.id);
+    text.push_str("\n## Subtree\n\n");
+    if subtree.is_empty() {
+        text.push_str("No subtasks yet.\n");
+    }
+    for (depth, pearl) in &subtree {
+        text.push_str(&"  ".repeat(*depth));
+        text.push_str(&pearl_line(pearl, &graph));
+    }
+    Ok(text)
+}
+
+fn handoff(pearls: &[Pearl], id: &str) -> Result<String, AppError> {
+    let pearl = find(pearls, id)?;
+    let mut text = format!(
+        "Write a handoff summary for {} so another agent or person can pick it up. \
+         Cover what it is about, what has been done, what is left, open questions, \
+         and anything the next owner should watch out for.\n\n",
+        pearl.id
+    );
+    text.push_str(&pearl_detail(pearl));
+
+    if !pearl.deps.is_empty() {
+        text.push_str("\n## Dependencies\n\n");
+        for dep in &pearl.deps {
+            let title = pearls
+                .iter()
+                .find(|candidate| candidate.id == dep.target_id)
+                .map(|target| format!(" {} [{}]", target.title, status_key(target.status)))
+                .unwrap_or_default();
+            let _ = writeln!(
+                text,
+                "- {} {}{}",
+                dep_label(dep.dep_type),
+                dep.target_id,
+                title
+            );
+        }
+    }
+
+    text.push_str("\n## Comments\n\n");
+    if pearl.comments.is_empty() {
+        text.push_str("No comments.\n");
+    }
+    for comment in &pearl.comments {
+        if comment.kind.is_note() {
+            let _ = writeln!(text, "- {}: {}", comment.author, comment.body);
+        } else {
+            let _ = writeln!(
+                text,
+                "- {} ({}): {}",
+                comment.author,
+                comment.kind.as_str(),
+                comment.body
+            );
+        }
+    }
+
+    text.push_str("\n## Linked commits\n\n");
+    let commits = pearls_app::linked_commits(pearl);
+    if commits.is_empty() {
+        text.push_str("No linked commits.\n");
+    }
+    for commit in &commits {
+        let sha = commit.sha.get(..7).unwrap_or(&commit.sha);
+        let _ = writeln!(text, "- {} {} ({})", sha, commit.subject, commit.author);
+    }
+    Ok(text)
+}
+
+fn breakdown(pearls: &[Pearl], id: &str) -> Result<String, AppError> {
+    let graph = IssueGraph::from_pearls(pearls.to_vec())?;
+    let pearl = find(pearls, id)?;
+    let mut text = format!(
+        "Break {} into subtasks that can each be finished and reviewed on their \
+         own. Give every subtask a title, a short description, and a priority. \
+         Create them with `create`, link each to {} with `link` using the \
+         `parent_child` type, and add `blocks` links where one subtask must \
+         finish before another.\n\n",
+        pearl.id, pearl.id
+    );
+    text.push_str(&pearl_detail(pearl));
+
+    let children = descendants(pearls, &pearl.id);
+    if !children.is_empty() {
+        text.push_str("\n## Existing subtasks\n\n");
+        for (depth, child) in &children {
+            text.push_str(&"  ".repeat(*depth));
+            text.push_str(&pearl_line(child, &graph));
+        }
+    }
+    Ok(text)
+}
+
+fn find<'a>(pearls: &'a [Pearl], id: &str) -> Result<&'a Pearl, AppError> {
+    let full_id = resolve_pearl_id(id, pearls)?;
+    pearls
+        .iter()
+        .find(|pearl| pearl.id == full_id)
+        .ok_or_else(|| AppError::Core(pearls_core::Error::NotFound(full_id)))
+}
+
+fn open_pearls(pearls: &[Pearl]) -> Vec<&Pearl> {
+    let mut open: Vec<&Pearl> = pearls
+        .iter()
+        .filter(|pearl| pearl.status != Status::Closed)
+        .collect();
+    open.sort_by(|a, b| a.priority.cmp(&b.priority).then(a.id.cmp(&b.id)));
+    open
+}
+
+/// Returns the Pearls below `id` in the parent-child tree, depth first, with
+/// their depth starting at zero.
+fn descendants<'a>(pearls: &'a [Pearl], id: &str) -> Vec<(usize, &'a Pearl)> {
+    fn visit<'a>(
+        pearls: &'a [Pearl],
+        parent: &str,
+        depth: usize,
+        out: &mut Vec<(usize, &'a Pearl)>,
+    ) {
+        let mut children: Vec<&Pearl> = pearls
+            .iter()
+            .filter(|pearl| {
+                pearl
+                    .deps
+                    .iter()
+                    .any(|dep| dep.dep_type == DepType::ParentChild && dep.target_id == parent)
+            })
+            .collect();
+        children.sort_by(|a, b| a.priority.cmp(&b.priority).then(a.id.cmp(&b.id)));
+        for child in children {
+            if out.iter().any(|(_, seen)| seen.id == child.id) {
+                continue;
+            }
+            out.push((depth, child));
+            visit(pearls, &child.id, depth + 1, out);
+        }
+    }
+
+    let mut out = Vec::new();
+    visit(pearls, id, 0, &mut out);
+    out
+}
+
+fn pearl_lines(pearls: Vec<&Pearl>, graph: &IssueGraph) -> String {
+    if pearls.is_empty() {
+        return "None.\n".to_string();
+    }
+    pearls
+        .into_iter()
+        .map(|pearl| pearl_line(pearl, graph))
+        .collect()
+}
+
+/// Renders a Pearl as one list item with its open blockers.
+fn pearl_line(pearl: &Pearl, graph: &IssueGraph) -> String {
+    let mut line = format!(
+        "- {} [P{}, {}] {}",
+        pearl.id,
+        pearl.priority,
+        status_key(pearl.status),
+        pearl.title
+    );
+    if !pearl.labels.is_empty() {
+        let _ = write!(line, " #{}", pearl.labels.join(" #"));
+    }
+    let blockers: Vec<&str> = graph
+        .blocking_deps(&pearl.id)
+        .into_iter()
+        .map(|blocker| blocker.id.as_str())
+        .collect();
+    if !blockers.is_empty() {
+        let _ = write!(line, " (blocked by {})", blockers.join(", "));
+    }
+    line.push('\n');
+    line
+}
+
+/// Renders the heading, fields, and description of a Pearl.
+fn pearl_detail(pearl: &Pearl) -> String {
+    let mut text = format!("# {} {}\n\n", pearl.id, pearl.title);
+    let _ = writeln!(text, "- Status: {}", status_key(pearl.status));
+    let _ = writeln!(text, "- Priority: P{}", pearl.priority);
+    let _ = writeln!(text, "- Author: {}", pearl.author);
+    if !pearl.labels.is_empty() {
+        let _ = writeln!(text, "- Labels: {}", pearl.labels.join(", "));
+    }
+    if !pearl.description.trim().is_empty() {
+        let _ = write!(text, "\n{}\n", pearl.description.trim_end());
+    }
+    text
+}
+
+fn dep_label(dep_type: DepType) -> &'static str {
+    match dep_type {
+        DepType::Blocks => "blocked by",
+        DepType::ParentChild => "child of",
+        DepType::Related => "related to",
+        DepType::DiscoveredFrom => "discovered from",
+    }
+}
+
+/// Reads the custom templates, keyed by prompt name.
+fn load_custom(repo: &RepoContext) -> Result<HashMap<String, String>, AppError> {
+    let dir = repo.pearls_dir().join(PROMPTS_DIR);
+    let mut templates = HashMap::new();
+    if !dir.is_dir() {
+        return Ok(templates);
+    }
+    for entry in std::fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "md") || !path.is_file() {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        templates.insert(name.to_string(), std::fs::read_to_string(&path)?);
+    }
+    Ok(templates)
+}
+
+/// Uses the first line of a template, without heading marks, as its
+/// description.
+fn template_description(template: &str) -> Option<String> {
+    template
+        .lines()
+        .map(|line| line.trim_start_matches('#').trim())
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// Returns the placeholder names of a template in order of first use.
+fn placeholders(template: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("}}") else {
+            break;
+        };
+        let name = rest[..end].trim();
+        if !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            && !names.iter().any(|seen| seen == name)
+        {
+            names.push(name.to_string());
+        }
+        rest = &rest[end + 2..];
+    }
+    names
+}
+
+/// Returns the arguments of a template: its placeholders other than the data
+/// placeholders, plus `id` when `{{pearl}}` is used.
+fn template_arguments(template: &str) -> Vec<String> {
+    let names = placeholders(template);
+    let mut arguments: Vec<String> = names
+        .iter()
+        .filter(|name| !DATA_PLACEHOLDERS.contains(&name.as_str()))
+        .cloned()
+        .collect();
+    if names.iter().any(|name| name == "pearl") && !arguments.iter().any(|name| name == "id") {
+        arguments.push("id".to_string());
+    }
+    arguments
+}
+
+fn fill_template(
+    template: &str,
+    arguments: &HashMap<String, String>,
+    pearls: &[Pearl],
+) -> Result<String, AppError> {
+    let names = placeholders(template);
+    let graph = IssueGraph::from_pearls(pearls.to_vec())?;
+    let mut values = HashMap::new();
+    for name in template_arguments(template) {
+        let value = required(arguments, &name)?;
+        values.insert(name, value.to_string());
+    }
+    for name in &names {
+        let value = match name.as_str() {
+            "open_pearls" => pearl_lines(open_pearls(pearls), &graph),
+            "ready_pearls" => pearl_lines(graph.ready_queue(), &graph),
+            "pearl" => pearl_detail(find(pearls, &values["id"])?),
+            _ => continue,
+        };
+        values.insert(name.clone(), value);
+    }
+
+    Ok(substitute(template, &values))
+}
+
+/// Replaces each `{{name}}` in one left-to-right pass, so substituted values
+/// are never expanded again. Unknown placeholders are kept as written.
+fn substitute(template: &str, values: &HashMap<String, String>) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}").map(|end| start + 2 + end) else {
+            break;
+        };
+        text.push_str(&rest[..start]);
+        match values.get(rest[start + 2..end].trim()) {
+            Some(value) => text.push_str(value),
+            None => text.push_str(&rest[start..end + 2]),
+        }
+        rest = &rest[end + 2..];
+    }
+    text.push_str(rest);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pearls_core::Dependency;
+
+    fn sample_pearl(id: &str, title: &str, parent: Option<&str>) -> Pearl {
+        let mut pearl = Pearl::new(title.to_string(), "author".to_string());
+        pearl.id = id.to_string();
+        pearl.deps = parent
+            .map(|target| Dependency {
+                target_id: target.to_string(),
+                dep_type: DepType::ParentChild,
+            })
+            .into_iter()
+            .collect();
+        pearl
+    }
+
+    #[test]
+    fn test_plan_epic_and_custom_template() {
+        let pearls = vec![
+            sample_pearl("prl-aaa111", "Epic", None),
+            sample_pearl("prl-bbb222", "Task", Some("prl-aaa111")),
+            sample_pearl("prl-ccc333", "Subtask", Some("prl-bbb222")),
+            sample_pearl("prl-ddd444", "Unrelated", None),
+        ];
+
+        let text = plan_epic(&pearls, "prl-aaa").expect("plan_epic failed");
+        assert!(text.contains("# prl-aaa111 Epic"));
+        assert!(text.contains("- prl-bbb222 [P2, open] Task"));
+        assert!(text.contains("  - prl-ccc333 [P2, open] Subtask"));
+        assert!(!text.contains("Unrelated"));
+
+        let template = "# Review for {{team}}\n{{ pearl }}\n{{open_pearls}}";
+        assert_eq!(template_arguments(template), vec!["team", "id"]);
+        assert_eq!(
+            template_description(template).as_deref(),
+            Some("Review for {{team}}")
+        );
+        let arguments = HashMap::from([
+            ("team".to_string(), "core".to_string()),
+            ("id".to_string(), "prl-ddd".to_string()),
+        ]);
+        let text = fill_template(template, &arguments, &pearls).expect("fill failed");
+        assert!(text.contains("for core"));
+        assert!(text.contains("# prl-ddd444 Unrelated"));
+        assert!(text.contains("- prl-ccc333 [P2, open] Subtask"));
+        assert!(fill_template(template, &HashMap::new(), &pearls).is_err());
+
+        // Values are inserted as written, even when they look like placeholders.
+        let template = "{{a}} and {{ b }} then {{other";
+        let arguments = HashMap::from([
+            ("a".to_string(), "{{b}}".to_string()),
+            ("b".to_string(), "{{a}}".to_string()),
+        ]);
+        assert_eq!(
+            fill_template(template, &arguments, &pearls).expect("fill failed"),
+            "{{b}} and {{a}} then {{other"
+        );
+    }
+
+    #[test]
+    fn test_list_and_get_prompts_with_custom_templates() {
+        let temp = tempfile::TempDir::new().expect("Failed to create temp dir");
+        let pearls_dir = temp.path().join(".pearls");
+        std::fs::create_dir_all(pearls_dir.join(PROMPTS_DIR)).expect("Failed to create dirs");
+        std::fs::File::create(pearls_dir.join("issues.jsonl")).expect("Failed to create issues");
+        pearls_core::Config::default()
+            .save(&pearls_dir)
+            .expect("Failed to save config");
+        std::fs::write(
+            pearls_dir.join(PROMPTS_DIR).join("standup.md"),
+            "Standup for {{team}}\n{{ready_pearls}}",
+        )
+        .expect("Failed to write template");
+        let repo = RepoContext::discover(Some(temp.path())).expect("Failed to discover repo");
+
+        let names: Vec<String> = list_prompts(&repo)
+            .expect("list failed")
+            .into_iter()
+            .map(|prompt| prompt.name)
+            .collect();
+        assert_eq!(
+            names,
+            vec!["breakdown", "handoff", "plan_epic", "standup", "triage"]
+        );
+
+        let arguments = HashMap::from([("team".to_string(), "core".to_string())]);
+        let result = get_prompt(&repo, "standup", &arguments).expect("get failed");
+        assert_eq!(result.description.as_deref(), Some("Standup for {{team}}"));
+        assert!(get_prompt(&repo, "triage", &HashMap::new()).is_ok());
+        assert!(get_prompt(&repo, "handoff", &HashMap::new()).is_err());
+        assert!(get_prompt(&repo, "missing", &HashMap::new()).is_err());
+    }
+}
@@ -1,41 +1,67 @@
 // Rust guideline compliant 2026-02-09
 
 //! MCP server runtime for Pearls.
-
+//!
+//! The server speaks MCP over stdio, or over the streamable HTTP transport at
+//! `/mcp` so several agents can share one server. Sessions share a write lock,
+//! so read-modify-write tool calls from different agents do not interleave.
+//! Every tool call passes the access policy from `[mcp.policy]` or
+//! `--policy`, and mutating calls are recorded in the audit log.
+
+use crate::page::{paginate, Projection};
+use crate::policy::{self, AuditEntry};
+use crate::prompts;
 use crate::types::{
-    BlockedChain, CloseInput, CloseResult, CommentsAddInput, CommentsAddResult, CommentsDeleteInput,
-    CommentsDeleteResult, CommentsListInput, CommentsListResult, CreateInput, CreateResult,
-    EmptyInput, LinkInput, LinkItem, LinkResult, ListInput, ListResult,
-    NextActionResult, PlanSnapshotInput, PlanSnapshotResult, ReadyInput, ReadyResource, ShowInput,
-    ShowResult, StatusCount, TransitionSafeInput, TransitionSafeResult, UnlinkInput, UnlinkItem,
-    UnlinkResult, UpdateInput, UpdateResult,
+    BlockedChain, CloseInput, CloseResult, CommentsAddInput, CommentsAddResult,
+    CommentsDeleteInput, CommentsDeleteResult, CommentsListInput, CommentsListResult, CompactInput,
+    CreateInput, CreateResult, DoctorInput, EmptyInput, LinkInput, LinkItem, LinkResult, ListInput,
+    ListResult, MetaKeyInput, MetaResult, MetaSetInput, NextActionResult, PlanSnapshotInput,
+    PlanSnapshotResult, ReadyInput, ReadyResource, ResumeInput, ResumeResult, SessionEndInput,
+    SessionNoteInput, SessionStartInput, ShowInput, ShowResult, StatusCount, TransitionSafeInput,
+    TransitionSafeResult, UnlinkInput, UnlinkItem, UnlinkResult, UpdateInput, UpdateResult,
 };
+use crate::watch::{pearl_uri, watch, Notifier, READY_URI};
 use pearls_app::{
-    list_pearls, parse_dep_type, parse_status, ready_queue, resolve_pearl_id, unix_timestamp,
-    validate_transition, AppError, ErrorEnvelope, ListOptions, RepoContext, SuccessEnvelope,
+    add_session_note, compact, diagnose, end_session, list_pearls, load_candidates,
+    load_ready_queue, parse_comment_kind, parse_dep_type, parse_status, resolve_pearl_id, resume,
+    start_session, unix_timestamp, validate_transition, AppError, CompactReport, DoctorReport,
+    ErrorEnvelope, ListOptions, QueryCache, RepoContext, Session, SessionNote, SuccessEnvelope,
 };
-use rmcp::handler::server::{router::tool::ToolRouter, wrapper::Parameters};
+use pearls_core::{Config, ConfigSource, McpPolicy};
+use rmcp::handler::server::{router::tool::ToolRouter, tool::ToolCallContext, wrapper::Parameters};
 use rmcp::model::{
-    AnnotateAble, CallToolResult, Content, ErrorData, Implementation, ListResourceTemplatesResult,
-    ListResourcesResult, PaginatedRequestParams, ProtocolVersion, RawResource,
-    RawResourceTemplate, ReadResourceRequestParams, ReadResourceResult, ResourceContents,
-    ServerCapabilities, ServerInfo,
+    AnnotateAble, CallToolRequestParams, CallToolResult, Content, ErrorData,
+    GetPromptRequestParams, GetPromptResult, Implementation, ListPromptsResult,
+    ListResourceTemplatesResult, ListResourcesResult, ListToolsResult, PaginatedRequestParams,
+    ProtocolVersion, RawResource, RawResourceTemplate, ReadResourceRequestParams,
+    ReadResourceResult, ResourceContents, ServerCapabilities, ServerInfo, SubscribeRequestParams,
+    UnsubscribeRequestParams,
 };
+use rmcp::service::{NotificationContext, RequestContext};
 use rmcp::transport::stdio;
-use rmcp::{tool, tool_handler, tool_router, RoleServer, ServiceExt};
-use rmcp::service::RequestContext;
+use rmcp::transport::streamable_http_server::{
+    session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
+};
+use rmcp::{tool, tool_router, RoleServer, ServiceExt};
 use std::collections::HashMap;
 use std::fs::OpenOptions;
+use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
 use thiserror::Error;
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
 use tracing::Level;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::fmt;
 
+/// Number of entries per `resources/list` page.
+const RESOURCE_PAGE_SIZE: usize = 100;
+
 /// Runtime options for the MCP server.
 #[derive(Debug, Clone)]
 pub struct McpOptions {
-    /// Optional repository root to pin to.
+    /// Optional directory to discover the repository from.
     pub repo: Option<PathBuf>,
     /// Whether mutating tools are disabled.
     pub read_only: bool,
@@ -43,6 +69,10 @@ pub struct McpOptions {
     pub log_level: String,
     /// Optional log file path.
     pub log_file: Option<PathBuf>,
+    /// Address to serve the streamable HTTP transport on instead of stdio.
+    pub http: Option<SocketAddr>,
+    /// Policy file used instead of `[mcp.policy]` from the config.
+    pub policy: Option<PathBuf>,
 }
 
 impl Default for McpOptions {
@@ -52,6 +82,8 @@ impl Default for McpOptions {
             read_only: false,
             log_level: "info".to_string(),
             log_file: None,
+            http: None,
+            policy: None,
         }
     }
 }
@@ -70,7 +102,9 @@ pub enum McpServerError {
     Transport(String),
 }
 
-/// Runs the MCP server on stdio.
+/// Runs the MCP server on stdio, or on HTTP when `options.http` is set.
+///
+/// The HTTP server stops gracefully on Ctrl-C.
 ///
 /// # Arguments
 ///
@@ -86,12 +120,31 @@ pub enum McpServerError {
 pub fn run(options: McpOptions) -> Result<(), McpServerError> {
     let _guard = init_tracing(&options)?;
 
+    if let Some(addr) = options.http {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()?;
+        return runtime.block_on(async move {
+            let listener = TcpListener::bind(addr).await?;
+            let shutdown = CancellationToken::new();
+            let signal = shutdown.clone();
+            tokio::spawn(async move {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    signal.cancel();
+                }
+            });
+            serve_http(listener, options, shutdown).await
+        });
+    }
+
     let runtime = tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()?;
 
     runtime.block_on(async move {
-        let server = PearlsMcp::new(options);
+        let notifier = Arc::new(Notifier::default());
+        let _watcher = start_watcher(&options, &notifier);
+        let server = PearlsMcp::shared(options, notifier, Arc::default());
         let service = server
             .serve(stdio())
             .await
@@ -104,6 +157,118 @@ pub fn run(options: McpOptions) -> Result<(), McpServerError> {
     })
 }
 
+/// Serves the streamable HTTP transport at `/mcp` until `shutdown` is
+/// cancelled.
+///
+/// Requests must carry `Authorization: Bearer <token>` when `[mcp] auth_token`
+/// or `PEARLS_MCP_AUTH_TOKEN` is set. The token is a secret, so it is refused
+/// in the repository config, which is committed.
+///
+/// # Arguments
+///
+/// * `listener` - Bound TCP listener
+/// * `options` - MCP runtime options; `options.http` is ignored
+/// * `shutdown` - Token that stops the server and closes all sessions
+///
+/// # Returns
+///
+/// Ok once the server has shut down.
+///
+/// # Errors
+///
+/// Returns an error if the repository config cannot be loaded, sets the auth
+/// token, or the server fails.
+pub async fn serve_http(
+    listener: TcpListener,
+    options: McpOptions,
+    shutdown: CancellationToken,
+) -> Result<(), McpServerError> {
+    let layered = RepoContext::discover(options.repo.as_deref())
+        .and_then(|repo| Ok(Config::load_layered(Some(repo.pearls_dir()))?))
+        .map_err(|err| McpServerError::Transport(err.to_string()))?;
+    if layered.source("mcp.auth_token") == ConfigSource::Repo {
+        return Err(McpServerError::Transport(
+            "mcp.auth_token must not be set in the committed .pearls/config.toml; \
+             use PEARLS_MCP_AUTH_TOKEN or the global config"
+                .to_string(),
+        ));
+    }
+    let token = layered.config.mcp.auth_token;
+
+    let notifier = Arc::new(Notifier::default());
+    let _watcher = start_watcher(&options, &notifier);
+    let writes = Arc::new(Mutex::new(()));
+    let service = StreamableHttpService::new(
+        move || {
+            Ok(PearlsMcp::shared(
+                options.clone(),
+                notifier.clone(),
+                writes.clone(),
+            ))
+        },
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig {
+            cancellation_token: shutdown.child_token(),
+            ..Default::default()
+        },
+    );
+
+    let mut router = axum::Router::new().nest_service("/mcp", service);
+    if let Some(token) = token {
+        let expected: Arc<str> = format!("Bearer {}", token).into();
+        router = router.layer(axum::middleware::from_fn_with_state(
+            expected,
+            require_bearer,
+        ));
+    }
+
+    tracing::info!(addr = ?listener.local_addr().ok(), "serving MCP over HTTP");
+    axum::serve(listener, router)
+        .with_graceful_shutdown(shutdown.cancelled_owned())
+        .await?;
+    Ok(())
+}
+
+async fn require_bearer(
+    axum::extract::State(expected): axum::extract::State<Arc<str>>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let authorized = request
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .is_some_and(|value| constant_time_eq(value.as_bytes(), expected.as_bytes()));
+    if authorized {
+        return next.run(request).await;
+    }
+    axum::response::Response::builder()
+        .status(axum::http::StatusCode::UNAUTHORIZED)
+        .header(axum::http::header::WWW_AUTHENTICATE, "Bearer")
+        .body(axum::body::Body::empty())
+        .unwrap_or_default()
+}
+
+/// Compares two byte strings in time that depends only on their lengths.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Starts the `.pearls` watcher, logging instead of failing when it cannot.
+fn start_watcher(
+    options: &McpOptions,
+    notifier: &Arc<Notifier>,
+) -> Option<notify::RecommendedWatcher> {
+    match RepoContext::discover(options.repo.as_deref())
+        .and_then(|repo| watch(repo, notifier.clone()))
+    {
+        Ok(watcher) => Some(watcher),
+        Err(err) => {
+            tracing::warn!(error = %err, "resource change notifications disabled");
+            None
+        }
+    }
+}
+
 fn init_tracing(options: &McpOptions) -> Result<Option<WorkerGuard>, McpServerError> {
     let level = parse_log_level(&options.log_level)?;
 
@@ -143,14 +308,36 @@ fn parse_log_level(level: &str) -> Result<Level, McpServerError> {
 struct PearlsMcp {
     tool_router: ToolRouter<Self>,
     options: McpOptions,
+    notifier: Arc<Notifier>,
+    session: u64,
+    writes: Arc<Mutex<()>>,
 }
 
 impl PearlsMcp {
+    #[cfg(test)]
     fn new(options: McpOptions) -> Self {
+        Self::shared(options, Arc::default(), Arc::default())
+    }
+
+    fn shared(options: McpOptions, notifier: Arc<Notifier>, writes: Arc<Mutex<()>>) -> Self {
         Self {
             tool_router: Self::tool_router(),
             options,
+            session: notifier.next_session(),
+            notifier,
+            writes,
+        }
+    }
+
+    /// Rejects mutations in read-only mode and otherwise takes the write lock
+    /// shared by all sessions.
+    fn begin_write(&self) -> Result<MutexGuard<'_, ()>, AppError> {
+        if self.options.read_only {
+            return Err(AppError::PolicyDenied(
+                "Server is running in read-only mode".to_string(),
+            ));
         }
+        Ok(self.writes.lock().unwrap_or_else(|err| err.into_inner()))
     }
 
     fn repo_context(&self) -> Result<RepoContext, AppError> {
@@ -158,42 +345,84 @@ impl PearlsMcp {
         RepoContext::discover(root)
     }
 
-    fn ready_resource(&self) -> Result<ReadyResource, AppError> {
-        let repo = self.repo_context()?;
-        let storage = repo.open_storage()?;
-        let pearls = storage.load_all()?;
-        if pearls.is_empty() {
-            return Ok(ReadyResource {
-                ready: Vec::new(),
-                total: 0,
-                returned: 0,
-                message: Some("No Pearls found".to_string()),
-            });
-        }
+    /// Returns the author recorded when a call names none.
+    fn default_author(&self) -> Option<String> {
+        pearls_app::default_author(self.repo_context().ok().as_ref())
+    }
 
-        let ready = ready_queue(pearls)?;
-        if ready.is_empty() {
-            return Ok(ReadyResource {
-                ready: Vec::new(),
-                total: 0,
-                returned: 0,
-                message: Some("No Pearls ready for work".to_string()),
-            });
+    /// Loads the policy file given with `--policy`, or `[mcp.policy]`.
+    fn policy(&self) -> Result<McpPolicy, AppError> {
+        match &self.options.policy {
+            Some(path) => Ok(McpPolicy::load(path)?),
+            None => Ok(self.repo_context()?.load_config()?.mcp.policy),
         }
+    }
 
-        let returned = ready.len();
-        Ok(ReadyResource {
-            ready,
-            total: returned,
-            returned,
-            message: None,
-        })
+    /// Delivers events for a mutation in the background; failures are only
+    /// logged.
+    fn notify_events(&self) {
+        let Ok(repo) = self.repo_context() else {
+            return;
+        };
+        tokio::task::spawn_blocking(move || match pearls_app::dispatch_events(&repo, None) {
+            Ok(report) => {
+                if report.untrusted > 0 {
+                    tracing::warn!(
+                        skipped = report.untrusted,
+                        "event hook commands from .pearls/config.toml were not run"
+                    );
+                }
+                if report.failed > 0 {
+                    tracing::warn!(
+                        failed = report.failed,
+                        "event notifications failed; see .pearls/events-dead.jsonl"
+                    );
+                }
+            }
+            Err(err) => tracing::warn!(error = %err, "failed to dispatch events"),
+        });
     }
 
-    fn load_active_pearls(&self) -> Result<Vec<pearls_core::Pearl>, AppError> {
-        let repo = self.repo_context()?;
-        let storage = repo.open_storage()?;
-        Ok(storage.load_all()?)
+    /// Appends a mutating call to the audit log; failures are only logged.
+    fn audit(
+        &self,
+        client: &str,
+        tool: &str,
+        arguments: Option<&rmcp::model::JsonObject>,
+        result: &Result<CallToolResult, ErrorData>,
+    ) {
+        let (outcome, message) = match result {
+            Ok(_) => ("ok".to_string(), None),
+            Err(error) => {
+                let code = error
+                    .data
+                    .as_ref()
+                    .and_then(|data| data.get("code"))
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or("error")
+                    .to_string();
+                (code, Some(error.message.as_ref()))
+            }
+        };
+        let written = unix_timestamp().and_then(|timestamp| {
+            let entry = AuditEntry {
+                timestamp,
+                client,
+                session: self.session,
+                tool,
+                arguments,
+                outcome: &outcome,
+                message,
+            };
+            policy::append_audit(&self.repo_context()?, &entry)
+        });
+        if let Err(err) = written {
+            tracing::warn!("Failed to write audit log: {}", err);
+        }
+    }
+
+    fn ready_resource(&self) -> Result<ReadyResource, AppError> {
+        self.ready_tool(ReadyInput::default())
     }
 
     fn load_all_pearls(&self, include_archived: bool) -> Result<Vec<pearls_core::Pearl>, AppError> {
@@ -201,27 +430,13 @@ impl PearlsMcp {
         let storage = repo.open_storage()?;
         let mut pearls = storage.load_all()?;
         if include_archived {
-            if let Some(archive_storage) = repo.open_archive_storage()? {
-                if let Ok(archived) = archive_storage.load_all() {
-                    let mut archived = archived;
-                    for pearl in &mut archived {
-                        pearl
-                            .metadata
-                            .insert("archived".to_string(), serde_json::Value::Bool(true));
-                    }
-                    pearls.extend(archived);
-                }
-            }
+            pearls.extend(load_archived_pearls(&repo)?);
         }
         Ok(pearls)
     }
 
     fn create_tool(&self, input: CreateInput) -> Result<CreateResult, AppError> {
-        if self.options.read_only {
-            return Err(AppError::InvalidInput(
-                "Server is running in read-only mode".to_string(),
-            ));
-        }
+        let _write = self.begin_write()?;
 
         if input.items.is_empty() {
             return Err(AppError::InvalidInput(
@@ -231,6 +446,8 @@ impl PearlsMcp {
 
         let repo = self.repo_context()?;
         let config = repo.load_config()?;
+        let policy = self.policy()?;
+        policy::check_batch(&policy, "create", input.items.len())?;
         let mut created = Vec::new();
 
         for item in input.items {
@@ -240,7 +457,7 @@ impl PearlsMcp {
 
             let author = item
                 .author
-                .or_else(default_author)
+                .or_else(|| self.default_author())
                 .unwrap_or_else(|| "unknown".to_string());
             let mut pearl = pearls_core::Pearl::new(item.title, author);
 
@@ -262,6 +479,7 @@ impl PearlsMcp {
             }
 
             if let Some(labels) = item.labels {
+                policy::check_labels(&policy, &labels)?;
                 pearl.labels = labels;
             }
 
@@ -288,11 +506,7 @@ impl PearlsMcp {
     }
 
     fn update_tool(&self, input: UpdateInput) -> Result<UpdateResult, AppError> {
-        if self.options.read_only {
-            return Err(AppError::InvalidInput(
-                "Server is running in read-only mode".to_string(),
-            ));
-        }
+        let _write = self.begin_write()?;
 
         let repo = self.repo_context()?;
         let mut storage = repo.open_storage()?;
@@ -303,6 +517,7 @@ impl PearlsMcp {
             .position(|pearl| pearl.id == full_id)
             .ok_or_else(|| AppError::Core(pearls_core::Error::NotFound(full_id.clone())))?;
         let mut pearl = pearls[position].clone();
+        let policy = self.policy()?;
 
         if let Some(title) = input.title {
             pearl.title = title;
@@ -322,12 +537,15 @@ impl PearlsMcp {
         }
         if let Some(status) = input.status {
             let new_status = parse_status(&status)?;
+            let priorities = [pearls[position].priority, pearl.priority];
+            policy::check_transition(&policy, &pearl, &priorities, new_status)?;
             let graph = pearls_core::IssueGraph::from_pearls(pearls.clone())?;
             validate_transition(&pearl, new_status, &graph)?;
             pearl.status = new_status;
         }
 
         if let Some(add_labels) = input.add_labels {
+            policy::check_labels(&policy, &add_labels)?;
             for label in add_labels {
                 if !pearl.labels.contains(&label) {
                     pearl.labels.push(label);
@@ -335,6 +553,7 @@ impl PearlsMcp {
             }
         }
         if let Some(remove_labels) = input.remove_labels {
+            policy::check_labels(&policy, &remove_labels)?;
             for label in remove_labels {
                 pearl.labels.retain(|existing| existing != &label);
             }
@@ -350,11 +569,7 @@ impl PearlsMcp {
     }
 
     fn close_tool(&self, input: CloseInput) -> Result<CloseResult, AppError> {
-        if self.options.read_only {
-            return Err(AppError::InvalidInput(
-                "Server is running in read-only mode".to_string(),
-            ));
-        }
+        let _write = self.begin_write()?;
 
         let repo = self.repo_context()?;
         let mut storage = repo.open_storage()?;
@@ -366,6 +581,12 @@ impl PearlsMcp {
             .ok_or_else(|| AppError::Core(pearls_core::Error::NotFound(full_id.clone())))?;
         let mut pearl = pearls[position].clone();
 
+        policy::check_transition(
+            &self.policy()?,
+            &pearl,
+            &[pearl.priority],
+            pearls_core::Status::Closed,
+        )?;
         let graph = pearls_core::IssueGraph::from_pearls(pearls.clone())?;
         validate_transition(&pearl, pearls_core::Status::Closed, &graph)?;
         pearl.status = pearls_core::Status::Closed;
@@ -379,45 +600,53 @@ impl PearlsMcp {
     }
 
     fn ready_tool(&self, input: ReadyInput) -> Result<ReadyResource, AppError> {
-        let pearls = self.load_active_pearls()?;
-        if pearls.is_empty() {
+        let projection = Projection::new(input.fields, input.max_description)?;
+        let repo = self.repo_context()?;
+        let ready = if repo.load_config()?.use_cache {
+            let cache = QueryCache::open(&repo)?;
+            (cache.count()? > 0)
+                .then(|| cache.ready_queue())
+                .transpose()?
+        } else {
+            let storage = repo.open_storage()?;
+            (!storage.is_empty()?)
+                .then(|| load_ready_queue(&storage))
+                .transpose()?
+        };
+        let Some(ready) = ready else {
             return Ok(ReadyResource {
                 ready: Vec::new(),
                 total: 0,
                 returned: 0,
+                next_cursor: None,
                 message: Some("No Pearls found".to_string()),
             });
-        }
+        };
 
-        let ready = ready_queue(pearls)?;
-        let total = ready.len();
-        if total == 0 {
+        if ready.is_empty() {
             return Ok(ReadyResource {
                 ready: Vec::new(),
                 total: 0,
                 returned: 0,
+                next_cursor: None,
                 message: Some("No Pearls ready for work".to_string()),
             });
         }
 
-        let limited: Vec<pearls_core::Pearl> = ready
-            .into_iter()
-            .take(input.limit.unwrap_or(usize::MAX))
-            .collect();
+        let page = paginate(ready, input.cursor.as_deref(), input.limit, |pearl| {
+            &pearl.id
+        })?;
         Ok(ReadyResource {
-            ready: limited.clone(),
-            total,
-            returned: limited.len(),
+            ready: projection.apply_all(&page.items)?,
+            total: page.total,
+            returned: page.items.len(),
+            next_cursor: page.next_cursor,
             message: None,
         })
     }
 
     fn comments_add_tool(&self, input: CommentsAddInput) -> Result<CommentsAddResult, AppError> {
-        if self.options.read_only {
-            return Err(AppError::InvalidInput(
-                "Server is running in read-only mode".to_string(),
-            ));
-        }
+        let _write = self.begin_write()?;
 
         let repo = self.repo_context()?;
         let mut storage = repo.open_storage()?;
@@ -427,10 +656,14 @@ impl PearlsMcp {
 
         let author = input
             .author
-            .or_else(default_author)
+            .or_else(|| self.default_author())
             .unwrap_or_else(|| "unknown".to_string());
+        let kind = match input.kind.as_deref() {
+            Some(kind) => parse_comment_kind(kind)?,
+            None => pearls_core::CommentKind::Note,
+        };
         let comment_id = pearl
-            .add_comment(author, input.body)
+            .add_comment_of_kind(author, input.body, kind)
             .map_err(AppError::from)?;
         pearl.validate()?;
         storage.save(&pearl)?;
@@ -441,20 +674,25 @@ impl PearlsMcp {
         })
     }
 
-    fn comments_list_tool(
-        &self,
-        input: CommentsListInput,
-    ) -> Result<CommentsListResult, AppError> {
+    fn comments_list_tool(&self, input: CommentsListInput) -> Result<CommentsListResult, AppError> {
         let repo = self.repo_context()?;
         let mut storage = repo.open_storage()?;
         let pearls = storage.load_all()?;
         let full_id = resolve_pearl_id(&input.id, &pearls)?;
         let pearl = storage.load_by_id(&full_id)?;
+        let page = paginate(
+            pearl.comments,
+            input.cursor.as_deref(),
+            input.limit,
+            |comment| &comment.id,
+        )?;
 
         Ok(CommentsListResult {
             id: pearl.id,
-            comments: pearl.comments.clone(),
-            total: pearl.comments.len(),
+            returned: page.items.len(),
+            comments: page.items,
+            total: page.total,
+            next_cursor: page.next_cursor,
         })
     }
 
@@ -462,11 +700,7 @@ impl PearlsMcp {
         &self,
         input: CommentsDeleteInput,
     ) -> Result<CommentsDeleteResult, AppError> {
-        if self.options.read_only {
-            return Err(AppError::InvalidInput(
-                "Server is running in read-only mode".to_string(),
-            ));
-        }
+        let _write = self.begin_write()?;
 
         let repo = self.repo_context()?;
         let mut storage = repo.open_storage()?;
@@ -490,12 +724,138 @@ impl PearlsMcp {
         })
     }
 
+    fn meta_get_tool(&self, input: MetaKeyInput) -> Result<MetaResult, AppError> {
+        let repo = self.repo_context()?;
+        let mut storage = repo.open_storage()?;
+        let pearls = storage.load_all()?;
+        let full_id = resolve_pearl_id(&input.id, &pearls)?;
+        let pearl = storage.load_by_id(&full_id)?;
+
+        let value = pearl
+            .metadata
+            .get(&input.key)
+            .cloned()
+            .ok_or_else(|| missing_meta_key(&input.key, &pearl.id))?;
+        Ok(MetaResult {
+            id: pearl.id,
+            key: input.key,
+            value,
+        })
+    }
+
+    fn meta_set_tool(&self, input: MetaSetInput) -> Result<MetaResult, AppError> {
+        let _write = self.begin_write()?;
+
+        let repo = self.repo_context()?;
+        let mut storage = repo.open_storage()?;
+        let pearls = storage.load_all()?;
+        let full_id = resolve_pearl_id(&input.id, &pearls)?;
+        let mut pearl = storage.load_by_id(&full_id)?;
+
+        pearl
+            .metadata
+            .insert(input.key.clone(), input.value.clone());
+        pearl.updated_at = unix_timestamp()?;
+        pearl.validate()?;
+        storage.save(&pearl)?;
+
+        Ok(MetaResult {
+            id: pearl.id,
+            key: input.key,
+            value: input.value,
+        })
+    }
+
+    fn meta_delete_tool(&self, input: MetaKeyInput) -> Result<MetaResult, AppError> {
+        let _write = self.begin_write()?;
+
+        let repo = self.repo_context()?;
+        let mut storage = repo.open_storage()?;
+        let pearls = storage.load_all()?;
+        let full_id = resolve_pearl_id(&input.id, &pearls)?;
+        let mut pearl = storage.load_by_id(&full_id)?;
+
+        let value = pearl
+            .metadata
+            .remove(&input.key)
+            .ok_or_else(|| missing_meta_key(&input.key, &pearl.id))?;
+        pearl.updated_at = unix_timestamp()?;
+        pearl.validate()?;
+        storage.save(&pearl)?;
+
+        Ok(MetaResult {
+            id: pearl.id,
+            key: input.key,
+            value,
+        })
+    }
+
+    fn doctor_tool(&self, input: DoctorInput) -> Result<DoctorReport, AppError> {
+        let fix = input.fix.unwrap_or(false);
+        let _write = if fix { Some(self.begin_write()?) } else { None };
+
+        diagnose(&self.repo_context()?, fix, &|_| {})
+    }
+
+    fn compact_tool(&self, input: CompactInput) -> Result<CompactReport, AppError> {
+        let dry_run = input.dry_run.unwrap_or(false);
+        let _write = if dry_run {
+            None
+        } else {
+            Some(self.begin_write()?)
+        };
+
+        compact(&self.repo_context()?, input.threshold_days, dry_run)
+    }
+
+    fn session_start_tool(&self, input: SessionStartInput) -> Result<Session, AppError> {
+        let _write = self.begin_write()?;
+
+        let agent = input
+            .agent
+            .or_else(|| self.default_author())
+            .unwrap_or_else(|| "unknown".to_string());
+        let repo = self.repo_context()?;
+        let policy = self.policy()?;
+        policy::check_batch(&policy, "session_start", input.ids.len())?;
+        start_session(&repo, &agent, &input.ids, &|pearl, to| {
+            policy::check_transition(&policy, pearl, &[pearl.priority], to)
+        })
+    }
+
+    fn session_note_tool(&self, input: SessionNoteInput) -> Result<SessionNote, AppError> {
+        let _write = self.begin_write()?;
+
+        let kind = parse_comment_kind(&input.kind)?;
+        add_session_note(
+            &self.repo_context()?,
+            &input.session,
+            input.id.as_deref(),
+            kind,
+            &input.body,
+        )
+    }
+
+    fn session_end_tool(&self, input: SessionEndInput) -> Result<Session, AppError> {
+        let _write = self.begin_write()?;
+
+        end_session(&self.repo_context()?, &input.session, &input.summary)
+    }
+
+    fn resume_tool(&self, input: ResumeInput) -> Result<ResumeResult, AppError> {
+        let pearls = resume(
+            &self.repo_context()?,
+            input.agent.as_deref(),
+            input.id.as_deref(),
+        )?;
+        Ok(ResumeResult {
+            total: pearls.len(),
+            pearls,
+        })
+    }
+
     fn link_tool(&self, input: LinkInput) -> Result<LinkResult, AppError> {
-        if self.options.read_only {
-            return Err(AppError::InvalidInput(
-                "Server is running in read-only mode".to_string(),
-            ));
-        }
+        let _write = self.begin_write()?;
 
         if input.links.is_empty() {
             return Err(AppError::InvalidInput(
@@ -504,6 +864,7 @@ impl PearlsMcp {
         }
 
         let repo = self.repo_context()?;
+        policy::check_batch(&self.policy()?, "link", input.links.len())?;
         let mut storage = repo.open_storage()?;
         let mut pearls = storage.load_all()?;
         let mut resolved = Vec::new();
@@ -547,11 +908,7 @@ impl PearlsMcp {
     }
 
     fn unlink_tool(&self, input: UnlinkInput) -> Result<UnlinkResult, AppError> {
-        if self.options.read_only {
-            return Err(AppError::InvalidInput(
-                "Server is running in read-only mode".to_string(),
-            ));
-        }
+        let _write = self.begin_write()?;
 
         if input.links.is_empty() {
             return Err(AppError::InvalidInput(
@@ -560,6 +917,7 @@ impl PearlsMcp {
         }
 
         let repo = self.repo_context()?;
+        policy::check_batch(&self.policy()?, "unlink", input.links.len())?;
         let mut storage = repo.open_storage()?;
         let mut pearls = storage.load_all()?;
         let mut resolved = Vec::new();
@@ -598,8 +956,7 @@ impl PearlsMcp {
     }
 
     fn list_tool(&self, input: ListInput) -> Result<ListResult, AppError> {
-        let pearls = self.load_all_pearls(input.include_archived.unwrap_or(false))?;
-
+        let projection = Projection::new(input.fields, input.max_description)?;
         let status = match input.status.as_deref() {
             Some(status) => Some(parse_status(status)?),
             None => None,
@@ -623,9 +980,28 @@ impl PearlsMcp {
             sort: input.sort,
         };
 
+        let repo = self.repo_context()?;
+        let include_archived = input.include_archived.unwrap_or(false);
+        let pearls = if repo.load_config()?.use_cache {
+            QueryCache::open(&repo)?.list(&options, include_archived)?
+        } else {
+            let mut pearls = load_candidates(&repo.open_storage()?, &options)?;
+            if include_archived {
+                pearls.extend(load_archived_pearls(&repo)?);
+            }
+            pearls
+        };
+
         let pearls = list_pearls(pearls, &options);
-        let total = pearls.len();
-        Ok(ListResult { pearls, total })
+        let page = paginate(pearls, input.cursor.as_deref(), input.limit, |pearl| {
+            &pearl.id
+        })?;
+        Ok(ListResult {
+            pearls: projection.apply_all(&page.items)?,
+            total: page.total,
+            returned: page.items.len(),
+            next_cursor: page.next_cursor,
+        })
     }
 
     fn next_action_tool(&self) -> Result<NextActionResult, AppError> {
@@ -747,11 +1123,7 @@ impl PearlsMcp {
         &self,
         input: TransitionSafeInput,
     ) -> Result<TransitionSafeResult, AppError> {
-        if self.options.read_only {
-            return Err(AppError::InvalidInput(
-                "Server is running in read-only mode".to_string(),
-            ));
-        }
+        let _write = self.begin_write()?;
 
         let repo = self.repo_context()?;
         let mut storage = repo.open_storage()?;
@@ -764,6 +1136,7 @@ impl PearlsMcp {
         let mut pearl = pearls[position].clone();
 
         let new_status = parse_status(&input.status)?;
+        policy::check_transition(&self.policy()?, &pearl, &[pearl.priority], new_status)?;
         let graph = pearls_core::IssueGraph::from_pearls(pearls.clone())?;
 
         if let Err(error) = validate_transition(&pearl, new_status, &graph) {
@@ -800,10 +1173,7 @@ impl PearlsMcp {
 impl PearlsMcp {
     /// Lists Pearls with optional filtering and sorting.
     #[tool(description = "List Pearls with optional filters.")]
-    async fn list(
-        &self,
-        params: Parameters<ListInput>,
-    ) -> Result<CallToolResult, ErrorData> {
+    async fn list(&self, params: Parameters<ListInput>) -> Result<CallToolResult, ErrorData> {
         let input = params.0;
         let result = self.list_tool(input).map_err(map_app_error)?;
         let envelope = SuccessEnvelope::new(result);
@@ -815,10 +1185,7 @@ impl PearlsMcp {
 
     /// Creates a new Pearl.
     #[tool(description = "Create a Pearl.")]
-    async fn create(
-        &self,
-        params: Parameters<CreateInput>,
-    ) -> Result<CallToolResult, ErrorData> {
+    async fn create(&self, params: Parameters<CreateInput>) -> Result<CallToolResult, ErrorData> {
         let result = self.create_tool(params.0).map_err(map_app_error)?;
         let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
             ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
@@ -828,10 +1195,7 @@ impl PearlsMcp {
 
     /// Shows a Pearl by ID.
     #[tool(description = "Show a Pearl by ID.")]
-    async fn show(
-        &self,
-        params: Parameters<ShowInput>,
-    ) -> Result<CallToolResult, ErrorData> {
+    async fn show(&self, params: Parameters<ShowInput>) -> Result<CallToolResult, ErrorData> {
         let result = self.show_tool(params.0).map_err(map_app_error)?;
         let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
             ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
@@ -841,10 +1205,7 @@ impl PearlsMcp {
 
     /// Updates a Pearl.
     #[tool(description = "Update a Pearl.")]
-    async fn update(
-        &self,
-        params: Parameters<UpdateInput>,
-    ) -> Result<CallToolResult, ErrorData> {
+    async fn update(&self, params: Parameters<UpdateInput>) -> Result<CallToolResult, ErrorData> {
         let result = self.update_tool(params.0).map_err(map_app_error)?;
         let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
             ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
@@ -854,10 +1215,7 @@ impl PearlsMcp {
 
     /// Closes a Pearl.
     #[tool(description = "Close a Pearl.")]
-    async fn close(
-        &self,
-        params: Parameters<CloseInput>,
-    ) -> Result<CallToolResult, ErrorData> {
+    async fn close(&self, params: Parameters<CloseInput>) -> Result<CallToolResult, ErrorData> {
         let result = self.close_tool(params.0).map_err(map_app_error)?;
         let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
             ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
@@ -867,10 +1225,7 @@ impl PearlsMcp {
 
     /// Returns the ready queue.
     #[tool(description = "Return the ready queue.")]
-    async fn ready(
-        &self,
-        params: Parameters<ReadyInput>,
-    ) -> Result<CallToolResult, ErrorData> {
+    async fn ready(&self, params: Parameters<ReadyInput>) -> Result<CallToolResult, ErrorData> {
         let result = self.ready_tool(params.0).map_err(map_app_error)?;
         let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
             ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
@@ -905,7 +1260,10 @@ impl PearlsMcp {
     }
 
     /// Deletes a comment from a Pearl.
-    #[tool(name = "comments_delete", description = "Delete a comment from a Pearl.")]
+    #[tool(
+        name = "comments_delete",
+        description = "Delete a comment from a Pearl."
+    )]
     async fn comments_delete(
         &self,
         params: Parameters<CommentsDeleteInput>,
@@ -917,12 +1275,136 @@ impl PearlsMcp {
         Ok(CallToolResult::success(vec![Content::text(payload)]))
     }
 
-    /// Links two Pearls with a dependency.
-    #[tool(description = "Link Pearls with a dependency (from depends on to).")]
-    async fn link(
+    /// Reads a metadata value of a Pearl.
+    #[tool(name = "meta_get", description = "Get a metadata value of a Pearl.")]
+    async fn meta_get(
+        &self,
+        params: Parameters<MetaKeyInput>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let result = self.meta_get_tool(params.0).map_err(map_app_error)?;
+        let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
+            ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
+        })?;
+        Ok(CallToolResult::success(vec![Content::text(payload)]))
+    }
+
+    /// Sets a metadata value of a Pearl.
+    #[tool(
+        name = "meta_set",
+        description = "Set a metadata value of a Pearl to any JSON value."
+    )]
+    async fn meta_set(
+        &self,
+        params: Parameters<MetaSetInput>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let result = self.meta_set_tool(params.0).map_err(map_app_error)?;
+        let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
+            ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
+        })?;
+        Ok(CallToolResult::success(vec![Content::text(payload)]))
+    }
+
+    /// Removes a metadata key from a Pearl.
+    #[tool(
+        name = "meta_delete",
+        description = "Remove a metadata key from a Pearl."
+    )]
+    async fn meta_delete(
+        &self,
+        params: Parameters<MetaKeyInput>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let result = self.meta_delete_tool(params.0).map_err(map_app_error)?;
+        let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
+            ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
+        })?;
+        Ok(CallToolResult::success(vec![Content::text(payload)]))
+    }
+
+    /// Checks repository integrity and optionally repairs it.
+    #[tool(
+        description = "Check repository integrity and return findings with suggested fixes. Set fix to apply fixable ones."
+    )]
+    async fn doctor(&self, params: Parameters<DoctorInput>) -> Result<CallToolResult, ErrorData> {
+        let result = self.doctor_tool(params.0).map_err(map_app_error)?;
+        let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
+            ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
+        })?;
+        Ok(CallToolResult::success(vec![Content::text(payload)]))
+    }
+
+    /// Archives old closed Pearls.
+    #[tool(description = "Archive closed Pearls older than a threshold. Use dry_run to preview.")]
+    async fn compact(&self, params: Parameters<CompactInput>) -> Result<CallToolResult, ErrorData> {
+        let result = self.compact_tool(params.0).map_err(map_app_error)?;
+        let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
+            ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
+        })?;
+        Ok(CallToolResult::success(vec![Content::text(payload)]))
+    }
+
+    /// Starts an agent session claiming Pearls.
+    #[tool(
+        name = "session_start",
+        description = "Start an agent session that claims Pearls and moves open ones to in_progress."
+    )]
+    async fn session_start(
         &self,
-        params: Parameters<LinkInput>,
+        params: Parameters<SessionStartInput>,
     ) -> Result<CallToolResult, ErrorData> {
+        let result = self.session_start_tool(params.0).map_err(map_app_error)?;
+        let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
+            ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
+        })?;
+        Ok(CallToolResult::success(vec![Content::text(payload)]))
+    }
+
+    /// Adds a typed note during an agent session.
+    #[tool(
+        name = "session_note",
+        description = "Record a progress, decision, blocker, or handoff note on a Pearl claimed by a session."
+    )]
+    async fn session_note(
+        &self,
+        params: Parameters<SessionNoteInput>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let result = self.session_note_tool(params.0).map_err(map_app_error)?;
+        let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
+            ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
+        })?;
+        Ok(CallToolResult::success(vec![Content::text(payload)]))
+    }
+
+    /// Ends an agent session with a handoff summary.
+    #[tool(
+        name = "session_end",
+        description = "End an agent session, leaving a handoff summary on every claimed Pearl."
+    )]
+    async fn session_end(
+        &self,
+        params: Parameters<SessionEndInput>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let result = self.session_end_tool(params.0).map_err(map_app_error)?;
+        let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
+            ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
+        })?;
+        Ok(CallToolResult::success(vec![Content::text(payload)]))
+    }
+
+    /// Returns the work in flight with sessions, handoffs, notes, and blockers.
+    #[tool(
+        description = "Resume work in flight: claimed or in-progress Pearls with their session, latest handoff, notes, and blockers."
+    )]
+    async fn resume(&self, params: Parameters<ResumeInput>) -> Result<CallToolResult, ErrorData> {
+        let result = self.resume_tool(params.0).map_err(map_app_error)?;
+        let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
+            ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
+        })?;
+        Ok(CallToolResult::success(vec![Content::text(payload)]))
+    }
+
+    /// Links two Pearls with a dependency.
+    #[tool(description = "Link Pearls with a dependency (from depends on to).")]
+    async fn link(&self, params: Parameters<LinkInput>) -> Result<CallToolResult, ErrorData> {
         let result = self.link_tool(params.0).map_err(map_app_error)?;
         let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
             ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
@@ -932,10 +1414,7 @@ impl PearlsMcp {
 
     /// Removes a dependency between Pearls.
     #[tool(description = "Unlink two Pearls.")]
-    async fn unlink(
-        &self,
-        params: Parameters<UnlinkInput>,
-    ) -> Result<CallToolResult, ErrorData> {
+    async fn unlink(&self, params: Parameters<UnlinkInput>) -> Result<CallToolResult, ErrorData> {
         let result = self.unlink_tool(params.0).map_err(map_app_error)?;
         let payload = serde_json::to_string(&SuccessEnvelope::new(result)).map_err(|err| {
             ErrorData::internal_error("Failed to serialize response", Some(err.to_string().into()))
@@ -944,7 +1423,10 @@ impl PearlsMcp {
     }
 
     /// Returns the next recommended Pearl and blocker context.
-    #[tool(name = "next_action", description = "Return the next recommended Pearl.")]
+    #[tool(
+        name = "next_action",
+        description = "Return the next recommended Pearl."
+    )]
     async fn next_action(
         &self,
         _params: Parameters<EmptyInput>,
@@ -957,7 +1439,10 @@ impl PearlsMcp {
     }
 
     /// Returns a compact plan snapshot for the board.
-    #[tool(name = "plan_snapshot", description = "Return a compact plan snapshot.")]
+    #[tool(
+        name = "plan_snapshot",
+        description = "Return a compact plan snapshot."
+    )]
     async fn plan_snapshot(
         &self,
         params: Parameters<PlanSnapshotInput>,
@@ -970,7 +1455,10 @@ impl PearlsMcp {
     }
 
     /// Attempts a safe transition and returns blockers if denied.
-    #[tool(name = "transition_safe", description = "Safely transition a Pearl status.")]
+    #[tool(
+        name = "transition_safe",
+        description = "Safely transition a Pearl status."
+    )]
     async fn transition_safe(
         &self,
         params: Parameters<TransitionSafeInput>,
@@ -983,14 +1471,16 @@ impl PearlsMcp {
     }
 }
 
-#[tool_handler(router = self.tool_router)]
 impl rmcp::ServerHandler for PearlsMcp {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             protocol_version: ProtocolVersion::default(),
             capabilities: ServerCapabilities::builder()
                 .enable_tools()
+                .enable_prompts()
                 .enable_resources()
+                .enable_resources_subscribe()
+                .enable_resources_list_changed()
                 .build(),
             server_info: Implementation {
                 name: "pearls".to_string(),
@@ -1003,13 +1493,92 @@ impl rmcp::ServerHandler for PearlsMcp {
         }
     }
 
-    async fn list_resources(
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let tool = request.name.to_string();
+        let arguments = request.arguments.clone();
+        let client = context
+            .peer
+            .peer_info()
+            .map(|info| info.client_info.name.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let result = match self
+            .policy()
+            .and_then(|policy| policy::check_tool(&policy, &tool))
+        {
+            Ok(()) => {
+                let call = ToolCallContext::new(self, request, context);
+                self.tool_router.call(call).await
+            }
+            Err(error) => Err(map_app_error(error)),
+        };
+        if policy::is_mutating(&tool, arguments.as_ref()) {
+            self.audit(&client, &tool, arguments.as_ref(), &result);
+            if result.is_ok() {
+                self.notify_events();
+            }
+        }
+        result
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, ErrorData> {
+        let policy = match self.policy() {
+            Ok(policy) => policy,
+            Err(AppError::RepoNotInitialized { .. }) => McpPolicy::default(),
+            Err(error) => return Err(map_app_error(error)),
+        };
+        let tools = self
+            .tool_router
+            .list_all()
+            .into_iter()
+            .filter(|tool| policy::check_tool(&policy, &tool.name).is_ok())
+            .collect();
+        Ok(ListToolsResult::with_all_items(tools))
+    }
+
+    async fn list_prompts(
         &self,
         _request: Option<PaginatedRequestParams>,
         _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, ErrorData> {
+        let repo = self.repo_context().map_err(map_app_error)?;
+        let prompts = prompts::list_prompts(&repo).map_err(map_app_error)?;
+        Ok(ListPromptsResult::with_all_items(prompts))
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        let arguments: HashMap<String, String> = request
+            .arguments
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, value)| match value {
+                serde_json::Value::String(value) => (name, value),
+                other => (name, other.to_string()),
+            })
+            .collect();
+        let repo = self.repo_context().map_err(map_app_error)?;
+        prompts::get_prompt(&repo, &request.name, &arguments).map_err(map_app_error)
+    }
+
+    async fn list_resources(
+        &self,
+        request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
     ) -> Result<ListResourcesResult, ErrorData> {
         let ready = RawResource {
-            uri: "pearls://ready".to_string(),
+            uri: READY_URI.to_string(),
             name: "ready".to_string(),
             title: Some("Ready queue".to_string()),
             description: Some("Ready queue of unblocked Pearls".to_string()),
@@ -1020,7 +1589,35 @@ impl rmcp::ServerHandler for PearlsMcp {
         }
         .no_annotation();
 
-        Ok(ListResourcesResult::with_all_items(vec![ready]))
+        let pearls = self.load_all_pearls(false).map_err(map_app_error)?;
+        let mut resources = vec![ready];
+        resources.extend(pearls.into_iter().map(|pearl| {
+            RawResource {
+                uri: pearl_uri(&pearl.id),
+                name: pearl.id,
+                title: Some(pearl.title),
+                description: None,
+                mime_type: Some("application/json".to_string()),
+                size: None,
+                icons: None,
+                meta: None,
+            }
+            .no_annotation()
+        }));
+
+        let cursor = request.and_then(|request| request.cursor);
+        let page = paginate(
+            resources,
+            cursor.as_deref(),
+            Some(RESOURCE_PAGE_SIZE),
+            |resource| &resource.raw.uri,
+        )
+        .map_err(map_app_error)?;
+        Ok(ListResourcesResult {
+            meta: None,
+            next_cursor: page.next_cursor,
+            resources: page.items,
+        })
     }
 
     async fn list_resource_templates(
@@ -1048,6 +1645,30 @@ impl rmcp::ServerHandler for PearlsMcp {
     ) -> Result<ReadResourceResult, ErrorData> {
         self.read_resource_by_uri(request.uri.as_str())
     }
+
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        let uri = self.canonical_uri(&request.uri)?;
+        self.notifier.subscribe(self.session, context.peer, uri);
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        request: UnsubscribeRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        let uri = self.canonical_uri(&request.uri)?;
+        self.notifier.unsubscribe(self.session, &uri);
+        Ok(())
+    }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        self.notifier.register(self.session, context.peer);
+    }
 }
 
 fn map_app_error(error: AppError) -> ErrorData {
@@ -1055,6 +1676,7 @@ fn map_app_error(error: AppError) -> ErrorData {
     let data = serde_json::to_value(&envelope).ok();
     match envelope.code {
         pearls_app::ErrorCode::NotFound => ErrorData::resource_not_found(envelope.message, data),
+        pearls_app::ErrorCode::PolicyDenied => ErrorData::invalid_request(envelope.message, data),
         pearls_app::ErrorCode::AmbiguousId
         | pearls_app::ErrorCode::InvalidTransition
         | pearls_app::ErrorCode::ValidationError
@@ -1065,11 +1687,12 @@ fn map_app_error(error: AppError) -> ErrorData {
         pearls_app::ErrorCode::IoError
         | pearls_app::ErrorCode::GitError
         | pearls_app::ErrorCode::JsonError
+        | pearls_app::ErrorCode::UnsupportedSchema
         | pearls_app::ErrorCode::Unknown => ErrorData::internal_error(envelope.message, data),
     }
 }
 
-fn status_key(status: pearls_core::Status) -> String {
+pub(crate) fn status_key(status: pearls_core::Status) -> String {
     match status {
         pearls_core::Status::Open => "open",
         pearls_core::Status::InProgress => "in_progress",
@@ -1080,10 +1703,17 @@ fn status_key(status: pearls_core::Status) -> String {
     .to_string()
 }
 
-fn default_author() -> Option<String> {
-    std::env::var("USER")
-        .ok()
-        .or_else(|| std::env::var("USERNAME").ok())
+fn load_archived_pearls(repo: &RepoContext) -> Result<Vec<pearls_core::Pearl>, AppError> {
+    let Some(archive_storage) = repo.open_archive_storage()? else {
+        return Ok(Vec::new());
+    };
+    let mut archived = archive_storage.load_all().unwrap_or_default();
+    for pearl in &mut archived {
+        pearl
+            .metadata
+            .insert("archived".to_string(), serde_json::Value::Bool(true));
+    }
+    Ok(archived)
 }
 
 fn enforce_description_limit(description: &str) -> Result<(), AppError> {
@@ -1096,6 +1726,10 @@ fn enforce_description_limit(description: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+fn missing_meta_key(key: &str, id: &str) -> AppError {
+    AppError::InvalidInput(format!("Metadata key '{}' not found for {}", key, id))
+}
+
 fn resolve_comment_id(
     partial: &str,
     comments: &[pearls_core::Comment],
@@ -1127,6 +1761,24 @@ fn resolve_comment_id(
 }
 
 impl PearlsMcp {
+    /// Resolves a resource URI with a partial Pearl ID to its full form.
+    fn canonical_uri(&self, uri: &str) -> Result<String, ErrorData> {
+        if uri == READY_URI {
+            return Ok(uri.to_string());
+        }
+        match uri.strip_prefix("pearls://") {
+            Some(id) if id.starts_with("prl-") => {
+                let pearls = self.load_all_pearls(true).map_err(map_app_error)?;
+                let full_id = resolve_pearl_id(id, &pearls).map_err(map_app_error)?;
+                Ok(pearl_uri(&full_id))
+            }
+            _ => Err(ErrorData::resource_not_found(
+                "Resource not found",
+                Some(serde_json::json!({ "uri": uri })),
+            )),
+        }
+    }
+
     fn read_resource_by_uri(&self, uri: &str) -> Result<ReadResourceResult, ErrorData> {
         if uri == "pearls://ready" {
             let ready = self.ready_resource().map_err(map_app_error)?;
@@ -1217,6 +1869,8 @@ mod tests {
             read_only: false,
             log_level: "info".to_string(),
             log_file: None,
+            http: None,
+            policy: None,
         })
     }
 
@@ -1251,6 +1905,7 @@ mod tests {
                 id: created.pearls[0].id.clone(),
                 body: "Hello".to_string(),
                 author: Some("tester".to_string()),
+                kind: None,
             })
             .expect("comment add failed");
 
@@ -1286,7 +1941,7 @@ mod tests {
         assert_eq!(closed.pearl.status, Status::Closed);
 
         let ready = server
-            .ready_tool(ReadyInput { limit: None })
+            .ready_tool(ReadyInput::default())
             .expect("ready failed");
         assert!(ready.ready.is_empty());
     }
@@ -1313,12 +1968,14 @@ mod tests {
                 id: created.pearls[0].id.clone(),
                 body: "First".to_string(),
                 author: None,
+                kind: Some("decision".to_string()),
             })
             .expect("comment add failed");
 
         let list = server
             .comments_list_tool(CommentsListInput {
                 id: created.pearls[0].id.clone(),
+                ..CommentsListInput::default()
             })
             .expect("comment list failed");
         assert_eq!(list.total, 1);
@@ -1335,11 +1992,193 @@ mod tests {
         let list = server
             .comments_list_tool(CommentsListInput {
                 id: created.pearls[0].id.clone(),
+                ..CommentsListInput::default()
             })
             .expect("comment list failed");
         assert_eq!(list.total, 0);
     }
 
+    #[test]
+    fn test_session_start_applies_transition_policy() {
+        let temp = init_repo();
+        let pearls_dir = temp.path().join(".pearls");
+        let mut config = Config::load(&pearls_dir).expect("Failed to load config");
+        config
+            .mcp
+            .policy
+            .deny_transitions
+            .push(pearls_core::TransitionRule {
+                to: Status::InProgress,
+                priorities: Vec::new(),
+            });
+        config.save(&pearls_dir).expect("Failed to save config");
+        let server = server_for(&temp);
+
+        let created = server
+            .create_tool(CreateInput {
+                items: vec![CreateItem {
+                    title: "Guarded".to_string(),
+                    description: None,
+                    priority: None,
+                    labels: None,
+                    author: None,
+                }],
+            })
+            .expect("create failed");
+        let id = created.pearls[0].id.clone();
+
+        let err = server
+            .session_start_tool(SessionStartInput {
+                agent: Some("agent-1".to_string()),
+                ids: vec![id.clone()],
+            })
+            .expect_err("policy should refuse the claim");
+        assert!(matches!(err, AppError::PolicyDenied(_)));
+        let pearl = server
+            .repo_context()
+            .expect("repo")
+            .open_storage()
+            .expect("storage")
+            .load_by_id(&id)
+            .expect("load failed");
+        assert_eq!(pearl.status, Status::Open);
+        assert!(pearls_app::SessionInfo::of(&pearl).is_none());
+    }
+
+    #[test]
+    fn test_session_resume_after_reset() {
+        let temp = init_repo();
+        let server = server_for(&temp);
+
+        let created = server
+            .create_tool(CreateInput {
+                items: vec![CreateItem {
+                    title: "Refactor parser".to_string(),
+                    description: None,
+                    priority: None,
+                    labels: None,
+                    author: None,
+                }],
+            })
+            .expect("create failed");
+        let id = created.pearls[0].id.clone();
+
+        let started = server
+            .session_start_tool(SessionStartInput {
+                agent: Some("agent-1".to_string()),
+                ids: vec![id.clone()],
+            })
+            .expect("session start failed");
+        assert_eq!(started.pearls[0].status, Status::InProgress);
+        server
+            .session_note_tool(SessionNoteInput {
+                session: started.session.id.clone(),
+                id: None,
+                kind: "blocker".to_string(),
+                body: "Grammar is ambiguous".to_string(),
+            })
+            .expect("session note failed");
+        assert!(server
+            .session_note_tool(SessionNoteInput {
+                session: started.session.id.clone(),
+                id: None,
+                kind: "rumor".to_string(),
+                body: "Nope".to_string(),
+            })
+            .is_err());
+
+        let resumed = server
+            .resume_tool(ResumeInput::default())
+            .expect("resume failed");
+        assert_eq!(resumed.total, 1);
+        assert_eq!(resumed.pearls[0].session, Some(started.session.clone()));
+        assert_eq!(
+            resumed.pearls[0].notes[0].kind,
+            pearls_core::CommentKind::Blocker
+        );
+
+        server
+            .session_end_tool(SessionEndInput {
+                session: started.session.id.clone(),
+                summary: "Split the grammar next".to_string(),
+            })
+            .expect("session end failed");
+        let resumed = server
+            .resume_tool(ResumeInput {
+                agent: Some("agent-1".to_string()),
+                id: Some(id),
+            })
+            .expect("resume failed");
+        assert!(resumed.pearls[0].session.is_none());
+        let handoff = resumed.pearls[0].handoff.as_ref().expect("missing handoff");
+        assert_eq!(handoff.body, "Split the grammar next");
+        assert_eq!(handoff.author, "agent-1");
+    }
+
+    #[test]
+    fn test_list_pages_and_projects() {
+        let temp = init_repo();
+        let server = server_for(&temp);
+
+        server
+            .create_tool(CreateInput {
+                items: ["One", "Two", "Three"]
+                    .into_iter()
+                    .map(|title| CreateItem {
+                        title: title.to_string(),
+                        description: Some("A long description".to_string()),
+                        priority: None,
+                        labels: None,
+                        author: None,
+                    })
+                    .collect(),
+            })
+            .expect("create failed");
+
+        let first = server
+            .list_tool(ListInput {
+                limit: Some(2),
+                fields: Some(vec!["title".to_string(), "description".to_string()]),
+                max_description: Some(6),
+                ..ListInput::default()
+            })
+            .expect("list failed");
+        assert_eq!(first.total, 3);
+        assert_eq!(first.returned, 2);
+        assert_eq!(first.pearls[0]["description"], "A long");
+        assert_eq!(first.pearls[0]["more"], true);
+        assert!(first.pearls[0].get("status").is_none());
+
+        let rest = server
+            .list_tool(ListInput {
+                cursor: first.next_cursor.clone(),
+                ..ListInput::default()
+            })
+            .expect("list failed");
+        assert_eq!(rest.returned, 1);
+        assert!(rest.next_cursor.is_none());
+        assert_eq!(rest.pearls[0]["description"], "A long description");
+
+        let ready = server
+            .ready_tool(ReadyInput {
+                limit: Some(1),
+                fields: Some(vec!["priority".to_string()]),
+                ..ReadyInput::default()
+            })
+            .expect("ready failed");
+        assert_eq!(ready.total, 3);
+        let last_id = ready.ready[0]["id"].as_str().expect("Missing id");
+        assert_eq!(ready.next_cursor, Some(format!("1:{}", last_id)));
+        assert_eq!(ready.ready[0].as_object().map(|pearl| pearl.len()), Some(2));
+
+        assert!(server
+            .list_tool(ListInput {
+                fields: Some(vec!["bogus".to_string()]),
+                ..ListInput::default()
+            })
+            .is_err());
+    }
+
     #[test]
     fn test_link_and_unlink() {
         let temp = init_repo();
@@ -1466,6 +2305,8 @@ mod tests {
             read_only: true,
             log_level: "info".to_string(),
             log_file: None,
+            http: None,
+            policy: None,
         });
 
         let result = server.create_tool(CreateInput {
@@ -1479,6 +2320,109 @@ mod tests {
         });
 
         assert!(result.is_err());
+        assert!(server
+            .meta_set_tool(MetaSetInput {
+                id: "prl-abc".to_string(),
+                key: "k".to_string(),
+                value: serde_json::json!(1),
+            })
+            .is_err());
+        assert!(server.doctor_tool(DoctorInput { fix: Some(true) }).is_err());
+        assert!(server.doctor_tool(DoctorInput { fix: None }).is_ok());
+        assert!(server.compact_tool(CompactInput::default()).is_err());
+        assert!(server
+            .compact_tool(CompactInput {
+                threshold_days: None,
+                dry_run: Some(true),
+            })
+            .is_ok());
+    }
+
+    #[test]
+    fn test_meta_doctor_and_compact() {
+        let temp = init_repo();
+        let server = server_for(&temp);
+        let created = server
+            .create_tool(CreateInput {
+                items: vec![CreateItem {
+                    title: "Meta Pearl".to_string(),
+                    description: None,
+                    priority: None,
+                    labels: None,
+                    author: None,
+                }],
+            })
+            .expect("create failed");
+        let id = created.pearls[0].id.clone();
+
+        server
+            .meta_set_tool(MetaSetInput {
+                id: id[..6].to_string(),
+                key: "estimate".to_string(),
+                value: serde_json::json!({ "hours": 3 }),
+            })
+            .expect("meta set failed");
+        let got = server
+            .meta_get_tool(MetaKeyInput {
+                id: id.clone(),
+                key: "estimate".to_string(),
+            })
+            .expect("meta get failed");
+        assert_eq!(got.value, serde_json::json!({ "hours": 3 }));
+        let deleted = server
+            .meta_delete_tool(MetaKeyInput {
+                id: id.clone(),
+                key: "estimate".to_string(),
+            })
+            .expect("meta delete failed");
+        assert_eq!(deleted.value, got.value);
+        assert!(server
+            .meta_get_tool(MetaKeyInput {
+                id: id.clone(),
+                key: "estimate".to_string(),
+            })
+            .is_err());
+
+        let issues_path = temp.path().join(".pearls/issues.jsonl");
+        let mut content = fs::read_to_string(&issues_path).expect("read failed");
+        content.push_str("not json\n");
+        fs::write(&issues_path, content).expect("write failed");
+        let report = server
+            .doctor_tool(DoctorInput { fix: None })
+            .expect("doctor failed");
+        assert!(report.has_errors());
+        assert!(report
+            .findings
+            .iter()
+            .any(|finding| finding.message.contains("Invalid JSON") && finding.fixable));
+        let report = server
+            .doctor_tool(DoctorInput { fix: Some(true) })
+            .expect("doctor fix failed");
+        assert!(report.fix_applied && !report.unrepairable);
+        let report = server
+            .doctor_tool(DoctorInput { fix: None })
+            .expect("doctor failed");
+        assert!(!report.has_errors());
+
+        server
+            .close_tool(CloseInput { id: id.clone() })
+            .expect("close failed");
+        let preview = server
+            .compact_tool(CompactInput {
+                threshold_days: Some(0),
+                dry_run: Some(true),
+            })
+            .expect("compact dry run failed");
+        assert_eq!(preview.eligible.len(), 1);
+        assert_eq!(preview.archived_total, 0);
+        let compacted = server
+            .compact_tool(CompactInput {
+                threshold_days: Some(0),
+                dry_run: None,
+            })
+            .expect("compact failed");
+        assert_eq!(compacted.archived_total, 1);
+        assert_eq!(compacted.active_remaining, 0);
     }
 
     #[test]
@@ -1537,9 +2481,7 @@ mod tests {
 
         let repo = server.repo_context().expect("repo context");
         let mut storage = repo.open_storage().expect("storage");
-        let mut pearl = storage
-            .load_by_id(&transition_id)
-            .expect("load pearl");
+        let mut pearl = storage.load_by_id(&transition_id).expect("load pearl");
         pearl.deps.push(pearls_core::Dependency {
             target_id: blocker_id.clone(),
             dep_type: pearls_core::DepType::Blocks,
@@ -1557,6 +2499,113 @@ mod tests {
         assert!(result.message.contains("transition"));
     }
 
+    struct Recorder(tokio::sync::mpsc::UnboundedSender<String>);
+
+    impl rmcp::ClientHandler for Recorder {
+        async fn on_resource_updated(
+            &self,
+            params: rmcp::model::ResourceUpdatedNotificationParam,
+            _context: NotificationContext<rmcp::RoleClient>,
+        ) {
+            let _ = self.0.send(params.uri);
+        }
+
+        async fn on_resource_list_changed(&self, _context: NotificationContext<rmcp::RoleClient>) {
+            let _ = self.0.send("list_changed".to_string());
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_subscriptions_receive_change_notifications() {
+        let temp = init_repo();
+        let notifier = Arc::new(Notifier::default());
+        let repo = RepoContext::discover(Some(temp.path())).expect("repo context");
+        let _watcher = watch(repo, notifier.clone()).expect("watch failed");
+        let server = PearlsMcp::shared(server_for(&temp).options, notifier, Arc::default());
+
+        let (server_io, client_io) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(async move {
+            if let Ok(service) = server.serve(server_io).await {
+                let _ = service.waiting().await;
+            }
+        });
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let client = Recorder(sender)
+            .serve(client_io)
+            .await
+            .expect("client failed");
+        client
+            .subscribe(SubscribeRequestParams {
+                meta: None,
+                uri: READY_URI.to_string(),
+            })
+            .await
+            .expect("subscribe failed");
+
+        server_for(&temp)
+            .create_tool(CreateInput {
+                items: vec![CreateItem {
+                    title: "Watched".to_string(),
+                    description: None,
+                    priority: None,
+                    labels: None,
+                    author: None,
+                }],
+            })
+            .expect("create failed");
+
+        let mut seen = Vec::new();
+        while !(seen.iter().any(|uri| uri == READY_URI)
+            && seen.iter().any(|uri| uri == "list_changed"))
+        {
+            let next = tokio::time::timeout(std::time::Duration::from_secs(10), receiver.recv())
+                .await
+                .expect("timed out waiting for notifications")
+                .expect("client closed");
+            seen.push(next);
+        }
+        client.cancel().await.expect("cancel failed");
+    }
+
+    #[test]
+    fn test_list_and_ready_use_cache() {
+        let temp = init_repo();
+        let pearls_dir = temp.path().join(".pearls");
+        let config = Config {
+            use_cache: true,
+            ..Config::default()
+        };
+        config.save(&pearls_dir).expect("Failed to save config");
+        let server = server_for(&temp);
+
+        let ready = server
+            .ready_tool(ReadyInput::default())
+            .expect("ready failed");
+        assert_eq!(ready.message.as_deref(), Some("No Pearls found"));
+
+        server
+            .create_tool(CreateInput {
+                items: vec![CreateItem {
+                    title: "Cached".to_string(),
+                    description: None,
+                    priority: None,
+                    labels: None,
+                    author: None,
+                }],
+            })
+            .expect("create failed");
+
+        let list = server.list_tool(ListInput::default()).expect("list failed");
+        assert_eq!(list.total, 1);
+        assert!(pearls_dir.join("cache.db").exists());
+
+        let ready = server
+            .ready_tool(ReadyInput::default())
+            .expect("ready failed");
+        assert_eq!(ready.total, 1);
+        assert_eq!(ready.ready[0]["title"], "Cached");
+    }
+
     #[test]
     fn test_ready_resource_empty() {
         let temp = init_repo();
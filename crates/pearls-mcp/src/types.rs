@@ -2,12 +2,13 @@
 
 //! MCP tool input and output types for Pearls.
 
+use pearls_app::ResumeEntry;
 use pearls_core::Pearl;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Input parameters for the `list` tool.
-#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
 pub struct ListInput {
     /// Filter by status.
     pub status: Option<String>,
@@ -31,15 +32,30 @@ pub struct ListInput {
     pub updated_after: Option<i64>,
     /// Filter by updated_at <= timestamp.
     pub updated_before: Option<i64>,
+    /// Maximum number of items to return.
+    pub limit: Option<usize>,
+    /// Cursor from `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+    /// Pearl fields to return (for example `title`, `status`, `priority`);
+    /// `id` is always returned. Defaults to all fields.
+    pub fields: Option<Vec<String>>,
+    /// Maximum description length in characters; shortened descriptions are
+    /// marked with `more`.
+    pub max_description: Option<usize>,
 }
 
 /// Output payload for the `list` tool.
 #[derive(Debug, Clone, Serialize)]
 pub struct ListResult {
-    /// List of Pearls.
-    pub pearls: Vec<Pearl>,
-    /// Total number of Pearls returned.
+    /// Pearls on this page, projected to the requested fields.
+    pub pearls: Vec<serde_json::Value>,
+    /// Total number of matching Pearls.
     pub total: usize,
+    /// Number of Pearls on this page.
+    pub returned: usize,
+    /// Cursor of the next page, if there is one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 /// Empty input for tools without parameters.
@@ -132,10 +148,18 @@ pub struct CloseResult {
 }
 
 /// Input parameters for the `ready` tool.
-#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
 pub struct ReadyInput {
     /// Maximum number of items to return.
     pub limit: Option<usize>,
+    /// Cursor from `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+    /// Pearl fields to return (for example `title`, `status`, `priority`);
+    /// `id` is always returned. Defaults to all fields.
+    pub fields: Option<Vec<String>>,
+    /// Maximum description length in characters; shortened descriptions are
+    /// marked with `more`.
+    pub max_description: Option<usize>,
 }
 
 /// Input parameters for the `comments_add` tool.
@@ -147,6 +171,8 @@ pub struct CommentsAddInput {
     pub body: String,
     /// Optional author override.
     pub author: Option<String>,
+    /// Comment kind: note (default), progress, decision, blocker, or handoff.
+    pub kind: Option<String>,
 }
 
 /// Output payload for the `comments_add` tool.
@@ -159,10 +185,14 @@ pub struct CommentsAddResult {
 }
 
 /// Input parameters for the `comments_list` tool.
-#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
 pub struct CommentsListInput {
     /// Pearl ID (full or partial).
     pub id: String,
+    /// Maximum number of comments to return.
+    pub limit: Option<usize>,
+    /// Cursor from `next_cursor` of the previous page.
+    pub cursor: Option<String>,
 }
 
 /// Output payload for the `comments_list` tool.
@@ -170,10 +200,15 @@ pub struct CommentsListInput {
 pub struct CommentsListResult {
     /// Pearl ID.
     pub id: String,
-    /// Comments for the Pearl.
+    /// Comments on this page.
     pub comments: Vec<pearls_core::Comment>,
     /// Total number of comments.
     pub total: usize,
+    /// Number of comments on this page.
+    pub returned: usize,
+    /// Cursor of the next page, if there is one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 /// Input parameters for the `comments_delete` tool.
@@ -314,15 +349,114 @@ pub struct TransitionSafeResult {
     pub message: String,
 }
 
+/// Input parameters for the `meta_get` and `meta_delete` tools.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct MetaKeyInput {
+    /// Pearl ID (full or partial).
+    pub id: String,
+    /// Metadata key.
+    pub key: String,
+}
+
+/// Input parameters for the `meta_set` tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct MetaSetInput {
+    /// Pearl ID (full or partial).
+    pub id: String,
+    /// Metadata key.
+    pub key: String,
+    /// Metadata value, any JSON value.
+    pub value: serde_json::Value,
+}
+
+/// Output payload for the `meta_get`, `meta_set`, and `meta_delete` tools.
+#[derive(Debug, Clone, Serialize)]
+pub struct MetaResult {
+    /// Pearl ID.
+    pub id: String,
+    /// Metadata key.
+    pub key: String,
+    /// Current value; the removed value for `meta_delete`.
+    pub value: serde_json::Value,
+}
+
+/// Input parameters for the `doctor` tool.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct DoctorInput {
+    /// Apply the repairs of fixable findings.
+    pub fix: Option<bool>,
+}
+
+/// Input parameters for the `compact` tool.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct CompactInput {
+    /// Archive closed Pearls older than this many days (defaults to config).
+    pub threshold_days: Option<u32>,
+    /// Report eligible Pearls without archiving them.
+    pub dry_run: Option<bool>,
+}
+
+/// Input parameters for the `session_start` tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct SessionStartInput {
+    /// Agent identifier (defaults to the git user).
+    pub agent: Option<String>,
+    /// Pearl IDs (full or partial) to claim.
+    pub ids: Vec<String>,
+}
+
+/// Input parameters for the `session_note` tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct SessionNoteInput {
+    /// Session ID from `session_start`.
+    pub session: String,
+    /// Pearl ID (full or partial); optional when the session claims one Pearl.
+    pub id: Option<String>,
+    /// Note kind: progress, decision, blocker, or handoff.
+    pub kind: String,
+    /// Note text.
+    pub body: String,
+}
+
+/// Input parameters for the `session_end` tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct SessionEndInput {
+    /// Session ID from `session_start`.
+    pub session: String,
+    /// Handoff summary for the next agent.
+    pub summary: String,
+}
+
+/// Input parameters for the `resume` tool.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct ResumeInput {
+    /// Only Pearls claimed or handed off by this agent.
+    pub agent: Option<String>,
+    /// Only this Pearl (full or partial ID).
+    pub id: Option<String>,
+}
+
+/// Output payload for the `resume` tool.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResumeResult {
+    /// Pearls in flight, most recently updated first.
+    pub pearls: Vec<ResumeEntry>,
+    /// Number of Pearls returned.
+    pub total: usize,
+}
+
 /// Output payload for `pearls://ready`.
 #[derive(Debug, Clone, Serialize)]
 pub struct ReadyResource {
-    /// Ready queue entries.
-    pub ready: Vec<Pearl>,
+    /// Ready queue entries, projected to the requested fields.
+    pub ready: Vec<serde_json::Value>,
     /// Total ready items.
     pub total: usize,
     /// Number of returned items.
     pub returned: usize,
+    /// Cursor of the next page, if there is one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
     /// Optional message when empty.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
@@ -0,0 +1,361 @@
+// Rust guideline compliant 2026-02-10
+
+//! Change notifications for MCP resource subscriptions.
+//!
+//! A filesystem watcher on `.pearls` feeds a debounced reload. Each reload is
+//! compared with the previous snapshot, and subscribed sessions receive
+//! `notifications/resources/updated` for the Pearls that changed and for the
+//! ready queue. Every session receives `notifications/resources/list_changed`
+//! when Pearls are created, deleted, or archived.
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use pearls_app::{load_ready_queue, AppError, RepoContext};
+use rmcp::model::ResourceUpdatedNotificationParam;
+use rmcp::{Peer, RoleServer};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// URI of the ready queue resource.
+pub(crate) const READY_URI: &str = "pearls://ready";
+
+/// Quiet period after the last filesystem event before reloading.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Returns the resource URI of a Pearl.
+pub(crate) fn pearl_uri(id: &str) -> String {
+    format!("pearls://{}", id)
+}
+
+/// Connected sessions and the resources they subscribed to.
+#[derive(Default)]
+pub(crate) struct Notifier {
+    next_session: AtomicU64,
+    sessions: Mutex<HashMap<u64, Session>>,
+}
+
+struct Session {
+    peer: Peer<RoleServer>,
+    uris: HashSet<String>,
+}
+
+impl Notifier {
+    /// Allocates an ID for a new session.
+    pub(crate) fn next_session(&self) -> u64 {
+        self.next_session.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Registers the peer of an initialized session.
+    pub(crate) fn register(&self, session: u64, peer: Peer<RoleServer>) {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|err| err.into_inner());
+        sessions.entry(session).or_insert_with(|| Session {
+            peer,
+            uris: HashSet::new(),
+        });
+    }
+
+    /// Subscribes a session to a resource.
+    pub(crate) fn subscribe(&self, session: u64, peer: Peer<RoleServer>, uri: String) {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|err| err.into_inner());
+        sessions
+            .entry(session)
+            .or_insert_with(|| Session {
+                peer,
+                uris: HashSet::new(),
+            })
+            .uris
+            .insert(uri);
+    }
+
+    /// Removes a subscription of a session.
+    pub(crate) fn unsubscribe(&self, session: u64, uri: &str) {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(session) = sessions.get_mut(&session) {
+            session.uris.remove(uri);
+        }
+    }
+
+    /// Sends the notifications for a set of changes.
+    ///
+    /// Sessions whose transport has closed are dropped.
+    pub(crate) async fn publish(&self, changes: &Changes) {
+        let targets: Vec<(u64, Peer<RoleServer>, Vec<String>)> = {
+            let sessions = self.sessions.lock().unwrap_or_else(|err| err.into_inner());
+            sessions
+                .iter()
+                .map(|(id, session)| {
+                    let uris = changes
+                        .updated_uris()
+                        .filter(|uri| session.uris.contains(uri))
+                        .collect();
+                    (*id, session.peer.clone(), uris)
+                })
+                .collect()
+        };
+
+        let mut closed = Vec::new();
+        for (id, peer, uris) in targets {
+            let mut result = Ok(());
+            for uri in uris {
+                result = peer
+                    .notify_resource_updated(ResourceUpdatedNotificationParam { uri })
+                    .await;
+                if result.is_err() {
+                    break;
+                }
+            }
+            if result.is_ok() && changes.list_changed {
+                result = peer.notify_resource_list_changed().await;
+            }
+            if let Err(err) = result {
+                tracing::debug!(session = id, error = %err, "dropping closed MCP session");
+                closed.push(id);
+            }
+        }
+
+        if !closed.is_empty() {
+            let mut sessions = self.sessions.lock().unwrap_or_else(|err| err.into_inner());
+            for id in closed {
+                sessions.remove(&id);
+            }
+        }
+    }
+}
+
+/// State of the repository used to detect changes.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct Snapshot {
+    /// Serialized Pearls by ID, active and archived.
+    pearls: HashMap<String, String>,
+    /// IDs of active Pearls.
+    active: HashSet<String>,
+    /// Ready queue IDs in order.
+    ready: Vec<String>,
+}
+
+impl Snapshot {
+    /// Loads the current state of a repository.
+    pub(crate) fn load(repo: &RepoContext) -> Result<Self, AppError> {
+        let storage = repo.open_storage()?;
+        let active = storage.load_all()?;
+        let ready = load_ready_queue(&storage)?
+            .into_iter()
+            .map(|pearl| pearl.id)
+            .collect();
+        let mut snapshot = Self {
+            active: active.iter().map(|pearl| pearl.id.clone()).collect(),
+            ready,
+            ..Self::default()
+        };
+        let archived = match repo.open_archive_storage()? {
+            Some(archive) => archive.load_all().unwrap_or_default(),
+            None => Vec::new(),
+        };
+        for pearl in archived.into_iter().chain(active) {
+            let json = serde_json::to_string(&pearl).map_err(pearls_core::Error::from)?;
+            snapshot.pearls.insert(pearl.id, json);
+        }
+        Ok(snapshot)
+    }
+
+    /// Compares this snapshot with a newer one.
+    pub(crate) fn diff(&self, newer: &Snapshot) -> Changes {
+        let mut updated: Vec<String> = newer
+            .pearls
+            .iter()
+            .filter(|(id, json)| self.pearls.get(*id) != Some(*json))
+            .map(|(id, _)| id.clone())
+            .chain(
+                self.pearls
+                    .keys()
+                    .filter(|id| !newer.pearls.contains_key(*id))
+                    .cloned(),
+            )
+            .collect();
+        updated.sort();
+
+        let ready_changed = self.ready != newer.ready
+            || updated
+                .iter()
+                .any(|id| newer.ready.contains(id) || self.ready.contains(id));
+        Changes {
+            updated,
+            ready_changed,
+            list_changed: self.active != newer.active,
+        }
+    }
+}
+
+/// Differences between two snapshots.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct Changes {
+    /// IDs of Pearls that were created, changed, or removed.
+    pub(crate) updated: Vec<String>,
+    /// Whether the ready queue resource changed.
+    pub(crate) ready_changed: bool,
+    /// Whether the set of active Pearls changed.
+    pub(crate) list_changed: bool,
+}
+
+impl Changes {
+    /// Whether there is nothing to report.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.updated.is_empty() && !self.ready_changed && !self.list_changed
+    }
+
+    fn updated_uris(&self) -> impl Iterator<Item = String> + '_ {
+        self.ready_changed
+            .then(|| READY_URI.to_string())
+            .into_iter()
+            .chain(self.updated.iter().map(|id| pearl_uri(id)))
+    }
+}
+
+/// Starts watching a repository and publishing its changes.
+///
+/// Must be called from within a Tokio runtime. The watcher stops when the
+/// returned handle is dropped.
+///
+/// # Arguments
+///
+/// * `repo` - Repository to watch
+/// * `notifier` - Sessions to notify
+///
+/// # Returns
+///
+/// The watcher handle.
+///
+/// # Errors
+///
+/// Returns an error if the repository cannot be loaded or watched.
+pub(crate) fn watch(
+    repo: RepoContext,
+    notifier: std::sync::Arc<Notifier>,
+) -> Result<RecommendedWatcher, AppError> {
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            if event.paths.iter().any(|path| is_pearls_file(path)) {
+                let _ = sender.send(());
+            }
+        }
+    })
+    .map_err(watch_error)?;
+    watcher
+        .watch(repo.pearls_dir(), RecursiveMode::Recursive)
+        .map_err(watch_error)?;
+
+    let mut snapshot = Snapshot::load(&repo)?;
+    tokio::spawn(async move {
+        while receiver.recv().await.is_some() {
+            loop {
+                tokio::time::sleep(DEBOUNCE).await;
+                if receiver.try_recv().is_err() {
+                    break;
+                }
+                while receiver.try_recv().is_ok() {}
+            }
+            match Snapshot::load(&repo) {
+                Ok(newer) => {
+                    let changes = snapshot.diff(&newer);
+                    snapshot = newer;
+                    if !changes.is_empty() {
+                        notifier.publish(&changes).await;
+                    }
+                }
+                Err(err) => tracing::warn!(error = %err, "failed to reload Pearls"),
+            }
+        }
+    });
+    Ok(watcher)
+}
+
+fn watch_error(err: notify::Error) -> AppError {
+    AppError::Io(std::io::Error::other(format!(
+        "Failed to watch .pearls: {}",
+        err
+    )))
+}
+
+/// Whether a path holds Pearls, as opposed to the index, cache, or lock.
+fn is_pearls_file(path: &Path) -> bool {
+    let in_issues_dir = path
+        .parent()
+        .and_then(Path::file_name)
+        .is_some_and(|name| name == "issues");
+    match path.file_name().and_then(|name| name.to_str()) {
+        Some("issues.jsonl") | Some("archive.jsonl") => true,
+        Some(name) => in_issues_dir && name.ends_with(".json"),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(pearls: &[(&str, &str)], archived: &[&str], ready: &[&str]) -> Snapshot {
+        Snapshot {
+            pearls: pearls
+                .iter()
+                .map(|(id, json)| (id.to_string(), json.to_string()))
+                .collect(),
+            active: pearls
+                .iter()
+                .map(|(id, _)| id.to_string())
+                .filter(|id| !archived.contains(&id.as_str()))
+                .collect(),
+            ready: ready.iter().map(|id| id.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_diff_reports_changes() {
+        let before = snapshot(
+            &[("prl-aaa111", "a"), ("prl-bbb222", "b")],
+            &[],
+            &["prl-aaa111"],
+        );
+
+        let edited = snapshot(
+            &[("prl-aaa111", "a"), ("prl-bbb222", "B")],
+            &[],
+            &["prl-aaa111"],
+        );
+        assert_eq!(
+            before.diff(&edited),
+            Changes {
+                updated: vec!["prl-bbb222".to_string()],
+                ready_changed: false,
+                list_changed: false,
+            }
+        );
+
+        let unblocked = snapshot(
+            &[
+                ("prl-aaa111", "A"),
+                ("prl-bbb222", "b"),
+                ("prl-ccc333", "c"),
+            ],
+            &[],
+            &["prl-bbb222"],
+        );
+        let changes = before.diff(&unblocked);
+        assert_eq!(changes.updated, vec!["prl-aaa111", "prl-ccc333"]);
+        assert!(changes.ready_changed && changes.list_changed);
+
+        let archived = snapshot(
+            &[("prl-aaa111", "a"), ("prl-bbb222", "b")],
+            &["prl-bbb222"],
+            &["prl-aaa111"],
+        );
+        let changes = before.diff(&archived);
+        assert!(changes.updated.is_empty() && changes.list_changed);
+        assert!(before.diff(&before).is_empty());
+        assert!(is_pearls_file(Path::new(".pearls/issues/prl-aaa111.json")));
+        assert!(!is_pearls_file(Path::new(".pearls/index.bin")));
+    }
+}
@@ -0,0 +1,257 @@
+// Rust guideline compliant 2026-02-10
+
+//! Integration tests for the streamable HTTP transport.
+
+use pearls_core::{Config, McpConfig};
+use pearls_mcp::{serve_http, McpOptions};
+use rmcp::model::CallToolRequestParams;
+use rmcp::service::RunningService;
+use rmcp::transport::streamable_http_client::StreamableHttpClientTransportConfig;
+use rmcp::transport::StreamableHttpClientTransport;
+use rmcp::{RoleClient, ServiceExt};
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+
+/// Token every test server requires; set through the environment, since the
+/// repository config may not hold it.
+const TOKEN: &str = "secret";
+
+fn init_repo() -> TempDir {
+    let temp = TempDir::new().expect("Failed to create temp dir");
+    let pearls_dir = temp.path().join(".pearls");
+    std::fs::create_dir(&pearls_dir).expect("Failed to create .pearls dir");
+    std::fs::File::create(pearls_dir.join("issues.jsonl")).expect("Failed to create issues.jsonl");
+    Config::default()
+        .save(&pearls_dir)
+        .expect("Failed to save config");
+    std::env::set_var("PEARLS_MCP_AUTH_TOKEN", TOKEN);
+    temp
+}
+
+async fn connect(
+    url: &str,
+    token: Option<&str>,
+) -> Result<RunningService<RoleClient, ()>, rmcp::service::ClientInitializeError> {
+    let mut config = StreamableHttpClientTransportConfig::with_uri(url);
+    if let Some(token) = token {
+        config = config.auth_header(token);
+    }
+    ().serve(StreamableHttpClientTransport::from_config(config))
+        .await
+}
+
+async fn call(
+    client: &RunningService<RoleClient, ()>,
+    name: &'static str,
+    arguments: serde_json::Value,
+) -> serde_json::Value {
+    let result = client
+        .call_tool(CallToolRequestParams {
+            meta: None,
+            name: name.into(),
+            arguments: arguments.as_object().cloned(),
+            task: None,
+        })
+        .await
+        .expect("Tool call failed");
+    let text = result.content[0]
+        .as_text()
+        .expect("Expected text content")
+        .text
+        .clone();
+    serde_json::from_str(&text).expect("Invalid tool payload")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_http_clients_share_one_server() {
+    let temp = init_repo();
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Failed to bind");
+    let url = format!("http://{}/mcp", listener.local_addr().unwrap());
+    let shutdown = CancellationToken::new();
+    let server = tokio::spawn(serve_http(
+        listener,
+        McpOptions {
+            repo: Some(temp.path().to_path_buf()),
+            ..McpOptions::default()
+        },
+        shutdown.clone(),
+    ));
+
+    assert!(connect(&url, None).await.is_err());
+    assert!(connect(&url, Some("wrong")).await.is_err());
+
+    let first = connect(&url, Some(TOKEN))
+        .await
+        .expect("First client failed");
+    let second = connect(&url, Some(TOKEN))
+        .await
+        .expect("Second client failed");
+
+    let created = call(
+        &first,
+        "create",
+        serde_json::json!({ "items": [{ "title": "Shared" }] }),
+    )
+    .await;
+    let id = created["result"]["pearls"][0]["id"]
+        .as_str()
+        .expect("Missing ID")
+        .to_string();
+
+    let listed = call(&second, "list", serde_json::json!({})).await;
+    assert_eq!(listed["result"]["total"], 1);
+    assert_eq!(listed["result"]["pearls"][0]["id"], id.as_str());
+
+    let (a, b) = tokio::join!(
+        call(
+            &first,
+            "update",
+            serde_json::json!({ "id": id, "add_labels": ["a"] })
+        ),
+        call(
+            &second,
+            "update",
+            serde_json::json!({ "id": id, "add_labels": ["b"] })
+        ),
+    );
+    assert_eq!(a["status"], "ok");
+    assert_eq!(b["status"], "ok");
+    let shown = call(&first, "show", serde_json::json!({ "id": id })).await;
+    let labels = shown["result"]["pearl"]["labels"]
+        .as_array()
+        .expect("Missing labels");
+    assert_eq!(labels.len(), 2, "Concurrent updates should both apply");
+
+    first.cancel().await.expect("Cancel failed");
+    second.cancel().await.expect("Cancel failed");
+    shutdown.cancel();
+    tokio::time::timeout(Duration::from_secs(10), server)
+        .await
+        .expect("Server did not shut down")
+        .expect("Server task panicked")
+        .expect("Server failed");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_http_policy_denies_and_audits() {
+    let temp = init_repo();
+    let policy_path = temp.path().join("policy.toml");
+    std::fs::write(
+        &policy_path,
+        "deny_tools = [\"compact\"]\nmax_batch = 1\n\n[[deny_transitions]]\nto = \"closed\"\npriorities = [0]\n",
+    )
+    .expect("Failed to write policy");
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Failed to bind");
+    let url = format!("http://{}/mcp", listener.local_addr().unwrap());
+    let shutdown = CancellationToken::new();
+    let server = tokio::spawn(serve_http(
+        listener,
+        McpOptions {
+            repo: Some(temp.path().to_path_buf()),
+            policy: Some(policy_path),
+            ..McpOptions::default()
+        },
+        shutdown.clone(),
+    ));
+    let client = connect(&url, Some(TOKEN)).await.expect("Client failed");
+
+    let tools = client.list_all_tools().await.expect("List tools failed");
+    assert!(tools.iter().any(|tool| tool.name == "close"));
+    assert!(!tools.iter().any(|tool| tool.name == "compact"));
+
+    let created = call(
+        &client,
+        "create",
+        serde_json::json!({ "items": [{ "title": "Urgent", "priority": 0 }] }),
+    )
+    .await;
+    let id = created["result"]["pearls"][0]["id"]
+        .as_str()
+        .expect("Missing ID")
+        .to_string();
+
+    for (name, arguments) in [
+        ("close", serde_json::json!({ "id": id })),
+        ("compact", serde_json::json!({})),
+        (
+            "create",
+            serde_json::json!({ "items": [{ "title": "A" }, { "title": "B" }] }),
+        ),
+    ] {
+        let error = client
+            .call_tool(CallToolRequestParams {
+                meta: None,
+                name: name.into(),
+                arguments: arguments.as_object().cloned(),
+                task: None,
+            })
+            .await
+            .expect_err("Call should be denied");
+        let rmcp::ServiceError::McpError(error) = error else {
+            panic!("Unexpected error: {error}");
+        };
+        assert_eq!(
+            error.data.expect("Missing error data")["code"],
+            "policy_denied"
+        );
+    }
+
+    let audit = std::fs::read_to_string(temp.path().join(".pearls/audit.jsonl"))
+        .expect("Missing audit log");
+    let entries: Vec<serde_json::Value> = audit
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("Invalid audit entry"))
+        .collect();
+    assert_eq!(entries.len(), 4);
+    assert_eq!(entries[0]["tool"], "create");
+    assert_eq!(entries[0]["outcome"], "ok");
+    assert_eq!(entries[1]["tool"], "close");
+    assert_eq!(entries[1]["outcome"], "policy_denied");
+    assert!(entries[1]["client"]
+        .as_str()
+        .is_some_and(|name| !name.is_empty()));
+
+    client.cancel().await.expect("Cancel failed");
+    shutdown.cancel();
+    tokio::time::timeout(Duration::from_secs(10), server)
+        .await
+        .expect("Server did not shut down")
+        .expect("Server task panicked")
+        .expect("Server failed");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_http_refuses_token_in_repository_config() {
+    let temp = init_repo();
+    let config = Config {
+        mcp: McpConfig {
+            auth_token: Some(TOKEN.to_string()),
+            ..McpConfig::default()
+        },
+        ..Config::default()
+    };
+    config
+        .save(&temp.path().join(".pearls"))
+        .expect("Failed to save config");
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Failed to bind");
+
+    let err = serve_http(
+        listener,
+        McpOptions {
+            repo: Some(temp.path().to_path_buf()),
+            ..McpOptions::default()
+        },
+        CancellationToken::new(),
+    )
+    .await
+    .expect_err("Token in the repository config should be refused");
+    assert!(err.to_string().contains("PEARLS_MCP_AUTH_TOKEN"));
+}
@@ -3,7 +3,7 @@
 //! Three-way merge algorithm for Pearls JSONL files.
 
 use anyhow::Result;
-use pearls_core::{DepType, Dependency, Pearl};
+use pearls_core::{Comment, DepType, Dependency, Pearl};
 use std::collections::{HashMap, HashSet};
 
 /// Conflict encountered during merge.
@@ -66,10 +66,10 @@ pub fn merge_with_conflicts(
             ours_map.get(&id),
             theirs_map.get(&id),
         ) {
-            (_, Some(ours), Some(theirs)) => {
+            (ancestor, Some(ours), Some(theirs)) => {
                 if ours == theirs {
                     merged.push(ours.clone());
-                } else if let Ok(result) = merge_pearl(ours, theirs) {
+                } else if let Ok(result) = merge_pearl(ancestor, ours, theirs) {
                     merged.push(result);
                 } else {
                     conflicts.push(MergeConflict {
@@ -93,7 +93,12 @@ fn to_map(pearls: Vec<Pearl>) -> HashMap<String, Pearl> {
     pearls.into_iter().map(|p| (p.id.clone(), p)).collect()
 }
 
-fn merge_pearl(ours: &Pearl, theirs: &Pearl) -> Result<Pearl> {
+/// Merges two versions of a Pearl.
+///
+/// Scalar fields come from the newer side. Comments and metadata are merged
+/// against the ancestor, so that a removal on one side is kept instead of
+/// being undone by the other side's unchanged copy.
+fn merge_pearl(ancestor: Option<&Pearl>, ours: &Pearl, theirs: &Pearl) -> Result<Pearl> {
     if ours.id != theirs.id {
         anyhow::bail!("Cannot merge different Pearl IDs");
     }
@@ -102,17 +107,23 @@ fn merge_pearl(ours: &Pearl, theirs: &Pearl) -> Result<Pearl> {
         anyhow::bail!("Conflicting updates with identical timestamps");
     }
 
-    let newer = if ours.updated_at > theirs.updated_at {
-        ours
+    let (newer, older) = if ours.updated_at > theirs.updated_at {
+        (ours, theirs)
     } else {
-        theirs
+        (theirs, ours)
     };
 
     let mut merged = newer.clone();
     merged.created_at = std::cmp::min(ours.created_at, theirs.created_at);
     merged.labels = union_labels(&ours.labels, &theirs.labels);
     merged.deps = union_deps(&ours.deps, &theirs.deps);
+    merged.comments = union_comments(
+        ancestor.map(|pearl| pearl.comments.as_slice()),
+        &newer.comments,
+        &older.comments,
+    );
     merged.metadata = merge_metadata(
+        ancestor.map(|pearl| &pearl.metadata),
         &ours.metadata,
         &theirs.metadata,
         ours.updated_at,
@@ -155,7 +166,45 @@ fn union_deps(ours: &[Dependency], theirs: &[Dependency]) -> Vec<Dependency> {
     deps
 }
 
+/// Keeps the comments of both sides, preferring the newer side's version of
+/// a comment, including its kind, when both have it.
+///
+/// A comment in the ancestor that either side no longer has was deleted and is
+/// dropped.
+fn union_comments(
+    ancestor: Option<&[Comment]>,
+    newer: &[Comment],
+    older: &[Comment],
+) -> Vec<Comment> {
+    let deleted = |comment: &Comment| {
+        ancestor.is_some_and(|ancestor| ancestor.iter().any(|base| base.id == comment.id))
+            && !(newer.iter().any(|other| other.id == comment.id)
+                && older.iter().any(|other| other.id == comment.id))
+    };
+    let mut comments: Vec<Comment> = newer
+        .iter()
+        .filter(|comment| !deleted(comment))
+        .cloned()
+        .collect();
+    for comment in older {
+        if !deleted(comment) && !comments.iter().any(|existing| existing.id == comment.id) {
+            comments.push(comment.clone());
+        }
+    }
+    comments.sort_by(|a, b| {
+        a.created_at
+            .cmp(&b.created_at)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    comments
+}
+
+/// Merges metadata key by key against the ancestor.
+///
+/// A key changed or removed on one side only takes that side's value. When both
+/// sides changed a key differently, the newer side wins.
 fn merge_metadata(
+    ancestor: Option<&HashMap<String, serde_json::Value>>,
     ours: &HashMap<String, serde_json::Value>,
     theirs: &HashMap<String, serde_json::Value>,
     ours_ts: i64,
@@ -164,25 +213,20 @@ fn merge_metadata(
     let mut merged = HashMap::new();
     let keys: HashSet<&String> = ours.keys().chain(theirs.keys()).collect();
     for key in keys {
-        match (ours.get(key), theirs.get(key)) {
-            (Some(a), Some(b)) => {
-                if a == b {
-                    merged.insert(key.clone(), a.clone());
-                } else if ours_ts == theirs_ts {
-                    anyhow::bail!("Metadata conflict on key {}", key);
-                } else if ours_ts > theirs_ts {
-                    merged.insert(key.clone(), a.clone());
-                } else {
-                    merged.insert(key.clone(), b.clone());
-                }
-            }
-            (Some(a), None) => {
-                merged.insert(key.clone(), a.clone());
-            }
-            (None, Some(b)) => {
-                merged.insert(key.clone(), b.clone());
-            }
-            _ => {}
+        let (a, b) = (ours.get(key), theirs.get(key));
+        let value = match ancestor.map(|ancestor| ancestor.get(key)) {
+            _ if a == b => a,
+            Some(base) if a == base => b,
+            Some(base) if b == base => a,
+            // Without an ancestor, a key on one side only was added there.
+            None if b.is_none() => a,
+            None if a.is_none() => b,
+            _ if ours_ts == theirs_ts => anyhow::bail!("Metadata conflict on key {}", key),
+            _ if ours_ts > theirs_ts => a,
+            _ => b,
+        };
+        if let Some(value) = value {
+            merged.insert(key.clone(), value.clone());
         }
     }
     Ok(merged)
@@ -2,7 +2,7 @@
 
 //! Tests for the Pearls merge driver.
 
-use pearls_core::{DepType, Dependency, Pearl, Status};
+use pearls_core::{CommentKind, DepType, Dependency, Pearl, Status};
 use pearls_merge::merge::{merge_with_conflicts, three_way_merge};
 
 fn base_pearl(id: &str) -> Pearl {
@@ -19,6 +19,7 @@ fn base_pearl(id: &str) -> Pearl {
         deps: Vec::new(),
         metadata: Default::default(),
         comments: Vec::new(),
+        extra: Default::default(),
     }
 }
 
@@ -59,6 +60,29 @@ fn test_merge_unions_lists() {
     assert!(merged.deps.iter().any(|dep| dep.target_id == "prl-bbb222"));
 }
 
+#[test]
+fn test_merge_keeps_comments_and_kinds() {
+    let mut ours = base_pearl("prl-abc123");
+    let mut theirs = base_pearl("prl-abc123");
+    ours.add_comment_at("agent".to_string(), "Parser done".to_string(), 1200)
+        .unwrap();
+    ours.comments[0].kind = CommentKind::Progress;
+    theirs
+        .add_comment_at("agent".to_string(), "Use nom".to_string(), 1100)
+        .unwrap();
+    theirs.comments[0].kind = CommentKind::Decision;
+    ours.updated_at = 2000;
+    theirs.updated_at = 1500;
+
+    let merged = three_way_merge(vec![], vec![ours], vec![theirs]).unwrap();
+    let kinds: Vec<CommentKind> = merged[0]
+        .comments
+        .iter()
+        .map(|comment| comment.kind)
+        .collect();
+    assert_eq!(kinds, vec![CommentKind::Decision, CommentKind::Progress]);
+}
+
 #[test]
 fn test_conflict_detection() {
     let mut ours = base_pearl("prl-abc123");
@@ -71,3 +95,98 @@ fn test_conflict_detection() {
     let (_merged, conflicts) = merge_with_conflicts(vec![], vec![ours], vec![theirs]).unwrap();
     assert_eq!(conflicts.len(), 1);
 }
+
+#[test]
+fn test_merge_keeps_deletions_from_the_ancestor() {
+    let mut ancestor = base_pearl("prl-abc123");
+    ancestor
+        .add_comment_at("agent".to_string(), "Stale note".to_string(), 1100)
+        .unwrap();
+    ancestor
+        .metadata
+        .insert("session".to_string(), serde_json::json!({"agent": "a"}));
+
+    // Ours ends the session and deletes the note; theirs adds a comment.
+    let mut ours = ancestor.clone();
+    ours.comments.clear();
+    ours.metadata.remove("session");
+    ours.updated_at = 1500;
+    let mut theirs = ancestor.clone();
+    theirs
+        .add_comment_at("agent".to_string(), "New note".to_string(), 1200)
+        .unwrap();
+    theirs.updated_at = 2000;
+
+    let merged = three_way_merge(vec![ancestor], vec![ours], vec![theirs]).unwrap();
+    let bodies: Vec<&str> = merged[0]
+        .comments
+        .iter()
+        .map(|comment| comment.body.as_str())
+        .collect();
+    assert_eq!(bodies, vec!["New note"]);
+    assert!(!merged[0].metadata.contains_key("session"));
+}
+
+#[test]
+fn test_merge_metadata_drops_key_deleted_on_one_side() {
+    let mut ancestor = base_pearl("prl-abc123");
+    ancestor
+        .metadata
+        .insert("session".to_string(), serde_json::json!({"agent": "a"}));
+    ancestor
+        .metadata
+        .insert("estimate".to_string(), serde_json::json!(3));
+
+    // Theirs is older but is the only side that touched `session`.
+    let mut ours = ancestor.clone();
+    ours.metadata
+        .insert("estimate".to_string(), serde_json::json!(5));
+    ours.updated_at = 2000;
+    let mut theirs = ancestor.clone();
+    theirs.metadata.remove("session");
+    theirs.updated_at = 1500;
+
+    let merged = three_way_merge(vec![ancestor], vec![ours], vec![theirs]).unwrap();
+    assert!(!merged[0].metadata.contains_key("session"));
+    assert_eq!(merged[0].metadata["estimate"], serde_json::json!(5));
+}
+
+#[test]
+fn test_merge_metadata_both_sides_changed() {
+    let mut ancestor = base_pearl("prl-abc123");
+    ancestor
+        .metadata
+        .insert("estimate".to_string(), serde_json::json!(3));
+    ancestor
+        .metadata
+        .insert("owner".to_string(), serde_json::json!("ada"));
+
+    let mut ours = ancestor.clone();
+    ours.metadata
+        .insert("estimate".to_string(), serde_json::json!(5));
+    ours.metadata.remove("owner");
+    ours.updated_at = 2000;
+    let mut theirs = ancestor.clone();
+    theirs
+        .metadata
+        .insert("estimate".to_string(), serde_json::json!(8));
+    theirs
+        .metadata
+        .insert("owner".to_string(), serde_json::json!("grace"));
+    theirs.updated_at = 1500;
+
+    // The newer side wins each key changed on both sides, deletions included.
+    let merged = three_way_merge(
+        vec![ancestor.clone()],
+        vec![ours.clone()],
+        vec![theirs.clone()],
+    )
+    .unwrap();
+    assert_eq!(merged[0].metadata["estimate"], serde_json::json!(5));
+    assert!(!merged[0].metadata.contains_key("owner"));
+
+    theirs.updated_at = ours.updated_at;
+    let (_merged, conflicts) =
+        merge_with_conflicts(vec![ancestor], vec![ours], vec![theirs]).unwrap();
+    assert_eq!(conflicts.len(), 1);
+}